@@ -6,7 +6,15 @@ use bevy::prelude::*;
 fn main() {
     App::new()
         .insert_resource(ClearColor(Color::srgb(0.1, 0.1, 0.1))) // Start with dark theme background
-        .add_plugins(DefaultPlugins)
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "Avian Physics Editor".into(),
+                ..default()
+            }),
+            // Closing asks to save unsaved changes first
+            close_when_requested: false,
+            ..default()
+        }))
         .add_plugins(AvianEditorPlugin)
         .add_systems(Startup, setup)
         .add_systems(Update, handle_mode_switching)
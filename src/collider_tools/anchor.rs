@@ -29,6 +29,7 @@ use super::utils::{
     calculate_anchor_world_position_from_anchor, create_anchor_at_position,
     get_mouse_world_position,
 };
+use crate::collider_tools::measure::{GUIDE_SNAP_DISTANCE, MeasureGuides};
 use crate::collider_tools::utils::calculate_snapped_position;
 use crate::selection::EditorSelection;
 use avian2d::prelude::*;
@@ -407,6 +408,7 @@ fn update_anchor_preview(
     mut anchor_state: ResMut<AnchorCreationState>,
     collider_query: Query<(&GlobalTransform, &Collider, Option<&Children>)>,
     selection: Res<EditorSelection>,
+    guides: Option<Res<MeasureGuides>>,
 ) {
     // Only update preview when in preview mode
     if !anchor_state.preview_mode {
@@ -428,12 +430,16 @@ fn update_anchor_preview(
 
         for collider_entity in selection.iter() {
             if let Ok((collider_transform, collider, _)) = collider_query.get(collider_entity) {
-                let target_pos = calculate_snapped_position(
-                    mouse_pos,
-                    collider_transform,
-                    collider,
-                    anchor_state.shift_pressed,
-                    anchor_state.ctrl_pressed,
+                let target_pos = snap_anchor_to_guides(
+                    calculate_snapped_position(
+                        mouse_pos,
+                        collider_transform,
+                        collider,
+                        anchor_state.shift_pressed,
+                        anchor_state.ctrl_pressed,
+                    ),
+                    &anchor_state,
+                    guides.as_deref(),
                 );
 
                 let distance = (target_pos - mouse_pos).length();
@@ -459,6 +465,22 @@ fn update_anchor_preview(
     }
 }
 
+/// Snap freely placed anchors to measure guides
+///
+/// Vertex (Shift) and precise (Ctrl) placement already pick an exact point on the collider.
+fn snap_anchor_to_guides(
+    position: Vec2,
+    anchor_state: &AnchorCreationState,
+    guides: Option<&MeasureGuides>,
+) -> Vec2 {
+    match guides {
+        Some(guides) if !anchor_state.shift_pressed && !anchor_state.ctrl_pressed => {
+            guides.snap_point(position, GUIDE_SNAP_DISTANCE).0
+        }
+        _ => position,
+    }
+}
+
 fn update_anchor_preview_visualization<Config: GizmoConfigGroup>(
     mut gizmos: Gizmos<Config>,
     anchor_state: Res<AnchorCreationState>,
@@ -507,6 +529,7 @@ fn handle_anchor_dragging(
     mut anchor_state: ResMut<AnchorCreationState>,
     mut anchor_query: Query<(&mut AnchorPoint, &mut Transform)>,
     collider_query: Query<(&GlobalTransform, &Collider, Option<&Children>)>,
+    guides: Option<Res<MeasureGuides>>,
     mut selection: ResMut<EditorSelection>,
     mut commands: Commands,
     all_anchor_used_by_query: Query<(Entity, &AnchorUsedBy)>,
@@ -591,12 +614,16 @@ fn handle_anchor_dragging(
     // Get the parent collider's transform
     if let Ok((collider_transform, collider, _)) = collider_query.get(anchor.parent_entity) {
         // Calculate the target position with snapping
-        let target_pos = calculate_snapped_position(
-            mouse_pos,
-            collider_transform,
-            collider,
-            anchor_state.shift_pressed,
-            anchor_state.ctrl_pressed,
+        let target_pos = snap_anchor_to_guides(
+            calculate_snapped_position(
+                mouse_pos,
+                collider_transform,
+                collider,
+                anchor_state.shift_pressed,
+                anchor_state.ctrl_pressed,
+            ),
+            &anchor_state,
+            guides.as_deref(),
         );
 
         // Calculate local anchor position relative to collider origin
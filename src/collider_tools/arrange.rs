@@ -0,0 +1,351 @@
+//! Align, distribute and stack commands for a multi-selection
+//!
+//! Every command works on the world-space AABBs of the selected colliders, so shapes line up
+//! by their visible extents rather than their origins. Selected entities without a collider are
+//! left where they are.
+//!
+//! ## Hotkeys (Select mode)
+//! - Alt + J / L / I / K: align left / right / top / bottom
+//! - Alt + H / V: align centers horizontally / vertically
+//! - Alt + Shift + H / V: distribute horizontally / vertically
+//! - Alt + Shift + J / K: stack horizontally / vertically
+
+use super::utils::collider_world_aabb;
+use crate::selection::EditorSelection;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::input::egui_wants_any_input;
+
+/// Side or center line that selected colliders are lined up on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum AlignEdge {
+    /// Left sides
+    Left,
+    /// Right sides
+    Right,
+    /// Top sides
+    Top,
+    /// Bottom sides
+    Bottom,
+    /// Horizontal centers, giving a vertical column
+    CenterX,
+    /// Vertical centers, giving a horizontal row
+    CenterY,
+}
+
+impl AlignEdge {
+    /// All edges, in UI order
+    pub const ALL: [AlignEdge; 6] = [
+        AlignEdge::Left,
+        AlignEdge::CenterX,
+        AlignEdge::Right,
+        AlignEdge::Top,
+        AlignEdge::CenterY,
+        AlignEdge::Bottom,
+    ];
+
+    /// Translation key of the command name
+    pub fn key(self) -> &'static str {
+        match self {
+            AlignEdge::Left => "align_left",
+            AlignEdge::Right => "align_right",
+            AlignEdge::Top => "align_top",
+            AlignEdge::Bottom => "align_bottom",
+            AlignEdge::CenterX => "align_center_x",
+            AlignEdge::CenterY => "align_center_y",
+        }
+    }
+
+    /// Coordinate of this edge on a rectangle
+    fn of(self, rect: Rect) -> f32 {
+        match self {
+            AlignEdge::Left => rect.min.x,
+            AlignEdge::Right => rect.max.x,
+            AlignEdge::Top => rect.max.y,
+            AlignEdge::Bottom => rect.min.y,
+            AlignEdge::CenterX => rect.center().x,
+            AlignEdge::CenterY => rect.center().y,
+        }
+    }
+
+    /// Axis the edge coordinate is measured on
+    fn axis(self) -> ArrangeAxis {
+        match self {
+            AlignEdge::Left | AlignEdge::Right | AlignEdge::CenterX => ArrangeAxis::Horizontal,
+            AlignEdge::Top | AlignEdge::Bottom | AlignEdge::CenterY => ArrangeAxis::Vertical,
+        }
+    }
+}
+
+/// Direction for distributing and stacking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum ArrangeAxis {
+    /// Along x, left to right
+    Horizontal,
+    /// Along y, bottom to top
+    Vertical,
+}
+
+impl ArrangeAxis {
+    /// Unit vector of the axis
+    fn unit(self) -> Vec2 {
+        match self {
+            ArrangeAxis::Horizontal => Vec2::X,
+            ArrangeAxis::Vertical => Vec2::Y,
+        }
+    }
+
+    /// Extent of a rectangle along the axis as `(min, max)`
+    fn span(self, rect: Rect) -> (f32, f32) {
+        match self {
+            ArrangeAxis::Horizontal => (rect.min.x, rect.max.x),
+            ArrangeAxis::Vertical => (rect.min.y, rect.max.y),
+        }
+    }
+}
+
+/// What alignment lines up against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum AlignReference {
+    /// Bounds of the whole selection
+    #[default]
+    SelectionBounds,
+    /// The primary selection, which stays in place
+    Active,
+}
+
+/// Arrangement command for the current selection
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum ArrangeEvent {
+    /// Line up one edge or center
+    Align(AlignEdge),
+    /// Space colliders evenly between the outermost two
+    Distribute(ArrangeAxis),
+    /// Place colliders one after another with [`ArrangeSettings::stack_gap`] between them
+    Stack(ArrangeAxis),
+}
+
+/// Options shared by the arrange commands
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ArrangeSettings {
+    /// What alignment lines up against
+    pub reference: AlignReference,
+    /// Gap left between stacked colliders, in world units
+    pub stack_gap: f32,
+}
+
+impl Default for ArrangeSettings {
+    fn default() -> Self {
+        Self {
+            reference: AlignReference::SelectionBounds,
+            stack_gap: 0.0,
+        }
+    }
+}
+
+/// Plugin providing align, distribute and stack commands
+#[derive(Default)]
+pub struct ArrangePlugin;
+
+impl Plugin for ArrangePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ArrangeSettings>()
+            .register_type::<ArrangeSettings>()
+            .add_event::<ArrangeEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_arrange_hotkeys.run_if(
+                        in_state(crate::collider_tools::ToolMode::Select)
+                            .and(not(egui_wants_any_input)),
+                    ),
+                    apply_arrange_events,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// World-space offsets that carry out a command
+///
+/// `bounds` holds the AABB of each selected collider and `active` the index of the primary
+/// selection. The result has one offset per rectangle.
+pub(crate) fn arrange_offsets(
+    command: ArrangeEvent,
+    bounds: &[Rect],
+    active: Option<usize>,
+    reference: AlignReference,
+    stack_gap: f32,
+) -> Vec<Vec2> {
+    let mut offsets = vec![Vec2::ZERO; bounds.len()];
+    if bounds.len() < 2 {
+        return offsets;
+    }
+
+    match command {
+        ArrangeEvent::Align(edge) => {
+            let target_rect = match (reference, active) {
+                (AlignReference::Active, Some(active)) => bounds[active],
+                _ => bounds.iter().copied().reduce(|a, b| a.union(b)).unwrap(),
+            };
+            let target = edge.of(target_rect);
+            let unit = edge.axis().unit();
+            for (offset, rect) in offsets.iter_mut().zip(bounds) {
+                *offset = unit * (target - edge.of(*rect));
+            }
+        }
+        ArrangeEvent::Distribute(axis) => {
+            if bounds.len() < 3 {
+                return offsets;
+            }
+            let order = sorted_along(bounds, axis, |rect| rect.center());
+            // The outermost colliders stay put and the rest share the space between them
+            let start = axis.span(bounds[order[0]]).0;
+            let end = axis.span(bounds[order[order.len() - 1]]).1;
+            let total: f32 = bounds
+                .iter()
+                .map(|rect| {
+                    let (min, max) = axis.span(*rect);
+                    max - min
+                })
+                .sum();
+            let gap = (end - start - total) / (bounds.len() - 1) as f32;
+
+            let mut cursor = start;
+            for &index in &order {
+                let (min, max) = axis.span(bounds[index]);
+                offsets[index] = axis.unit() * (cursor - min);
+                cursor += max - min + gap;
+            }
+        }
+        ArrangeEvent::Stack(axis) => {
+            let order = sorted_along(bounds, axis, |rect| rect.min);
+            let mut cursor = axis.span(bounds[order[0]]).0;
+            for &index in &order {
+                let (min, max) = axis.span(bounds[index]);
+                offsets[index] = axis.unit() * (cursor - min);
+                cursor += max - min + stack_gap;
+            }
+        }
+    }
+    offsets
+}
+
+/// Indices of `bounds` sorted by a point of each rectangle along an axis
+fn sorted_along(bounds: &[Rect], axis: ArrangeAxis, key: impl Fn(&Rect) -> Vec2) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..bounds.len()).collect();
+    order.sort_by(|&a, &b| {
+        key(&bounds[a])
+            .dot(axis.unit())
+            .total_cmp(&key(&bounds[b]).dot(axis.unit()))
+    });
+    order
+}
+
+/// Keyboard shortcuts for the arrange commands
+fn handle_arrange_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut events: EventWriter<ArrangeEvent>,
+) {
+    if !keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+        return;
+    }
+    let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    let command = if shift {
+        if keyboard.just_pressed(KeyCode::KeyH) {
+            Some(ArrangeEvent::Distribute(ArrangeAxis::Horizontal))
+        } else if keyboard.just_pressed(KeyCode::KeyV) {
+            Some(ArrangeEvent::Distribute(ArrangeAxis::Vertical))
+        } else if keyboard.just_pressed(KeyCode::KeyJ) {
+            Some(ArrangeEvent::Stack(ArrangeAxis::Horizontal))
+        } else if keyboard.just_pressed(KeyCode::KeyK) {
+            Some(ArrangeEvent::Stack(ArrangeAxis::Vertical))
+        } else {
+            None
+        }
+    } else if keyboard.just_pressed(KeyCode::KeyJ) {
+        Some(ArrangeEvent::Align(AlignEdge::Left))
+    } else if keyboard.just_pressed(KeyCode::KeyL) {
+        Some(ArrangeEvent::Align(AlignEdge::Right))
+    } else if keyboard.just_pressed(KeyCode::KeyI) {
+        Some(ArrangeEvent::Align(AlignEdge::Top))
+    } else if keyboard.just_pressed(KeyCode::KeyK) {
+        Some(ArrangeEvent::Align(AlignEdge::Bottom))
+    } else if keyboard.just_pressed(KeyCode::KeyH) {
+        Some(ArrangeEvent::Align(AlignEdge::CenterX))
+    } else if keyboard.just_pressed(KeyCode::KeyV) {
+        Some(ArrangeEvent::Align(AlignEdge::CenterY))
+    } else {
+        None
+    };
+
+    if let Some(command) = command {
+        events.write(command);
+    }
+}
+
+/// Move the selected colliders for each arrange command
+fn apply_arrange_events(
+    mut events: EventReader<ArrangeEvent>,
+    selection: Res<EditorSelection>,
+    settings: Res<ArrangeSettings>,
+    mut collider_query: Query<(
+        Entity,
+        &Collider,
+        &GlobalTransform,
+        &mut Transform,
+        Option<&ChildOf>,
+    )>,
+    parent_query: Query<&GlobalTransform>,
+) {
+    for command in events.read() {
+        let mut entities = Vec::new();
+        let mut bounds = Vec::new();
+        let mut active = None;
+        for (entity, collider, global_transform, ..) in &collider_query {
+            if !selection.contains(entity) {
+                continue;
+            }
+            if selection.primary() == Some(entity) {
+                active = Some(entities.len());
+            }
+            let aabb = collider_world_aabb(collider, global_transform);
+            entities.push(entity);
+            bounds.push(Rect::from_corners(aabb.min, aabb.max));
+        }
+        if entities.len() < 2 {
+            info!("Arrange needs at least two selected colliders");
+            continue;
+        }
+
+        let offsets = arrange_offsets(
+            *command,
+            &bounds,
+            active,
+            settings.reference,
+            settings.stack_gap,
+        );
+        for (entity, offset) in entities.into_iter().zip(offsets) {
+            if offset == Vec2::ZERO {
+                continue;
+            }
+            let Ok((.., mut transform, child_of)) = collider_query.get_mut(entity) else {
+                continue;
+            };
+            // Offsets are in world space; bring them into the parent's space first
+            let local_offset = child_of
+                .and_then(|child_of| parent_query.get(child_of.parent()).ok())
+                .map(|parent| {
+                    parent
+                        .affine()
+                        .inverse()
+                        .transform_vector3(offset.extend(0.0))
+                })
+                .unwrap_or(offset.extend(0.0));
+            transform.translation += local_offset;
+        }
+        info!("Applied {:?} to {} colliders", command, bounds.len());
+    }
+}
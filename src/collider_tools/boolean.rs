@@ -0,0 +1,277 @@
+//! Boolean shape operations: union, subtract and intersect
+//!
+//! Operands are tessellated into convex pieces in world space. Circles and capsules are sampled
+//! with the same outlines the editor draws for them, and compound colliders contribute each of
+//! their parts. Working on convex pieces keeps every step a half-plane clip:
+//! - intersection clips each pair of pieces against each other
+//! - subtraction cuts a piece along the edges of the subtracted piece and keeps what lies outside
+//! - union adds what is left of the second shape after subtracting the first
+//!
+//! The pieces are traced back into outline contours and stored on the base body as a
+//! [`PolygonSource`], which rebuilds the collider from convex parts and stays editable. The first
+//! selected body is the base and keeps its rigid body settings; the other operands are removed.
+
+use super::polygon::{
+    PolygonSource, clip_left, outline_contours, signed_area, simplify_contour, weld_tolerance,
+};
+use super::sketch::SketchStroke;
+use super::visualization::{generate_capsule_polyline, generate_circle_polyline};
+use super::{ColliderCreationState, ColliderType};
+use crate::selection::EditorSelection;
+use avian2d::parry::shape::{SharedShape, TypedShape};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+/// Points used to approximate a full circle
+const CURVE_RESOLUTION: u32 = 32;
+
+/// How the operands are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum BooleanOp {
+    /// Area covered by any operand
+    Union,
+    /// Base shape with the other operands cut away
+    Subtract,
+    /// Area covered by every operand
+    Intersect,
+}
+
+impl BooleanOp {
+    /// All operations, in UI order
+    pub const ALL: [BooleanOp; 3] = [BooleanOp::Union, BooleanOp::Subtract, BooleanOp::Intersect];
+
+    /// Translation key of the operation name
+    pub fn key(self) -> &'static str {
+        match self {
+            BooleanOp::Union => "boolean_union",
+            BooleanOp::Subtract => "boolean_subtract",
+            BooleanOp::Intersect => "boolean_intersect",
+        }
+    }
+}
+
+/// Combine the selected colliders into the first selected one
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct BooleanEvent(pub BooleanOp);
+
+/// Plugin providing boolean shape operations on the selection
+#[derive(Default)]
+pub struct BooleanPlugin;
+
+impl Plugin for BooleanPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BooleanEvent>()
+            .add_systems(Update, apply_boolean_events);
+    }
+}
+
+/// Combine two sets of non-overlapping convex pieces
+pub(crate) fn boolean_pieces(
+    op: BooleanOp,
+    base: &[Vec<Vec2>],
+    operand: &[Vec<Vec2>],
+) -> Vec<Vec<Vec2>> {
+    let epsilon = weld_tolerance(base.iter().chain(operand));
+    match op {
+        BooleanOp::Union => {
+            let mut pieces = base.to_vec();
+            pieces.extend(subtract_pieces(operand, base, epsilon));
+            pieces
+        }
+        BooleanOp::Subtract => subtract_pieces(base, operand, epsilon),
+        BooleanOp::Intersect => base
+            .iter()
+            .flat_map(|p| {
+                operand
+                    .iter()
+                    .filter_map(move |q| intersect_convex(p, q, epsilon))
+            })
+            .collect(),
+    }
+}
+
+/// Cut every piece of `operand` out of the pieces of `base`
+fn subtract_pieces(base: &[Vec<Vec2>], operand: &[Vec<Vec2>], epsilon: f32) -> Vec<Vec<Vec2>> {
+    operand.iter().fold(base.to_vec(), |pieces, cutter| {
+        pieces
+            .iter()
+            .flat_map(|piece| subtract_convex(piece, cutter, epsilon))
+            .collect()
+    })
+}
+
+/// Overlap of two counter-clockwise convex polygons
+fn intersect_convex(p: &[Vec2], q: &[Vec2], epsilon: f32) -> Option<Vec<Vec2>> {
+    let mut clipped = p.to_vec();
+    for i in 0..q.len() {
+        clipped = clip_left(&clipped, q[i], q[(i + 1) % q.len()]);
+        if clipped.len() < 3 {
+            return None;
+        }
+    }
+    simplify_contour(clipped, epsilon)
+}
+
+/// Convex pieces of `p` outside `q`
+///
+/// Walking the edges of `q`, the part of `p` beyond each edge is split off and the rest carried
+/// on to the next edge. What remains at the end is the overlap, which is dropped.
+fn subtract_convex(p: &[Vec2], q: &[Vec2], epsilon: f32) -> Vec<Vec<Vec2>> {
+    if intersect_convex(p, q, epsilon).is_none() {
+        return vec![p.to_vec()];
+    }
+    let mut pieces = Vec::new();
+    let mut remaining = p.to_vec();
+    for i in 0..q.len() {
+        let (a, b) = (q[i], q[(i + 1) % q.len()]);
+        pieces.extend(simplify_contour(clip_left(&remaining, b, a), epsilon));
+        remaining = clip_left(&remaining, a, b);
+        if remaining.len() < 3 {
+            break;
+        }
+    }
+    pieces
+}
+
+/// Counter-clockwise convex pieces of a collider in world space
+///
+/// Returns `None` for shapes that have no area, such as segments.
+pub(crate) fn collider_world_pieces(
+    collider: &Collider,
+    transform: &GlobalTransform,
+) -> Option<Vec<Vec<Vec2>>> {
+    let (_, rotation, translation) = transform.to_scale_rotation_translation();
+    let to_world = |point: Vec2| (rotation * point.extend(0.0)).truncate() + translation.truncate();
+    let mut pieces = Vec::new();
+    shape_pieces(collider.shape_scaled(), &to_world, &mut pieces).then_some(pieces)
+}
+
+/// Append the pieces of one shape, mapped through `to_world`
+fn shape_pieces(
+    shape: &SharedShape,
+    to_world: &dyn Fn(Vec2) -> Vec2,
+    pieces: &mut Vec<Vec<Vec2>>,
+) -> bool {
+    let vec = |p: &avian2d::parry::math::Point<f32>| Vec2::new(p.x, p.y);
+    let local = match shape.as_typed_shape() {
+        TypedShape::Cuboid(cuboid) => {
+            let h = Vec2::new(cuboid.half_extents.x, cuboid.half_extents.y);
+            vec![
+                Vec2::new(-h.x, -h.y),
+                Vec2::new(h.x, -h.y),
+                Vec2::new(h.x, h.y),
+                Vec2::new(-h.x, h.y),
+            ]
+        }
+        TypedShape::Ball(ball) => {
+            generate_circle_polyline(Vec2::ZERO, ball.radius, CURVE_RESOLUTION)
+        }
+        TypedShape::Capsule(capsule) => {
+            let (a, b) = (vec(&capsule.segment.a), vec(&capsule.segment.b));
+            if a.distance(b) <= f32::EPSILON {
+                generate_circle_polyline(a, capsule.radius, CURVE_RESOLUTION)
+            } else {
+                generate_capsule_polyline(a, b, capsule.radius, CURVE_RESOLUTION)
+            }
+        }
+        TypedShape::Triangle(triangle) => {
+            vec![vec(&triangle.a), vec(&triangle.b), vec(&triangle.c)]
+        }
+        TypedShape::ConvexPolygon(polygon) => polygon.points().iter().map(vec).collect(),
+        TypedShape::Compound(compound) => {
+            return compound.shapes().iter().all(|(isometry, part)| {
+                let to_parent = |point: Vec2| {
+                    let moved = isometry * avian2d::parry::math::Point::new(point.x, point.y);
+                    to_world(Vec2::new(moved.x, moved.y))
+                };
+                shape_pieces(part, &to_parent, pieces)
+            });
+        }
+        _ => return false,
+    };
+
+    let mut piece: Vec<Vec2> = local.into_iter().map(to_world).collect();
+    if signed_area(&piece) < 0.0 {
+        piece.reverse();
+    }
+    pieces.push(piece);
+    true
+}
+
+/// Run boolean operations on the selection
+fn apply_boolean_events(
+    mut commands: Commands,
+    mut events: EventReader<BooleanEvent>,
+    mut selection: ResMut<EditorSelection>,
+    mut state: ResMut<ColliderCreationState>,
+    collider_query: Query<(&Collider, &GlobalTransform)>,
+) {
+    for BooleanEvent(op) in events.read() {
+        let operands: Vec<Entity> = selection
+            .iter()
+            .filter(|&entity| collider_query.contains(entity))
+            .collect();
+        if operands.len() < 2 {
+            info!("{:?} needs at least two selected colliders", op);
+            continue;
+        }
+
+        let mut operand_pieces = Vec::with_capacity(operands.len());
+        for &entity in &operands {
+            let (collider, transform) = collider_query.get(entity).unwrap();
+            match collider_world_pieces(collider, transform) {
+                Some(pieces) => operand_pieces.push(pieces),
+                None => {
+                    warn!("The shape of {:?} cannot be combined", entity);
+                    break;
+                }
+            }
+        }
+        if operand_pieces.len() < operands.len() {
+            continue;
+        }
+
+        let mut operand_pieces = operand_pieces.into_iter();
+        let first = operand_pieces.next().unwrap();
+        let pieces = operand_pieces.fold(first, |pieces, operand| {
+            boolean_pieces(*op, &pieces, &operand)
+        });
+        let contours = outline_contours(&pieces);
+        if contours.is_empty() {
+            warn!("{:?} leaves nothing; the selection is unchanged", op);
+            continue;
+        }
+
+        // Bring the outline into the base body's unscaled local space
+        let base = operands[0];
+        let (_, base_transform) = collider_query.get(base).unwrap();
+        let to_local = base_transform.affine().inverse();
+        let mirrored = to_local.matrix3.determinant() < 0.0;
+        let contours = contours
+            .into_iter()
+            .map(|contour| {
+                let mut local: Vec<Vec2> = contour
+                    .into_iter()
+                    .map(|point| to_local.transform_point3(point.extend(0.0)).truncate())
+                    .collect();
+                if mirrored {
+                    local.reverse();
+                }
+                local
+            })
+            .collect();
+
+        // A sketched base no longer matches its stroke, which would otherwise rebuild the
+        // old outline
+        commands
+            .entity(base)
+            .insert((PolygonSource::new(contours), ColliderType::Polygon))
+            .remove::<SketchStroke>();
+        for &entity in &operands[1..] {
+            commands.entity(entity).despawn();
+            state.created_colliders.retain(|&e| e != entity);
+        }
+        selection.set(base);
+        info!("Applied {:?} to {} colliders", op, operands.len());
+    }
+}
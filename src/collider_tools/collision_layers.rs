@@ -313,6 +313,185 @@ impl CollisionLayerPresets {
     }
 }
 
+/// 导入场景时与当前碰撞层的冲突
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerConflict {
+    /// 同名的层位于不同的位
+    Bit {
+        name: String,
+        current: u8,
+        imported: u8,
+    },
+    /// 不同名的层占用同一位
+    Name {
+        bit: u8,
+        current: String,
+        imported: String,
+    },
+}
+
+/// 碰撞层冲突的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerResolution {
+    /// 导入的层并入当前层，导入实体改用当前层的位
+    Merge,
+    /// 导入的层作为新层保留（同名时改名），必要时移到空闲位
+    Separate,
+}
+
+impl LayerConflict {
+    /// 默认处理：同名视为同一层，同位不同名视为不同层
+    pub fn default_resolution(&self) -> LayerResolution {
+        match self {
+            LayerConflict::Bit { .. } => LayerResolution::Merge,
+            LayerConflict::Name { .. } => LayerResolution::Separate,
+        }
+    }
+}
+
+impl CollisionLayerPresets {
+    /// 列出导入的层与当前层的冲突，顺序与导入层一致
+    pub fn layer_conflicts(&self, imported: &CollisionLayerPresets) -> Vec<LayerConflict> {
+        imported
+            .layers
+            .iter()
+            .filter_map(|layer| {
+                if let Some(current) = self.layers.iter().find(|l| l.name == layer.name) {
+                    (current.bit != layer.bit).then(|| LayerConflict::Bit {
+                        name: layer.name.clone(),
+                        current: current.bit,
+                        imported: layer.bit,
+                    })
+                } else {
+                    self.layers
+                        .iter()
+                        .find(|l| l.bit == layer.bit)
+                        .map(|current| LayerConflict::Name {
+                            bit: layer.bit,
+                            current: current.name.clone(),
+                            imported: layer.name.clone(),
+                        })
+                }
+            })
+            .collect()
+    }
+
+    /// 合并导入的层和自定义预设，返回导入实体的位重映射
+    ///
+    /// `resolutions` 与 [`Self::layer_conflicts`] 的结果一一对应，缺少的按默认处理。
+    /// 同名的自定义预设保留当前的版本。
+    pub fn merge_imported(
+        &mut self,
+        imported: &CollisionLayerPresets,
+        resolutions: &[LayerResolution],
+    ) -> Result<HashMap<u8, u8>, String> {
+        let conflicts = self.layer_conflicts(imported);
+        let mut resolutions = conflicts.iter().enumerate().map(|(index, conflict)| {
+            resolutions
+                .get(index)
+                .copied()
+                .unwrap_or_else(|| conflict.default_resolution())
+        });
+
+        // 无冲突的导入层保留原位，移动的层只能使用其余的位
+        let mut reserved: Vec<u8> = self.layers.iter().map(|l| l.bit).collect();
+        reserved.extend(
+            imported
+                .layers
+                .iter()
+                .filter(|layer| {
+                    !self
+                        .layers
+                        .iter()
+                        .any(|l| l.name == layer.name || l.bit == layer.bit)
+                })
+                .map(|layer| layer.bit),
+        );
+
+        // 冲突按合并前的层判断，与 `conflicts` 保持一致
+        let current_layers = self.layers.clone();
+        let mut remap = HashMap::new();
+        for layer in &imported.layers {
+            let same_name = current_layers.iter().find(|l| l.name == layer.name);
+            let same_bit = current_layers.iter().any(|l| l.bit == layer.bit);
+            let (name, bit) = match same_name {
+                Some(current) if current.bit == layer.bit => continue,
+                Some(current) => match resolutions.next() {
+                    Some(LayerResolution::Merge) | None => {
+                        remap.insert(layer.bit, current.bit);
+                        continue;
+                    }
+                    Some(LayerResolution::Separate) => {
+                        let name = self.unused_layer_name(&layer.name);
+                        if reserved.contains(&layer.bit) {
+                            (name, free_bit(&mut reserved)?)
+                        } else {
+                            reserved.push(layer.bit);
+                            (name, layer.bit)
+                        }
+                    }
+                },
+                None if same_bit => match resolutions.next() {
+                    Some(LayerResolution::Merge) | None => continue,
+                    Some(LayerResolution::Separate) => {
+                        (layer.name.clone(), free_bit(&mut reserved)?)
+                    }
+                },
+                None => (layer.name.clone(), layer.bit),
+            };
+            if bit != layer.bit {
+                remap.insert(layer.bit, bit);
+            }
+            self.layers
+                .push(RuntimeCollisionLayer::new(&name, bit, &layer.description));
+            self.name_to_bit.insert(name, bit);
+        }
+
+        for preset in &imported.custom_presets {
+            if self.custom_presets.iter().any(|p| p.name == preset.name) {
+                continue;
+            }
+            self.custom_presets.push(CustomCollisionLayerPreset {
+                layers: CollisionLayers {
+                    memberships: remap_layer_mask(preset.layers.memberships, &remap),
+                    filters: remap_layer_mask(preset.layers.filters, &remap),
+                },
+                ..preset.clone()
+            });
+        }
+        Ok(remap)
+    }
+
+    /// 不与现有层重名的层名称
+    fn unused_layer_name(&self, name: &str) -> String {
+        (2..)
+            .map(|index| format!("{name} {index}"))
+            .find(|candidate| !self.name_to_bit.contains_key(candidate))
+            .unwrap_or_else(|| name.to_string())
+    }
+}
+
+/// 占用并返回一个未保留的位
+fn free_bit(reserved: &mut Vec<u8>) -> Result<u8, String> {
+    let bit = (0..32)
+        .find(|bit| !reserved.contains(bit))
+        .ok_or_else(|| "没有可用的位位置 (0-31 都已使用)".to_string())?;
+    reserved.push(bit);
+    Ok(bit)
+}
+
+/// 按位重映射图层掩码，未列出的位保持不变
+pub fn remap_layer_mask(mask: LayerMask, remap: &HashMap<u8, u8>) -> LayerMask {
+    let moved = remap.keys().fold(0u32, |bits, &from| bits | (1 << from));
+    let mut result = mask.0 & !moved;
+    for (&from, &to) in remap {
+        if mask.0 & (1 << from) != 0 {
+            result |= 1 << to;
+        }
+    }
+    LayerMask(result)
+}
+
 /// 碰撞层管理事件
 #[derive(Event, Debug, Reflect)]
 pub struct AddCustomLayerEvent {
@@ -1,7 +1,8 @@
 use crate::{EditorSelection, Selectable};
 
 use super::{
-    ColliderType, PreviewCollider, calculate_collider_vertices, utils::add_mass_properties,
+    ColliderType, ForceFalloff, ForceField, PreviewCollider, calculate_collider_vertices,
+    utils::add_mass_properties,
 };
 use avian2d::prelude::*;
 use bevy::prelude::*;
@@ -106,6 +107,8 @@ pub struct AdvancedPhysicsConfig {
     pub constant_local_linear_acceleration: Option<Vec2>,
     /// 常角加速度
     pub constant_angular_acceleration: Option<f32>,
+    /// 力场（通常配合传感器使用）
+    pub force_field: Option<ForceField>,
 }
 
 /// Creation properties for colliders
@@ -252,6 +255,43 @@ impl CreationProperties {
         self.material.restitution = Some(0.3);
         self.collision.collision_events = true;
     }
+
+    /// 风区预设（基于触发器）
+    pub fn wind_zone(&mut self) {
+        self.trigger_zone();
+        self.color = Color::srgba(0.7, 0.9, 1.0, 0.6);
+        self.advanced.force_field = Some(ForceField::wind(Vec2::X, 300.0));
+    }
+
+    /// 水体预设（浮力与阻力）
+    pub fn water_volume(&mut self) {
+        self.trigger_zone();
+        self.color = Color::srgba(0.2, 0.4, 1.0, 0.6);
+        self.advanced.force_field = Some(ForceField::water(2.0));
+    }
+
+    /// 重力区预设（区域内重力反向）
+    pub fn gravity_zone(&mut self) {
+        self.trigger_zone();
+        self.color = Color::srgba(0.8, 0.5, 1.0, 0.6);
+        self.advanced.force_field = Some(ForceField::gravity_zone(Vec2::new(0.0, 500.0)));
+    }
+
+    /// 吸引点预设（负强度即为排斥）
+    pub fn attractor_zone(&mut self) {
+        self.trigger_zone();
+        self.collider_type = ColliderType::Circle;
+        self.color = Color::srgba(1.0, 0.6, 0.2, 0.6);
+        self.advanced.force_field = Some(ForceField::attractor(800.0, ForceFalloff::Linear));
+    }
+
+    /// 漩涡预设
+    pub fn vortex_zone(&mut self) {
+        self.trigger_zone();
+        self.collider_type = ColliderType::Circle;
+        self.color = Color::srgba(0.4, 1.0, 0.8, 0.6);
+        self.advanced.force_field = Some(ForceField::vortex(600.0, 0.3));
+    }
 }
 
 /// Resource for collider creation state management
@@ -654,23 +694,31 @@ pub fn create_collider_from_preview(
     // === 智能添加质量属性 ===
     add_mass_properties_components(&mut entity_commands, &properties, &preview);
 
+    add_body_properties(&mut entity_commands, properties);
+
+    let entity = entity_commands.id();
+    state.created_colliders.push(entity);
+}
+
+/// 添加与形状无关的创建属性（材料、运动、碰撞、性能与高级物理）
+pub(super) fn add_body_properties(
+    entity_commands: &mut EntityCommands,
+    properties: &CreationProperties,
+) {
     // === 智能添加材料属性 ===
-    add_material_properties(&mut entity_commands, &properties);
+    add_material_properties(entity_commands, properties);
 
     // === 智能添加运动属性 ===
-    add_motion_properties(&mut entity_commands, &properties);
+    add_motion_properties(entity_commands, properties);
 
     // === 智能添加碰撞属性 ===
-    add_collision_properties(&mut entity_commands, &properties);
+    add_collision_properties(entity_commands, properties);
 
     // === 智能添加性能属性 ===
-    add_performance_properties(&mut entity_commands, &properties);
+    add_performance_properties(entity_commands, properties);
 
     // === 智能添加高级物理属性 ===
-    add_advanced_physics_properties(&mut entity_commands, &properties);
-
-    let entity = entity_commands.id();
-    state.created_colliders.push(entity);
+    add_advanced_physics_properties(entity_commands, properties);
 }
 
 /// System called when entering Create mode
@@ -952,4 +1000,9 @@ fn add_advanced_physics_properties(
     if let Some(accel) = advanced.constant_angular_acceleration {
         entity_commands.insert(ConstantAngularAcceleration(accel));
     }
+
+    // 力场
+    if let Some(field) = advanced.force_field {
+        entity_commands.insert(field);
+    }
 }
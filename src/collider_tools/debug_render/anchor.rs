@@ -97,8 +97,11 @@ pub fn sync_anchor_transforms(
                 parent_transform.rotation() * anchor.local_anchor_position.extend(0.0);
             let world_pos = parent_pos + rotated_anchor.xy();
 
-            // Update transform to match calculated world position
-            transform.translation = world_pos.extend(0.0);
+            // Update transform to match calculated world position, leaving unmoved anchors unchanged
+            let translation = world_pos.extend(0.0);
+            if transform.translation != translation {
+                transform.translation = translation;
+            }
 
             trace!(
                 "Synced anchor transform: parent_pos={:?}, local_anchor_position={:?}, world_pos={:?}",
@@ -3,6 +3,10 @@ use crate::{DragCancelClick, EditorGizmoConfigGroup, EditorSelection};
 use super::{ColliderCreationState, ColliderType, utils::*, visualization::*};
 
 use super::ColliderData;
+use super::polygon::{
+    PolygonSource, apply_polygon_source_changes, generate_polygon_source_control_points,
+};
+use super::sketch::SketchStroke;
 use avian2d::parry::shape::TypedShape;
 use avian2d::prelude::*;
 use bevy::prelude::*;
@@ -162,7 +166,12 @@ pub fn handle_control_point_interaction(
     keyboard: Res<ButtonInput<KeyCode>>,
     windows: Query<&Window>,
     camera_query: Query<(&Camera, &GlobalTransform), With<SpritePickingCamera>>,
-    mut collider_query: Query<(&mut Transform, &mut Collider, &ColliderType)>,
+    mut collider_query: Query<(
+        &mut Transform,
+        &mut Collider,
+        &ColliderType,
+        Option<&mut PolygonSource>,
+    )>,
 ) {
     // Get cursor position once at the beginning
     let cursor_pos = if let (Ok(window), Ok((camera, camera_transform))) =
@@ -175,7 +184,7 @@ pub fn handle_control_point_interaction(
 
     // Update dynamic control point position for all supported collider types
     if let Some(selected_entity) = selection.primary() {
-        if let Ok((transform, collider, collider_type)) = collider_query.get(selected_entity) {
+        if let Ok((transform, collider, collider_type, _)) = collider_query.get(selected_entity) {
             match collider_type {
                 ColliderType::Rectangle => {
                     update_dynamic_rectangle_control_point(
@@ -217,13 +226,15 @@ pub fn handle_control_point_interaction(
             // Generate control points if they don't exist
             if edit_state.control_points.is_empty() {
                 if let Some(entity) = selection.primary() {
-                    if let Ok((transform, collider, created_collider)) = collider_query.get(entity)
+                    if let Ok((transform, collider, created_collider, source)) =
+                        collider_query.get(entity)
                     {
-                        generate_control_points(
+                        regenerate_control_points(
                             &mut edit_state,
                             transform,
                             collider,
                             created_collider,
+                            source,
                         );
                     }
                 }
@@ -252,7 +263,8 @@ pub fn handle_control_point_interaction(
                 // Save the entity being edited and current state to history
                 if let Some(entity) = selection.primary() {
                     edit_state.editing_entity = Some(entity);
-                    if let Ok((transform, collider, collider_type)) = collider_query.get(entity) {
+                    if let Ok((transform, collider, collider_type, _)) = collider_query.get(entity)
+                    {
                         let current_state = ColliderData {
                             transform: *transform,
                             collider: collider.clone(),
@@ -273,15 +285,22 @@ pub fn handle_control_point_interaction(
 
                 // Apply changes to the collider using the preserved editing entity
                 if let Some(entity) = edit_state.editing_entity {
-                    if let Ok((mut transform, mut collider, collider_type)) =
+                    if let Ok((mut transform, mut collider, collider_type, source)) =
                         collider_query.get_mut(entity)
                     {
-                        apply_control_point_changes(
-                            &mut edit_state,
-                            &mut transform,
-                            &mut collider,
-                            collider_type,
-                        );
+                        // Edited vertices replace a sketched outline for good
+                        commands.entity(entity).remove::<SketchStroke>();
+                        if let Some(mut source) = source {
+                            // The collider is rebuilt from the source
+                            apply_polygon_source_changes(&edit_state, &transform, &mut source);
+                        } else {
+                            apply_control_point_changes(
+                                &mut edit_state,
+                                &mut transform,
+                                &mut collider,
+                                collider_type,
+                            );
+                        }
                     }
                 }
             }
@@ -295,7 +314,7 @@ pub fn handle_control_point_interaction(
 
             // Save state for undo after dragging is complete
             if let Some(entity) = edit_state.editing_entity {
-                if let Ok((transform, collider, collider_type)) = collider_query.get(entity) {
+                if let Ok((transform, collider, collider_type, _)) = collider_query.get(entity) {
                     let new_data = ColliderData {
                         transform: *transform,
                         collider: collider.clone(),
@@ -317,20 +336,28 @@ pub fn handle_control_point_interaction(
 
             // Restore original state if we were dragging
             if let Some(selected_entity) = selection.primary() {
-                if let Ok((transform, collider, collider_type)) =
+                if let Ok((transform, collider, collider_type, source)) =
                     collider_query.get(selected_entity)
                 {
                     // Regenerate control points to reset their positions
-                    generate_control_points(&mut edit_state, transform, collider, collider_type);
+                    regenerate_control_points(
+                        &mut edit_state,
+                        transform,
+                        collider,
+                        collider_type,
+                        source,
+                    );
                 }
             }
         }
     }
 
     // Handle undo/redo
+    // The edit history stores colliders, so polygon sources are left out of undo and reset
     if keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight) {
         if let Some(selected_entity) = selection.primary() {
-            if let Ok((transform, collider, created_collider)) = collider_query.get(selected_entity)
+            if let Ok((transform, collider, created_collider, None)) =
+                collider_query.get(selected_entity)
             {
                 if keyboard.just_pressed(KeyCode::KeyZ) {
                     if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight)
@@ -376,7 +403,8 @@ pub fn handle_control_point_interaction(
     // Handle reset
     if keyboard.just_pressed(KeyCode::KeyR) && keyboard.pressed(KeyCode::ControlLeft) {
         if let Some(selected_entity) = selection.primary() {
-            if let Ok((transform, collider, created_collider)) = collider_query.get(selected_entity)
+            if let Ok((transform, collider, created_collider, None)) =
+                collider_query.get(selected_entity)
             {
                 info!("Edit mode: Resetting collider to original state");
                 handle_reset(
@@ -397,7 +425,13 @@ pub fn update_control_points(
     mut commands: Commands,
     mut edit_state: ResMut<ColliderEditState>,
     selection: Res<EditorSelection>,
-    collider_query: Query<(Entity, &Transform, &Collider, &ColliderType)>,
+    collider_query: Query<(
+        Entity,
+        &Transform,
+        &Collider,
+        &ColliderType,
+        Option<&PolygonSource>,
+    )>,
     control_point_query: Query<(Entity, &ControlPointMarker, &Transform), With<ControlPointEntity>>,
 ) {
     // Always update when selection changes or control points are empty
@@ -406,20 +440,32 @@ pub fn update_control_points(
         (Some(selected_entity), Some(last_entity)) if selected_entity != last_entity => {
             // Selection changed to a different entity
             edit_state.last_selected_entity = Some(selected_entity);
-            if let Ok((_, transform, collider, created_collider)) =
+            if let Ok((_, transform, collider, created_collider, source)) =
                 collider_query.get(selected_entity)
             {
-                generate_control_points(&mut edit_state, transform, collider, created_collider);
+                regenerate_control_points(
+                    &mut edit_state,
+                    transform,
+                    collider,
+                    created_collider,
+                    source,
+                );
             }
             true
         }
         (Some(selected_entity), None) => {
             // New selection when previously none
             edit_state.last_selected_entity = Some(selected_entity);
-            if let Ok((_, transform, collider, created_collider)) =
+            if let Ok((_, transform, collider, created_collider, source)) =
                 collider_query.get(selected_entity)
             {
-                generate_control_points(&mut edit_state, transform, collider, created_collider);
+                regenerate_control_points(
+                    &mut edit_state,
+                    transform,
+                    collider,
+                    created_collider,
+                    source,
+                );
             }
             true
         }
@@ -439,10 +485,16 @@ pub fn update_control_points(
         (Some(selected_entity), Some(last_entity)) if selected_entity == last_entity => {
             // Same entity selected, only update if control points are empty
             if edit_state.control_points.is_empty() {
-                if let Ok((_, transform, collider, created_collider)) =
+                if let Ok((_, transform, collider, created_collider, source)) =
                     collider_query.get(selected_entity)
                 {
-                    generate_control_points(&mut edit_state, transform, collider, created_collider);
+                    regenerate_control_points(
+                        &mut edit_state,
+                        transform,
+                        collider,
+                        created_collider,
+                        source,
+                    );
                 }
                 true
             } else {
@@ -498,6 +550,20 @@ pub fn update_control_points(
     }
 }
 
+/// Generate control points, using the contour vertices when the collider has a polygon source
+fn regenerate_control_points(
+    edit_state: &mut ColliderEditState,
+    transform: &Transform,
+    collider: &Collider,
+    collider_type: &ColliderType,
+    source: Option<&PolygonSource>,
+) {
+    match source {
+        Some(source) => generate_polygon_source_control_points(edit_state, transform, source),
+        None => generate_control_points(edit_state, transform, collider, collider_type),
+    }
+}
+
 /// Apply control point changes to the collider
 pub fn apply_control_point_changes(
     edit_state: &mut ColliderEditState,
@@ -881,7 +947,13 @@ pub(super) fn on_enter_edit_mode(
     mut state: ResMut<ColliderCreationState>,
     mut edit_state: ResMut<ColliderEditState>,
     mut selection: ResMut<EditorSelection>,
-    collider_query: Query<(Entity, &Transform, &Collider, &ColliderType)>,
+    collider_query: Query<(
+        Entity,
+        &Transform,
+        &Collider,
+        &ColliderType,
+        Option<&PolygonSource>,
+    )>,
 ) {
     info!("Entering Edit mode");
 
@@ -895,9 +967,16 @@ pub(super) fn on_enter_edit_mode(
 
     // Generate control points for the selected collider if any
     if let Some(selected_entity) = selection.primary() {
-        if let Ok((_, transform, collider, created_collider)) = collider_query.get(selected_entity)
+        if let Ok((_, transform, collider, created_collider, source)) =
+            collider_query.get(selected_entity)
         {
-            generate_control_points(&mut edit_state, transform, collider, created_collider);
+            regenerate_control_points(
+                &mut edit_state,
+                transform,
+                collider,
+                created_collider,
+                source,
+            );
 
             // Spawn control point entities for visualization
             for (index, control_point) in edit_state.control_points.iter().enumerate() {
@@ -0,0 +1,436 @@
+//! Force fields and area effectors
+//!
+//! A force field is a sensor collider with a [`ForceField`] component. Every dynamic body
+//! whose center lies inside the field's collider is pushed according to the field kind:
+//! directional wind, point attractor/repulsor, vortex, buoyancy or a custom gravity zone.
+//!
+//! ## Simulation and Drawing
+//! [`ForceFieldPlugin`] pushes bodies whenever physics time is running, so fields act in the
+//! editor's play mode. It uses the editor's collider point test, so a game needs this crate to
+//! reuse it. [`ForceFieldGizmoPlugin`] draws the viewport arrows on the editor gizmo group.
+//!
+//! ## Strength Units
+//! Strength is an acceleration in world units per second squared. With
+//! [`ForceField::mass_dependent`] set it is treated as a force instead and divided by the body's
+//! mass, so heavier bodies are pushed less. Buoyancy and gravity zones are always accelerations.
+
+use super::EditorGizmoConfigGroup;
+use super::utils::is_point_inside_collider;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+/// World-space length of a field arrow per unit of strength
+const ARROW_SCALE: f32 = 0.1;
+
+/// Longest arrow drawn for a field, in world units
+const MAX_ARROW_LENGTH: f32 = 120.0;
+
+/// Spacing between sample arrows inside a field, in world units
+const ARROW_SPACING: f32 = 60.0;
+
+/// Distance falloff of a point field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum ForceFalloff {
+    /// Same strength everywhere in the field
+    #[default]
+    Constant,
+    /// Full strength at the center, fading to zero at the field's edge
+    Linear,
+}
+
+/// Behaviour of a force field
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum ForceFieldKind {
+    /// Constant push along a direction in the field's local space
+    Directional {
+        /// Local direction, rotated with the field entity
+        direction: Vec2,
+    },
+    /// Pull toward the field center (negative strength pushes away)
+    Point {
+        /// How strength changes with distance from the center
+        falloff: ForceFalloff,
+    },
+    /// Spin around the field center (positive strength is counter-clockwise)
+    Vortex {
+        /// Extra pull toward the center, as a fraction of strength
+        inward: f32,
+    },
+    /// Water volume whose surface is the top of the field's bounds
+    Buoyancy {
+        /// Fluid density compared against the body's [`ColliderDensity`]
+        fluid_density: f32,
+        /// Linear drag applied to the submerged part of a body
+        linear_drag: f32,
+        /// Angular drag applied to the submerged part of a body
+        angular_drag: f32,
+    },
+    /// Replaces world [`Gravity`] for bodies inside the field
+    Gravity {
+        /// Gravity acceleration inside the field
+        gravity: Vec2,
+    },
+}
+
+impl Default for ForceFieldKind {
+    fn default() -> Self {
+        Self::Directional { direction: Vec2::X }
+    }
+}
+
+impl ForceFieldKind {
+    /// Short identifier used for translation keys
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Directional { .. } => "directional",
+            Self::Point { .. } => "point",
+            Self::Vortex { .. } => "vortex",
+            Self::Buoyancy { .. } => "buoyancy",
+            Self::Gravity { .. } => "gravity",
+        }
+    }
+}
+
+/// Area effector attached to a sensor collider
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component, Default)]
+pub struct ForceField {
+    /// What the field does to bodies inside it
+    pub kind: ForceFieldKind,
+    /// Field strength (ignored by buoyancy and gravity zones)
+    pub strength: f32,
+    /// Treat strength as a force that is divided by the body's mass
+    pub mass_dependent: bool,
+    /// Whether the field currently affects bodies
+    pub enabled: bool,
+}
+
+impl Default for ForceField {
+    fn default() -> Self {
+        Self {
+            kind: ForceFieldKind::default(),
+            strength: 200.0,
+            mass_dependent: false,
+            enabled: true,
+        }
+    }
+}
+
+impl ForceField {
+    /// Wind blowing along a local direction
+    pub fn wind(direction: Vec2, strength: f32) -> Self {
+        Self {
+            kind: ForceFieldKind::Directional { direction },
+            strength,
+            ..default()
+        }
+    }
+
+    /// Point attractor; use a negative strength for a repulsor
+    pub fn attractor(strength: f32, falloff: ForceFalloff) -> Self {
+        Self {
+            kind: ForceFieldKind::Point { falloff },
+            strength,
+            ..default()
+        }
+    }
+
+    /// Vortex spinning counter-clockwise for positive strength
+    pub fn vortex(strength: f32, inward: f32) -> Self {
+        Self {
+            kind: ForceFieldKind::Vortex { inward },
+            strength,
+            ..default()
+        }
+    }
+
+    /// Water volume
+    pub fn water(fluid_density: f32) -> Self {
+        Self {
+            kind: ForceFieldKind::Buoyancy {
+                fluid_density,
+                linear_drag: 2.0,
+                angular_drag: 1.0,
+            },
+            strength: 0.0,
+            ..default()
+        }
+    }
+
+    /// Gravity zone
+    pub fn gravity_zone(gravity: Vec2) -> Self {
+        Self {
+            kind: ForceFieldKind::Gravity { gravity },
+            strength: 0.0,
+            ..default()
+        }
+    }
+}
+
+/// Runtime plugin applying force fields while physics is running
+#[derive(Default)]
+pub struct ForceFieldPlugin;
+
+impl Plugin for ForceFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ForceField>()
+            .register_type::<ForceFieldKind>()
+            .register_type::<ForceFalloff>()
+            .add_systems(FixedUpdate, apply_force_fields);
+    }
+}
+
+/// Editor plugin drawing force field arrows in the viewport
+#[derive(Default)]
+pub struct ForceFieldGizmoPlugin;
+
+impl Plugin for ForceFieldGizmoPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, draw_force_fields::<EditorGizmoConfigGroup>);
+    }
+}
+
+/// Acceleration a field applies at a point, ignoring buoyancy drag
+///
+/// `field_center` and `field_rotation` come from the field's global transform; `radius` is
+/// the distance at which linear falloff reaches zero. Gravity zones return the zone gravity,
+/// the caller is responsible for cancelling world gravity.
+pub(crate) fn field_acceleration(
+    field: &ForceField,
+    field_center: Vec2,
+    field_rotation: f32,
+    radius: f32,
+    point: Vec2,
+) -> Vec2 {
+    let to_center = field_center - point;
+    let distance = to_center.length();
+
+    match field.kind {
+        ForceFieldKind::Directional { direction } => {
+            Vec2::from_angle(field_rotation).rotate(direction.normalize_or_zero()) * field.strength
+        }
+        ForceFieldKind::Point { falloff } => {
+            let factor = match falloff {
+                ForceFalloff::Constant => 1.0,
+                ForceFalloff::Linear if radius > 0.0 => (1.0 - distance / radius).max(0.0),
+                ForceFalloff::Linear => 0.0,
+            };
+            to_center.normalize_or_zero() * field.strength * factor
+        }
+        ForceFieldKind::Vortex { inward } => {
+            let radial = to_center.normalize_or_zero();
+            // Tangent of a counter-clockwise orbit around the center
+            -radial.perp() * field.strength + radial * field.strength.abs() * inward
+        }
+        ForceFieldKind::Gravity { gravity } => gravity,
+        ForceFieldKind::Buoyancy { .. } => Vec2::ZERO,
+    }
+}
+
+/// Fraction of a body's bounds below the fluid surface
+pub(crate) fn submerged_fraction(body: &ColliderAabb, surface: f32) -> f32 {
+    let height = body.max.y - body.min.y;
+    if height <= 0.0 {
+        return if body.min.y <= surface { 1.0 } else { 0.0 };
+    }
+    ((surface - body.min.y) / height).clamp(0.0, 1.0)
+}
+
+/// Angle of an entity around the Z axis
+fn rotation_angle(transform: &GlobalTransform) -> f32 {
+    transform.rotation().to_euler(EulerRot::ZYX).0
+}
+
+/// Half of the largest extent of a collider's bounds
+fn field_radius(collider: &Collider) -> f32 {
+    let aabb = collider.aabb(avian2d::math::Vector::ZERO, 0.0);
+    (aabb.max - aabb.min).max_element() * 0.5
+}
+
+/// Apply all enabled force fields to the dynamic bodies inside them
+fn apply_force_fields(
+    time: Res<Time>,
+    physics_time: Res<Time<Physics>>,
+    gravity: Option<Res<Gravity>>,
+    fields: Query<
+        (
+            &ForceField,
+            &Collider,
+            &GlobalTransform,
+            Option<&CollisionLayers>,
+        ),
+        Without<ColliderDisabled>,
+    >,
+    mut bodies: Query<
+        (
+            &RigidBody,
+            &Collider,
+            &GlobalTransform,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+            Option<&ComputedMass>,
+            Option<&ColliderDensity>,
+            Option<&GravityScale>,
+            Option<&CollisionLayers>,
+        ),
+        (Without<ForceField>, Without<RigidBodyDisabled>),
+    >,
+) {
+    if physics_time.is_paused() || fields.is_empty() {
+        return;
+    }
+
+    let delta = time.delta_secs() * physics_time.relative_speed();
+    let world_gravity = gravity.map(|gravity| gravity.0).unwrap_or_default();
+
+    for (field, field_collider, field_transform, field_layers) in &fields {
+        if !field.enabled {
+            continue;
+        }
+
+        let field_center = field_transform.translation().truncate();
+        let field_rotation = rotation_angle(field_transform);
+        let field_aabb = field_collider.aabb(field_center, field_rotation);
+        let radius = field_radius(field_collider);
+
+        for (
+            rigid_body,
+            collider,
+            body_transform,
+            mut linear_velocity,
+            mut angular_velocity,
+            mass,
+            density,
+            gravity_scale,
+            body_layers,
+        ) in &mut bodies
+        {
+            if !rigid_body.is_dynamic() {
+                continue;
+            }
+
+            let layers = field_layers.copied().unwrap_or_default();
+            if !layers.interacts_with(body_layers.copied().unwrap_or_default()) {
+                continue;
+            }
+
+            let point = body_transform.translation().truncate();
+
+            if let ForceFieldKind::Buoyancy {
+                fluid_density,
+                linear_drag,
+                angular_drag,
+            } = field.kind
+            {
+                // Partially submerged bodies count, even when their center is above the surface
+                let body_aabb = collider.aabb(point, rotation_angle(body_transform));
+                if !body_aabb.intersects(&field_aabb) {
+                    continue;
+                }
+                let fraction = submerged_fraction(&body_aabb, field_aabb.max.y);
+                if fraction <= 0.0 {
+                    continue;
+                }
+
+                let body_density = density.map(|density| density.0).unwrap_or(1.0).max(0.001);
+                let scale = gravity_scale.map(|scale| scale.0).unwrap_or(1.0);
+                let buoyancy = -world_gravity * scale * (fluid_density / body_density) * fraction;
+                linear_velocity.0 += buoyancy * delta;
+                linear_velocity.0 *= 1.0 / (1.0 + linear_drag * fraction * delta);
+                angular_velocity.0 *= 1.0 / (1.0 + angular_drag * fraction * delta);
+                continue;
+            }
+
+            if !is_point_inside_collider(point, field_collider, field_transform) {
+                continue;
+            }
+
+            let mut acceleration =
+                field_acceleration(field, field_center, field_rotation, radius, point);
+
+            match field.kind {
+                ForceFieldKind::Gravity { .. } => {
+                    // Swap world gravity for the zone gravity, keeping the body's gravity scale
+                    let scale = gravity_scale.map(|scale| scale.0).unwrap_or(1.0);
+                    acceleration = (acceleration - world_gravity) * scale;
+                }
+                _ if field.mass_dependent => {
+                    let mass = mass.map(|mass| mass.value()).unwrap_or(1.0);
+                    if mass > 0.0 {
+                        acceleration /= mass;
+                    }
+                }
+                _ => {}
+            }
+
+            linear_velocity.0 += acceleration * delta;
+        }
+    }
+}
+
+/// Draw sample arrows showing the direction and strength of each field
+fn draw_force_fields<Config: GizmoConfigGroup>(
+    mut gizmos: Gizmos<Config>,
+    theme_colors: Res<crate::ui::theme_colors::EditorThemeColors>,
+    fields: Query<(&ForceField, &Collider, &GlobalTransform)>,
+) {
+    for (field, collider, transform) in &fields {
+        let color = if field.enabled {
+            theme_colors.force_field_arrow
+        } else {
+            theme_colors.force_field_disabled
+        };
+
+        let center = transform.translation().truncate();
+        let rotation = rotation_angle(transform);
+        let aabb = collider.aabb(center, rotation);
+        let radius = field_radius(collider);
+
+        if let ForceFieldKind::Buoyancy { .. } = field.kind {
+            // Surface line with small upward arrows underneath it
+            let surface = aabb.max.y;
+            gizmos.line_2d(
+                Vec2::new(aabb.min.x, surface),
+                Vec2::new(aabb.max.x, surface),
+                theme_colors.force_field_surface,
+            );
+            let mut x = aabb.min.x + ARROW_SPACING * 0.5;
+            while x < aabb.max.x {
+                let start = Vec2::new(x, surface - ARROW_SPACING);
+                gizmos
+                    .arrow_2d(start, start + Vec2::Y * ARROW_SPACING * 0.5, color)
+                    .with_tip_length(6.0);
+                x += ARROW_SPACING;
+            }
+            continue;
+        }
+
+        // Sample the field on a grid clipped to the collider
+        let mut y = aabb.min.y + ARROW_SPACING * 0.5;
+        while y < aabb.max.y {
+            let mut x = aabb.min.x + ARROW_SPACING * 0.5;
+            while x < aabb.max.x {
+                let point = Vec2::new(x, y);
+                x += ARROW_SPACING;
+                if !is_point_inside_collider(point, collider, transform) {
+                    continue;
+                }
+
+                let acceleration = field_acceleration(field, center, rotation, radius, point);
+                let length = (acceleration.length() * ARROW_SCALE).min(MAX_ARROW_LENGTH);
+                if length < 1.0 {
+                    continue;
+                }
+                let end = point + acceleration.normalize() * length;
+                gizmos
+                    .arrow_2d(point, end, color)
+                    .with_tip_length((length * 0.3).min(10.0));
+            }
+            y += ARROW_SPACING;
+        }
+
+        if let ForceFieldKind::Point { .. } | ForceFieldKind::Vortex { .. } = field.kind {
+            gizmos.circle_2d(center, 4.0, color);
+        }
+    }
+}
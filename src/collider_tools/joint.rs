@@ -30,10 +30,33 @@ use super::utils::{
 };
 use crate::physics_management::PhysicsManager;
 use crate::selection::Selectable;
+use crate::sub_scene::{JointToSubScene, SubSceneJointEnds};
 use avian2d::prelude::*;
 use bevy::prelude::*;
 use bevy_egui::input::egui_wants_any_input;
 
+/// Joint waiting to be rebuilt from its configuration by [`rebuild_marked_joints`]
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RebuildJoint;
+
+/// Rebuild the physics joints of marked joint entities from their configuration and anchors
+pub fn rebuild_marked_joints(
+    mut commands: Commands,
+    joint_query: Query<(Entity, &JointConfig), With<RebuildJoint>>,
+    anchor_query: Query<&AnchorPoint>,
+    visualized_by_query: Query<&VisualizedBy>,
+) {
+    for (joint_entity, config) in &joint_query {
+        regenerate_joint_for_anchor(
+            &mut commands,
+            joint_entity,
+            config,
+            &anchor_query,
+            &visualized_by_query,
+        );
+    }
+}
+
 /// Regenerate a joint with updated anchor positions using relationship system
 pub fn regenerate_joint_for_anchor(
     commands: &mut Commands,
@@ -46,9 +69,6 @@ pub fn regenerate_joint_for_anchor(
     let visualization_entity =
         joint_relationships::get_visualization_for_joint(joint_entity, visualized_by_query);
 
-    // Remove the old joint
-    commands.entity(joint_entity).despawn();
-
     // Get updated anchor positions
     let anchor_a_offset = if config.anchor_a_is_anchor {
         if let Ok(anchor) = anchor_query.get(config.anchor_a) {
@@ -82,6 +102,12 @@ pub fn regenerate_joint_for_anchor(
     // Add JointConfig component to the new joint
     commands.entity(new_joint_entity).insert(config.clone());
 
+    // Sub-scene links are only recorded on the joint, so they move over before it goes
+    commands
+        .entity(joint_entity)
+        .clone_components::<(JointToSubScene, SubSceneJointEnds)>(new_joint_entity)
+        .despawn();
+
     // Re-establish anchor relationships
     let mut anchor_entities = Vec::new();
     if config.anchor_a_is_anchor {
@@ -290,7 +316,8 @@ impl Plugin for JointCreationPlugin {
                 (handle_joint_mode_input, update_joint_preview).run_if(
                     in_state(crate::collider_tools::ToolMode::Joint).and(not(egui_wants_any_input)),
                 ),
-            );
+            )
+            .add_systems(Update, rebuild_marked_joints);
     }
 }
 
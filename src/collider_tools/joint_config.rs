@@ -104,6 +104,16 @@ impl JointConfigurationEnum {
         }
     }
 
+    /// Joint type of this configuration
+    pub fn joint_type(&self) -> JointType {
+        match self {
+            JointConfigurationEnum::Fixed { .. } => JointType::Fixed,
+            JointConfigurationEnum::Distance { .. } => JointType::Distance,
+            JointConfigurationEnum::Prismatic { .. } => JointType::Prismatic,
+            JointConfigurationEnum::Revolute { .. } => JointType::Revolute,
+        }
+    }
+
     /// Get advanced configuration for current joint type
     pub fn advanced(&self) -> &AdvancedJointConfig {
         match self {
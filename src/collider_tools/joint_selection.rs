@@ -135,7 +135,10 @@ fn update_joint_selection_visual(
     selection: Res<EditorSelection>,
 ) {
     for (entity, mut joint_vis) in joint_query.iter_mut() {
-        joint_vis.selected = selection.contains(entity);
+        let selected = selection.contains(entity);
+        if joint_vis.selected != selected {
+            joint_vis.selected = selected;
+        }
     }
 }
 
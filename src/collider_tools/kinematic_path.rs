@@ -0,0 +1,661 @@
+//! Keyframed kinematic animation
+//!
+//! [`KinematicPath`] moves a kinematic body through a list of keyframes (position, rotation,
+//! time). Each keyframe's easing shapes the segment that leaves it, and the path can play once,
+//! loop or ping-pong.
+//!
+//! ## Velocity Driving
+//! Bodies are moved by writing [`LinearVelocity`] / [`AngularVelocity`] each fixed step, not by
+//! teleporting the transform. The solver then sees a moving body and pushes, carries and
+//! crushes other bodies correctly. Velocities aim at the next sample from the body's actual
+//! position, so numerical drift never accumulates.
+//!
+//! ## Plugins
+//! [`KinematicPathPlugin`] registers the path types and drives bodies in `FixedUpdate`.
+//! [`KinematicPathEditorPlugin`] adds the path tool mode with keyframe handles and a timed
+//! preview. It pauses physics while keyframes are placed and sends every platform back to its
+//! first keyframe when play starts from the beginning ([`PlayFromStart`]); pausing and
+//! resuming leave platforms where they are.
+
+use super::EditorGizmoConfigGroup;
+use super::utils::get_mouse_world_position;
+use super::visualization::{draw_dashed_line, draw_selection_outline};
+use crate::physics_management::{PhysicsManager, PlayFromStart};
+use crate::selection::EditorSelection;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::input::egui_wants_any_input;
+use std::f32::consts::{PI, TAU};
+
+/// Pick radius for keyframe handles, in world units
+const KEYFRAME_PICK_RADIUS: f32 = 10.0;
+
+/// Time added after the last keyframe when appending a new one
+pub const DEFAULT_KEYFRAME_SPACING: f32 = 1.0;
+
+/// Interval between timing dots on the path preview, in seconds
+const TIMING_DOT_INTERVAL: f32 = 0.1;
+
+/// Easing applied to the segment leaving a keyframe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum PathEasing {
+    /// Constant speed
+    #[default]
+    Linear,
+    /// Start slow, end fast
+    EaseIn,
+    /// Start fast, end slow
+    EaseOut,
+    /// Slow at both ends
+    EaseInOut,
+    /// Hold the keyframe until the next one, then jump
+    Step,
+}
+
+impl PathEasing {
+    /// All easing curves, in UI order
+    pub const ALL: [PathEasing; 5] = [
+        PathEasing::Linear,
+        PathEasing::EaseIn,
+        PathEasing::EaseOut,
+        PathEasing::EaseInOut,
+        PathEasing::Step,
+    ];
+
+    /// Map linear progress `t` in `[0, 1]` through the curve
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            PathEasing::Linear => t,
+            PathEasing::EaseIn => t * t,
+            PathEasing::EaseOut => t * (2.0 - t),
+            PathEasing::EaseInOut => t * t * (3.0 - 2.0 * t),
+            PathEasing::Step => {
+                if t >= 1.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// Translation key for the easing name
+    pub fn key(self) -> &'static str {
+        match self {
+            PathEasing::Linear => "easing_linear",
+            PathEasing::EaseIn => "easing_ease_in",
+            PathEasing::EaseOut => "easing_ease_out",
+            PathEasing::EaseInOut => "easing_ease_in_out",
+            PathEasing::Step => "easing_step",
+        }
+    }
+}
+
+/// What happens when a path reaches its last keyframe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum PathLoopMode {
+    /// Stop at the last keyframe
+    Once,
+    /// Restart from the first keyframe (make the last keyframe match the first for a seamless loop)
+    #[default]
+    Loop,
+    /// Play backwards to the first keyframe, then forwards again
+    PingPong,
+}
+
+/// A single pose on a kinematic path
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+pub struct PathKeyframe {
+    /// World position
+    pub position: Vec2,
+    /// Rotation in radians; not wrapped, so `0 -> TAU` is a full turn
+    pub rotation: f32,
+    /// Time in seconds from the start of the path
+    pub time: f32,
+    /// Easing of the segment leaving this keyframe
+    pub easing: PathEasing,
+}
+
+/// Keyframed animation for a kinematic body
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component, Default)]
+pub struct KinematicPath {
+    /// Keyframes sorted by time
+    pub keyframes: Vec<PathKeyframe>,
+    /// Playback mode after the last keyframe
+    pub mode: PathLoopMode,
+    /// Seconds to skip at start, used to desynchronise identical platforms
+    pub time_offset: f32,
+    /// Whether the path drives the body
+    pub enabled: bool,
+    /// Seconds played since the path started
+    #[reflect(ignore)]
+    elapsed: f32,
+    /// Whether the body has been placed at the path start
+    #[reflect(ignore)]
+    started: bool,
+}
+
+impl Default for KinematicPath {
+    fn default() -> Self {
+        Self {
+            keyframes: Vec::new(),
+            mode: PathLoopMode::default(),
+            time_offset: 0.0,
+            enabled: true,
+            elapsed: 0.0,
+            started: false,
+        }
+    }
+}
+
+impl KinematicPath {
+    /// Path with a single keyframe at the given pose
+    pub fn from_pose(position: Vec2, rotation: f32) -> Self {
+        Self {
+            keyframes: vec![PathKeyframe {
+                position,
+                rotation,
+                ..default()
+            }],
+            ..default()
+        }
+    }
+
+    /// Time between the first and last keyframe
+    pub fn duration(&self) -> f32 {
+        match (self.keyframes.first(), self.keyframes.last()) {
+            (Some(first), Some(last)) => (last.time - first.time).max(0.0),
+            _ => 0.0,
+        }
+    }
+
+    /// Append a keyframe after the last one, keeping its rotation and easing
+    pub fn push_keyframe(&mut self, position: Vec2) -> usize {
+        let keyframe = match self.keyframes.last() {
+            Some(last) => PathKeyframe {
+                position,
+                time: last.time + DEFAULT_KEYFRAME_SPACING,
+                ..*last
+            },
+            None => PathKeyframe {
+                position,
+                ..default()
+            },
+        };
+        self.keyframes.push(keyframe);
+        self.keyframes.len() - 1
+    }
+
+    /// Restore time order after keyframe times were edited
+    pub fn sort_keyframes(&mut self) {
+        self.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+
+    /// Restart playback from the beginning
+    pub fn restart(&mut self) {
+        self.elapsed = 0.0;
+        self.started = false;
+    }
+
+    /// Map seconds of playback onto a keyframe time, honouring the loop mode
+    pub fn path_time(&self, elapsed: f32) -> f32 {
+        let Some(first) = self.keyframes.first() else {
+            return 0.0;
+        };
+        let duration = self.duration();
+        if duration <= 0.0 {
+            return first.time;
+        }
+
+        let t = (elapsed + self.time_offset).max(0.0);
+        let local = match self.mode {
+            PathLoopMode::Once => t.min(duration),
+            PathLoopMode::Loop => t.rem_euclid(duration),
+            PathLoopMode::PingPong => {
+                let phase = t.rem_euclid(duration * 2.0);
+                if phase > duration {
+                    duration * 2.0 - phase
+                } else {
+                    phase
+                }
+            }
+        };
+        first.time + local
+    }
+
+    /// Pose (position, rotation) at a keyframe time
+    pub fn sample(&self, time: f32) -> Option<(Vec2, f32)> {
+        let first = self.keyframes.first()?;
+        if self.keyframes.len() == 1 || time <= first.time {
+            return Some((first.position, first.rotation));
+        }
+
+        for pair in self.keyframes.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            if time <= to.time {
+                let span = to.time - from.time;
+                let t = if span > 0.0 {
+                    from.easing.apply((time - from.time) / span)
+                } else {
+                    1.0
+                };
+                return Some((
+                    from.position.lerp(to.position, t),
+                    from.rotation + (to.rotation - from.rotation) * t,
+                ));
+            }
+        }
+
+        let last = self.keyframes.last()?;
+        Some((last.position, last.rotation))
+    }
+}
+
+/// Runtime plugin driving kinematic bodies along their paths
+#[derive(Default)]
+pub struct KinematicPathPlugin;
+
+impl Plugin for KinematicPathPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<KinematicPath>()
+            .register_type::<PathKeyframe>()
+            .register_type::<PathEasing>()
+            .register_type::<PathLoopMode>()
+            .add_systems(FixedUpdate, drive_kinematic_paths);
+    }
+}
+
+/// Wrap an angle into the `(-PI, PI]` range
+fn wrap_angle(angle: f32) -> f32 {
+    let wrapped = (angle + PI).rem_euclid(TAU) - PI;
+    if wrapped <= -PI {
+        wrapped + TAU
+    } else {
+        wrapped
+    }
+}
+
+/// Set body velocities so it reaches the next path sample at the end of the step
+fn drive_kinematic_paths(
+    time: Res<Time>,
+    physics_time: Res<Time<Physics>>,
+    mut query: Query<
+        (
+            &mut KinematicPath,
+            &RigidBody,
+            &mut Position,
+            &mut Rotation,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        Without<RigidBodyDisabled>,
+    >,
+) {
+    if physics_time.is_paused() {
+        return;
+    }
+
+    let delta = time.delta_secs() * physics_time.relative_speed();
+    if delta <= 0.0 {
+        return;
+    }
+
+    for (mut path, rigid_body, mut position, mut rotation, mut linear, mut angular) in &mut query {
+        if !path.enabled || !rigid_body.is_kinematic() || path.keyframes.is_empty() {
+            continue;
+        }
+
+        // Place the body at the path start before the first step
+        if !path.started {
+            path.started = true;
+            if let Some((start, angle)) = path.sample(path.path_time(0.0)) {
+                position.0 = start;
+                *rotation = Rotation::radians(angle);
+            }
+        }
+
+        let previous = path.path_time(path.elapsed);
+        path.elapsed += delta;
+        let next = path.path_time(path.elapsed);
+
+        let Some((target, target_angle)) = path.sample(next) else {
+            continue;
+        };
+
+        // A loop wrapped around: jump back instead of sweeping through the level
+        if path.mode == PathLoopMode::Loop && next < previous {
+            position.0 = target;
+            *rotation = Rotation::radians(target_angle);
+            linear.0 = Vec2::ZERO;
+            angular.0 = 0.0;
+            continue;
+        }
+
+        linear.0 = (target - position.0) / delta;
+        angular.0 = wrap_angle(target_angle - rotation.as_radians()) / delta;
+    }
+}
+
+/// State for keyframe editing in path mode
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default)]
+pub struct PathEditState {
+    /// Kinematic body whose keyframes are shown
+    pub target: Option<Entity>,
+    /// Keyframe under the cursor
+    pub hovered: Option<usize>,
+    /// Keyframe being dragged
+    pub dragging: Option<usize>,
+    /// Keyframe edited in the tool panel
+    pub selected: Option<usize>,
+    /// Keyframe position before the drag started
+    pub drag_original: Option<Vec2>,
+    /// Keyframe time shown by the ghost preview
+    pub preview_time: f32,
+    /// Whether Shift is held to lock dragging to an axis
+    pub shift_pressed: bool,
+    /// Whether physics was already paused when the mode was entered
+    physics_was_paused: bool,
+}
+
+/// Editor plugin for authoring kinematic paths
+#[derive(Default)]
+pub struct KinematicPathEditorPlugin;
+
+impl Plugin for KinematicPathEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PathEditState>()
+            .add_systems(
+                OnEnter(crate::collider_tools::ToolMode::Path),
+                on_enter_path_mode,
+            )
+            .add_systems(
+                OnExit(crate::collider_tools::ToolMode::Path),
+                on_exit_path_mode,
+            )
+            .add_systems(
+                Update,
+                (update_path_target, handle_path_tool_input).chain().run_if(
+                    in_state(crate::collider_tools::ToolMode::Path).and(not(egui_wants_any_input)),
+                ),
+            )
+            .add_systems(
+                Update,
+                draw_kinematic_paths::<EditorGizmoConfigGroup>
+                    .run_if(in_state(crate::collider_tools::ToolMode::Path)),
+            )
+            .add_systems(Update, restart_paths_on_play);
+    }
+}
+
+/// Initialize path mode
+///
+/// Physics is paused so platforms hold still while keyframes are placed.
+fn on_enter_path_mode(
+    mut state: ResMut<PathEditState>,
+    mut physics_manager: ResMut<PhysicsManager>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    info!("Entering Path mode");
+    *state = PathEditState {
+        physics_was_paused: physics_manager.is_physics_paused(),
+        ..default()
+    };
+    physics_manager.pause(&mut physics_time);
+}
+
+/// Cleanup when exiting path mode
+fn on_exit_path_mode(
+    mut state: ResMut<PathEditState>,
+    mut physics_manager: ResMut<PhysicsManager>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    info!("Exiting Path mode");
+    let physics_was_paused = state.physics_was_paused;
+    *state = PathEditState::default();
+    if !physics_was_paused {
+        physics_manager.unpause(&mut physics_time);
+    }
+}
+
+/// Restart every path from its first keyframe when play starts from the beginning
+fn restart_paths_on_play(
+    mut play_events: EventReader<PlayFromStart>,
+    mut path_query: Query<&mut KinematicPath>,
+) {
+    if play_events.read().count() > 0 {
+        for mut path in &mut path_query {
+            path.restart();
+        }
+    }
+}
+
+/// Track the primary selection as the path target
+///
+/// Only kinematic bodies can follow a path; other selections clear the target.
+fn update_path_target(
+    mut state: ResMut<PathEditState>,
+    selection: Res<EditorSelection>,
+    body_query: Query<&RigidBody, With<Collider>>,
+) {
+    if state.dragging.is_some() {
+        return;
+    }
+
+    let target = selection
+        .primary()
+        .filter(|entity| matches!(body_query.get(*entity), Ok(RigidBody::Kinematic)));
+
+    if state.target != target {
+        state.target = target;
+        state.hovered = None;
+        state.selected = None;
+        state.preview_time = 0.0;
+    }
+}
+
+/// Handle mouse and keyboard input for path mode
+fn handle_path_tool_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut state: ResMut<PathEditState>,
+    mut body_query: Query<(&GlobalTransform, Option<&mut KinematicPath>)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<SpritePickingCamera>>,
+    window_query: Query<&Window>,
+) {
+    state.shift_pressed = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let ctrl_pressed = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+
+    let Some(target) = state.target else {
+        state.hovered = None;
+        return;
+    };
+    let Ok((body_transform, mut path)) = body_query.get_mut(target) else {
+        state.target = None;
+        state.hovered = None;
+        return;
+    };
+
+    // Cancel drag with Escape, restoring the keyframe position
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        if let (Some(index), Some(original), Some(path)) =
+            (state.dragging, state.drag_original, path.as_deref_mut())
+        {
+            if let Some(keyframe) = path.keyframes.get_mut(index) {
+                keyframe.position = original;
+            }
+            info!("Cancelled keyframe drag");
+        }
+        state.dragging = None;
+        state.drag_original = None;
+        return;
+    }
+
+    let (Ok((camera, camera_transform)), Ok(window)) =
+        (camera_query.single(), window_query.single())
+    else {
+        return;
+    };
+    let Some(mouse_pos) = get_mouse_world_position(window, camera, camera_transform) else {
+        return;
+    };
+
+    // Hover detection
+    if state.dragging.is_none() {
+        state.hovered = path.as_deref().and_then(|path| {
+            path.keyframes
+                .iter()
+                .enumerate()
+                .map(|(index, keyframe)| (index, keyframe.position.distance(mouse_pos)))
+                .filter(|(_, distance)| *distance <= KEYFRAME_PICK_RADIUS)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(index, _)| index)
+        });
+    }
+
+    // Right click deletes a keyframe, keeping at least the start pose
+    if mouse_input.just_pressed(MouseButton::Right) && state.dragging.is_none() {
+        if let (Some(index), Some(path)) = (state.hovered, path.as_deref_mut()) {
+            if path.keyframes.len() > 1 {
+                path.keyframes.remove(index);
+                state.hovered = None;
+                state.selected = None;
+                info!("Deleted keyframe {} of {:?}", index, target);
+            }
+        }
+        return;
+    }
+
+    if mouse_input.just_pressed(MouseButton::Left) && state.dragging.is_none() {
+        if let Some(index) = state.hovered {
+            // Start dragging an existing keyframe
+            state.dragging = Some(index);
+            state.selected = Some(index);
+            state.drag_original = path
+                .as_deref()
+                .and_then(|path| path.keyframes.get(index))
+                .map(|keyframe| keyframe.position);
+        } else if ctrl_pressed {
+            // Ctrl+Click appends a keyframe, starting the path at the current pose if needed
+            match path.as_deref_mut() {
+                Some(path) => {
+                    let index = path.push_keyframe(mouse_pos);
+                    state.selected = Some(index);
+                }
+                None => {
+                    let mut new_path = KinematicPath::from_pose(
+                        body_transform.translation().truncate(),
+                        body_transform.rotation().to_euler(EulerRot::ZYX).0,
+                    );
+                    let index = new_path.push_keyframe(mouse_pos);
+                    state.selected = Some(index);
+                    commands.entity(target).insert(new_path);
+                }
+            }
+            info!("Added keyframe to {:?} at {:?}", target, mouse_pos);
+        }
+        return;
+    }
+
+    let (Some(index), Some(original)) = (state.dragging, state.drag_original) else {
+        return;
+    };
+
+    // Update keyframe while dragging; Shift locks movement to the dominant axis
+    let mut offset = mouse_pos - original;
+    if state.shift_pressed {
+        if offset.x.abs() > offset.y.abs() {
+            offset.y = 0.0;
+        } else {
+            offset.x = 0.0;
+        }
+    }
+    if let Some(keyframe) = path
+        .as_deref_mut()
+        .and_then(|path| path.keyframes.get_mut(index))
+    {
+        keyframe.position = original + offset;
+    }
+
+    if mouse_input.just_released(MouseButton::Left) {
+        state.dragging = None;
+        state.drag_original = None;
+        info!(
+            "Moved keyframe {} of {:?} to {:?}",
+            index,
+            target,
+            original + offset
+        );
+    }
+}
+
+/// Draw paths, keyframe handles, timing dots and the ghost preview
+fn draw_kinematic_paths<Config: GizmoConfigGroup>(
+    mut gizmos: Gizmos<Config>,
+    state: Res<PathEditState>,
+    path_query: Query<(Entity, &KinematicPath, &Collider)>,
+    theme_colors: Res<crate::ui::theme_colors::EditorThemeColors>,
+) {
+    for (entity, path, collider) in path_query.iter() {
+        let is_target = state.target == Some(entity);
+        let line_color = if is_target {
+            theme_colors.path_line
+        } else {
+            theme_colors.path_line.with_alpha(0.35)
+        };
+
+        // Path segments
+        for pair in path.keyframes.windows(2) {
+            gizmos.line_2d(pair[0].position, pair[1].position, line_color);
+        }
+        if path.mode == PathLoopMode::Loop && path.keyframes.len() > 2 {
+            if let (Some(first), Some(last)) = (path.keyframes.first(), path.keyframes.last()) {
+                draw_dashed_line(&mut gizmos, last.position, first.position, line_color, 0.0);
+            }
+        }
+
+        if !is_target {
+            continue;
+        }
+
+        // Timing dots: spacing shows where easing speeds up or slows down
+        let duration = path.duration();
+        if let Some(first) = path.keyframes.first() {
+            let steps = (duration / TIMING_DOT_INTERVAL).floor() as usize;
+            for step in 1..steps {
+                let time = first.time + step as f32 * TIMING_DOT_INTERVAL;
+                if let Some((position, _)) = path.sample(time) {
+                    gizmos.circle_2d(position, 1.5, theme_colors.path_line);
+                }
+            }
+        }
+
+        // Keyframe handles with a rotation tick
+        for (index, keyframe) in path.keyframes.iter().enumerate() {
+            let active = state.hovered == Some(index)
+                || state.dragging == Some(index)
+                || state.selected == Some(index);
+            let color = if active {
+                theme_colors.path_keyframe_active
+            } else {
+                theme_colors.path_keyframe
+            };
+            gizmos.circle_2d(keyframe.position, 6.0, color);
+            gizmos.line_2d(
+                keyframe.position,
+                keyframe.position + Vec2::from_angle(keyframe.rotation) * 14.0,
+                color,
+            );
+        }
+
+        // Ghost of the body at the preview time
+        if let Some((position, rotation)) = path.sample(state.preview_time) {
+            let ghost = Transform::from_translation(position.extend(0.0))
+                .with_rotation(Quat::from_rotation_z(rotation));
+            draw_selection_outline(&mut gizmos, &ghost, collider, theme_colors.path_ghost, 0.0);
+        }
+    }
+}
@@ -0,0 +1,463 @@
+//! Scene validation for the Diagnostics panel
+//!
+//! The scene is checked twice a second for setups that load fine but misbehave once simulated:
+//! joints left behind by deleted bodies or anchors, colliders without area, joints that can
+//! never move, joints between bodies of very different mass, dynamic bodies that start inside
+//! each other, sensors nobody hears from, collision layers that rule out every contact and
+//! anchors that drifted off their collider.
+//!
+//! Each [`LintFinding`] names the entity to select for it and, when the problem has an obvious
+//! remedy, a [`LintFix`] applied through [`LintFixEvent`].
+
+use super::ColliderCreationState;
+use super::debug_render::anchor::AnchorPoint;
+use super::debug_render::joint::{AnchorUsedBy, JointConfig, VisualizedBy};
+use super::force_field::ForceField;
+use super::joint::regenerate_joints_for_anchor;
+use crate::selection::Selectable;
+use avian2d::parry::math::{Isometry, Vector as ParryVector};
+use avian2d::parry::query;
+use avian2d::parry::shape::TypedShape;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+
+/// Colliders with less area than this, in square world units, are reported as degenerate
+pub const MIN_COLLIDER_AREA: f32 = 1e-3;
+
+/// Dynamic bodies overlapping deeper than this, in world units, are reported
+pub const OVERLAP_TOLERANCE: f32 = 0.1;
+
+/// Anchors further than this from their collider, in world units, are reported
+pub const ANCHOR_TOLERANCE: f32 = 0.5;
+
+/// Scene validation plugin
+pub struct LintPlugin;
+
+impl Plugin for LintPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LintSettings>()
+            .register_type::<LintSettings>()
+            .init_resource::<SceneLint>()
+            .add_event::<LintFixEvent>()
+            .add_systems(
+                Update,
+                (
+                    update_scene_lint.run_if(on_timer(Duration::from_millis(500))),
+                    apply_lint_fixes,
+                ),
+            );
+    }
+}
+
+/// Scene validation options
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct LintSettings {
+    /// Check the scene in the background
+    pub enabled: bool,
+    /// Heaviest-to-lightest mass ratio across a joint before it is reported
+    pub max_mass_ratio: f32,
+}
+
+impl Default for LintSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_mass_ratio: 100.0,
+        }
+    }
+}
+
+/// Kind of problem found in the scene
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum LintKind {
+    /// A joint's body or anchor no longer exists
+    DanglingJoint,
+    /// A collider has no area
+    DegenerateCollider,
+    /// A joint connects two static bodies
+    StaticJoint,
+    /// A joint connects bodies with a mass ratio above [`LintSettings::max_mass_ratio`]
+    MassRatio,
+    /// Two dynamic bodies overlap
+    OverlappingBodies,
+    /// A sensor does not have [`CollisionEventsEnabled`]
+    SensorWithoutEvents,
+    /// A collider's layers rule out every contact
+    LayersNeverCollide,
+    /// An anchor lies outside its collider
+    AnchorOutsideCollider,
+}
+
+impl LintKind {
+    /// Translation key of the description
+    pub fn key(self) -> &'static str {
+        match self {
+            LintKind::DanglingJoint => "lint_dangling_joint",
+            LintKind::DegenerateCollider => "lint_degenerate_collider",
+            LintKind::StaticJoint => "lint_static_joint",
+            LintKind::MassRatio => "lint_mass_ratio",
+            LintKind::OverlappingBodies => "lint_overlapping_bodies",
+            LintKind::SensorWithoutEvents => "lint_sensor_without_events",
+            LintKind::LayersNeverCollide => "lint_layers_never_collide",
+            LintKind::AnchorOutsideCollider => "lint_anchor_outside_collider",
+        }
+    }
+}
+
+/// One-click remedy for a finding
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintFix {
+    /// Despawn a joint and its visualization, releasing its anchors
+    RemoveJoint {
+        joint: Entity,
+        visualization: Option<Entity>,
+        anchors: Vec<Entity>,
+    },
+    /// Despawn a collider
+    DeleteCollider(Entity),
+    /// Make a body dynamic
+    MakeDynamic(Entity),
+    /// Insert [`CollisionEventsEnabled`]
+    EnableCollisionEvents(Entity),
+    /// Put a collider back on the default collision layers
+    ResetCollisionLayers(Entity),
+    /// Move an anchor to a local position on its collider, rebuilding its joints
+    MoveAnchor { anchor: Entity, position: Vec2 },
+}
+
+impl LintFix {
+    /// Translation key of the button label
+    pub fn key(&self) -> &'static str {
+        match self {
+            LintFix::RemoveJoint { .. } => "lint_fix_remove_joint",
+            LintFix::DeleteCollider(_) => "lint_fix_delete_collider",
+            LintFix::MakeDynamic(_) => "lint_fix_make_dynamic",
+            LintFix::EnableCollisionEvents(_) => "lint_fix_enable_events",
+            LintFix::ResetCollisionLayers(_) => "lint_fix_reset_layers",
+            LintFix::MoveAnchor { .. } => "lint_fix_move_anchor",
+        }
+    }
+}
+
+/// Problem found in the scene
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintFinding {
+    pub kind: LintKind,
+    /// Entity selected to show the problem
+    pub entity: Entity,
+    /// Names and values involved, shown after the description
+    pub detail: String,
+    pub fix: Option<LintFix>,
+}
+
+/// Findings of the last check
+#[derive(Resource, Debug, Default)]
+pub struct SceneLint {
+    pub findings: Vec<LintFinding>,
+}
+
+/// Request to apply a fix
+#[derive(Event, Debug, Clone)]
+pub struct LintFixEvent(pub LintFix);
+
+/// Name of an entity for finding details
+fn label(world: &World, entity: Entity) -> String {
+    world
+        .get::<Name>(entity)
+        .map_or_else(|| entity.to_string(), |name| name.to_string())
+}
+
+/// Whether a body takes part in the simulation as a static body
+fn is_static(world: &World, entity: Entity) -> bool {
+    world
+        .get::<RigidBody>(entity)
+        .is_none_or(|body| body.is_static())
+}
+
+/// Area of a collider, or `None` for open shapes that are not meant to have one
+fn collider_area(collider: &Collider) -> Option<f32> {
+    let shape = collider.shape_scaled();
+    match shape.as_typed_shape() {
+        TypedShape::Polyline(_) | TypedShape::Segment(_) => None,
+        _ => Some(shape.mass_properties(1.0).mass()),
+    }
+}
+
+fn isometry(transform: &GlobalTransform) -> Isometry<f32> {
+    let (_, rotation, translation) = transform.to_scale_rotation_translation();
+    Isometry::new(
+        ParryVector::new(translation.x, translation.y),
+        rotation.to_euler(EulerRot::ZYX).0,
+    )
+}
+
+/// Check the scene for problems
+pub fn scene_lint(world: &World, settings: &LintSettings) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    let exists = |entity: Entity| world.get_entity(entity).is_ok();
+    let layers = |entity: Entity| {
+        world
+            .get::<CollisionLayers>(entity)
+            .copied()
+            .unwrap_or_default()
+    };
+
+    // Editor bodies, without previews and other helper colliders
+    let bodies: Vec<(Entity, &Collider, &GlobalTransform)> = world
+        .iter_entities()
+        .filter(|entity| entity.contains::<Selectable>())
+        .filter_map(|entity| {
+            Some((
+                entity.id(),
+                entity.get::<Collider>()?,
+                entity.get::<GlobalTransform>()?,
+            ))
+        })
+        .collect();
+
+    let mut jointed_pairs = Vec::new();
+    for entity in world.iter_entities() {
+        let Some(config) = entity.get::<JointConfig>() else {
+            continue;
+        };
+        let visualization = entity.get::<VisualizedBy>().and_then(VisualizedBy::get);
+        let shown = visualization.unwrap_or(entity.id());
+        let (parent, child) = (config.parent_entity, config.child_entity);
+
+        let missing_anchor = [
+            (config.anchor_a, config.anchor_a_is_anchor),
+            (config.anchor_b, config.anchor_b_is_anchor),
+        ]
+        .into_iter()
+        .any(|(anchor, is_anchor)| is_anchor && world.get::<AnchorPoint>(anchor).is_none());
+        if !exists(parent) || !exists(child) || missing_anchor {
+            findings.push(LintFinding {
+                kind: LintKind::DanglingJoint,
+                entity: shown,
+                detail: entity.id().to_string(),
+                fix: Some(LintFix::RemoveJoint {
+                    joint: entity.id(),
+                    visualization,
+                    anchors: [config.anchor_a, config.anchor_b]
+                        .into_iter()
+                        .filter(|&anchor| world.get::<AnchorPoint>(anchor).is_some())
+                        .collect(),
+                }),
+            });
+            continue;
+        }
+
+        let detail = format!("{} – {}", label(world, parent), label(world, child));
+        if is_static(world, parent) && is_static(world, child) {
+            findings.push(LintFinding {
+                kind: LintKind::StaticJoint,
+                entity: shown,
+                detail: detail.clone(),
+                fix: Some(LintFix::MakeDynamic(child)),
+            });
+        }
+
+        let mass = |body: Entity| {
+            let dynamic = world
+                .get::<RigidBody>(body)
+                .is_some_and(|body| body.is_dynamic());
+            world
+                .get::<ComputedMass>(body)
+                .map(|mass| mass.value())
+                .filter(|mass| dynamic && mass.is_finite() && *mass > 0.0)
+        };
+        if let (Some(a), Some(b)) = (mass(parent), mass(child)) {
+            let ratio = a.max(b) / a.min(b);
+            if ratio > settings.max_mass_ratio {
+                findings.push(LintFinding {
+                    kind: LintKind::MassRatio,
+                    entity: shown,
+                    detail: format!("{detail} ({ratio:.0}:1)"),
+                    fix: None,
+                });
+            }
+        }
+
+        if config.joint_config_details.common().disable_collision {
+            jointed_pairs.push((parent, child));
+        }
+    }
+
+    for (index, &(entity, collider, transform)) in bodies.iter().enumerate() {
+        if collider_area(collider).is_some_and(|area| area.is_nan() || area <= MIN_COLLIDER_AREA) {
+            findings.push(LintFinding {
+                kind: LintKind::DegenerateCollider,
+                entity,
+                detail: label(world, entity),
+                fix: Some(LintFix::DeleteCollider(entity)),
+            });
+        }
+
+        // Force fields are sensors that act without events
+        if world.get::<Sensor>(entity).is_some()
+            && world.get::<CollisionEventsEnabled>(entity).is_none()
+            && world.get::<ForceField>(entity).is_none()
+        {
+            findings.push(LintFinding {
+                kind: LintKind::SensorWithoutEvents,
+                entity,
+                detail: label(world, entity),
+                fix: Some(LintFix::EnableCollisionEvents(entity)),
+            });
+        }
+
+        let own_layers = layers(entity);
+        let isolated = own_layers.memberships.0 == 0
+            || own_layers.filters.0 == 0
+            || (bodies.len() > 1
+                && bodies.iter().all(|&(other, _, _)| {
+                    other == entity || !own_layers.interacts_with(layers(other))
+                }));
+        if isolated {
+            findings.push(LintFinding {
+                kind: LintKind::LayersNeverCollide,
+                entity,
+                detail: label(world, entity),
+                fix: Some(LintFix::ResetCollisionLayers(entity)),
+            });
+        }
+
+        let dynamic_solid = |entity: Entity| {
+            world
+                .get::<RigidBody>(entity)
+                .is_some_and(|body| body.is_dynamic())
+                && world.get::<Sensor>(entity).is_none()
+        };
+        if !dynamic_solid(entity) {
+            continue;
+        }
+        for &(other, other_collider, other_transform) in &bodies[index + 1..] {
+            if !dynamic_solid(other)
+                || !own_layers.interacts_with(layers(other))
+                || jointed_pairs.contains(&(entity, other))
+                || jointed_pairs.contains(&(other, entity))
+            {
+                continue;
+            }
+            let overlapping = query::contact(
+                &isometry(transform),
+                &**collider.shape_scaled(),
+                &isometry(other_transform),
+                &**other_collider.shape_scaled(),
+                0.0,
+            )
+            .ok()
+            .flatten()
+            .is_some_and(|contact| contact.dist < -OVERLAP_TOLERANCE);
+            if overlapping {
+                findings.push(LintFinding {
+                    kind: LintKind::OverlappingBodies,
+                    entity,
+                    detail: format!("{} – {}", label(world, entity), label(world, other)),
+                    fix: None,
+                });
+            }
+        }
+    }
+
+    for entity in world.iter_entities() {
+        let Some(anchor) = entity.get::<AnchorPoint>() else {
+            continue;
+        };
+        let Some(collider) = world.get::<Collider>(anchor.parent_entity) else {
+            continue;
+        };
+        // Anchors are stored relative to the body origin, in the body's rotated frame
+        let (closest, inside) =
+            collider.project_point(Vec2::ZERO, 0.0, anchor.local_anchor_position, true);
+        if !inside && closest.distance(anchor.local_anchor_position) > ANCHOR_TOLERANCE {
+            findings.push(LintFinding {
+                kind: LintKind::AnchorOutsideCollider,
+                entity: entity.id(),
+                detail: label(world, anchor.parent_entity),
+                fix: Some(LintFix::MoveAnchor {
+                    anchor: entity.id(),
+                    position: closest,
+                }),
+            });
+        }
+    }
+
+    findings
+}
+
+/// Re-check the scene
+fn update_scene_lint(world: &mut World) {
+    let settings = world.resource::<LintSettings>().clone();
+    let findings = if settings.enabled {
+        scene_lint(world, &settings)
+    } else {
+        Vec::new()
+    };
+    let mut lint = world.resource_mut::<SceneLint>();
+    if lint.findings != findings {
+        lint.findings = findings;
+    }
+}
+
+/// Apply requested fixes
+fn apply_lint_fixes(
+    mut events: EventReader<LintFixEvent>,
+    mut commands: Commands,
+    mut state: ResMut<ColliderCreationState>,
+    mut anchor_query: Query<&mut AnchorPoint>,
+    all_anchor_used_by_query: Query<(Entity, &AnchorUsedBy)>,
+    joint_config_query: Query<&JointConfig>,
+    visualized_by_query: Query<&VisualizedBy>,
+) {
+    for LintFixEvent(fix) in events.read() {
+        match fix {
+            LintFix::RemoveJoint {
+                joint,
+                visualization,
+                anchors,
+            } => {
+                for &anchor in anchors {
+                    if let Ok(mut anchor) = anchor_query.get_mut(anchor) {
+                        anchor.in_joint = false;
+                    }
+                }
+                if let Some(visualization) = visualization {
+                    commands.entity(*visualization).try_despawn();
+                }
+                commands.entity(*joint).try_despawn();
+            }
+            LintFix::DeleteCollider(entity) => {
+                commands.entity(*entity).try_despawn();
+                state.created_colliders.retain(|&e| e != *entity);
+            }
+            LintFix::MakeDynamic(entity) => {
+                commands.entity(*entity).insert(RigidBody::Dynamic);
+            }
+            LintFix::EnableCollisionEvents(entity) => {
+                commands.entity(*entity).insert(CollisionEventsEnabled);
+            }
+            LintFix::ResetCollisionLayers(entity) => {
+                commands.entity(*entity).insert(CollisionLayers::default());
+            }
+            LintFix::MoveAnchor { anchor, position } => {
+                let Ok(mut anchor_point) = anchor_query.get_mut(*anchor) else {
+                    continue;
+                };
+                anchor_point.local_anchor_position = *position;
+                regenerate_joints_for_anchor(
+                    &mut commands,
+                    *anchor,
+                    &anchor_query.as_readonly(),
+                    &all_anchor_used_by_query,
+                    &joint_config_query,
+                    &visualized_by_query,
+                );
+            }
+        }
+        info!("Applied diagnostics fix {:?}", fix);
+    }
+}
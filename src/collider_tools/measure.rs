@@ -0,0 +1,829 @@
+//! Measurement tools, rulers and guides
+//!
+//! Measure mode places distance and angle dimensions in the viewport. Each point snaps to the
+//! nearest anchor, collider vertex or body origin under the cursor, and snapped points are
+//! stored relative to that entity, so a dimension follows the bodies it was measured on.
+//! Completed dimensions are kept in [`MeasureAnnotations`] until they are deleted.
+//!
+//! Guides are infinite horizontal or vertical lines dragged out of the screen-edge rulers.
+//! Measure points, gizmo translation and anchor placement snap to them.
+//!
+//! ## Controls
+//! - Click: place the next point (two for a distance, vertex then two arms for an angle)
+//! - Shift: constrain a distance to 15° steps
+//! - Right click: delete the hovered dimension or guide
+//! - Drag a guide to move it
+//! - Escape: cancel the measurement in progress
+
+use super::EditorGizmoConfigGroup;
+use super::debug_render::AnchorPoint;
+use super::units::UnitConverter;
+use super::utils::{find_closest_vertex, get_mouse_world_position};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::input::egui_wants_any_input;
+use std::f32::consts::PI;
+
+/// Distance within which measure points snap to anchors, vertices and origins, in world units
+const SNAP_RADIUS: f32 = 12.0;
+
+/// Distance within which points snap to guides, in world units
+pub const GUIDE_SNAP_DISTANCE: f32 = 8.0;
+
+/// Pick radius for dimensions and guides, in world units
+const PICK_RADIUS: f32 = 6.0;
+
+/// Angle increment used when Shift constrains a distance (15 degrees)
+const SNAP_ANGLE: f32 = PI / 12.0;
+
+/// Length of the end ticks drawn on distance dimensions
+const TICK_LENGTH: f32 = 6.0;
+
+/// Radius of the arc drawn on angle dimensions
+const ANGLE_ARC_RADIUS: f32 = 30.0;
+
+/// A point a dimension is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum MeasurePoint {
+    /// Fixed world position
+    World(Vec2),
+    /// Point fixed in an entity's local space, such as a vertex, origin or anchor
+    Attached {
+        /// Entity the point moves with
+        entity: Entity,
+        /// Position in the entity's local space
+        local: Vec2,
+    },
+}
+
+impl MeasurePoint {
+    /// World position, given a lookup for entity transforms
+    ///
+    /// Returns `None` when the attached entity no longer exists.
+    pub fn resolve(
+        &self,
+        transform_of: impl Fn(Entity) -> Option<GlobalTransform>,
+    ) -> Option<Vec2> {
+        match *self {
+            MeasurePoint::World(position) => Some(position),
+            MeasurePoint::Attached { entity, local } => transform_of(entity)
+                .map(|transform| transform.transform_point(local.extend(0.0)).truncate()),
+        }
+    }
+}
+
+/// What a measure point snapped to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum MeasureSnap {
+    /// Nothing nearby; the raw cursor position
+    Free,
+    /// An anchor point
+    Anchor,
+    /// A collider vertex
+    Vertex,
+    /// A body origin
+    Origin,
+    /// One or two guide lines
+    Guide,
+}
+
+/// Kind of dimension placed by the measure tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum MeasureKind {
+    /// Distance between two points
+    #[default]
+    Distance,
+    /// Angle at a vertex between two arms
+    Angle,
+}
+
+impl MeasureKind {
+    /// Number of points that complete a dimension
+    pub fn point_count(self) -> usize {
+        match self {
+            MeasureKind::Distance => 2,
+            MeasureKind::Angle => 3,
+        }
+    }
+}
+
+/// A persistent measurement
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum Dimension {
+    /// Distance from `start` to `end`
+    Distance {
+        /// First point
+        start: MeasurePoint,
+        /// Second point
+        end: MeasurePoint,
+    },
+    /// Angle at `vertex` from the `from` arm to the `to` arm
+    Angle {
+        /// Corner of the angle
+        vertex: MeasurePoint,
+        /// End of the first arm
+        from: MeasurePoint,
+        /// End of the second arm
+        to: MeasurePoint,
+    },
+}
+
+impl Dimension {
+    /// Build a dimension from the points placed by the tool
+    pub fn from_points(kind: MeasureKind, points: &[MeasurePoint]) -> Option<Self> {
+        match (kind, points) {
+            (MeasureKind::Distance, [start, end]) => Some(Dimension::Distance {
+                start: *start,
+                end: *end,
+            }),
+            (MeasureKind::Angle, [vertex, from, to]) => Some(Dimension::Angle {
+                vertex: *vertex,
+                from: *from,
+                to: *to,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Current value, or `None` when an attached entity no longer exists
+    pub fn measure(
+        &self,
+        transform_of: impl Fn(Entity) -> Option<GlobalTransform> + Copy,
+    ) -> Option<MeasuredValue> {
+        match self {
+            Dimension::Distance { start, end } => Some(MeasuredValue::Distance(
+                end.resolve(transform_of)? - start.resolve(transform_of)?,
+            )),
+            Dimension::Angle { vertex, from, to } => {
+                let vertex = vertex.resolve(transform_of)?;
+                Some(MeasuredValue::Angle(
+                    angle_between(
+                        from.resolve(transform_of)? - vertex,
+                        to.resolve(transform_of)? - vertex,
+                    )
+                    .abs(),
+                ))
+            }
+        }
+    }
+
+    /// Where the readout of the dimension is placed
+    pub fn label_position(
+        &self,
+        transform_of: impl Fn(Entity) -> Option<GlobalTransform> + Copy,
+    ) -> Option<Vec2> {
+        match self {
+            Dimension::Distance { start, end } => {
+                Some((start.resolve(transform_of)? + end.resolve(transform_of)?) * 0.5)
+            }
+            Dimension::Angle { vertex, .. } => vertex.resolve(transform_of),
+        }
+    }
+
+    /// Points of the dimension, in placement order
+    pub fn points(&self) -> Vec<MeasurePoint> {
+        match *self {
+            Dimension::Distance { start, end } => vec![start, end],
+            Dimension::Angle { vertex, from, to } => vec![vertex, from, to],
+        }
+    }
+}
+
+/// Value of a dimension at its current point positions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeasuredValue {
+    /// Offset from start to end
+    Distance(Vec2),
+    /// Unsigned angle between the arms, in radians
+    Angle(f32),
+}
+
+impl MeasuredValue {
+    /// Readout text, e.g. `2.50 m  Δx 2.00  Δy 1.50  36.9°`
+    pub fn format(&self, units: &UnitConverter) -> String {
+        match *self {
+            MeasuredValue::Distance(delta) => format!(
+                "{}  Δx {:.*}  Δy {:.*}  {:.1}°",
+                units.format_length(delta.length()),
+                units.decimals(),
+                units.to_display(delta.x),
+                units.decimals(),
+                units.to_display(delta.y),
+                delta.y.atan2(delta.x).to_degrees()
+            ),
+            MeasuredValue::Angle(radians) => format!("{:.1}°", radians.to_degrees()),
+        }
+    }
+}
+
+/// Dimensions kept in the viewport
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource, Default)]
+pub struct MeasureAnnotations {
+    /// Completed dimensions
+    pub dimensions: Vec<Dimension>,
+    /// Whether dimensions are shown outside measure mode
+    pub visible: bool,
+}
+
+impl Default for MeasureAnnotations {
+    fn default() -> Self {
+        Self {
+            dimensions: Vec::new(),
+            visible: true,
+        }
+    }
+}
+
+/// Orientation of a guide line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum GuideAxis {
+    /// Vertical line at a fixed x
+    Vertical,
+    /// Horizontal line at a fixed y
+    Horizontal,
+}
+
+/// Guide lines dragged out of the rulers
+///
+/// Saved with the scene, since guides usually mark level dimensions such as jump heights.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct MeasureGuides {
+    /// X positions of vertical guides
+    pub vertical: Vec<f32>,
+    /// Y positions of horizontal guides
+    pub horizontal: Vec<f32>,
+    /// Whether points, gizmo moves and anchors snap to guides
+    pub snap_enabled: bool,
+}
+
+impl Default for MeasureGuides {
+    fn default() -> Self {
+        Self {
+            vertical: Vec::new(),
+            horizontal: Vec::new(),
+            snap_enabled: true,
+        }
+    }
+}
+
+impl MeasureGuides {
+    /// Guide positions along one axis
+    pub fn positions(&self, axis: GuideAxis) -> &[f32] {
+        match axis {
+            GuideAxis::Vertical => &self.vertical,
+            GuideAxis::Horizontal => &self.horizontal,
+        }
+    }
+
+    /// Mutable guide positions along one axis
+    pub fn positions_mut(&mut self, axis: GuideAxis) -> &mut Vec<f32> {
+        match axis {
+            GuideAxis::Vertical => &mut self.vertical,
+            GuideAxis::Horizontal => &mut self.horizontal,
+        }
+    }
+
+    /// Snap each coordinate of a point to the nearest guide within `distance`
+    ///
+    /// Returns the snapped point and whether any coordinate moved.
+    pub fn snap_point(&self, point: Vec2, distance: f32) -> (Vec2, bool) {
+        if !self.snap_enabled {
+            return (point, false);
+        }
+
+        let nearest = |guides: &[f32], value: f32| {
+            guides
+                .iter()
+                .copied()
+                .filter(|guide| (guide - value).abs() <= distance)
+                .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+        };
+
+        let x = nearest(&self.vertical, point.x);
+        let y = nearest(&self.horizontal, point.y);
+        (
+            Vec2::new(x.unwrap_or(point.x), y.unwrap_or(point.y)),
+            x.is_some() || y.is_some(),
+        )
+    }
+
+    /// Guide nearest to a point within `distance`
+    pub fn guide_at(&self, point: Vec2, distance: f32) -> Option<(GuideAxis, usize)> {
+        let vertical = self
+            .vertical
+            .iter()
+            .enumerate()
+            .map(|(index, x)| (GuideAxis::Vertical, index, (x - point.x).abs()));
+        let horizontal = self
+            .horizontal
+            .iter()
+            .enumerate()
+            .map(|(index, y)| (GuideAxis::Horizontal, index, (y - point.y).abs()));
+
+        vertical
+            .chain(horizontal)
+            .filter(|(_, _, d)| *d <= distance)
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(axis, index, _)| (axis, index))
+    }
+}
+
+/// State of the measure tool
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default)]
+pub struct MeasureState {
+    /// Kind of dimension being placed
+    pub kind: MeasureKind,
+    /// Points placed so far for the dimension in progress
+    pub points: Vec<MeasurePoint>,
+    /// Snapped point under the cursor
+    pub cursor: Option<(MeasurePoint, MeasureSnap)>,
+    /// Dimension under the cursor
+    pub hovered_dimension: Option<usize>,
+    /// Guide under the cursor
+    pub hovered_guide: Option<(GuideAxis, usize)>,
+    /// Guide being dragged in the viewport
+    pub dragging_guide: Option<(GuideAxis, usize)>,
+    /// Guide being dragged out of a ruler, with its current position
+    pub ruler_drag: Option<(GuideAxis, f32)>,
+}
+
+impl MeasureState {
+    /// Dimension from the placed points to the cursor
+    ///
+    /// While an angle only has its vertex, the first arm is previewed as a distance.
+    pub fn preview(&self) -> Option<Dimension> {
+        let (cursor, _) = self.cursor?;
+        let mut points = self.points.clone();
+        points.push(cursor);
+        match self.kind {
+            MeasureKind::Angle if points.len() == 2 => {
+                Dimension::from_points(MeasureKind::Distance, &points)
+            }
+            kind => Dimension::from_points(kind, &points),
+        }
+    }
+}
+
+/// Plugin for the measure tool, rulers and guides
+#[derive(Default)]
+pub struct MeasurePlugin;
+
+impl Plugin for MeasurePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MeasureState>()
+            .init_resource::<MeasureAnnotations>()
+            .init_resource::<MeasureGuides>()
+            .register_type::<MeasureAnnotations>()
+            .register_type::<MeasureGuides>()
+            .add_systems(
+                OnExit(crate::collider_tools::ToolMode::Measure),
+                on_exit_measure_mode,
+            )
+            .add_systems(
+                Update,
+                handle_measure_input.run_if(
+                    in_state(crate::collider_tools::ToolMode::Measure)
+                        .and(not(egui_wants_any_input)),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    draw_measure_preview::<EditorGizmoConfigGroup>
+                        .run_if(in_state(crate::collider_tools::ToolMode::Measure)),
+                    draw_dimensions::<EditorGizmoConfigGroup>,
+                    draw_guides::<EditorGizmoConfigGroup>,
+                    prune_dimensions,
+                ),
+            );
+    }
+}
+
+/// Drop the measurement in progress when leaving measure mode
+fn on_exit_measure_mode(mut state: ResMut<MeasureState>) {
+    let kind = state.kind;
+    *state = MeasureState { kind, ..default() };
+}
+
+/// Remove dimensions whose bodies were despawned
+fn prune_dimensions(
+    mut annotations: ResMut<MeasureAnnotations>,
+    transform_query: Query<&GlobalTransform>,
+) {
+    let resolves = |dimension: &Dimension| {
+        dimension.points().iter().all(|point| {
+            point
+                .resolve(|entity| transform_query.get(entity).ok().copied())
+                .is_some()
+        })
+    };
+
+    if !annotations.dimensions.iter().all(resolves) {
+        annotations.dimensions.retain(resolves);
+    }
+}
+
+/// Find the point the cursor snaps to
+///
+/// Anchors, vertices and origins within [`SNAP_RADIUS`] win over guides, and the nearest of
+/// them is used.
+pub(crate) fn find_measure_snap<'a>(
+    cursor: Vec2,
+    colliders: impl Iterator<Item = (Entity, &'a GlobalTransform, &'a Collider)>,
+    anchors: impl Iterator<Item = (Entity, &'a GlobalTransform)>,
+    guides: &MeasureGuides,
+) -> (MeasurePoint, MeasureSnap) {
+    let mut best: Option<(f32, MeasurePoint, MeasureSnap)> = None;
+    let mut consider = |distance: f32, point: MeasurePoint, snap: MeasureSnap| {
+        if distance <= SNAP_RADIUS
+            && best.is_none_or(|(best_distance, ..)| distance < best_distance)
+        {
+            best = Some((distance, point, snap));
+        }
+    };
+
+    for (entity, transform) in anchors {
+        let position = transform.translation().truncate();
+        consider(
+            position.distance(cursor),
+            MeasurePoint::Attached {
+                entity,
+                local: Vec2::ZERO,
+            },
+            MeasureSnap::Anchor,
+        );
+    }
+
+    for (entity, transform, collider) in colliders {
+        let origin = transform.translation().truncate();
+        consider(
+            origin.distance(cursor),
+            MeasurePoint::Attached {
+                entity,
+                local: Vec2::ZERO,
+            },
+            MeasureSnap::Origin,
+        );
+
+        if let Some(vertex) = find_closest_vertex(cursor, collider, transform) {
+            let local = transform
+                .compute_matrix()
+                .inverse()
+                .transform_point(vertex.extend(0.0))
+                .truncate();
+            consider(
+                vertex.distance(cursor),
+                MeasurePoint::Attached { entity, local },
+                MeasureSnap::Vertex,
+            );
+        }
+    }
+
+    if let Some((_, point, snap)) = best {
+        return (point, snap);
+    }
+
+    match guides.snap_point(cursor, GUIDE_SNAP_DISTANCE) {
+        (snapped, true) => (MeasurePoint::World(snapped), MeasureSnap::Guide),
+        _ => (MeasurePoint::World(cursor), MeasureSnap::Free),
+    }
+}
+
+/// Constrain `point` so the segment from `origin` is a multiple of 15°
+fn constrain_angle(origin: Vec2, point: Vec2) -> Vec2 {
+    let offset = point - origin;
+    let length = offset.length();
+    if length <= f32::EPSILON {
+        return point;
+    }
+    let angle = (offset.y.atan2(offset.x) / SNAP_ANGLE).round() * SNAP_ANGLE;
+    origin + Vec2::from_angle(angle) * length
+}
+
+/// Distance from a point to a segment
+fn distance_to_segment(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let t = if segment.length_squared() > f32::EPSILON {
+        ((point - start).dot(segment) / segment.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    point.distance(start + segment * t)
+}
+
+/// Handle mouse and keyboard input for measure mode
+fn handle_measure_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut state: ResMut<MeasureState>,
+    mut annotations: ResMut<MeasureAnnotations>,
+    mut guides: ResMut<MeasureGuides>,
+    collider_query: Query<(Entity, &GlobalTransform, &Collider)>,
+    anchor_query: Query<(Entity, &GlobalTransform), With<AnchorPoint>>,
+    transform_query: Query<&GlobalTransform>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<SpritePickingCamera>>,
+    window_query: Query<&Window>,
+) {
+    // Cancel the measurement in progress with Escape
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        state.points.clear();
+        state.dragging_guide = None;
+        return;
+    }
+
+    let (Ok((camera, camera_transform)), Ok(window)) =
+        (camera_query.single(), window_query.single())
+    else {
+        return;
+    };
+    let Some(mouse_pos) = get_mouse_world_position(window, camera, camera_transform) else {
+        state.cursor = None;
+        return;
+    };
+    let transform_of = |entity: Entity| transform_query.get(entity).ok().copied();
+
+    // Move the dragged guide
+    if let Some((axis, index)) = state.dragging_guide {
+        if let Some(position) = guides.positions_mut(axis).get_mut(index) {
+            *position = match axis {
+                GuideAxis::Vertical => mouse_pos.x,
+                GuideAxis::Horizontal => mouse_pos.y,
+            };
+        }
+        if mouse_input.just_released(MouseButton::Left) {
+            state.dragging_guide = None;
+        }
+        return;
+    }
+
+    // Snap the cursor
+    let (mut point, snap) = find_measure_snap(
+        mouse_pos,
+        collider_query.iter(),
+        anchor_query.iter(),
+        &guides,
+    );
+    let shift_pressed = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if shift_pressed && snap == MeasureSnap::Free {
+        if let Some(previous) = state.points.last().and_then(|p| p.resolve(transform_of)) {
+            point = MeasurePoint::World(constrain_angle(previous, mouse_pos));
+        }
+    }
+    state.cursor = Some((point, snap));
+
+    // Hover detection for existing dimensions and guides
+    if state.points.is_empty() {
+        state.hovered_dimension = annotations.dimensions.iter().position(|dimension| {
+            let points: Vec<Vec2> = dimension
+                .points()
+                .iter()
+                .filter_map(|point| point.resolve(transform_of))
+                .collect();
+            if points.len() < 2 {
+                return false;
+            }
+            let segments: Vec<(Vec2, Vec2)> = match dimension {
+                Dimension::Distance { .. } => points.windows(2).map(|p| (p[0], p[1])).collect(),
+                Dimension::Angle { .. } => points[1..].iter().map(|p| (points[0], *p)).collect(),
+            };
+            segments
+                .iter()
+                .any(|(start, end)| distance_to_segment(mouse_pos, *start, *end) <= PICK_RADIUS)
+        });
+        let guide_reachable = (state.hovered_dimension.is_none() && snap == MeasureSnap::Free)
+            || snap == MeasureSnap::Guide;
+        state.hovered_guide = if guide_reachable {
+            guides.guide_at(mouse_pos, PICK_RADIUS)
+        } else {
+            None
+        };
+    } else {
+        state.hovered_dimension = None;
+        state.hovered_guide = None;
+    }
+
+    // Right click deletes the hovered dimension or guide
+    if mouse_input.just_pressed(MouseButton::Right) {
+        if let Some(index) = state.hovered_dimension.take() {
+            annotations.dimensions.remove(index);
+            info!("Deleted dimension {}", index);
+        } else if let Some((axis, index)) = state.hovered_guide.take() {
+            guides.positions_mut(axis).remove(index);
+            info!("Deleted {:?} guide {}", axis, index);
+        }
+        return;
+    }
+
+    if !mouse_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    // Start dragging a guide when nothing snaps closer
+    if state.points.is_empty() && matches!(snap, MeasureSnap::Free | MeasureSnap::Guide) {
+        if let Some(guide) = state.hovered_guide {
+            state.dragging_guide = Some(guide);
+            return;
+        }
+    }
+
+    state.points.push(point);
+    let kind = state.kind;
+    if state.points.len() >= kind.point_count() {
+        if let Some(dimension) = Dimension::from_points(kind, &state.points) {
+            annotations.dimensions.push(dimension);
+            info!("Added {:?} dimension", kind);
+        }
+        state.points.clear();
+    }
+}
+
+/// Draw the measurement in progress and the cursor snap marker
+fn draw_measure_preview<Config: GizmoConfigGroup>(
+    mut gizmos: Gizmos<Config>,
+    state: Res<MeasureState>,
+    transform_query: Query<&GlobalTransform>,
+    theme_colors: Res<crate::ui::theme_colors::EditorThemeColors>,
+) {
+    let transform_of = |entity: Entity| transform_query.get(entity).ok().copied();
+    let Some((cursor, snap)) = state.cursor else {
+        return;
+    };
+    let Some(cursor_pos) = cursor.resolve(transform_of) else {
+        return;
+    };
+
+    // Snap marker: square for vertices, circle for anchors, cross for origins
+    let color = theme_colors.measure_snap;
+    match snap {
+        MeasureSnap::Vertex => {
+            gizmos.rect_2d(
+                Isometry2d::from_translation(cursor_pos),
+                Vec2::splat(8.0),
+                color,
+            );
+        }
+        MeasureSnap::Anchor => {
+            gizmos.circle_2d(cursor_pos, 5.0, color);
+        }
+        MeasureSnap::Origin => {
+            gizmos.line_2d(
+                cursor_pos - Vec2::X * 5.0,
+                cursor_pos + Vec2::X * 5.0,
+                color,
+            );
+            gizmos.line_2d(
+                cursor_pos - Vec2::Y * 5.0,
+                cursor_pos + Vec2::Y * 5.0,
+                color,
+            );
+        }
+        MeasureSnap::Guide => {
+            gizmos.circle_2d(cursor_pos, 3.0, color);
+        }
+        MeasureSnap::Free => {}
+    }
+
+    if state.points.is_empty() {
+        return;
+    }
+
+    if let Some(preview) = state.preview() {
+        draw_dimension(
+            &mut gizmos,
+            &preview,
+            transform_of,
+            theme_colors.measure_line,
+        );
+    }
+}
+
+/// Draw the kept dimensions
+fn draw_dimensions<Config: GizmoConfigGroup>(
+    mut gizmos: Gizmos<Config>,
+    state: Res<MeasureState>,
+    annotations: Res<MeasureAnnotations>,
+    mode: Res<State<crate::collider_tools::ToolMode>>,
+    transform_query: Query<&GlobalTransform>,
+    theme_colors: Res<crate::ui::theme_colors::EditorThemeColors>,
+) {
+    let in_measure_mode = *mode.get() == crate::collider_tools::ToolMode::Measure;
+    if !annotations.visible && !in_measure_mode {
+        return;
+    }
+
+    let transform_of = |entity: Entity| transform_query.get(entity).ok().copied();
+    for (index, dimension) in annotations.dimensions.iter().enumerate() {
+        let color = if in_measure_mode && state.hovered_dimension == Some(index) {
+            theme_colors.measure_snap
+        } else {
+            theme_colors.measure_line
+        };
+        draw_dimension(&mut gizmos, dimension, transform_of, color);
+    }
+}
+
+/// Draw one dimension: a ticked line for distances, two arms and an arc for angles
+fn draw_dimension<Config: GizmoConfigGroup>(
+    gizmos: &mut Gizmos<Config>,
+    dimension: &Dimension,
+    transform_of: impl Fn(Entity) -> Option<GlobalTransform> + Copy,
+    color: Color,
+) {
+    match dimension {
+        Dimension::Distance { start, end } => {
+            let (Some(start), Some(end)) = (start.resolve(transform_of), end.resolve(transform_of))
+            else {
+                return;
+            };
+            gizmos.line_2d(start, end, color);
+            let normal = (end - start).normalize_or_zero().perp() * TICK_LENGTH;
+            gizmos.line_2d(start - normal, start + normal, color);
+            gizmos.line_2d(end - normal, end + normal, color);
+        }
+        Dimension::Angle { vertex, from, to } => {
+            let (Some(vertex), Some(from), Some(to)) = (
+                vertex.resolve(transform_of),
+                from.resolve(transform_of),
+                to.resolve(transform_of),
+            ) else {
+                return;
+            };
+            gizmos.line_2d(vertex, from, color);
+            gizmos.line_2d(vertex, to, color);
+
+            let start_angle = (from - vertex).to_angle();
+            let sweep = angle_between(from - vertex, to - vertex);
+            gizmos.arc_2d(
+                Isometry2d::new(vertex, Rot2::radians(start_angle + sweep * 0.5 - PI / 2.0)),
+                sweep.abs(),
+                ANGLE_ARC_RADIUS,
+                color,
+            );
+        }
+    }
+}
+
+/// Signed angle from `from` to `to`, in (-π, π]
+pub(crate) fn angle_between(from: Vec2, to: Vec2) -> f32 {
+    from.perp_dot(to).atan2(from.dot(to))
+}
+
+/// Draw guides across the visible part of the world
+fn draw_guides<Config: GizmoConfigGroup>(
+    mut gizmos: Gizmos<Config>,
+    guides: Res<MeasureGuides>,
+    state: Res<MeasureState>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<SpritePickingCamera>>,
+    theme_colors: Res<crate::ui::theme_colors::EditorThemeColors>,
+) {
+    if guides.vertical.is_empty() && guides.horizontal.is_empty() && state.ruler_drag.is_none() {
+        return;
+    }
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let Some(viewport) = camera.logical_viewport_rect() else {
+        return;
+    };
+    let (Ok(corner_a), Ok(corner_b)) = (
+        camera.viewport_to_world_2d(camera_transform, Vec2::ZERO),
+        camera.viewport_to_world_2d(camera_transform, viewport.size()),
+    ) else {
+        return;
+    };
+    let min = corner_a.min(corner_b);
+    let max = corner_a.max(corner_b);
+
+    let mut draw = |axis: GuideAxis, position: f32, color: Color| match axis {
+        GuideAxis::Vertical => gizmos.line_2d(
+            Vec2::new(position, min.y),
+            Vec2::new(position, max.y),
+            color,
+        ),
+        GuideAxis::Horizontal => gizmos.line_2d(
+            Vec2::new(min.x, position),
+            Vec2::new(max.x, position),
+            color,
+        ),
+    };
+
+    for axis in [GuideAxis::Vertical, GuideAxis::Horizontal] {
+        for (index, position) in guides.positions(axis).iter().enumerate() {
+            let active = state.hovered_guide == Some((axis, index))
+                || state.dragging_guide == Some((axis, index));
+            let color = if active {
+                theme_colors.measure_snap
+            } else {
+                theme_colors.guide_line
+            };
+            draw(axis, *position, color);
+        }
+    }
+
+    if let Some((axis, position)) = state.ruler_drag {
+        draw(axis, position, theme_colors.measure_snap);
+    }
+}
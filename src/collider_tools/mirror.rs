@@ -0,0 +1,502 @@
+//! Flip and mirror-copy for building symmetric contraptions
+//!
+//! Flipping reflects the selected bodies across a vertical or horizontal line. A reflected body
+//! keeps its scale, gets the negated rotation and has its shape mirrored in local space, so
+//! polygon and triangle vertices, capsule endpoints, sketched strokes, tile cells and anchor
+//! positions all land where a mirror image would put them. Joints between two flipped bodies are rebuilt with
+//! mirrored details: prismatic slider axes are reflected and revolute angle limits, basis and
+//! motor direction are negated.
+//!
+//! Mirror-copy duplicates the selection, including anchors and the joints between selected
+//! bodies, and flips the copy instead of the original.
+//!
+//! Bodies whose collider cannot be mirrored (compound shapes, polylines not drawn in Sketch mode)
+//! are left where they are and listed in [`SkippedFlips`] so the UI can tell the user.
+//!
+//! The flip runs in chained steps so that copies spawned with commands exist before they are
+//! reflected, and marks the affected joints so they are rebuilt from the already mirrored anchors.
+//!
+//! ## Hotkeys (Select mode)
+//! - Shift + H / V: flip horizontally / vertically
+//! - Ctrl + Shift + H / V: mirror-copy horizontally / vertically
+
+use super::debug_render::anchor::AnchorPoint;
+use super::debug_render::joint::{
+    AnchorUsedBy, JointConfig, JointVisualization, JointVisualizationOf,
+};
+use super::joint::{RebuildJoint, rebuild_marked_joints};
+use super::joint_config::JointConfigurationEnum;
+use super::polygon::PolygonSource;
+use super::sketch::SketchStroke;
+use super::tile::TileLayer;
+use super::utils::collider_world_aabb;
+use super::velocity::InitialVelocity;
+use crate::prefab::{PrefabKey, PrefabMemberOf};
+use crate::selection::{EditorSelection, Selectable};
+use crate::sub_scene::SubSceneMemberOf;
+use crate::transform_gizmos::TransformGizmoSettings;
+use avian2d::parry::shape::TypedShape;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::input::egui_wants_any_input;
+use std::collections::{HashMap, HashSet};
+
+/// Direction a flip reverses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum FlipDirection {
+    /// Reverse x, mirroring across a vertical line
+    Horizontal,
+    /// Reverse y, mirroring across a horizontal line
+    Vertical,
+}
+
+impl FlipDirection {
+    /// Mirror a vector in local space
+    pub fn mirror(self, vector: Vec2) -> Vec2 {
+        match self {
+            FlipDirection::Horizontal => Vec2::new(-vector.x, vector.y),
+            FlipDirection::Vertical => Vec2::new(vector.x, -vector.y),
+        }
+    }
+
+    /// Mirror a world point across the line at `line` (an x for horizontal flips, a y for
+    /// vertical ones)
+    pub fn reflect_point(self, point: Vec2, line: f32) -> Vec2 {
+        match self {
+            FlipDirection::Horizontal => Vec2::new(2.0 * line - point.x, point.y),
+            FlipDirection::Vertical => Vec2::new(point.x, 2.0 * line - point.y),
+        }
+    }
+}
+
+/// Where the mirror line is placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum MirrorLine {
+    /// Through the center of the selection bounds
+    #[default]
+    SelectionCenter,
+    /// Through the world origin
+    WorldOrigin,
+    /// Through the custom gizmo pivot (placed with P)
+    CustomPivot,
+}
+
+impl MirrorLine {
+    /// All line placements, in UI order
+    pub const ALL: [MirrorLine; 3] = [
+        MirrorLine::SelectionCenter,
+        MirrorLine::WorldOrigin,
+        MirrorLine::CustomPivot,
+    ];
+
+    /// Translation key of the placement name
+    pub fn key(self) -> &'static str {
+        match self {
+            MirrorLine::SelectionCenter => "mirror_line_selection",
+            MirrorLine::WorldOrigin => "mirror_line_origin",
+            MirrorLine::CustomPivot => "mirror_line_pivot",
+        }
+    }
+}
+
+/// Mirror options
+#[derive(Resource, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct MirrorSettings {
+    /// Where the mirror line is placed
+    pub line: MirrorLine,
+}
+
+/// Flip command for the current selection
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum MirrorEvent {
+    /// Flip the selected bodies in place
+    Flip(FlipDirection),
+    /// Flip a copy of the selected bodies, leaving the originals
+    MirrorCopy(FlipDirection),
+}
+
+/// Bodies the last flip left untouched because their collider shape cannot be mirrored
+///
+/// Inserted when there is something to report and removed once the user has seen the notice.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SkippedFlips {
+    /// Bodies with compound or polyline colliders
+    pub entities: Vec<Entity>,
+}
+
+/// Body waiting to be reflected by [`apply_pending_flips`]
+#[derive(Component, Debug, Clone, Copy)]
+struct PendingFlip {
+    direction: FlipDirection,
+    line: f32,
+}
+
+/// Plugin providing flip and mirror-copy
+#[derive(Default)]
+pub struct MirrorPlugin;
+
+impl Plugin for MirrorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MirrorSettings>()
+            .register_type::<MirrorSettings>()
+            .add_event::<MirrorEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_mirror_hotkeys.run_if(
+                        in_state(crate::collider_tools::ToolMode::Select)
+                            .and(not(egui_wants_any_input)),
+                    ),
+                    handle_mirror_events,
+                    apply_pending_flips,
+                )
+                    .chain()
+                    .before(rebuild_marked_joints),
+            );
+    }
+}
+
+/// Collider with its shape mirrored in local space
+///
+/// Works on the unscaled shape so the entity's scale still applies afterwards. Winding is
+/// restored for triangles and polygons. Returns `None` for shapes that cannot be mirrored.
+pub(crate) fn mirror_collider(collider: &Collider, direction: FlipDirection) -> Option<Collider> {
+    let point = |p: &avian2d::parry::math::Point<f32>| direction.mirror(Vec2::new(p.x, p.y));
+    match collider.shape().as_typed_shape() {
+        // Centered boxes and circles are their own mirror image
+        TypedShape::Ball(_) | TypedShape::Cuboid(_) => Some(collider.clone()),
+        TypedShape::Capsule(capsule) => Some(Collider::capsule_endpoints(
+            capsule.radius,
+            point(&capsule.segment.a),
+            point(&capsule.segment.b),
+        )),
+        // Swapping two vertices turns the clockwise mirror image back counter-clockwise;
+        // the convex hull below restores polygon winding the same way
+        TypedShape::Triangle(triangle) => Some(Collider::triangle(
+            point(&triangle.a),
+            point(&triangle.c),
+            point(&triangle.b),
+        )),
+        TypedShape::ConvexPolygon(polygon) => {
+            Collider::convex_hull(polygon.points().iter().map(point).collect())
+        }
+        TypedShape::Segment(segment) => {
+            Some(Collider::segment(point(&segment.a), point(&segment.b)))
+        }
+        _ => None,
+    }
+}
+
+/// Mirror the frame-dependent parts of a joint whose bodies were both flipped
+pub(crate) fn mirror_joint_details(details: &mut JointConfigurationEnum, direction: FlipDirection) {
+    match details {
+        JointConfigurationEnum::Prismatic { config, .. } => {
+            config.free_axis = direction.mirror(config.free_axis);
+        }
+        JointConfigurationEnum::Revolute {
+            config, advanced, ..
+        } => {
+            // Rotations run the other way in a mirror image
+            let (min, max) = (config.min_angle, config.max_angle);
+            config.min_angle = max.map(|max| -max);
+            config.max_angle = min.map(|min| -min);
+            config.basis = config.basis.map(|basis| -basis);
+            advanced.motor_target_velocity = -advanced.motor_target_velocity;
+        }
+        JointConfigurationEnum::Fixed { .. } | JointConfigurationEnum::Distance { .. } => {}
+    }
+}
+
+/// Keyboard shortcuts for flipping
+fn handle_mirror_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut events: EventWriter<MirrorEvent>,
+) {
+    let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let alt = keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    if !shift || alt {
+        return;
+    }
+    let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+
+    let direction = if keyboard.just_pressed(KeyCode::KeyH) {
+        FlipDirection::Horizontal
+    } else if keyboard.just_pressed(KeyCode::KeyV) {
+        FlipDirection::Vertical
+    } else {
+        return;
+    };
+    events.write(if ctrl {
+        MirrorEvent::MirrorCopy(direction)
+    } else {
+        MirrorEvent::Flip(direction)
+    });
+}
+
+/// Pick the mirror line and mark the bodies to flip, copying them first for mirror-copy
+fn handle_mirror_events(
+    mut commands: Commands,
+    mut events: EventReader<MirrorEvent>,
+    mut selection: ResMut<EditorSelection>,
+    settings: Res<MirrorSettings>,
+    gizmo_settings: Option<Res<TransformGizmoSettings>>,
+    body_query: Query<(Entity, &Collider, &GlobalTransform)>,
+    anchor_query: Query<(Entity, &AnchorPoint)>,
+    joint_query: Query<&JointConfig>,
+) {
+    for event in events.read() {
+        let bodies: Vec<Entity> = body_query
+            .iter()
+            .filter(|(entity, ..)| selection.contains(*entity))
+            .map(|(entity, ..)| entity)
+            .collect();
+        if bodies.is_empty() {
+            continue;
+        }
+
+        let (MirrorEvent::Flip(direction) | MirrorEvent::MirrorCopy(direction)) = *event;
+        let line = match settings.line {
+            MirrorLine::SelectionCenter => bodies
+                .iter()
+                .filter_map(|entity| body_query.get(*entity).ok())
+                .map(|(_, collider, transform)| {
+                    let aabb = collider_world_aabb(collider, transform);
+                    Rect::from_corners(aabb.min, aabb.max)
+                })
+                .reduce(|a, b| a.union(b))
+                .map(|bounds| bounds.center())
+                .unwrap_or_default(),
+            MirrorLine::WorldOrigin => Vec2::ZERO,
+            MirrorLine::CustomPivot => gizmo_settings
+                .as_ref()
+                .map(|settings| settings.custom_pivot.truncate())
+                .unwrap_or_default(),
+        };
+        let line = match direction {
+            FlipDirection::Horizontal => line.x,
+            FlipDirection::Vertical => line.y,
+        };
+        let flip = PendingFlip { direction, line };
+
+        let targets = match event {
+            MirrorEvent::Flip(_) => bodies,
+            MirrorEvent::MirrorCopy(_) => {
+                copy_bodies(&mut commands, &bodies, &anchor_query, &joint_query)
+            }
+        };
+        for &entity in &targets {
+            commands.entity(entity).insert(flip);
+        }
+        if matches!(event, MirrorEvent::MirrorCopy(_)) {
+            *selection = EditorSelection::from_iter(targets.iter().copied());
+        }
+        info!("{:?} across {} for {} bodies", event, line, targets.len());
+    }
+}
+
+/// Duplicate bodies with their anchors and the joints between them
+///
+/// Returns the copies in the same order as `bodies`. Copied joints start as bare
+/// [`JointConfig`] entities marked for rebuilding once the copies have been flipped.
+fn copy_bodies(
+    commands: &mut Commands,
+    bodies: &[Entity],
+    anchor_query: &Query<(Entity, &AnchorPoint)>,
+    joint_query: &Query<&JointConfig>,
+) -> Vec<Entity> {
+    // Copies of prefab or sub-scene members belong to the scene itself, not to the instance
+    let mut mapping: HashMap<Entity, Entity> = HashMap::default();
+    for &body in bodies {
+        let copy = commands
+            .entity(body)
+            .clone_and_spawn()
+            .remove::<(PrefabMemberOf, PrefabKey, SubSceneMemberOf)>()
+            .id();
+        mapping.insert(body, copy);
+    }
+
+    for (anchor_entity, anchor) in anchor_query {
+        let Some(&parent_copy) = mapping.get(&anchor.parent_entity) else {
+            continue;
+        };
+        let anchor_copy = commands.entity(anchor_entity).clone_and_spawn().id();
+        commands
+            .entity(anchor_copy)
+            .remove::<(AnchorUsedBy, PrefabMemberOf, PrefabKey, SubSceneMemberOf)>()
+            .insert((
+                AnchorPoint {
+                    parent_entity: parent_copy,
+                    ..anchor.clone()
+                },
+                ChildOf(parent_copy),
+            ));
+        mapping.insert(anchor_entity, anchor_copy);
+    }
+
+    let copied: HashSet<Entity> = bodies.iter().copied().collect();
+    for config in joint_query {
+        if !copied.contains(&config.parent_entity) || !copied.contains(&config.child_entity) {
+            continue;
+        }
+        let remap = |entity: Entity| mapping.get(&entity).copied().unwrap_or(entity);
+        let joint_copy = JointConfig {
+            anchor_a: remap(config.anchor_a),
+            anchor_b: remap(config.anchor_b),
+            parent_entity: remap(config.parent_entity),
+            child_entity: remap(config.child_entity),
+            ..config.clone()
+        };
+        let visualization = JointVisualization {
+            anchor_a: joint_copy.anchor_a,
+            anchor_b: joint_copy.anchor_b,
+            joint_type: joint_copy.joint_config_details.joint_type(),
+            selected: false,
+        };
+        let joint_entity = commands.spawn((joint_copy, RebuildJoint)).id();
+        commands.spawn((
+            visualization,
+            Selectable,
+            JointVisualizationOf(joint_entity),
+        ));
+    }
+
+    bodies.iter().map(|body| mapping[body]).collect()
+}
+
+/// Reflect marked bodies, their anchors and the joints that connect them
+fn apply_pending_flips(
+    mut commands: Commands,
+    mut body_query: Query<(
+        Entity,
+        &PendingFlip,
+        &GlobalTransform,
+        &mut Transform,
+        Option<&ChildOf>,
+        Option<&mut Collider>,
+        Option<&mut LinearVelocity>,
+        Option<&mut AngularVelocity>,
+        Option<&mut InitialVelocity>,
+        Option<&mut PolygonSource>,
+        Option<&mut SketchStroke>,
+        Option<&mut TileLayer>,
+    )>,
+    parent_query: Query<&GlobalTransform>,
+    mut anchor_query: Query<&mut AnchorPoint>,
+    mut joint_query: Query<(Entity, &mut JointConfig)>,
+) {
+    let mut flipped: HashMap<Entity, FlipDirection> = HashMap::default();
+    let mut skipped = Vec::new();
+
+    for (
+        entity,
+        flip,
+        global_transform,
+        mut transform,
+        child_of,
+        collider,
+        linear_velocity,
+        angular_velocity,
+        initial_velocity,
+        source,
+        stroke,
+        tile_layer,
+    ) in &mut body_query
+    {
+        let direction = flip.direction;
+        commands.entity(entity).remove::<PendingFlip>();
+
+        // Outlines, strokes and tile cells are mirrored below; other shapes must be mirrorable
+        // before the body is moved, or it would end up reflected around an unchanged shape
+        let mirrored_collider = match (&source, &stroke, &tile_layer, &collider) {
+            (None, None, None, Some(collider)) => match mirror_collider(collider, direction) {
+                Some(mirrored) => Some(mirrored),
+                None => {
+                    warn!("Cannot mirror the collider shape of {:?}", entity);
+                    skipped.push(entity);
+                    continue;
+                }
+            },
+            _ => None,
+        };
+
+        let world = global_transform.compute_transform();
+        let angle = world.rotation.to_euler(EulerRot::ZYX).0;
+        let mirrored = Transform {
+            translation: direction
+                .reflect_point(world.translation.truncate(), flip.line)
+                .extend(world.translation.z),
+            rotation: Quat::from_rotation_z(-angle),
+            scale: world.scale,
+        };
+        let parent_matrix = child_of
+            .and_then(|child_of| parent_query.get(child_of.parent()).ok())
+            .map(GlobalTransform::compute_matrix)
+            .unwrap_or(Mat4::IDENTITY);
+        *transform = Transform::from_matrix(parent_matrix.inverse() * mirrored.compute_matrix());
+
+        // The stroke rebuilds the collider of sketched bodies, so it has to be mirrored as well
+        if let Some(mut stroke) = stroke {
+            stroke
+                .points
+                .iter_mut()
+                .for_each(|point| *point = direction.mirror(*point));
+            if stroke.closed {
+                stroke.points.reverse();
+            }
+        }
+        // Tile colliders are rebuilt from the cells
+        if let Some(mut layer) = tile_layer {
+            layer.mirror(direction);
+        }
+        if let Some(mut source) = source {
+            // The collider follows the mirrored outline; reversing keeps solids counter-clockwise
+            for contour in &mut source.contours {
+                contour
+                    .iter_mut()
+                    .for_each(|point| *point = direction.mirror(*point));
+                contour.reverse();
+            }
+        } else if let (Some(mut collider), Some(mirrored)) = (collider, mirrored_collider) {
+            *collider = mirrored;
+        }
+        if let Some(mut velocity) = linear_velocity {
+            velocity.0 = direction.mirror(velocity.0);
+        }
+        if let Some(mut velocity) = angular_velocity {
+            velocity.0 = -velocity.0;
+        }
+        if let Some(mut velocity) = initial_velocity {
+            velocity.linear = direction.mirror(velocity.linear);
+            velocity.angular = -velocity.angular;
+        }
+
+        flipped.insert(entity, direction);
+    }
+    if !skipped.is_empty() {
+        commands.insert_resource(SkippedFlips { entities: skipped });
+    }
+    if flipped.is_empty() {
+        return;
+    }
+
+    for mut anchor in &mut anchor_query {
+        if let Some(direction) = flipped.get(&anchor.parent_entity) {
+            anchor.local_anchor_position = direction.mirror(anchor.local_anchor_position);
+        }
+    }
+
+    for (joint_entity, mut config) in &mut joint_query {
+        let parent = flipped.get(&config.parent_entity);
+        let child = flipped.get(&config.child_entity);
+        match (parent, child) {
+            (Some(direction), Some(_)) => {
+                mirror_joint_details(&mut config.joint_config_details, *direction);
+            }
+            // Only one side moved; the anchors changed but the joint frame did not
+            (Some(_), None) | (None, Some(_)) => {}
+            (None, None) => continue,
+        }
+        commands.entity(joint_entity).insert(RebuildJoint);
+    }
+}
@@ -7,36 +7,78 @@ use bevy_egui::input::egui_wants_any_keyboard_input;
 
 pub mod anchor;
 pub use anchor::*;
+pub mod arrange;
+pub mod boolean;
 pub mod joint;
 pub use joint::*;
 pub mod creation;
 pub mod edit;
+pub mod force_field;
 pub mod joint_config;
 pub mod joint_selection;
+pub mod kinematic_path;
+pub mod lint;
+pub mod measure;
+pub mod mirror;
+pub mod object_snap;
+pub mod polygon;
 // Selection module for collider interaction
 pub mod collision_layers;
 pub mod debug_render;
 pub mod physics_management;
 pub mod selection;
+pub mod sketch;
+pub mod tile;
+pub mod units;
 pub mod utils;
+pub mod velocity;
 pub mod visualization;
+pub mod world_settings;
 
+pub use arrange::*;
+pub use boolean::*;
 pub use creation::*;
 pub use debug_render::*;
 pub use edit::*;
+pub use force_field::*;
+pub use kinematic_path::*;
+pub use lint::*;
+pub use measure::*;
+pub use mirror::*;
+pub use object_snap::*;
 pub use physics_management::*;
+pub use polygon::*;
 pub use selection::*;
+pub use sketch::*;
+pub use tile::*;
+pub use units::*;
+pub use velocity::*;
 pub use visualization::*;
+pub use world_settings::*;
 
 // Export individual plugins for modular usage
 pub use anchor::AnchorCreationPlugin;
+pub use arrange::ArrangePlugin;
+pub use boolean::BooleanPlugin;
 pub use collision_layers::CollisionLayerManagementPlugin;
 pub use creation::CreationPlugin;
 pub use edit::EditPlugin;
+pub use force_field::{ForceFieldGizmoPlugin, ForceFieldPlugin};
 pub use joint::JointCreationPlugin;
 pub use joint_selection::JointSelectionPlugin;
+pub use kinematic_path::{KinematicPathEditorPlugin, KinematicPathPlugin};
+pub use lint::LintPlugin;
+pub use measure::MeasurePlugin;
+pub use mirror::MirrorPlugin;
+pub use object_snap::ObjectSnapPlugin;
 pub use physics_management::PhysicsManagementPlugin;
+pub use polygon::PolygonSourcePlugin;
 pub use selection::ColliderSelectionPlugin;
+pub use sketch::SketchPlugin;
+pub use tile::TilePlugin;
+pub use units::UnitsPlugin;
+pub use velocity::VelocityAuthoringPlugin;
+pub use world_settings::WorldSettingsPlugin;
 
 #[cfg(test)]
 mod tests;
@@ -83,8 +125,24 @@ impl PluginGroup for ColliderToolsPluginGroup {
             .add(AnchorCreationPlugin)
             .add(JointCreationPlugin)
             .add(JointSelectionPlugin)
+            .add(VelocityAuthoringPlugin)
+            .add(ForceFieldPlugin)
+            .add(ForceFieldGizmoPlugin)
+            .add(KinematicPathPlugin)
+            .add(KinematicPathEditorPlugin)
+            .add(MeasurePlugin)
+            .add(ObjectSnapPlugin)
+            .add(ArrangePlugin)
+            .add(MirrorPlugin)
+            .add(PolygonSourcePlugin)
+            .add(BooleanPlugin)
+            .add(SketchPlugin)
+            .add(TilePlugin)
             .add(DebugRenderPlugin)
             .add(PhysicsManagementPlugin)
+            .add(WorldSettingsPlugin)
+            .add(LintPlugin)
+            .add(UnitsPlugin)
     }
 }
 
@@ -104,6 +162,16 @@ pub enum ToolMode {
     Anchor,
     /// Joint mode - create joints between anchors and center of mass
     Joint,
+    /// Velocity mode - drag handles to author initial linear and angular velocity
+    Velocity,
+    /// Path mode - place keyframes for kinematic bodies such as moving platforms
+    Path,
+    /// Measure mode - measure distances and angles, and manage guides
+    Measure,
+    /// Sketch mode - draw freehand strokes that are simplified into colliders
+    Sketch,
+    /// Tile mode - paint and erase grid cells of a tile layer
+    Tile,
 }
 
 /// Supported collider types for creation
@@ -167,7 +235,12 @@ pub fn handle_creation_mode_switching(
             ToolMode::Create => ToolMode::Edit,
             ToolMode::Edit => ToolMode::Anchor,
             ToolMode::Anchor => ToolMode::Joint,
-            ToolMode::Joint => ToolMode::Select,
+            ToolMode::Joint => ToolMode::Velocity,
+            ToolMode::Velocity => ToolMode::Path,
+            ToolMode::Path => ToolMode::Measure,
+            ToolMode::Measure => ToolMode::Sketch,
+            ToolMode::Sketch => ToolMode::Tile,
+            ToolMode::Tile => ToolMode::Select,
         });
         info!("Mode: {:?} (Tab)", next_mode);
     }
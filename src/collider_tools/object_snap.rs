@@ -0,0 +1,181 @@
+//! Object snapping for gizmo translation
+//!
+//! While the transform gizmo moves a selection, the moved colliders are pulled onto nearby
+//! colliders so platforms can be lined up edge to edge. Three kinds of contact are tried, in
+//! order of preference:
+//! 1. a moved vertex onto another collider's vertex
+//! 2. a moved vertex or origin onto another collider's origin
+//! 3. a moved vertex onto another collider's edge, or another collider's vertex onto a moved
+//!    edge
+//!
+//! The nearest contact of the best kind within the threshold wins. Holding Alt while dragging
+//! skips object snapping.
+
+use super::EditorGizmoConfigGroup;
+use super::utils::{
+    collider_snap_vertices, collider_world_aabb, find_closest_point_on_collider,
+    find_closest_vertex,
+};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+/// What a translation snapped to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Reflect)]
+pub enum ObjectSnapKind {
+    /// Vertex onto vertex
+    Vertex,
+    /// Onto a collider origin
+    Center,
+    /// Vertex onto edge
+    Edge,
+}
+
+/// A snap found for the moved colliders
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ObjectSnap {
+    /// Kind of contact
+    pub kind: ObjectSnapKind,
+    /// Extra translation that brings the contact together
+    pub offset: Vec2,
+    /// World position of the contact after snapping
+    pub point: Vec2,
+}
+
+/// Snap found during the current gizmo drag, for drawing the indicator
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ObjectSnapState {
+    /// Active snap, cleared when the drag ends
+    pub active: Option<ObjectSnap>,
+}
+
+/// Plugin drawing object snap indicators
+#[derive(Default)]
+pub struct ObjectSnapPlugin;
+
+impl Plugin for ObjectSnapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ObjectSnapState>()
+            .add_systems(Update, draw_object_snap_indicator::<EditorGizmoConfigGroup>);
+    }
+}
+
+/// Find the best snap for colliders being moved onto stationary ones
+///
+/// Both slices hold colliders at their world poses; `moving` poses already include the
+/// unsnapped translation.
+pub(crate) fn find_object_snap(
+    moving: &[(Collider, GlobalTransform)],
+    targets: &[(Collider, GlobalTransform)],
+    threshold: f32,
+) -> Option<ObjectSnap> {
+    let mut best: Option<(ObjectSnap, f32)> = None;
+    let mut consider = |kind: ObjectSnapKind, from: Vec2, to: Vec2| {
+        let distance = from.distance(to);
+        if distance > threshold {
+            return;
+        }
+        let better = best.is_none_or(|(current, current_distance)| {
+            (kind, distance) < (current.kind, current_distance)
+        });
+        if better {
+            best = Some((
+                ObjectSnap {
+                    kind,
+                    offset: to - from,
+                    point: to,
+                },
+                distance,
+            ));
+        }
+    };
+
+    for (moving_collider, moving_transform) in moving {
+        let moving_origin = moving_transform.translation().truncate();
+        let moving_aabb = collider_world_aabb(moving_collider, moving_transform);
+        let moving_vertices = world_vertices(moving_collider, moving_transform);
+
+        for (target_collider, target_transform) in targets {
+            let target_aabb = collider_world_aabb(target_collider, target_transform);
+            if !moving_aabb
+                .grow(Vec2::splat(threshold))
+                .intersects(&target_aabb)
+            {
+                continue;
+            }
+            let target_origin = target_transform.translation().truncate();
+
+            consider(ObjectSnapKind::Center, moving_origin, target_origin);
+
+            for &vertex in &moving_vertices {
+                if let Some(target_vertex) =
+                    find_closest_vertex(vertex, target_collider, target_transform)
+                {
+                    consider(ObjectSnapKind::Vertex, vertex, target_vertex);
+                }
+                consider(ObjectSnapKind::Center, vertex, target_origin);
+                consider(
+                    ObjectSnapKind::Edge,
+                    vertex,
+                    find_closest_point_on_collider(vertex, target_collider, target_transform),
+                );
+            }
+
+            for target_vertex in world_vertices(target_collider, target_transform) {
+                let edge_point = find_closest_point_on_collider(
+                    target_vertex,
+                    moving_collider,
+                    moving_transform,
+                );
+                consider(ObjectSnapKind::Edge, edge_point, target_vertex);
+            }
+        }
+    }
+
+    best.map(|(snap, _)| snap)
+}
+
+/// World-space snap vertices of a collider
+fn world_vertices(collider: &Collider, transform: &GlobalTransform) -> Vec<Vec2> {
+    collider_snap_vertices(collider)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|vertex| transform.transform_point(vertex.extend(0.0)).truncate())
+        .collect()
+}
+
+/// Draw a marker at the active snap: square for vertices, cross for centers, bar for edges
+fn draw_object_snap_indicator<Config: GizmoConfigGroup>(
+    mut gizmos: Gizmos<Config>,
+    state: Res<ObjectSnapState>,
+    theme_colors: Res<crate::ui::theme_colors::EditorThemeColors>,
+) {
+    let Some(snap) = state.active else {
+        return;
+    };
+    let color = theme_colors.measure_snap;
+    let point = snap.point;
+
+    match snap.kind {
+        ObjectSnapKind::Vertex => {
+            gizmos.rect_2d(
+                Isometry2d::from_translation(point),
+                Vec2::splat(10.0),
+                color,
+            );
+        }
+        ObjectSnapKind::Center => {
+            gizmos.line_2d(point - Vec2::splat(6.0), point + Vec2::splat(6.0), color);
+            gizmos.line_2d(
+                point + Vec2::new(-6.0, 6.0),
+                point + Vec2::new(6.0, -6.0),
+                color,
+            );
+        }
+        ObjectSnapKind::Edge => {
+            let along = snap.offset.normalize_or(Vec2::X).perp() * 8.0;
+            gizmos.line_2d(point - along, point + along, color);
+            gizmos.circle_2d(point, 3.0, color);
+        }
+    }
+}
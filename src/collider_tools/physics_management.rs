@@ -46,6 +46,14 @@ impl PhysicsManager {
     }
 }
 
+/// 从头播放事件
+///
+/// 表示模拟从作者设定的初始状态开始运行，初始速度、运动路径等据此重新生效。
+/// 启动时物理未暂停会自动发送一次；之后只在用户点击"从头播放"时发送，
+/// 普通的暂停/恢复不会触发。
+#[derive(Event, Debug, Clone, Copy, Default)]
+pub struct PlayFromStart;
+
 /// 物理管理插件
 ///
 /// 初始化物理状态管理系统并提供调试功能。
@@ -55,6 +63,9 @@ impl Plugin for PhysicsManagementPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PhysicsManager>()
             .register_type::<PhysicsIgnore>()
+            .add_event::<PlayFromStart>()
+            .add_systems(PostStartup, play_on_launch)
+            .add_systems(Update, handle_play_from_start)
             .add_systems(
                 Update,
                 debug_physics_state.run_if(|manager: Res<PhysicsManager>| manager.is_changed()),
@@ -80,6 +91,24 @@ impl Plugin for PhysicsManagementPlugin {
     }
 }
 
+/// 启动时物理已在运行，视为从头播放
+fn play_on_launch(physics_manager: Res<PhysicsManager>, mut events: EventWriter<PlayFromStart>) {
+    if !physics_manager.is_physics_paused() {
+        events.write(PlayFromStart);
+    }
+}
+
+/// 从头播放时恢复物理模拟
+fn handle_play_from_start(
+    mut events: EventReader<PlayFromStart>,
+    mut physics_manager: ResMut<PhysicsManager>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    if events.read().count() > 0 {
+        physics_manager.unpause(&mut physics_time);
+    }
+}
+
 /// 调试系统：显示当前物理状态
 fn debug_physics_state(physics_time: Res<Time<Physics>>) {
     debug!("Physics paused - Time paused: {}", physics_time.is_paused());
@@ -0,0 +1,608 @@
+//! Editable polygon outlines and their convex decomposition
+//!
+//! A [`PolygonSource`] keeps the outline of a collider that may be concave or have holes. The
+//! physics collider is rebuilt from it whenever it changes:
+//! 1. holes are bridged into the solid contour that contains them
+//! 2. the resulting polygon is triangulated by ear clipping
+//! 3. neighbouring triangles are merged while the union stays convex (Hertel–Mehlhorn)
+//!
+//! Each convex part becomes one shape of a compound collider. Outlines produced by boolean
+//! operations end up here, and in Edit mode the contour vertices can be dragged directly.
+
+use super::{ColliderEditState, ControlPoint, ControlPointType};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Outline a collider is generated from
+///
+/// Contours are closed, without a repeated end point, and in the entity's unscaled local space
+/// like the collider shape itself. Counter-clockwise contours are solid, clockwise contours are
+/// holes in the solid around them.
+#[derive(Component, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct PolygonSource {
+    /// Closed contours in local space
+    pub contours: Vec<Vec<Vec2>>,
+}
+
+impl PolygonSource {
+    /// Source with the given contours
+    pub fn new(contours: Vec<Vec<Vec2>>) -> Self {
+        Self { contours }
+    }
+
+    /// Convex parts covering the outline
+    pub fn convex_parts(&self) -> Vec<Vec<Vec2>> {
+        decompose_contours(&self.contours)
+    }
+
+    /// Collider made of the convex parts, or `None` when the outline encloses no area
+    pub fn collider(&self) -> Option<Collider> {
+        convex_parts_collider(&self.convex_parts())
+    }
+
+    /// Total number of contour vertices
+    pub fn vertex_count(&self) -> usize {
+        self.contours.iter().map(Vec::len).sum()
+    }
+
+    /// Contour and vertex index of the `index`-th vertex, counting through all contours
+    fn locate(&self, mut index: usize) -> Option<(usize, usize)> {
+        for (contour_index, contour) in self.contours.iter().enumerate() {
+            if index < contour.len() {
+                return Some((contour_index, index));
+            }
+            index -= contour.len();
+        }
+        None
+    }
+}
+
+/// Plugin keeping colliders in sync with their [`PolygonSource`]
+#[derive(Default)]
+pub struct PolygonSourcePlugin;
+
+impl Plugin for PolygonSourcePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PolygonSource>()
+            .add_systems(Update, rebuild_polygon_colliders);
+    }
+}
+
+/// Regenerate the collider of every entity whose polygon source changed
+fn rebuild_polygon_colliders(
+    mut commands: Commands,
+    query: Query<(Entity, &PolygonSource), Changed<PolygonSource>>,
+) {
+    for (entity, source) in &query {
+        match source.collider() {
+            Some(collider) => {
+                commands.entity(entity).insert(collider);
+            }
+            None => warn!("Polygon source of {:?} encloses no area", entity),
+        }
+    }
+}
+
+/// Collider for a set of convex parts: a single convex polygon or a compound
+pub(crate) fn convex_parts_collider(parts: &[Vec<Vec2>]) -> Option<Collider> {
+    let mut colliders: Vec<Collider> = parts
+        .iter()
+        .filter_map(|part| Collider::convex_hull(part.clone()))
+        .collect();
+    match colliders.len() {
+        0 => None,
+        1 => colliders.pop(),
+        _ => Some(Collider::compound(
+            colliders
+                .into_iter()
+                .map(|collider| (Position::default(), Rotation::default(), collider))
+                .collect(),
+        )),
+    }
+}
+
+/// Edit-mode control points: one vertex handle per contour vertex
+pub(super) fn generate_polygon_source_control_points(
+    edit_state: &mut ColliderEditState,
+    transform: &Transform,
+    source: &PolygonSource,
+) {
+    edit_state.control_points.clear();
+    let matrix = transform.compute_matrix();
+    for (index, vertex) in source.contours.iter().flatten().enumerate() {
+        edit_state.control_points.push(ControlPoint {
+            position: matrix.transform_point3(vertex.extend(0.0)).truncate(),
+            point_type: ControlPointType::Vertex,
+            vertex_index: Some(index),
+        });
+    }
+}
+
+/// Write dragged control points back into the contours
+pub(super) fn apply_polygon_source_changes(
+    edit_state: &ColliderEditState,
+    transform: &Transform,
+    source: &mut PolygonSource,
+) {
+    let inverse = transform.compute_matrix().inverse();
+    let Some(dragged) = edit_state
+        .dragging_point
+        .and_then(|index| edit_state.control_points.get(index))
+    else {
+        return;
+    };
+    let Some((contour, vertex)) = dragged.vertex_index.and_then(|index| source.locate(index))
+    else {
+        return;
+    };
+    let local = inverse
+        .transform_point3(dragged.position.extend(0.0))
+        .truncate();
+    source.contours[contour][vertex] = local;
+}
+
+// ===== GEOMETRY =====
+
+/// Signed area of a closed polygon, positive when counter-clockwise
+pub(crate) fn signed_area(points: &[Vec2]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| points[i].perp_dot(points[(i + 1) % n]))
+        .sum::<f32>()
+        * 0.5
+}
+
+/// Even-odd point in polygon test
+pub(crate) fn point_in_polygon(point: Vec2, polygon: &[Vec2]) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Distance below which two points count as the same, relative to the size of the input
+pub(crate) fn weld_tolerance<'a>(polygons: impl IntoIterator<Item = &'a Vec<Vec2>>) -> f32 {
+    let extent = polygons
+        .into_iter()
+        .flatten()
+        .fold(0.0_f32, |extent, point| {
+            extent.max(point.abs().max_element())
+        });
+    (extent * 1e-5).max(1e-5)
+}
+
+/// Remove repeated points, straight-through vertices and spikes from a closed contour
+///
+/// Returns `None` when less than a triangle with area remains. Orientation is preserved.
+pub(crate) fn simplify_contour(mut points: Vec<Vec2>, epsilon: f32) -> Option<Vec<Vec2>> {
+    let mut changed = true;
+    while changed && points.len() >= 3 {
+        changed = false;
+        let mut i = 0;
+        while i < points.len() && points.len() >= 3 {
+            let n = points.len();
+            let prev = points[(i + n - 1) % n];
+            let current = points[i];
+            let next = points[(i + 1) % n];
+            let chord = next - prev;
+            let redundant = current.distance(prev) <= epsilon
+                || chord.length() <= epsilon
+                || (current - prev).perp_dot(chord).abs() / chord.length() <= epsilon;
+            if redundant {
+                points.remove(i);
+                changed = true;
+            } else {
+                i += 1;
+            }
+        }
+    }
+    (points.len() >= 3 && signed_area(&points).abs() > epsilon * epsilon).then_some(points)
+}
+
+/// Part of a convex polygon on the left of the directed line through `a` and `b`
+pub(crate) fn clip_left(polygon: &[Vec2], a: Vec2, b: Vec2) -> Vec<Vec2> {
+    let direction = b - a;
+    let side = |point: Vec2| direction.perp_dot(point - a);
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for i in 0..polygon.len() {
+        let p = polygon[i];
+        let q = polygon[(i + 1) % polygon.len()];
+        let (side_p, side_q) = (side(p), side(q));
+        if side_p >= 0.0 {
+            clipped.push(p);
+        }
+        if (side_p > 0.0 && side_q < 0.0) || (side_p < 0.0 && side_q > 0.0) {
+            clipped.push(p + (q - p) * (side_p / (side_p - side_q)));
+        }
+    }
+    clipped
+}
+
+/// Outline contours of a set of non-overlapping convex pieces
+///
+/// Edges shared between pieces cancel out, so only the boundary of their union remains. Solid
+/// contours come out counter-clockwise and holes clockwise.
+pub(crate) fn outline_contours(pieces: &[Vec<Vec2>]) -> Vec<Vec<Vec2>> {
+    let epsilon = weld_tolerance(pieces);
+
+    // Weld nearly coincident vertices so neighbouring pieces share indices
+    let mut vertices: Vec<Vec2> = Vec::new();
+    let mut weld = |point: Vec2| {
+        vertices
+            .iter()
+            .position(|vertex| vertex.distance(point) <= epsilon)
+            .unwrap_or_else(|| {
+                vertices.push(point);
+                vertices.len() - 1
+            })
+    };
+    let mut edges = Vec::new();
+    for piece in pieces {
+        let indices: Vec<usize> = piece.iter().map(|&point| weld(point)).collect();
+        for i in 0..indices.len() {
+            let (a, b) = (indices[i], indices[(i + 1) % indices.len()]);
+            if a != b {
+                edges.push((a, b));
+            }
+        }
+    }
+
+    // Split edges at vertices of other pieces lying on them (T-junctions)
+    let mut split_edges = Vec::with_capacity(edges.len());
+    for (a, b) in edges {
+        let (start, end) = (vertices[a], vertices[b]);
+        let direction = end - start;
+        let length_squared = direction.length_squared();
+        let mut on_edge: Vec<(f32, usize)> = vertices
+            .iter()
+            .enumerate()
+            .filter(|&(k, _)| k != a && k != b)
+            .filter_map(|(k, &point)| {
+                let t = (point - start).dot(direction) / length_squared;
+                let inside =
+                    t > 0.0 && t < 1.0 && (start + direction * t).distance(point) <= epsilon;
+                inside.then_some((t, k))
+            })
+            .collect();
+        on_edge.sort_by(|x, y| x.0.total_cmp(&y.0));
+        let mut from = a;
+        for (_, k) in on_edge {
+            split_edges.push((from, k));
+            from = k;
+        }
+        split_edges.push((from, b));
+    }
+
+    // Interior edges appear once in each direction and cancel
+    let mut counts: HashMap<(usize, usize), usize> = HashMap::new();
+    for (a, b) in split_edges {
+        match counts.get_mut(&(b, a)) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => *counts.entry((a, b)).or_insert(0) += 1,
+        }
+    }
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut boundary: Vec<(usize, usize)> = counts
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .flat_map(|((a, b), count)| std::iter::repeat_n((a, b), count))
+        .collect();
+    boundary.sort_unstable();
+    for (a, b) in boundary {
+        outgoing.entry(a).or_default().push(b);
+    }
+
+    // Chain boundary edges into loops, taking the sharpest right turn where loops touch
+    let mut contours = Vec::new();
+    let mut starts: Vec<usize> = outgoing.keys().copied().collect();
+    starts.sort_unstable();
+    for start in starts {
+        while let Some(first) = outgoing.get_mut(&start).and_then(Vec::pop) {
+            let mut contour = vec![vertices[start]];
+            let (mut previous, mut current) = (start, first);
+            let mut closed = false;
+            for _ in 0..=vertices.len() {
+                if current == start {
+                    closed = true;
+                    break;
+                }
+                contour.push(vertices[current]);
+                let Some(candidates) = outgoing.get_mut(&current).filter(|c| !c.is_empty()) else {
+                    break;
+                };
+                let incoming = vertices[current] - vertices[previous];
+                let turn = |&next: &usize| {
+                    let out = vertices[next] - vertices[current];
+                    incoming.perp_dot(out).atan2(incoming.dot(out))
+                };
+                let choice = (0..candidates.len())
+                    .min_by(|&x, &y| turn(&candidates[x]).total_cmp(&turn(&candidates[y])))
+                    .unwrap();
+                let next = candidates.swap_remove(choice);
+                previous = current;
+                current = next;
+            }
+            if closed {
+                contours.extend(simplify_contour(contour, epsilon));
+            }
+        }
+    }
+    contours
+}
+
+/// Convex parts covering a set of contours
+///
+/// Clockwise contours are treated as holes of the smallest solid contour containing them.
+/// Holes outside every solid are ignored.
+pub(crate) fn decompose_contours(contours: &[Vec<Vec2>]) -> Vec<Vec<Vec2>> {
+    let epsilon = weld_tolerance(contours);
+    let (solids, holes): (Vec<Vec<Vec2>>, Vec<Vec<Vec2>>) = contours
+        .iter()
+        .filter_map(|contour| simplify_contour(contour.clone(), epsilon))
+        .partition(|contour| signed_area(contour) > 0.0);
+
+    let mut solid_holes: Vec<Vec<Vec<Vec2>>> = vec![Vec::new(); solids.len()];
+    for hole in holes {
+        let owner = solids
+            .iter()
+            .enumerate()
+            .map(|(index, solid)| {
+                let inside = hole
+                    .iter()
+                    .filter(|&&point| point_in_polygon(point, solid))
+                    .count();
+                (index, inside, signed_area(solid))
+            })
+            .filter(|&(_, inside, _)| inside > 0)
+            .max_by(|x, y| x.1.cmp(&y.1).then(y.2.total_cmp(&x.2)));
+        if let Some((index, ..)) = owner {
+            solid_holes[index].push(hole);
+        }
+    }
+
+    solids
+        .iter()
+        .zip(solid_holes)
+        .flat_map(|(solid, holes)| {
+            let polygon = bridge_holes(solid, holes, epsilon);
+            let triangles = ear_clip(&polygon, epsilon);
+            merge_convex(&polygon, triangles, epsilon)
+        })
+        .collect()
+}
+
+/// Join holes into a solid contour with zero-width bridges, giving one weakly simple polygon
+///
+/// Holes are connected rightmost first from their rightmost vertex to the nearest visible
+/// vertex of the polygon built so far.
+fn bridge_holes(solid: &[Vec2], mut holes: Vec<Vec<Vec2>>, epsilon: f32) -> Vec<Vec2> {
+    let max_x = |contour: &Vec<Vec2>| contour.iter().fold(f32::MIN, |m, p| m.max(p.x));
+    holes.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+
+    let mut polygon = solid.to_vec();
+    for index in 0..holes.len() {
+        let hole = &holes[index];
+        let rightmost = (0..hole.len())
+            .max_by(|&a, &b| hole[a].x.total_cmp(&hole[b].x))
+            .unwrap();
+        let from = hole[rightmost];
+
+        let mut candidates: Vec<usize> = (0..polygon.len()).collect();
+        candidates.sort_by(|&a, &b| {
+            polygon[a]
+                .distance_squared(from)
+                .total_cmp(&polygon[b].distance_squared(from))
+        });
+        let Some(target) = candidates
+            .into_iter()
+            .find(|&v| bridge_is_clear(from, polygon[v], &polygon, &holes[index..], epsilon))
+        else {
+            warn!("Could not connect a hole to its outline; the hole is ignored");
+            continue;
+        };
+
+        let mut bridged = Vec::with_capacity(polygon.len() + hole.len() + 2);
+        bridged.extend_from_slice(&polygon[..=target]);
+        bridged.extend((0..=hole.len()).map(|k| hole[(rightmost + k) % hole.len()]));
+        bridged.extend_from_slice(&polygon[target..]);
+        polygon = bridged;
+    }
+    polygon
+}
+
+/// Whether a bridge from `a` to `b` stays inside the polygon and clear of every contour
+fn bridge_is_clear(a: Vec2, b: Vec2, polygon: &[Vec2], holes: &[Vec<Vec2>], epsilon: f32) -> bool {
+    if a.distance(b) <= epsilon {
+        return false;
+    }
+    let middle = (a + b) * 0.5;
+    if !point_in_polygon(middle, polygon) || holes.iter().any(|h| point_in_polygon(middle, h)) {
+        return false;
+    }
+
+    let shares_end = |p: Vec2| p.distance(a) <= epsilon || p.distance(b) <= epsilon;
+    std::iter::once(polygon)
+        .chain(holes.iter().map(Vec::as_slice))
+        .all(|contour| {
+            (0..contour.len()).all(|i| {
+                let (c, d) = (contour[i], contour[(i + 1) % contour.len()]);
+                let touches = !shares_end(c) && distance_to_segment(c, a, b) <= epsilon;
+                let crosses = !shares_end(c) && !shares_end(d) && segments_cross(a, b, c, d);
+                !touches && !crosses
+            })
+        })
+}
+
+/// Whether two segments cross at a point inside both
+fn segments_cross(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    let d1 = (b - a).perp_dot(c - a);
+    let d2 = (b - a).perp_dot(d - a);
+    let d3 = (d - c).perp_dot(a - c);
+    let d4 = (d - c).perp_dot(b - c);
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+/// Distance from a point to a segment
+pub(crate) fn distance_to_segment(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let direction = b - a;
+    let t =
+        ((point - a).dot(direction) / direction.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+    point.distance(a + direction * t)
+}
+
+/// Whether `point` lies inside or on the counter-clockwise triangle `a b c`
+fn point_in_triangle(point: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).perp_dot(point - a) >= 0.0
+        && (c - b).perp_dot(point - b) >= 0.0
+        && (a - c).perp_dot(point - c) >= 0.0
+}
+
+/// Triangulate a counter-clockwise, weakly simple polygon by ear clipping
+///
+/// Returns triangles as indices into `polygon`. Degenerate corners are dropped without a
+/// triangle; if no ear can be found (self-intersecting input) the sharpest convex corner is
+/// clipped anyway so the loop always terminates.
+fn ear_clip(polygon: &[Vec2], epsilon: f32) -> Vec<[usize; 3]> {
+    let mut indices: Vec<usize> = (0..polygon.len()).collect();
+    let mut triangles = Vec::new();
+
+    while indices.len() >= 3 {
+        let n = indices.len();
+        let corner = |i: usize| (indices[(i + n - 1) % n], indices[i], indices[(i + 1) % n]);
+        if n == 3 {
+            let (a, b, c) = corner(1);
+            if (polygon[b] - polygon[a]).perp_dot(polygon[c] - polygon[a]) > 0.0 {
+                triangles.push([a, b, c]);
+            }
+            break;
+        }
+
+        let mut clipped = None;
+        let mut sharpest: Option<(usize, f32)> = None;
+        for i in 0..n {
+            let (ia, ib, ic) = corner(i);
+            let (a, b, c) = (polygon[ia], polygon[ib], polygon[ic]);
+            let chord = c - a;
+            let cross = (b - a).perp_dot(chord);
+            if chord.length() <= epsilon || cross.abs() / chord.length() <= epsilon {
+                clipped = Some((i, None));
+                break;
+            }
+            if cross < 0.0 {
+                continue;
+            }
+            if sharpest.is_none_or(|(_, best)| cross > best) {
+                sharpest = Some((i, cross));
+            }
+            let blocked = indices.iter().any(|&k| {
+                let p = polygon[k];
+                k != ia
+                    && k != ib
+                    && k != ic
+                    && [a, b, c].iter().all(|corner| corner.distance(p) > epsilon)
+                    && point_in_triangle(p, a, b, c)
+            });
+            if !blocked {
+                clipped = Some((i, Some([ia, ib, ic])));
+                break;
+            }
+        }
+
+        let (i, triangle) = match (clipped, sharpest) {
+            (Some(clipped), _) => clipped,
+            (None, Some((i, _))) => {
+                let (a, b, c) = corner(i);
+                (i, Some([a, b, c]))
+            }
+            (None, None) => break,
+        };
+        triangles.extend(triangle);
+        indices.remove(i);
+    }
+    triangles
+}
+
+/// Merge triangles across shared diagonals while the result stays convex (Hertel–Mehlhorn)
+fn merge_convex(polygon: &[Vec2], triangles: Vec<[usize; 3]>, epsilon: f32) -> Vec<Vec<Vec2>> {
+    let mut parts: Vec<Option<Vec<usize>>> = triangles
+        .into_iter()
+        .map(|triangle| Some(triangle.to_vec()))
+        .collect();
+    let mut owner: HashMap<(usize, usize), usize> = HashMap::new();
+    for (index, part) in parts.iter().enumerate() {
+        let part = part.as_ref().unwrap();
+        for i in 0..part.len() {
+            owner.insert((part[i], part[(i + 1) % part.len()]), index);
+        }
+    }
+
+    let mut merged_any = true;
+    while merged_any {
+        merged_any = false;
+        for index in 0..parts.len() {
+            let Some(part) = parts[index].clone() else {
+                continue;
+            };
+            for i in 0..part.len() {
+                let (a, b) = (part[i], part[(i + 1) % part.len()]);
+                let Some(&other_index) = owner.get(&(b, a)) else {
+                    continue;
+                };
+                let Some(other) = parts[other_index].as_ref().filter(|_| other_index != index)
+                else {
+                    continue;
+                };
+                let Some(j) =
+                    (0..other.len()).find(|&j| other[j] == b && other[(j + 1) % other.len()] == a)
+                else {
+                    continue;
+                };
+
+                // Walk this part from b round to a, then the other part past a round to b
+                let mut merged: Vec<usize> = (1..=part.len())
+                    .map(|k| part[(i + k) % part.len()])
+                    .collect();
+                merged.extend((2..other.len()).map(|k| other[(j + k) % other.len()]));
+                if !is_convex(polygon, &merged, epsilon) {
+                    continue;
+                }
+
+                for k in 0..merged.len() {
+                    owner.insert((merged[k], merged[(k + 1) % merged.len()]), index);
+                }
+                parts[index] = Some(merged);
+                parts[other_index] = None;
+                merged_any = true;
+                break;
+            }
+        }
+    }
+
+    parts
+        .into_iter()
+        .flatten()
+        .map(|part| part.into_iter().map(|i| polygon[i]).collect())
+        .collect()
+}
+
+/// Whether a counter-clockwise polygon given by indices has no reflex corner
+fn is_convex(polygon: &[Vec2], part: &[usize], epsilon: f32) -> bool {
+    let n = part.len();
+    (0..n).all(|i| {
+        let a = polygon[part[(i + n - 1) % n]];
+        let b = polygon[part[i]];
+        let c = polygon[part[(i + 1) % n]];
+        let chord = (c - a).length();
+        chord <= epsilon || (b - a).perp_dot(c - a) / chord >= -epsilon
+    })
+}
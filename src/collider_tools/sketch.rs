@@ -0,0 +1,324 @@
+//! Freehand sketching of colliders
+//!
+//! Sketch mode records the cursor path while the left button is held and simplifies it with the
+//! Ramer–Douglas–Peucker algorithm. A stroke that ends close to where it started is closed into a
+//! loop and decomposed into convex parts through a [`PolygonSource`]; any other stroke becomes a
+//! polyline collider.
+//!
+//! The recorded path stays on the entity as a [`SketchStroke`]. Changing its tolerance later
+//! simplifies the original path again, so detail removed by a coarse tolerance can be recovered.
+//! Boolean operations and vertex drags in Edit mode replace the outline, so they drop the stroke
+//! and the body keeps the edited shape.
+//! New bodies use the body type and physics properties from Create mode.
+//!
+//! ## Controls (Sketch mode)
+//! - Drag: draw a stroke
+//! - Escape: discard the stroke in progress
+
+use super::creation::{CreationProperties, add_body_properties};
+use super::polygon::{PolygonSource, distance_to_segment, signed_area};
+use super::utils::get_mouse_world_position;
+use super::{ColliderCreationState, ColliderType, EditorGizmoConfigGroup};
+use crate::selection::{EditorSelection, Selectable};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::input::egui_wants_any_input;
+
+/// Recorded path of a sketched collider
+///
+/// The collider is regenerated from the path whenever this component changes.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct SketchStroke {
+    /// Cursor samples in the entity's local space
+    pub points: Vec<Vec2>,
+    /// Whether the stroke was closed into a loop
+    pub closed: bool,
+    /// Largest distance the simplified outline may stray from the samples, in world units
+    pub tolerance: f32,
+}
+
+impl SketchStroke {
+    /// Simplified vertices: a polyline for open strokes, a counter-clockwise loop for closed ones
+    pub fn simplified(&self) -> Vec<Vec2> {
+        if self.closed {
+            let mut outline = simplify_loop(&self.points, self.tolerance);
+            if signed_area(&outline) < 0.0 {
+                outline.reverse();
+            }
+            outline
+        } else {
+            simplify_polyline(&self.points, self.tolerance)
+        }
+    }
+}
+
+/// Options for new strokes
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SketchSettings {
+    /// Simplification tolerance given to new strokes, in world units
+    pub tolerance: f32,
+    /// A stroke ending this close to its start is closed into a loop, in world units
+    pub close_distance: f32,
+    /// Minimum cursor movement before another sample is recorded, in world units
+    pub sample_spacing: f32,
+}
+
+impl Default for SketchSettings {
+    fn default() -> Self {
+        Self {
+            tolerance: 4.0,
+            close_distance: 16.0,
+            sample_spacing: 2.0,
+        }
+    }
+}
+
+/// Stroke being drawn
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SketchState {
+    /// World-space samples of the stroke in progress
+    pub points: Vec<Vec2>,
+}
+
+/// Plugin for the freehand sketch tool
+#[derive(Default)]
+pub struct SketchPlugin;
+
+impl Plugin for SketchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SketchSettings>()
+            .init_resource::<SketchState>()
+            .register_type::<SketchStroke>()
+            .register_type::<SketchSettings>()
+            .add_systems(OnExit(super::ToolMode::Sketch), on_exit_sketch_mode)
+            .add_systems(
+                Update,
+                handle_sketch_input
+                    .run_if(in_state(super::ToolMode::Sketch).and(not(egui_wants_any_input))),
+            )
+            .add_systems(
+                Update,
+                (
+                    draw_sketch_preview::<EditorGizmoConfigGroup>
+                        .run_if(in_state(super::ToolMode::Sketch)),
+                    rebuild_sketch_colliders,
+                ),
+            );
+    }
+}
+
+/// Simplify an open polyline with the Ramer–Douglas–Peucker algorithm
+///
+/// The end points are always kept.
+pub(crate) fn simplify_polyline(points: &[Vec2], tolerance: f32) -> Vec<Vec2> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let last = points.len() - 1;
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[last] = true;
+
+    let mut ranges = vec![(0, last)];
+    while let Some((start, end)) = ranges.pop() {
+        let farthest = (start + 1..end)
+            .map(|i| {
+                (
+                    i,
+                    distance_to_segment(points[i], points[start], points[end]),
+                )
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((index, distance)) = farthest {
+            if distance > tolerance {
+                keep[index] = true;
+                ranges.push((start, index));
+                ranges.push((index, end));
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(&point, keep)| keep.then_some(point))
+        .collect()
+}
+
+/// Simplify a closed loop
+///
+/// The loop is split at the sample farthest from the first one and both halves are simplified
+/// as open polylines, so neither split point can be dropped.
+pub(crate) fn simplify_loop(points: &[Vec2], tolerance: f32) -> Vec<Vec2> {
+    if points.len() < 4 {
+        return points.to_vec();
+    }
+    let far = (1..points.len())
+        .max_by(|&a, &b| {
+            points[a]
+                .distance_squared(points[0])
+                .total_cmp(&points[b].distance_squared(points[0]))
+        })
+        .unwrap();
+
+    let mut outline = simplify_polyline(&points[..=far], tolerance);
+    let mut back = points[far..].to_vec();
+    back.push(points[0]);
+    let back = simplify_polyline(&back, tolerance);
+    // Both halves repeat the split points
+    outline.pop();
+    outline.extend_from_slice(&back[..back.len() - 1]);
+    outline
+}
+
+/// Insert the collider for a stroke
+///
+/// Closed strokes get a [`PolygonSource`]; open strokes, and loops without area, a polyline.
+fn insert_stroke_collider(entity_commands: &mut EntityCommands, stroke: &SketchStroke) {
+    let vertices = stroke.simplified();
+    if stroke.closed {
+        let source = PolygonSource::new(vec![vertices.clone()]);
+        if let Some(collider) = source.collider() {
+            entity_commands.insert((source, collider));
+            return;
+        }
+    }
+    if vertices.len() >= 2 {
+        entity_commands
+            .remove::<PolygonSource>()
+            .insert(Collider::polyline(vertices, None));
+    }
+}
+
+/// Record strokes and turn finished ones into colliders
+fn handle_sketch_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut state: ResMut<SketchState>,
+    mut creation_state: ResMut<ColliderCreationState>,
+    mut selection: ResMut<EditorSelection>,
+    settings: Res<SketchSettings>,
+    properties: Res<CreationProperties>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<SpritePickingCamera>>,
+    window_query: Query<&Window>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        state.points.clear();
+        return;
+    }
+
+    let (Ok((camera, camera_transform)), Ok(window)) =
+        (camera_query.single(), window_query.single())
+    else {
+        return;
+    };
+    let mouse_pos = get_mouse_world_position(window, camera, camera_transform);
+
+    if mouse_input.just_pressed(MouseButton::Left) {
+        state.points = mouse_pos.into_iter().collect();
+    } else if mouse_input.pressed(MouseButton::Left) {
+        if let (Some(mouse_pos), Some(&last)) = (mouse_pos, state.points.last()) {
+            if mouse_pos.distance(last) >= settings.sample_spacing {
+                state.points.push(mouse_pos);
+            }
+        }
+    }
+
+    if !mouse_input.just_released(MouseButton::Left) {
+        return;
+    }
+    let points = std::mem::take(&mut state.points);
+    let length: f32 = points.windows(2).map(|w| w[0].distance(w[1])).sum();
+    if points.len() < 2 || length < settings.close_distance {
+        return;
+    }
+
+    // A loop needs some extent beyond the closing gap, or every short stroke would close
+    let closed = points.len() >= 3
+        && points[0].distance(points[points.len() - 1]) <= settings.close_distance
+        && length > settings.close_distance * 3.0;
+    let (min, max) = points
+        .iter()
+        .fold((Vec2::MAX, Vec2::MIN), |(min, max), &p| {
+            (min.min(p), max.max(p))
+        });
+    let origin = (min + max) * 0.5;
+    let stroke = SketchStroke {
+        points: points.iter().map(|&point| point - origin).collect(),
+        closed,
+        tolerance: settings.tolerance,
+    };
+
+    let mut entity_commands = commands.spawn((
+        Transform::from_translation(origin.extend(0.0)),
+        properties.body_type,
+        ColliderType::Polygon,
+        DebugRender {
+            collider_color: Some(properties.color),
+            axis_lengths: Some(avian2d::math::Vector::new(0.8, 0.8)),
+            ..default()
+        },
+        Selectable,
+        Pickable::default(),
+    ));
+    // The collider is built by `rebuild_sketch_colliders` once the stroke is added
+    entity_commands.insert(stroke);
+    add_body_properties(&mut entity_commands, &properties);
+
+    let entity = entity_commands.id();
+    creation_state.created_colliders.push(entity);
+    selection.set(entity);
+    info!(
+        "Sketched {} stroke with {} samples",
+        if closed { "closed" } else { "open" },
+        points.len()
+    );
+}
+
+/// Re-simplify strokes whose samples, closing or tolerance changed
+fn rebuild_sketch_colliders(
+    mut commands: Commands,
+    query: Query<(Entity, &SketchStroke), Changed<SketchStroke>>,
+) {
+    for (entity, stroke) in &query {
+        insert_stroke_collider(&mut commands.entity(entity), stroke);
+    }
+}
+
+/// Draw the raw stroke, its simplified outline and the closing gap
+fn draw_sketch_preview<Config: GizmoConfigGroup>(
+    mut gizmos: Gizmos<Config>,
+    state: Res<SketchState>,
+    settings: Res<SketchSettings>,
+    theme_colors: Res<crate::ui::theme_colors::EditorThemeColors>,
+) {
+    let points = &state.points;
+    if points.len() < 2 {
+        return;
+    }
+    gizmos.linestrip_2d(points.iter().copied(), theme_colors.dashed_line_base_alpha);
+    gizmos.linestrip_2d(
+        simplify_polyline(points, settings.tolerance),
+        theme_colors.selection_outline,
+    );
+
+    // Show where the stroke has to end to close
+    let (start, end) = (points[0], points[points.len() - 1]);
+    if start.distance(end) <= settings.close_distance {
+        gizmos.line_2d(end, start, theme_colors.measure_snap);
+    }
+    gizmos.circle_2d(
+        Isometry2d::from_translation(start),
+        settings.close_distance,
+        theme_colors.measure_snap,
+    );
+}
+
+/// Drop the stroke in progress when leaving sketch mode
+fn on_exit_sketch_mode(mut state: ResMut<SketchState>) {
+    state.points.clear();
+}
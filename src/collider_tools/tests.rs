@@ -2,9 +2,42 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::JointType;
     use crate::collider_tools::anchor::AnchorCreationState;
+    use crate::collider_tools::arrange::{
+        AlignEdge, AlignReference, ArrangeAxis, ArrangeEvent, arrange_offsets,
+    };
+    use crate::collider_tools::boolean::{BooleanOp, boolean_pieces, collider_world_pieces};
+    use crate::collider_tools::debug_render::anchor::AnchorPoint;
+    use crate::collider_tools::debug_render::joint::JointConfig;
+    use crate::collider_tools::force_field::{
+        ForceFalloff, ForceField, field_acceleration, submerged_fraction,
+    };
+    use crate::collider_tools::joint_config::{JointConfiguration, JointConfigurationEnum};
+    use crate::collider_tools::kinematic_path::{KinematicPath, PathEasing, PathLoopMode};
+    use crate::collider_tools::lint::{LintFix, LintKind, LintSettings, scene_lint};
+    use crate::collider_tools::measure::{
+        Dimension, GuideAxis, MeasureGuides, MeasurePoint, MeasuredValue,
+    };
+    use crate::collider_tools::mirror::{FlipDirection, mirror_collider, mirror_joint_details};
+    use crate::collider_tools::object_snap::{ObjectSnapKind, find_object_snap};
+    use crate::collider_tools::physics_management::PlayFromStart;
+    use crate::collider_tools::polygon::{
+        PolygonSource, decompose_contours, outline_contours, signed_area,
+    };
+    use crate::collider_tools::sketch::{SketchStroke, simplify_loop, simplify_polyline};
+    use crate::collider_tools::tile::{TileLayer, TileMergeMode, cell_outlines, merge_cells};
+    use crate::collider_tools::units::{LengthDisplayUnit, UnitConverter};
     use crate::collider_tools::utils::find_closest_vertex;
+    use crate::collider_tools::velocity::{
+        InitialVelocity, VELOCITY_PREVIEW_TIME, angular_handle_position, angular_handle_sweep,
+        apply_initial_velocities_on_play, linear_handle_position,
+    };
+    use crate::selection::Selectable;
+    use crate::transform_gizmos::{PivotMode, selection_pivot};
+    use avian2d::parry::shape::TypedShape;
     use avian2d::prelude::*;
+    use bevy::ecs::system::RunSystemOnce;
     use bevy::prelude::*;
 
     #[test]
@@ -58,6 +91,565 @@ mod tests {
         assert!(state.mouse_position.is_none());
     }
 
+    #[test]
+    fn test_velocity_handle_positions() {
+        let velocity = InitialVelocity {
+            linear: Vec2::new(100.0, 0.0),
+            angular: std::f32::consts::PI,
+        };
+
+        // Arrow tip shows where the body would be after the preview time
+        let tip = linear_handle_position(Vec2::new(10.0, 5.0), &velocity);
+        assert!((tip - Vec2::new(10.0 + 100.0 * VELOCITY_PREVIEW_TIME, 5.0)).length() < 1e-4);
+
+        // Angular handle sweeps counter-clockwise from the start angle
+        let handle = angular_handle_position(Vec2::ZERO, 0.0, 10.0, velocity.angular);
+        let expected_angle = std::f32::consts::PI * VELOCITY_PREVIEW_TIME;
+        assert!((handle - Vec2::from_angle(expected_angle) * 10.0).length() < 1e-4);
+
+        // Very fast spins are clamped to just under a full turn
+        let sweep = angular_handle_sweep(1000.0);
+        assert!(sweep < std::f32::consts::TAU);
+        assert!(angular_handle_sweep(-1000.0) > -std::f32::consts::TAU);
+    }
+
+    #[test]
+    fn test_force_field_acceleration() {
+        // Wind direction follows the field's rotation
+        let wind = ForceField::wind(Vec2::X, 100.0);
+        let rotated = field_acceleration(
+            &wind,
+            Vec2::ZERO,
+            std::f32::consts::FRAC_PI_2,
+            50.0,
+            Vec2::ZERO,
+        );
+        assert!((rotated - Vec2::new(0.0, 100.0)).length() < 1e-3);
+
+        // Linear falloff halves the pull halfway to the edge
+        let attractor = ForceField::attractor(100.0, ForceFalloff::Linear);
+        let pull = field_acceleration(&attractor, Vec2::ZERO, 0.0, 50.0, Vec2::new(25.0, 0.0));
+        assert!((pull - Vec2::new(-50.0, 0.0)).length() < 1e-3);
+
+        // Positive vortex strength spins counter-clockwise
+        let vortex = ForceField::vortex(10.0, 0.0);
+        let spin = field_acceleration(&vortex, Vec2::ZERO, 0.0, 50.0, Vec2::new(10.0, 0.0));
+        assert!((spin - Vec2::new(0.0, 10.0)).length() < 1e-3);
+
+        // Half of a body below the surface is half submerged
+        let body = ColliderAabb::new(Vec2::ZERO, Vec2::splat(10.0));
+        assert!((submerged_fraction(&body, 0.0) - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_initial_velocities_apply_only_on_play_from_start() {
+        let mut world = World::new();
+        world.init_resource::<Events<PlayFromStart>>();
+        let body = world
+            .spawn(InitialVelocity {
+                linear: Vec2::new(3.0, 4.0),
+                angular: 1.5,
+            })
+            .id();
+
+        // Resuming without playing from the start keeps the live velocities
+        world
+            .run_system_once(apply_initial_velocities_on_play)
+            .unwrap();
+        assert!(world.get::<LinearVelocity>(body).is_none());
+
+        world.send_event(PlayFromStart);
+        world
+            .run_system_once(apply_initial_velocities_on_play)
+            .unwrap();
+        assert_eq!(
+            world.get::<LinearVelocity>(body).unwrap().0,
+            Vec2::new(3.0, 4.0)
+        );
+        assert_eq!(world.get::<AngularVelocity>(body).unwrap().0, 1.5);
+    }
+
+    #[test]
+    fn test_kinematic_path_sampling() {
+        let mut path = KinematicPath::from_pose(Vec2::ZERO, 0.0);
+        path.push_keyframe(Vec2::new(100.0, 0.0));
+        path.keyframes[0].easing = PathEasing::EaseInOut;
+
+        // Ease in-out is symmetric, so the midpoint is still halfway
+        let (middle, _) = path.sample(0.5).unwrap();
+        assert!((middle.x - 50.0).abs() < 1e-3);
+        let (early, _) = path.sample(0.25).unwrap();
+        assert!(early.x < 25.0);
+
+        // Ping-pong plays back to the start, loop wraps around
+        path.mode = PathLoopMode::PingPong;
+        assert!((path.path_time(1.5) - 0.5).abs() < 1e-4);
+        path.mode = PathLoopMode::Loop;
+        assert!((path.path_time(1.25) - 0.25).abs() < 1e-4);
+        path.mode = PathLoopMode::Once;
+        assert!((path.path_time(5.0) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_unit_converter() {
+        let meters = UnitConverter {
+            unit: LengthDisplayUnit::Meters,
+            pixels_per_meter: 100.0,
+        };
+        assert!((meters.to_display(250.0) - 2.5).abs() < 1e-5);
+        assert!((meters.to_world(2.5) - 250.0).abs() < 1e-3);
+        assert_eq!(meters.format_length(250.0), "2.500 m");
+        assert_eq!(meters.format_speed(50.0), "0.500 m/s");
+
+        let pixels = UnitConverter::default();
+        assert_eq!(pixels.format_length(12.34), "12.3 px");
+    }
+
+    #[test]
+    fn test_measure_dimensions_and_guides() {
+        let no_entities = |_: Entity| None::<GlobalTransform>;
+
+        let distance = Dimension::Distance {
+            start: MeasurePoint::World(Vec2::ZERO),
+            end: MeasurePoint::World(Vec2::new(30.0, 40.0)),
+        };
+        match distance.measure(no_entities) {
+            Some(MeasuredValue::Distance(delta)) => assert!((delta.length() - 50.0).abs() < 1e-4),
+            other => panic!("unexpected measurement {:?}", other),
+        }
+
+        let angle = Dimension::Angle {
+            vertex: MeasurePoint::World(Vec2::ZERO),
+            from: MeasurePoint::World(Vec2::X),
+            to: MeasurePoint::World(Vec2::new(0.0, -5.0)),
+        };
+        match angle.measure(no_entities) {
+            Some(MeasuredValue::Angle(radians)) => {
+                assert!((radians.to_degrees() - 90.0).abs() < 1e-3)
+            }
+            other => panic!("unexpected measurement {:?}", other),
+        }
+
+        // Points attached to a missing entity do not resolve
+        let attached = Dimension::Distance {
+            start: MeasurePoint::World(Vec2::ZERO),
+            end: MeasurePoint::Attached {
+                entity: Entity::PLACEHOLDER,
+                local: Vec2::ZERO,
+            },
+        };
+        assert!(attached.measure(no_entities).is_none());
+
+        let guides = MeasureGuides {
+            vertical: vec![100.0],
+            horizontal: vec![-20.0, 50.0],
+            ..default()
+        };
+        let (snapped, moved) = guides.snap_point(Vec2::new(96.0, 47.0), 8.0);
+        assert!(moved);
+        assert_eq!(snapped, Vec2::new(100.0, 50.0));
+        assert!(!guides.snap_point(Vec2::new(80.0, 0.0), 8.0).1);
+        assert_eq!(
+            guides.guide_at(Vec2::new(0.0, -18.0), 6.0),
+            Some((GuideAxis::Horizontal, 0))
+        );
+    }
+
+    #[test]
+    fn test_object_snap() {
+        let at = |x: f32, y: f32| GlobalTransform::from(Transform::from_xyz(x, y, 0.0));
+        let ground = [(Collider::rectangle(100.0, 20.0), at(0.0, 0.0))];
+
+        // Corners 3px apart on both axes snap vertex to vertex
+        let moving = [(Collider::rectangle(40.0, 20.0), at(73.0, 3.0))];
+        let snap = find_object_snap(&moving, &ground, 10.0).unwrap();
+        assert_eq!(snap.kind, ObjectSnapKind::Vertex);
+        assert!((snap.offset - Vec2::new(-3.0, -3.0)).length() < 1e-3);
+
+        // Sinking 6px into the top edge pulls the box back onto it
+        let moving = [(Collider::rectangle(40.0, 20.0), at(60.0, 14.0))];
+        let snap = find_object_snap(&moving, &ground, 10.0).unwrap();
+        assert_eq!(snap.kind, ObjectSnapKind::Edge);
+        assert!((snap.offset - Vec2::new(0.0, 6.0)).length() < 1e-3);
+
+        // Nothing within the threshold
+        let moving = [(Collider::rectangle(40.0, 20.0), at(200.0, 100.0))];
+        assert!(find_object_snap(&moving, &ground, 10.0).is_none());
+    }
+
+    #[test]
+    fn test_selection_pivot() {
+        let origins = [
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(10.0, 0.0, 0.0),
+            Vec3::new(50.0, 30.0, 0.0),
+        ];
+        let custom = Vec3::new(-5.0, 5.0, 0.0);
+        let pivot = |mode, active| selection_pivot(mode, &origins, active, custom).unwrap();
+
+        assert_eq!(pivot(PivotMode::Median, None), Vec3::new(20.0, 10.0, 0.0));
+        assert_eq!(
+            pivot(PivotMode::BoundsCenter, None),
+            Vec3::new(25.0, 15.0, 0.0)
+        );
+        assert_eq!(pivot(PivotMode::Active, Some(origins[1])), origins[1]);
+        // Without a primary selection the median is used
+        assert_eq!(pivot(PivotMode::Active, None), Vec3::new(20.0, 10.0, 0.0));
+        assert_eq!(pivot(PivotMode::Custom, None), custom);
+
+        assert!(selection_pivot(PivotMode::Median, &[], None, custom).is_none());
+    }
+
+    #[test]
+    fn test_arrange_offsets() {
+        let bounds = [
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+            Rect::new(30.0, 5.0, 50.0, 15.0),
+            Rect::new(100.0, -5.0, 110.0, 5.0),
+        ];
+        let run =
+            |command, active, reference| arrange_offsets(command, &bounds, active, reference, 2.0);
+
+        let left = run(
+            ArrangeEvent::Align(AlignEdge::Left),
+            None,
+            AlignReference::SelectionBounds,
+        );
+        assert_eq!(
+            left,
+            vec![Vec2::ZERO, Vec2::new(-30.0, 0.0), Vec2::new(-100.0, 0.0)]
+        );
+
+        // Aligning to the active collider leaves it in place
+        let top = run(
+            ArrangeEvent::Align(AlignEdge::Top),
+            Some(1),
+            AlignReference::Active,
+        );
+        assert_eq!(
+            top,
+            vec![Vec2::new(0.0, 5.0), Vec2::ZERO, Vec2::new(0.0, 10.0)]
+        );
+
+        // 110 wide span, 40 occupied: 35 between each pair
+        let distribute = run(
+            ArrangeEvent::Distribute(ArrangeAxis::Horizontal),
+            None,
+            AlignReference::SelectionBounds,
+        );
+        assert_eq!(
+            distribute,
+            vec![Vec2::ZERO, Vec2::new(15.0, 0.0), Vec2::ZERO]
+        );
+
+        let stack = run(
+            ArrangeEvent::Stack(ArrangeAxis::Horizontal),
+            None,
+            AlignReference::SelectionBounds,
+        );
+        assert_eq!(
+            stack,
+            vec![Vec2::ZERO, Vec2::new(-18.0, 0.0), Vec2::new(-66.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_mirror_shapes_and_joints() {
+        let triangle = Collider::triangle(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(0.0, 20.0),
+        );
+        let mirrored = mirror_collider(&triangle, FlipDirection::Horizontal).unwrap();
+        let TypedShape::Triangle(shape) = mirrored.shape().as_typed_shape() else {
+            panic!("expected a triangle");
+        };
+        let (a, b, c) = (
+            Vec2::new(shape.a.x, shape.a.y),
+            Vec2::new(shape.b.x, shape.b.y),
+            Vec2::new(shape.c.x, shape.c.y),
+        );
+        for vertex in [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(-10.0, 0.0),
+            Vec2::new(0.0, 20.0),
+        ] {
+            assert!([a, b, c].contains(&vertex));
+        }
+        // Still counter-clockwise
+        assert!((b - a).perp_dot(c - a) > 0.0);
+
+        let capsule = Collider::capsule_endpoints(2.0, Vec2::new(1.0, 3.0), Vec2::new(4.0, 5.0));
+        let mirrored = mirror_collider(&capsule, FlipDirection::Vertical).unwrap();
+        let TypedShape::Capsule(shape) = mirrored.shape().as_typed_shape() else {
+            panic!("expected a capsule");
+        };
+        assert_eq!(
+            Vec2::new(shape.segment.a.x, shape.segment.a.y),
+            Vec2::new(1.0, -3.0)
+        );
+        assert_eq!(
+            Vec2::new(shape.segment.b.x, shape.segment.b.y),
+            Vec2::new(4.0, -5.0)
+        );
+
+        let mut revolute = JointConfiguration::new(JointType::Revolute);
+        revolute.revolute.min_angle = Some(-0.2);
+        revolute.revolute.max_angle = Some(0.8);
+        let mut details = revolute.to_enum();
+        mirror_joint_details(&mut details, FlipDirection::Horizontal);
+        let JointConfigurationEnum::Revolute { config, .. } = details else {
+            panic!("expected a revolute joint");
+        };
+        assert_eq!(
+            (config.min_angle, config.max_angle),
+            (Some(-0.8), Some(0.2))
+        );
+
+        let mut prismatic = JointConfiguration::new(JointType::Prismatic);
+        prismatic.prismatic.free_axis = Vec2::new(0.6, 0.8);
+        let mut details = prismatic.to_enum();
+        mirror_joint_details(&mut details, FlipDirection::Horizontal);
+        let JointConfigurationEnum::Prismatic { config, .. } = details else {
+            panic!("expected a prismatic joint");
+        };
+        assert_eq!(config.free_axis, Vec2::new(-0.6, 0.8));
+    }
+
+    #[test]
+    fn test_polygon_boolean_ops() {
+        let area = |pieces: &[Vec<Vec2>]| pieces.iter().map(|p| signed_area(p)).sum::<f32>();
+        let square = |center: Vec2| {
+            collider_world_pieces(
+                &Collider::rectangle(100.0, 100.0),
+                &GlobalTransform::from_translation(center.extend(0.0)),
+            )
+            .unwrap()
+        };
+        let a = square(Vec2::ZERO);
+        let b = square(Vec2::new(50.0, 50.0));
+
+        // Overlapping squares: one L-shaped outline for union and subtract
+        let union = outline_contours(&boolean_pieces(BooleanOp::Union, &a, &b));
+        assert_eq!(union.len(), 1);
+        assert!((signed_area(&union[0]) - 17500.0).abs() < 0.1);
+        let subtract = outline_contours(&boolean_pieces(BooleanOp::Subtract, &a, &b));
+        assert_eq!(subtract.len(), 1);
+        assert_eq!(subtract[0].len(), 6);
+        let intersect = boolean_pieces(BooleanOp::Intersect, &a, &b);
+        assert!((area(&intersect) - 2500.0).abs() < 0.1);
+
+        // A circle in the middle leaves a clockwise hole
+        let circle =
+            collider_world_pieces(&Collider::circle(20.0), &GlobalTransform::IDENTITY).unwrap();
+        let contours = outline_contours(&boolean_pieces(BooleanOp::Subtract, &a, &circle));
+        assert_eq!(contours.len(), 2);
+        assert!(signed_area(&contours[0]) * signed_area(&contours[1]) < 0.0);
+
+        // Convex parts of the holed square cover exactly its area
+        let parts = decompose_contours(&contours);
+        let expected = 10000.0 - area(&circle);
+        assert!((area(&parts) - expected).abs() < 0.1);
+        for part in &parts {
+            let n = part.len();
+            assert!((0..n).all(|i| {
+                (part[i] - part[(i + n - 1) % n]).perp_dot(part[(i + 1) % n] - part[i]) >= -1e-3
+            }));
+        }
+        assert!(PolygonSource::new(contours).collider().is_some());
+        assert!(PolygonSource::default().collider().is_none());
+    }
+
+    #[test]
+    fn test_sketch_simplification() {
+        // A jittery straight stroke collapses to its end points
+        let line: Vec<Vec2> = (0..=20)
+            .map(|i| Vec2::new(i as f32 * 5.0, if i % 2 == 0 { 0.5 } else { -0.5 }))
+            .collect();
+        let simplified = simplify_polyline(&line, 2.0);
+        assert_eq!(simplified, vec![line[0], line[20]]);
+        // A corner survives
+        let corner = [
+            Vec2::ZERO,
+            Vec2::new(50.0, 1.0),
+            Vec2::new(100.0, 0.0),
+            Vec2::new(100.0, 100.0),
+        ];
+        assert_eq!(simplify_polyline(&corner, 2.0).len(), 3);
+
+        // A densely sampled clockwise square loop keeps its four corners
+        let corners = [
+            Vec2::new(-50.0, -50.0),
+            Vec2::new(-50.0, 50.0),
+            Vec2::new(50.0, 50.0),
+            Vec2::new(50.0, -50.0),
+        ];
+        let samples: Vec<Vec2> = (0..4)
+            .flat_map(|i| {
+                let (a, b) = (corners[i], corners[(i + 1) % 4]);
+                (0..10).map(move |t| a.lerp(b, t as f32 / 10.0))
+            })
+            .collect();
+        let outline = simplify_loop(&samples, 1.0);
+        assert_eq!(outline.len(), 4);
+        assert!(corners.iter().all(|corner| outline.contains(corner)));
+
+        // Closed strokes are wound counter-clockwise for the polygon source
+        let stroke = SketchStroke {
+            points: samples,
+            closed: true,
+            tolerance: 1.0,
+        };
+        assert!(signed_area(&stroke.simplified()) > 0.0);
+        assert!(
+            PolygonSource::new(vec![stroke.simplified()])
+                .collider()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_tile_merging() {
+        // An L of five cells: a 3x1 row with a 1x2 column on its left end
+        let cells = [
+            IVec2::new(0, 0),
+            IVec2::new(1, 0),
+            IVec2::new(2, 0),
+            IVec2::new(0, 1),
+            IVec2::new(0, 2),
+        ];
+        let rects = merge_cells(&cells);
+        assert_eq!(rects.len(), 2);
+        assert_eq!(rects.iter().map(|r| r.width() * r.height()).sum::<i32>(), 5);
+        let outlines = cell_outlines(&cells);
+        assert_eq!(outlines.len(), 1);
+        assert_eq!(outlines[0].len(), 6);
+
+        // A ring has a counter-clockwise outside and a clockwise hole
+        let ring: Vec<IVec2> = (0..3)
+            .flat_map(|x| (0..3).map(move |y| IVec2::new(x, y)))
+            .filter(|&cell| cell != IVec2::ONE)
+            .collect();
+        let outlines = cell_outlines(&ring);
+        assert_eq!(outlines.len(), 2);
+        let area = |outline: &Vec<IVec2>| {
+            signed_area(&outline.iter().map(|c| c.as_vec2()).collect::<Vec<_>>())
+        };
+        assert_eq!(area(&outlines[0]) + area(&outlines[1]), 8.0);
+
+        // Cells touching only at a corner stay separate
+        assert_eq!(cell_outlines(&[IVec2::ZERO, IVec2::ONE]).len(), 2);
+
+        let mut layer = TileLayer::new(16.0, TileMergeMode::Rectangles);
+        assert!(layer.collider().is_none());
+        assert!(layer.set(layer.cell_at(Vec2::new(-1.0, 20.0)), true));
+        assert!(!layer.set(IVec2::new(-1, 1), true));
+        assert_eq!(layer.cells, vec![IVec2::new(-1, 1)]);
+        assert!(layer.collider().is_some());
+        layer.merge = TileMergeMode::Outline;
+        assert!(layer.collider().is_some());
+        assert!(layer.set(IVec2::new(-1, 1), false));
+        assert!(layer.cells.is_empty());
+
+        // Mirrored cells cover the reflected area and stay sorted by row
+        layer.cells = vec![IVec2::new(0, 0), IVec2::new(2, 0), IVec2::new(-3, 1)];
+        layer.mirror(FlipDirection::Horizontal);
+        assert_eq!(
+            layer.cells,
+            vec![IVec2::new(-3, 0), IVec2::new(-1, 0), IVec2::new(2, 1)]
+        );
+        layer.mirror(FlipDirection::Vertical);
+        assert_eq!(
+            layer.cells,
+            vec![IVec2::new(2, -2), IVec2::new(-3, -1), IVec2::new(-1, -1)]
+        );
+    }
+
+    #[test]
+    fn test_scene_lint() {
+        let mut world = World::new();
+        let mut spawn_body = |x: f32, body: RigidBody, collider: Collider| {
+            let transform = Transform::from_xyz(x, 0.0, 0.0);
+            world
+                .spawn((
+                    transform,
+                    GlobalTransform::from(transform),
+                    body,
+                    collider,
+                    Selectable::default(),
+                ))
+                .id()
+        };
+        let ground = spawn_body(-200.0, RigidBody::Static, Collider::rectangle(100.0, 10.0));
+        let post = spawn_body(-100.0, RigidBody::Static, Collider::rectangle(10.0, 10.0));
+        let a = spawn_body(0.0, RigidBody::Dynamic, Collider::rectangle(20.0, 20.0));
+        let b = spawn_body(10.0, RigidBody::Dynamic, Collider::rectangle(20.0, 20.0));
+        let sensor = spawn_body(100.0, RigidBody::Static, Collider::circle(5.0));
+        let isolated = spawn_body(200.0, RigidBody::Dynamic, Collider::circle(5.0));
+        let flat = spawn_body(300.0, RigidBody::Static, Collider::rectangle(0.0, 10.0));
+        world.entity_mut(sensor).insert(Sensor);
+        world
+            .entity_mut(isolated)
+            .insert(CollisionLayers::from_bits(1, 0));
+
+        let joint = |parent: Entity, child: Entity| JointConfig {
+            anchor_a: parent,
+            anchor_b: child,
+            anchor_a_is_anchor: false,
+            anchor_b_is_anchor: false,
+            parent_entity: parent,
+            child_entity: child,
+            joint_config_details: JointConfigurationEnum::default(),
+        };
+        let gone = world.spawn_empty().id();
+        world.despawn(gone);
+        world.spawn(joint(ground, post));
+        let dangling = world.spawn(joint(a, gone)).id();
+        let anchor = world
+            .spawn(AnchorPoint {
+                local_anchor_position: Vec2::new(30.0, 0.0),
+                parent_entity: a,
+                ..default()
+            })
+            .id();
+
+        let findings = scene_lint(&world, &LintSettings::default());
+        let find = |kind: LintKind| {
+            let matching: Vec<_> = findings.iter().filter(|f| f.kind == kind).collect();
+            assert_eq!(matching.len(), 1, "{kind:?}: {findings:?}");
+            matching[0]
+        };
+        assert_eq!(
+            find(LintKind::DanglingJoint).fix,
+            Some(LintFix::RemoveJoint {
+                joint: dangling,
+                visualization: None,
+                anchors: Vec::new(),
+            })
+        );
+        assert_eq!(
+            find(LintKind::StaticJoint).fix,
+            Some(LintFix::MakeDynamic(post))
+        );
+        assert_eq!(
+            find(LintKind::OverlappingBodies).detail,
+            format!("{a} – {b}")
+        );
+        assert_eq!(find(LintKind::SensorWithoutEvents).entity, sensor);
+        assert_eq!(find(LintKind::LayersNeverCollide).entity, isolated);
+        assert_eq!(find(LintKind::DegenerateCollider).entity, flat);
+        let Some(LintFix::MoveAnchor {
+            anchor: moved,
+            position,
+        }) = find(LintKind::AnchorOutsideCollider).fix
+        else {
+            panic!("anchor outside its collider should be movable");
+        };
+        assert_eq!(moved, anchor);
+        assert!(position.distance(Vec2::new(10.0, 0.0)) < 1e-4);
+        assert_eq!(findings.len(), 7);
+    }
+
     #[test]
     fn test_collision_layer_presets_initialization() {
         let mut app = App::new();
@@ -108,4 +700,53 @@ mod tests {
             "NONE preset should exist"
         );
     }
+
+    #[test]
+    fn test_imported_collision_layers_merge_with_resolutions() {
+        use crate::collider_tools::collision_layers::{
+            CollisionLayerPresets, LayerConflict, LayerResolution, remap_layer_mask,
+        };
+
+        let mut current = CollisionLayerPresets::default();
+        current.add_custom_layer("Player", "").unwrap(); // bit 0
+        current.add_custom_layer("Enemy", "").unwrap(); // bit 1
+
+        let mut imported = CollisionLayerPresets::default();
+        imported.add_custom_layer("Enemy", "").unwrap(); // bit 0
+        imported.add_custom_layer("Pickup", "").unwrap(); // bit 1
+        imported.add_custom_layer("Water", "").unwrap(); // bit 2
+
+        let conflicts = current.layer_conflicts(&imported);
+        assert_eq!(
+            conflicts,
+            vec![
+                LayerConflict::Bit {
+                    name: "Enemy".to_string(),
+                    current: 1,
+                    imported: 0,
+                },
+                LayerConflict::Name {
+                    bit: 1,
+                    current: "Enemy".to_string(),
+                    imported: "Pickup".to_string(),
+                },
+            ]
+        );
+
+        // Enemy is the same layer; Pickup needs a bit of its own past Water
+        let remap = current
+            .merge_imported(
+                &imported,
+                &[LayerResolution::Merge, LayerResolution::Separate],
+            )
+            .unwrap();
+        assert_eq!(remap.get(&0), Some(&1));
+        assert_eq!(remap.get(&1), Some(&3));
+        assert_eq!(current.name_to_bit.get("Pickup"), Some(&3));
+        assert_eq!(current.name_to_bit.get("Water"), Some(&2));
+
+        // Enemy + Pickup + Water in the imported file
+        let mask = remap_layer_mask(LayerMask(0b111), &remap);
+        assert_eq!(mask.0, 0b1110);
+    }
 }
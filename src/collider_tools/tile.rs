@@ -0,0 +1,493 @@
+//! Tile painting of colliders
+//!
+//! Tile mode paints cells of a grid into a tile layer: a single entity holding a [`TileLayer`]
+//! with the filled cells in its local space. New layers take their cell size from the editor
+//! grid spacing ([`InfiniteGridSettings::scale`]) and sit at the world origin, so their cells line
+//! up with the grid lines and with grid-snapped bodies.
+//!
+//! The cells are never turned into one collider each. Whenever the layer changes, its collider is
+//! rebuilt from merged cells:
+//! - [`TileMergeMode::Rectangles`] greedily merges rows of cells into as few rectangles as it can
+//! - [`TileMergeMode::Outline`] traces the outline of each connected region into one closed
+//!   polyline, which has no internal seams for bodies sliding along the surface to catch on
+//!
+//! Painting goes into the selected tile layer, or into the last layer painted when nothing else
+//! is selected. When there is no layer yet the first stroke creates one as a static body.
+//!
+//! ## Controls (Tile mode)
+//! - Left drag: paint cells
+//! - Right drag: erase cells
+
+use super::creation::{CreationProperties, add_body_properties};
+use super::mirror::FlipDirection;
+use super::utils::get_mouse_world_position;
+use super::{ColliderCreationState, ColliderType, EditorGizmoConfigGroup};
+use crate::grid::InfiniteGridSettings;
+use crate::selection::{EditorSelection, Selectable};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::input::egui_wants_any_input;
+use std::collections::{HashMap, HashSet};
+
+/// How the cells of a tile layer are merged into a collider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum TileMergeMode {
+    /// Solid rectangles covering the cells
+    #[default]
+    Rectangles,
+    /// Closed outline polylines around each region, without seams between tiles
+    Outline,
+}
+
+impl TileMergeMode {
+    /// All merge modes, in UI order
+    pub const ALL: [TileMergeMode; 2] = [TileMergeMode::Rectangles, TileMergeMode::Outline];
+
+    /// Translation key of the mode name
+    pub fn key(self) -> &'static str {
+        match self {
+            TileMergeMode::Rectangles => "tile_merge_rectangles",
+            TileMergeMode::Outline => "tile_merge_outline",
+        }
+    }
+}
+
+/// Painted cells of a tile layer
+///
+/// The collider is rebuilt from the cells whenever this component changes.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct TileLayer {
+    /// Edge length of a cell in the layer's local space
+    pub cell_size: f32,
+    /// Filled cells, sorted by row and then column
+    pub cells: Vec<IVec2>,
+    /// How the cells are merged into the collider
+    pub merge: TileMergeMode,
+}
+
+impl TileLayer {
+    /// Create an empty layer
+    pub fn new(cell_size: f32, merge: TileMergeMode) -> Self {
+        Self {
+            cell_size,
+            cells: Vec::new(),
+            merge,
+        }
+    }
+
+    fn search(&self, cell: IVec2) -> Result<usize, usize> {
+        self.cells
+            .binary_search_by_key(&(cell.y, cell.x), |c| (c.y, c.x))
+    }
+
+    /// Whether a cell is filled
+    pub fn contains(&self, cell: IVec2) -> bool {
+        self.search(cell).is_ok()
+    }
+
+    /// Fill or clear a cell, returning whether it changed
+    pub fn set(&mut self, cell: IVec2, filled: bool) -> bool {
+        match (self.search(cell), filled) {
+            (Err(index), true) => self.cells.insert(index, cell),
+            (Ok(index), false) => {
+                self.cells.remove(index);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Mirror the cells across the layer's local axis
+    ///
+    /// Cell `x` covers `x..x + 1`, so its mirror image is cell `-x - 1`.
+    pub fn mirror(&mut self, direction: FlipDirection) {
+        for cell in &mut self.cells {
+            match direction {
+                FlipDirection::Horizontal => cell.x = -cell.x - 1,
+                FlipDirection::Vertical => cell.y = -cell.y - 1,
+            }
+        }
+        self.cells.sort_by_key(|cell| (cell.y, cell.x));
+    }
+
+    /// Cell containing a point in the layer's local space
+    pub fn cell_at(&self, point: Vec2) -> IVec2 {
+        (point / self.cell_size).floor().as_ivec2()
+    }
+
+    /// Number of rectangles or outline loops the collider is built from
+    pub fn part_count(&self) -> usize {
+        match self.merge {
+            TileMergeMode::Rectangles => merge_cells(&self.cells).len(),
+            TileMergeMode::Outline => cell_outlines(&self.cells).len(),
+        }
+    }
+
+    /// Collider of the merged cells, or `None` for an empty layer
+    pub fn collider(&self) -> Option<Collider> {
+        if self.cells.is_empty() || self.cell_size <= 0.0 {
+            return None;
+        }
+        let size = self.cell_size;
+        match self.merge {
+            TileMergeMode::Rectangles => {
+                let parts = merge_cells(&self.cells)
+                    .into_iter()
+                    .map(|rect| {
+                        let extent = rect.size().as_vec2() * size;
+                        (
+                            Position::new(rect.as_rect().center() * size),
+                            Rotation::default(),
+                            Collider::rectangle(extent.x, extent.y),
+                        )
+                    })
+                    .collect();
+                Some(Collider::compound(parts))
+            }
+            TileMergeMode::Outline => {
+                // One polyline holds every loop, each closed by an index pair back to its start
+                let mut vertices = Vec::new();
+                let mut indices = Vec::new();
+                for contour in cell_outlines(&self.cells) {
+                    let first = vertices.len() as u32;
+                    let count = contour.len() as u32;
+                    vertices.extend(contour.iter().map(|corner| corner.as_vec2() * size));
+                    indices.extend((0..count).map(|i| [first + i, first + (i + 1) % count]));
+                }
+                Some(Collider::polyline(vertices, Some(indices)))
+            }
+        }
+    }
+}
+
+/// Options for painting
+#[derive(Resource, Debug, Clone, PartialEq, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct TileSettings {
+    /// Merge mode given to new layers
+    pub merge: TileMergeMode,
+}
+
+/// Stroke being painted
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default)]
+pub struct TilePaintState {
+    /// Layer painted into when no tile layer is selected
+    pub active_layer: Option<Entity>,
+    /// Cell reached by the previous sample of the stroke
+    pub last_cell: Option<IVec2>,
+}
+
+/// Plugin for tile painting
+#[derive(Default)]
+pub struct TilePlugin;
+
+impl Plugin for TilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TileSettings>()
+            .init_resource::<TilePaintState>()
+            .register_type::<TileLayer>()
+            .register_type::<TileSettings>()
+            .add_systems(OnExit(super::ToolMode::Tile), on_exit_tile_mode)
+            .add_systems(
+                Update,
+                handle_tile_input
+                    .run_if(in_state(super::ToolMode::Tile).and(not(egui_wants_any_input))),
+            )
+            .add_systems(
+                Update,
+                (
+                    draw_tile_preview::<EditorGizmoConfigGroup>
+                        .run_if(in_state(super::ToolMode::Tile)),
+                    rebuild_tile_colliders,
+                ),
+            );
+    }
+}
+
+/// Merge cells into rectangles, in cell units
+///
+/// Each rectangle starts at the lowest unmerged cell, grows along its row as far as the cells
+/// go, then grows upwards while the whole span of the next row is filled.
+pub(crate) fn merge_cells(cells: &[IVec2]) -> Vec<IRect> {
+    let filled: HashSet<IVec2> = cells.iter().copied().collect();
+    let mut ordered: Vec<IVec2> = filled.iter().copied().collect();
+    ordered.sort_unstable_by_key(|c| (c.y, c.x));
+
+    let mut merged: HashSet<IVec2> = HashSet::with_capacity(ordered.len());
+    let free =
+        |cell: IVec2, merged: &HashSet<IVec2>| filled.contains(&cell) && !merged.contains(&cell);
+    let mut rects = Vec::new();
+    for start in ordered {
+        if merged.contains(&start) {
+            continue;
+        }
+        let mut width = 1;
+        while free(start + IVec2::new(width, 0), &merged) {
+            width += 1;
+        }
+        let mut height = 1;
+        while (0..width).all(|x| free(start + IVec2::new(x, height), &merged)) {
+            height += 1;
+        }
+        for y in 0..height {
+            for x in 0..width {
+                merged.insert(start + IVec2::new(x, y));
+            }
+        }
+        rects.push(IRect::from_corners(
+            start,
+            start + IVec2::new(width, height),
+        ));
+    }
+    rects
+}
+
+/// Outline loops of filled cells, as cell corners
+///
+/// Outer boundaries run counter-clockwise and holes clockwise. Regions touching only at a corner
+/// get separate loops, and corners along straight runs are dropped.
+pub(crate) fn cell_outlines(cells: &[IVec2]) -> Vec<Vec<IVec2>> {
+    let filled: HashSet<IVec2> = cells.iter().copied().collect();
+
+    // Cell sides facing an empty neighbour, directed with the cell on their left
+    let mut outgoing: HashMap<IVec2, Vec<IVec2>> = HashMap::new();
+    for &cell in &filled {
+        let corners = [cell, cell + IVec2::X, cell + IVec2::ONE, cell + IVec2::Y];
+        let neighbours = [-IVec2::Y, IVec2::X, IVec2::Y, -IVec2::X];
+        for side in 0..4 {
+            if !filled.contains(&(cell + neighbours[side])) {
+                outgoing
+                    .entry(corners[side])
+                    .or_default()
+                    .push(corners[(side + 1) % 4]);
+            }
+        }
+    }
+
+    let mut starts: Vec<IVec2> = outgoing.keys().copied().collect();
+    starts.sort_unstable_by_key(|c| (c.y, c.x));
+    let mut loops = Vec::new();
+    for start in starts {
+        while let Some(first) = outgoing.get_mut(&start).and_then(Vec::pop) {
+            let mut corners = vec![start];
+            let (mut previous, mut current) = (start, first);
+            while current != start {
+                corners.push(current);
+                let incoming = current - previous;
+                // Where two regions touch at a corner, turning left stays on the current region
+                let candidates = outgoing.get_mut(&current).unwrap();
+                let choice = (0..candidates.len())
+                    .max_by_key(|&i| incoming.perp_dot(candidates[i] - current))
+                    .unwrap();
+                previous = current;
+                current = candidates.swap_remove(choice);
+            }
+
+            // Keep only the corners where the outline turns
+            let n = corners.len();
+            let turning: Vec<IVec2> = (0..n)
+                .filter(|&i| {
+                    let before = corners[i] - corners[(i + n - 1) % n];
+                    let after = corners[(i + 1) % n] - corners[i];
+                    before.perp_dot(after) != 0
+                })
+                .map(|i| corners[i])
+                .collect();
+            loops.push(turning);
+        }
+    }
+    loops
+}
+
+/// Cells crossed by a straight stroke from one cell to another, including both ends
+fn cell_line(from: IVec2, to: IVec2) -> Vec<IVec2> {
+    let steps = (to - from).abs().max_element().max(1);
+    let delta = (to - from).as_vec2() / steps as f32;
+    (0..=steps)
+        .map(|i| (from.as_vec2() + delta * i as f32).round().as_ivec2())
+        .collect()
+}
+
+/// Layer a stroke paints into: the selected tile layer, otherwise the last one painted
+fn target_layer(
+    selection: &EditorSelection,
+    state: &TilePaintState,
+    is_layer: impl Fn(Entity) -> bool,
+) -> Option<Entity> {
+    selection
+        .primary()
+        .filter(|&entity| is_layer(entity))
+        .or(state.active_layer.filter(|&entity| is_layer(entity)))
+}
+
+/// Paint and erase cells, creating a layer for the first stroke
+fn handle_tile_input(
+    mut commands: Commands,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut state: ResMut<TilePaintState>,
+    mut creation_state: ResMut<ColliderCreationState>,
+    mut selection: ResMut<EditorSelection>,
+    settings: Res<TileSettings>,
+    properties: Res<CreationProperties>,
+    grid_settings: Query<&InfiniteGridSettings>,
+    mut layers: Query<(&mut TileLayer, &GlobalTransform)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<SpritePickingCamera>>,
+    window_query: Query<&Window>,
+) {
+    let fill = if mouse_input.pressed(MouseButton::Left) {
+        true
+    } else if mouse_input.pressed(MouseButton::Right) {
+        false
+    } else {
+        state.last_cell = None;
+        return;
+    };
+    if mouse_input.any_just_pressed([MouseButton::Left, MouseButton::Right]) {
+        state.last_cell = None;
+    }
+
+    let (Ok((camera, camera_transform)), Ok(window)) =
+        (camera_query.single(), window_query.single())
+    else {
+        return;
+    };
+    let Some(mouse_pos) = get_mouse_world_position(window, camera, camera_transform) else {
+        return;
+    };
+
+    let Some(entity) = target_layer(&selection, &state, |entity| layers.contains(entity)) else {
+        // Only a fresh paint stroke starts a new layer
+        if !fill || !mouse_input.just_pressed(MouseButton::Left) {
+            return;
+        }
+        let cell_size = grid_settings
+            .single()
+            .map(|grid| grid.scale)
+            .unwrap_or(InfiniteGridSettings::for_2d().scale);
+        let mut layer = TileLayer::new(cell_size, settings.merge);
+        let cell = layer.cell_at(mouse_pos);
+        layer.set(cell, true);
+
+        let mut entity_commands = commands.spawn((
+            Transform::default(),
+            RigidBody::Static,
+            ColliderType::Polygon,
+            DebugRender {
+                collider_color: Some(properties.color),
+                axis_lengths: Some(avian2d::math::Vector::new(0.8, 0.8)),
+                ..default()
+            },
+            Selectable,
+            Pickable::default(),
+        ));
+        if let Some(collider) = layer.collider() {
+            entity_commands.insert(collider);
+        }
+        entity_commands.insert(layer);
+        add_body_properties(&mut entity_commands, &properties);
+
+        let entity = entity_commands.id();
+        creation_state.created_colliders.push(entity);
+        selection.set(entity);
+        state.active_layer = Some(entity);
+        state.last_cell = Some(cell);
+        info!(
+            "Created tile layer {:?} with {} cell size",
+            entity, cell_size
+        );
+        return;
+    };
+    state.active_layer = Some(entity);
+
+    let (mut layer, transform) = layers.get_mut(entity).unwrap();
+    let local = transform
+        .affine()
+        .inverse()
+        .transform_point3(mouse_pos.extend(0.0))
+        .truncate();
+    let cell = layer.cell_at(local);
+    for cell in cell_line(state.last_cell.unwrap_or(cell), cell) {
+        // Only touch the layer when a cell actually changes, so its collider is not rebuilt
+        if layer.contains(cell) != fill {
+            layer.set(cell, fill);
+        }
+    }
+    state.last_cell = Some(cell);
+}
+
+/// Rebuild the colliders of layers whose cells or merge mode changed
+fn rebuild_tile_colliders(
+    mut commands: Commands,
+    query: Query<(Entity, &TileLayer), Changed<TileLayer>>,
+) {
+    for (entity, layer) in &query {
+        match layer.collider() {
+            Some(collider) => {
+                commands.entity(entity).insert(collider);
+            }
+            None => {
+                commands.entity(entity).remove::<Collider>();
+            }
+        }
+    }
+}
+
+/// Outline the cell under the cursor in the layer that would be painted
+fn draw_tile_preview<Config: GizmoConfigGroup>(
+    mut gizmos: Gizmos<Config>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    state: Res<TilePaintState>,
+    selection: Res<EditorSelection>,
+    grid_settings: Query<&InfiniteGridSettings>,
+    layers: Query<(&TileLayer, &GlobalTransform)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<SpritePickingCamera>>,
+    window_query: Query<&Window>,
+    theme_colors: Res<crate::ui::theme_colors::EditorThemeColors>,
+) {
+    let (Ok((camera, camera_transform)), Ok(window)) =
+        (camera_query.single(), window_query.single())
+    else {
+        return;
+    };
+    let Some(mouse_pos) = get_mouse_world_position(window, camera, camera_transform) else {
+        return;
+    };
+
+    let (cell_size, to_world) =
+        match target_layer(&selection, &state, |entity| layers.contains(entity))
+            .and_then(|entity| layers.get(entity).ok())
+        {
+            Some((layer, transform)) => (layer.cell_size, transform.affine()),
+            None => match grid_settings.single() {
+                Ok(grid) => (grid.scale, GlobalTransform::IDENTITY.affine()),
+                Err(_) => return,
+            },
+        };
+    if cell_size <= 0.0 {
+        return;
+    }
+    let local = to_world
+        .inverse()
+        .transform_point3(mouse_pos.extend(0.0))
+        .truncate();
+    let corner = (local / cell_size).floor() * cell_size;
+    let outline = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y, Vec2::ZERO].map(|offset| {
+        to_world
+            .transform_point3((corner + offset * cell_size).extend(0.0))
+            .truncate()
+    });
+
+    let color = if mouse_input.pressed(MouseButton::Right) {
+        theme_colors.gizmo_x_axis
+    } else {
+        theme_colors.selection_outline
+    };
+    gizmos.linestrip_2d(outline, color);
+}
+
+/// End any stroke in progress when leaving tile mode
+fn on_exit_tile_mode(mut state: ResMut<TilePaintState>) {
+    state.last_cell = None;
+}
@@ -0,0 +1,164 @@
+//! Display units for lengths
+//!
+//! Colliders are authored in world units, which are pixels for a 2D scene. The number of world
+//! units per meter is Avian's [`PhysicsLengthUnit`], so that resource is the single source of
+//! truth for the pixels-per-meter scale: changing it in the world settings changes both the
+//! solver tolerances and every converted readout.
+//!
+//! [`UnitSettings`] only chooses how lengths are shown (px, m or cm) and whether scene exports
+//! are converted to SI units.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+/// Unit used to show lengths in the editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum LengthDisplayUnit {
+    /// World units (pixels)
+    #[default]
+    Pixels,
+    /// Meters, using [`PhysicsLengthUnit`] as pixels per meter
+    Meters,
+    /// Centimeters
+    Centimeters,
+}
+
+impl LengthDisplayUnit {
+    /// All units, in UI order
+    pub const ALL: [LengthDisplayUnit; 3] = [
+        LengthDisplayUnit::Pixels,
+        LengthDisplayUnit::Meters,
+        LengthDisplayUnit::Centimeters,
+    ];
+
+    /// Short unit symbol
+    pub fn symbol(self) -> &'static str {
+        match self {
+            LengthDisplayUnit::Pixels => "px",
+            LengthDisplayUnit::Meters => "m",
+            LengthDisplayUnit::Centimeters => "cm",
+        }
+    }
+}
+
+/// Length display and export preferences
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct UnitSettings {
+    /// Unit used by the inspector, tool panel and viewport readouts
+    pub display_unit: LengthDisplayUnit,
+    /// Label major grid lines with their coordinates
+    pub grid_labels: bool,
+    /// Convert lengths to meters when exporting a scene
+    pub export_si: bool,
+}
+
+impl Default for UnitSettings {
+    fn default() -> Self {
+        Self {
+            display_unit: LengthDisplayUnit::Pixels,
+            grid_labels: true,
+            export_si: false,
+        }
+    }
+}
+
+/// Converts world lengths to the chosen display unit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitConverter {
+    /// Unit values are shown in
+    pub unit: LengthDisplayUnit,
+    /// World units per meter
+    pub pixels_per_meter: f32,
+}
+
+impl Default for UnitConverter {
+    fn default() -> Self {
+        Self {
+            unit: LengthDisplayUnit::Pixels,
+            pixels_per_meter: 1.0,
+        }
+    }
+}
+
+impl UnitConverter {
+    /// Converter for the current settings and length unit of a world
+    pub fn from_world(world: &World) -> Self {
+        Self {
+            unit: world
+                .get_resource::<UnitSettings>()
+                .map(|settings| settings.display_unit)
+                .unwrap_or_default(),
+            pixels_per_meter: world
+                .get_resource::<PhysicsLengthUnit>()
+                .map(|unit| unit.0)
+                .unwrap_or(1.0),
+        }
+    }
+
+    /// Display units per world unit
+    pub fn factor(&self) -> f32 {
+        let pixels_per_meter = self.pixels_per_meter.max(f32::EPSILON);
+        match self.unit {
+            LengthDisplayUnit::Pixels => 1.0,
+            LengthDisplayUnit::Meters => 1.0 / pixels_per_meter,
+            LengthDisplayUnit::Centimeters => 100.0 / pixels_per_meter,
+        }
+    }
+
+    /// World length in display units
+    pub fn to_display(&self, world_length: f32) -> f32 {
+        world_length * self.factor()
+    }
+
+    /// Display length in world units
+    pub fn to_world(&self, display_length: f32) -> f32 {
+        display_length / self.factor()
+    }
+
+    /// Short unit symbol
+    pub fn symbol(&self) -> &'static str {
+        self.unit.symbol()
+    }
+
+    /// Decimal places that keep roughly pixel precision
+    pub fn decimals(&self) -> usize {
+        let pixel = self.factor();
+        if pixel >= 0.5 {
+            1
+        } else {
+            ((-pixel.log10()).ceil() as usize + 1).min(6)
+        }
+    }
+
+    /// Format a world length, e.g. `1.25 m`
+    pub fn format_length(&self, world_length: f32) -> String {
+        format!(
+            "{:.*} {}",
+            self.decimals(),
+            self.to_display(world_length),
+            self.symbol()
+        )
+    }
+
+    /// Format a world speed, e.g. `3.50 m/s`
+    pub fn format_speed(&self, world_speed: f32) -> String {
+        format!(
+            "{:.*} {}/s",
+            self.decimals(),
+            self.to_display(world_speed),
+            self.symbol()
+        )
+    }
+}
+
+/// Plugin registering unit settings
+#[derive(Default)]
+pub struct UnitsPlugin;
+
+impl Plugin for UnitsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UnitSettings>()
+            .register_type::<UnitSettings>();
+    }
+}
@@ -536,18 +536,20 @@ fn find_line_collider_intersection_fallback(
     Some(origin)
 }
 
-/// Find the closest vertex of a collider to a given point
-/// Returns the world position of the closest vertex
-pub(crate) fn find_closest_vertex(
-    point: Vec2,
+/// World-space bounds of a collider
+pub(crate) fn collider_world_aabb(
     collider: &Collider,
     transform: &GlobalTransform,
-) -> Option<Vec2> {
-    // Transform point to local space
-    let transform_inv = transform.compute_matrix().inverse();
-    let local_point = transform_inv.transform_point(point.extend(0.0)).truncate();
+) -> ColliderAabb {
+    let (_, rotation, translation) = transform.to_scale_rotation_translation();
+    collider.aabb(translation.truncate(), rotation.to_euler(EulerRot::ZYX).0)
+}
 
-    // Get vertices based on collider type
+/// Local-space vertices used for snapping to a collider
+///
+/// Round shapes are sampled around their outline. Returns `None` for shapes without
+/// meaningful vertices.
+pub(crate) fn collider_snap_vertices(collider: &Collider) -> Option<Vec<Vec2>> {
     let vertices = match collider.shape_scaled().as_typed_shape() {
         avian2d::parry::shape::TypedShape::Cuboid(cuboid) => {
             let half_extents = cuboid.half_extents;
@@ -635,6 +637,22 @@ pub(crate) fn find_closest_vertex(
         }
     };
 
+    Some(vertices)
+}
+
+/// Find the closest vertex of a collider to a given point
+/// Returns the world position of the closest vertex
+pub(crate) fn find_closest_vertex(
+    point: Vec2,
+    collider: &Collider,
+    transform: &GlobalTransform,
+) -> Option<Vec2> {
+    // Transform point to local space
+    let transform_inv = transform.compute_matrix().inverse();
+    let local_point = transform_inv.transform_point(point.extend(0.0)).truncate();
+
+    let vertices = collider_snap_vertices(collider)?;
+
     // Find closest vertex
     let mut closest_vertex = None;
     let mut min_distance = f32::MAX;
@@ -0,0 +1,504 @@
+//! Initial velocity authoring tools
+//!
+//! Provides a velocity tool mode where the user drags an arrow out of a dynamic body to
+//! author its initial linear velocity, and drags a curved handle around the body to author
+//! its initial angular velocity.
+//!
+//! ## Authored Data vs Simulation State
+//! Authored values are stored in [`InitialVelocity`], not in Avian's [`LinearVelocity`] /
+//! [`AngularVelocity`]. Those live components are overwritten by the solver every step, so
+//! they cannot hold design intent. Instead, [`InitialVelocity`] is copied into them when play
+//! starts from the beginning ([`PlayFromStart`]). Resuming a paused simulation keeps the
+//! velocities the bodies had.
+//!
+//! ## Handle Scale
+//! Handles show where the body would be after [`VELOCITY_PREVIEW_TIME`] seconds:
+//! - The arrow tip is at `origin + linear * VELOCITY_PREVIEW_TIME`.
+//! - The curved handle sweeps `angular * VELOCITY_PREVIEW_TIME` radians, starting from the
+//!   body's local X axis. Counter-clockwise is positive, matching Avian.
+
+use super::EditorGizmoConfigGroup;
+use super::utils::get_mouse_world_position;
+use crate::physics_management::{PhysicsManager, PlayFromStart};
+use crate::selection::EditorSelection;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::input::egui_wants_any_input;
+use std::f32::consts::{PI, TAU};
+
+/// Time in seconds visualized by the velocity handles
+pub const VELOCITY_PREVIEW_TIME: f32 = 0.25;
+
+/// Pick radius for velocity handles, in world units
+const HANDLE_PICK_RADIUS: f32 = 10.0;
+
+/// Gap between the collider bounds and the angular handle arc
+const ANGULAR_HANDLE_MARGIN: f32 = 20.0;
+
+/// Largest arc sweep the angular handle can represent (just under a full turn)
+const MAX_ANGULAR_SWEEP: f32 = TAU - 0.05;
+
+/// Angle increment used when snapping handles with Shift held (15 degrees)
+const SNAP_ANGLE: f32 = PI / 12.0;
+
+/// Authored initial velocity of a dynamic body
+///
+/// Applied to [`LinearVelocity`] and [`AngularVelocity`] when play starts from the beginning.
+/// Exported with the scene like any other reflected component.
+#[derive(Component, Reflect, Default, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default)]
+pub struct InitialVelocity {
+    /// Initial linear velocity in world units per second
+    pub linear: Vec2,
+    /// Initial angular velocity in radians per second (counter-clockwise positive)
+    pub angular: f32,
+}
+
+impl InitialVelocity {
+    /// Whether both linear and angular components are zero
+    pub fn is_zero(&self) -> bool {
+        self.linear == Vec2::ZERO && self.angular == 0.0
+    }
+}
+
+/// Velocity handle kinds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum VelocityHandle {
+    /// Arrow tip controlling linear velocity
+    Linear,
+    /// Curved handle controlling angular velocity
+    Angular,
+}
+
+/// State for velocity authoring
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default)]
+pub struct VelocityAuthoringState {
+    /// Body whose handles are shown (primary selection if it is a dynamic body)
+    pub target: Option<Entity>,
+    /// Handle under the cursor
+    pub hovered: Option<VelocityHandle>,
+    /// Handle currently being dragged
+    pub dragging: Option<VelocityHandle>,
+    /// Value before the drag started, restored when the drag is cancelled
+    pub original: Option<InitialVelocity>,
+    /// Whether Shift is held for snapping
+    pub shift_pressed: bool,
+    /// Cursor angle around the body on the previous frame of an angular drag
+    last_drag_angle: f32,
+    /// Unsnapped arc sweep accumulated during an angular drag
+    drag_sweep: f32,
+    /// Whether physics was already paused when the mode was entered
+    physics_was_paused: bool,
+}
+
+/// Plugin for initial velocity authoring
+#[derive(Default)]
+pub struct VelocityAuthoringPlugin;
+
+impl Plugin for VelocityAuthoringPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VelocityAuthoringState>()
+            .register_type::<InitialVelocity>()
+            .add_systems(
+                OnEnter(crate::collider_tools::ToolMode::Velocity),
+                on_enter_velocity_mode,
+            )
+            .add_systems(
+                OnExit(crate::collider_tools::ToolMode::Velocity),
+                on_exit_velocity_mode,
+            )
+            .add_systems(
+                Update,
+                (update_velocity_target, handle_velocity_tool_input)
+                    .chain()
+                    .run_if(
+                        in_state(crate::collider_tools::ToolMode::Velocity)
+                            .and(not(egui_wants_any_input)),
+                    ),
+            )
+            .add_systems(
+                Update,
+                draw_velocity_handles::<EditorGizmoConfigGroup>
+                    .run_if(in_state(crate::collider_tools::ToolMode::Velocity)),
+            )
+            .add_systems(Update, apply_initial_velocities_on_play);
+    }
+}
+
+/// Initialize velocity mode
+///
+/// Physics is paused so bodies stay put while their velocities are being authored.
+fn on_enter_velocity_mode(
+    mut state: ResMut<VelocityAuthoringState>,
+    mut physics_manager: ResMut<PhysicsManager>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    info!("Entering Velocity mode");
+    *state = VelocityAuthoringState {
+        physics_was_paused: physics_manager.is_physics_paused(),
+        ..default()
+    };
+    physics_manager.pause(&mut physics_time);
+}
+
+/// Cleanup when exiting velocity mode
+fn on_exit_velocity_mode(
+    mut state: ResMut<VelocityAuthoringState>,
+    mut physics_manager: ResMut<PhysicsManager>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    info!("Exiting Velocity mode");
+    let physics_was_paused = state.physics_was_paused;
+    *state = VelocityAuthoringState::default();
+
+    // Leave physics paused if it was paused before the mode was entered
+    if !physics_was_paused {
+        physics_manager.unpause(&mut physics_time);
+    }
+}
+
+/// Copy authored initial velocities into the live velocity components when play starts
+/// from the beginning
+pub(crate) fn apply_initial_velocities_on_play(
+    mut commands: Commands,
+    mut play_events: EventReader<PlayFromStart>,
+    initial_velocity_query: Query<(Entity, &InitialVelocity)>,
+) {
+    if play_events.read().count() > 0 {
+        for (entity, initial_velocity) in initial_velocity_query.iter() {
+            commands.entity(entity).insert((
+                LinearVelocity(initial_velocity.linear),
+                AngularVelocity(initial_velocity.angular),
+            ));
+        }
+        info!(
+            "Applied initial velocities to {} bodies",
+            initial_velocity_query.iter().count()
+        );
+    }
+}
+
+/// World position of the linear velocity handle (arrow tip)
+pub(crate) fn linear_handle_position(origin: Vec2, initial_velocity: &InitialVelocity) -> Vec2 {
+    origin + initial_velocity.linear * VELOCITY_PREVIEW_TIME
+}
+
+/// Radius of the angular velocity arc around a collider
+pub(crate) fn angular_handle_radius(collider: &Collider) -> f32 {
+    let aabb = collider.aabb(avian2d::math::Vector::ZERO, 0.0);
+    (aabb.max - aabb.min).length() * 0.5 + ANGULAR_HANDLE_MARGIN
+}
+
+/// Arc sweep in radians shown for an angular velocity
+pub(crate) fn angular_handle_sweep(angular: f32) -> f32 {
+    (angular * VELOCITY_PREVIEW_TIME).clamp(-MAX_ANGULAR_SWEEP, MAX_ANGULAR_SWEEP)
+}
+
+/// World position of the angular velocity handle (end of the arc)
+pub(crate) fn angular_handle_position(
+    origin: Vec2,
+    start_angle: f32,
+    radius: f32,
+    angular: f32,
+) -> Vec2 {
+    let end_angle = start_angle + angular_handle_sweep(angular);
+    origin + Vec2::from_angle(end_angle) * radius
+}
+
+/// Body rotation around the Z axis, used as the arc's start angle
+fn body_angle(transform: &GlobalTransform) -> f32 {
+    transform.rotation().to_euler(EulerRot::ZYX).0
+}
+
+/// Wrap an angle into the `(-PI, PI]` range
+fn wrap_angle(angle: f32) -> f32 {
+    let wrapped = (angle + PI).rem_euclid(TAU) - PI;
+    if wrapped <= -PI {
+        wrapped + TAU
+    } else {
+        wrapped
+    }
+}
+
+/// Track the primary selection as the velocity target
+///
+/// Only dynamic bodies can have an initial velocity; other selections clear the target.
+fn update_velocity_target(
+    mut state: ResMut<VelocityAuthoringState>,
+    selection: Res<EditorSelection>,
+    body_query: Query<&RigidBody, With<Collider>>,
+) {
+    if state.dragging.is_some() {
+        return;
+    }
+
+    let target = selection
+        .primary()
+        .filter(|entity| matches!(body_query.get(*entity), Ok(RigidBody::Dynamic)));
+
+    if state.target != target {
+        state.target = target;
+        state.hovered = None;
+    }
+}
+
+/// Handle mouse and keyboard input for velocity mode
+fn handle_velocity_tool_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut state: ResMut<VelocityAuthoringState>,
+    mut body_query: Query<(&GlobalTransform, &Collider, Option<&mut InitialVelocity>)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<SpritePickingCamera>>,
+    window_query: Query<&Window>,
+) {
+    state.shift_pressed = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    let Some(target) = state.target else {
+        state.hovered = None;
+        return;
+    };
+    let Ok((body_transform, collider, mut initial_velocity)) = body_query.get_mut(target) else {
+        state.target = None;
+        state.hovered = None;
+        return;
+    };
+
+    let current = initial_velocity.as_deref().copied().unwrap_or_default();
+    let origin = body_transform.translation().truncate();
+    let start_angle = body_angle(body_transform);
+    let radius = angular_handle_radius(collider);
+
+    // Cancel drag with Escape, restoring the value from before the drag
+    if keyboard_input.just_pressed(KeyCode::Escape) && state.dragging.is_some() {
+        match (state.original, initial_velocity.as_deref_mut()) {
+            (Some(original), Some(value)) => *value = original,
+            (None, _) => {
+                commands.entity(target).remove::<InitialVelocity>();
+            }
+            _ => {}
+        }
+        state.dragging = None;
+        state.original = None;
+        info!("Cancelled velocity drag");
+        return;
+    }
+
+    let (Ok((camera, camera_transform)), Ok(window)) =
+        (camera_query.single(), window_query.single())
+    else {
+        return;
+    };
+    let Some(mouse_pos) = get_mouse_world_position(window, camera, camera_transform) else {
+        return;
+    };
+
+    // Hover detection
+    if state.dragging.is_none() {
+        let linear_tip = linear_handle_position(origin, &current);
+        let angular_tip = angular_handle_position(origin, start_angle, radius, current.angular);
+
+        state.hovered = if mouse_pos.distance(linear_tip) <= HANDLE_PICK_RADIUS {
+            Some(VelocityHandle::Linear)
+        } else if mouse_pos.distance(angular_tip) <= HANDLE_PICK_RADIUS {
+            Some(VelocityHandle::Angular)
+        } else {
+            None
+        };
+    }
+
+    // Right click on a handle resets that component
+    if mouse_input.just_pressed(MouseButton::Right) && state.dragging.is_none() {
+        if let (Some(handle), Some(value)) = (state.hovered, initial_velocity.as_deref_mut()) {
+            match handle {
+                VelocityHandle::Linear => value.linear = Vec2::ZERO,
+                VelocityHandle::Angular => value.angular = 0.0,
+            }
+            info!("Reset {:?} velocity of {:?}", handle, target);
+        }
+        return;
+    }
+
+    // Start dragging on mouse press
+    if mouse_input.just_pressed(MouseButton::Left) && state.dragging.is_none() {
+        if let Some(handle) = state.hovered {
+            state.dragging = Some(handle);
+            state.original = initial_velocity.as_deref().copied();
+            state.last_drag_angle = (mouse_pos - origin).to_angle();
+            state.drag_sweep = angular_handle_sweep(current.angular);
+            info!("Started dragging {:?} velocity handle", handle);
+        }
+        return;
+    }
+
+    let Some(handle) = state.dragging else {
+        return;
+    };
+
+    // Update value while dragging
+    let mut updated = current;
+    match handle {
+        VelocityHandle::Linear => {
+            let mut offset = mouse_pos - origin;
+            if state.shift_pressed && offset != Vec2::ZERO {
+                let angle = (offset.to_angle() / SNAP_ANGLE).round() * SNAP_ANGLE;
+                offset = Vec2::from_angle(angle) * offset.length();
+            }
+            updated.linear = offset / VELOCITY_PREVIEW_TIME;
+        }
+        VelocityHandle::Angular => {
+            // Accumulate angle deltas so the sweep can grow past half a turn
+            let cursor_angle = (mouse_pos - origin).to_angle();
+            let delta = wrap_angle(cursor_angle - state.last_drag_angle);
+            state.last_drag_angle = cursor_angle;
+            state.drag_sweep =
+                (state.drag_sweep + delta).clamp(-MAX_ANGULAR_SWEEP, MAX_ANGULAR_SWEEP);
+
+            let sweep = if state.shift_pressed {
+                (state.drag_sweep / SNAP_ANGLE).round() * SNAP_ANGLE
+            } else {
+                state.drag_sweep
+            };
+            updated.angular = sweep / VELOCITY_PREVIEW_TIME;
+        }
+    }
+
+    match initial_velocity.as_mut() {
+        Some(value) => {
+            value.set_if_neq(updated);
+        }
+        None => {
+            commands.entity(target).insert(updated);
+        }
+    }
+
+    // End dragging on mouse release
+    if mouse_input.just_released(MouseButton::Left) {
+        state.dragging = None;
+        state.original = None;
+        info!(
+            "Set initial velocity of {:?}: linear {:?}, angular {:.2}",
+            target, updated.linear, updated.angular
+        );
+    }
+}
+
+/// Draw velocity arrows and arcs for all bodies with authored velocities
+fn draw_velocity_handles<Config: GizmoConfigGroup>(
+    mut gizmos: Gizmos<Config>,
+    state: Res<VelocityAuthoringState>,
+    body_query: Query<(
+        Entity,
+        &GlobalTransform,
+        &Collider,
+        Option<&InitialVelocity>,
+    )>,
+    theme_colors: Res<crate::ui::theme_colors::EditorThemeColors>,
+) {
+    for (entity, body_transform, collider, initial_velocity) in body_query.iter() {
+        let is_target = state.target == Some(entity);
+        if initial_velocity.is_none() && !is_target {
+            continue;
+        }
+
+        let value = initial_velocity.copied().unwrap_or_default();
+        let origin = body_transform.translation().truncate();
+        let start_angle = body_angle(body_transform);
+        let radius = angular_handle_radius(collider);
+
+        // Linear velocity arrow
+        let linear_tip = linear_handle_position(origin, &value);
+        if value.linear != Vec2::ZERO {
+            gizmos
+                .arrow_2d(origin, linear_tip, theme_colors.velocity_linear)
+                .with_tip_length(10.0);
+        }
+
+        // Angular velocity arc
+        let sweep = angular_handle_sweep(value.angular);
+        if sweep != 0.0 {
+            draw_angular_arc(
+                &mut gizmos,
+                origin,
+                radius,
+                start_angle,
+                sweep,
+                theme_colors.velocity_angular,
+            );
+        }
+
+        if !is_target {
+            continue;
+        }
+
+        // Reference line marking where the arc starts
+        gizmos.line_2d(
+            origin + Vec2::from_angle(start_angle) * (radius - 6.0),
+            origin + Vec2::from_angle(start_angle) * (radius + 6.0),
+            theme_colors.dashed_line_base,
+        );
+
+        // Handles
+        let angular_tip = angular_handle_position(origin, start_angle, radius, value.angular);
+        for (handle, position, color) in [
+            (
+                VelocityHandle::Linear,
+                linear_tip,
+                theme_colors.velocity_linear,
+            ),
+            (
+                VelocityHandle::Angular,
+                angular_tip,
+                theme_colors.velocity_angular,
+            ),
+        ] {
+            let active = state.dragging == Some(handle) || state.hovered == Some(handle);
+            let handle_color = if active {
+                theme_colors.velocity_handle_active
+            } else {
+                color
+            };
+            gizmos.circle_2d(position, 6.0, handle_color);
+            if active {
+                gizmos.circle_2d(position, HANDLE_PICK_RADIUS, handle_color);
+            }
+        }
+    }
+}
+
+/// Draw an arc with an arrow head at its end
+fn draw_angular_arc<Config: GizmoConfigGroup>(
+    gizmos: &mut Gizmos<Config>,
+    center: Vec2,
+    radius: f32,
+    start_angle: f32,
+    sweep: f32,
+    color: Color,
+) {
+    let segments = ((sweep.abs() / TAU * 64.0).ceil() as usize).max(2);
+    let step = sweep / segments as f32;
+
+    let mut previous = center + Vec2::from_angle(start_angle) * radius;
+    for i in 1..=segments {
+        let point = center + Vec2::from_angle(start_angle + step * i as f32) * radius;
+        gizmos.line_2d(previous, point, color);
+        previous = point;
+    }
+
+    // Arrow head pointing along the direction of rotation
+    let end_angle = start_angle + sweep;
+    let tangent = Vec2::from_angle(end_angle).perp() * sweep.signum();
+    let normal = Vec2::from_angle(end_angle);
+    let head_length = 10.0;
+    gizmos.line_2d(
+        previous,
+        previous - tangent * head_length + normal * head_length * 0.5,
+        color,
+    );
+    gizmos.line_2d(
+        previous,
+        previous - tangent * head_length - normal * head_length * 0.5,
+        color,
+    );
+}
@@ -53,6 +53,16 @@ pub(super) fn generate_capsule_polyline(
     points
 }
 
+/// 生成圆的轮廓点（逆时针，不重复起点）
+pub(super) fn generate_circle_polyline(center: Vec2, radius: f32, resolution: u32) -> Vec<Vec2> {
+    (0..resolution)
+        .map(|i| {
+            let angle = (i as f32 / resolution as f32) * std::f32::consts::TAU;
+            center + Vec2::new(angle.cos(), angle.sin()) * radius
+        })
+        .collect()
+}
+
 /// 使用完整轮廓点绘制胶囊体虚线
 pub(super) fn draw_dashed_capsule<Config: GizmoConfigGroup>(
     gizmos: &mut Gizmos<Config>,
@@ -212,12 +222,7 @@ pub fn calculate_collider_vertices(
         ColliderType::Circle => {
             let center = (start + end) / 2.0;
             let radius = start.distance(end);
-            (0..32)
-                .map(|i| {
-                    let angle = (i as f32 / 32.0) * std::f32::consts::TAU;
-                    center + Vec2::new(angle.cos(), angle.sin()) * radius
-                })
-                .collect()
+            generate_circle_polyline(center, radius, 32)
         }
         ColliderType::Capsule => {
             // Constants for capsule generation
@@ -550,6 +555,8 @@ pub fn generate_control_points(
                     .iter()
                     .map(|p| transform_point(Vec2::new(p.x, p.y)))
                     .collect(),
+                // Sketches and tile layers are reshaped through their stroke or cells instead
+                TypedShape::Polyline(_) | TypedShape::Compound(_) => Vec::new(),
                 _ => {
                     // Fallback to AABB-based vertices
                     let half_size = size * 0.5;
@@ -852,6 +859,37 @@ pub(super) fn draw_selection_outline<Config: GizmoConfigGroup>(
                 );
             }
         }
+        TypedShape::Polyline(polyline) => {
+            // 折线碰撞体：逐段绘制
+            for segment in polyline.segments() {
+                draw_dashed_line(
+                    gizmos,
+                    rotate_point(Vec2::new(segment.a.x, segment.a.y), rotation) + center,
+                    rotate_point(Vec2::new(segment.b.x, segment.b.y), rotation) + center,
+                    color,
+                    time_offset,
+                );
+            }
+        }
+        TypedShape::Compound(compound) => {
+            // 组合碰撞体：逐个绘制凸部件的轮廓
+            for (isometry, shape) in compound.shapes() {
+                let offset = Vec2::new(isometry.translation.x, isometry.translation.y);
+                let part_transform = Transform {
+                    translation: (rotate_point(offset, rotation) + center)
+                        .extend(transform.translation.z),
+                    rotation: Quat::from_rotation_z(rotation + isometry.rotation.angle()),
+                    ..default()
+                };
+                draw_selection_outline(
+                    gizmos,
+                    &part_transform,
+                    &Collider::from(shape.clone()),
+                    color,
+                    time_offset,
+                );
+            }
+        }
         _ => {
             // 其他类型的简单轮廓
             let size = Vec2::splat(20.0);
@@ -0,0 +1,110 @@
+//! Scene-level physics settings
+//!
+//! Gravity, substeps, solver parameters, length unit, sleeping thresholds and default
+//! materials are Avian resources and are edited directly. The only setting Avian does not keep
+//! in a reflectable resource is the fixed timestep, so [`PhysicsTimestep`] stores it and is
+//! synced into [`Time<Fixed>`] whenever it changes.
+//!
+//! All of these resources are saved with the scene by `export_scene`, and restored on import.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+/// Physics update rate saved with the scene
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct PhysicsTimestep {
+    /// Fixed updates per second
+    pub hz: f64,
+}
+
+impl Default for PhysicsTimestep {
+    fn default() -> Self {
+        // Bevy's default fixed timestep
+        Self { hz: 64.0 }
+    }
+}
+
+/// Named world settings presets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum WorldPreset {
+    /// Avian defaults
+    Default,
+    /// Strong gravity and no bounce for snappy jumping
+    Platformer,
+    /// No gravity, low friction and some bounce
+    Space,
+    /// No gravity, high friction, seen from above
+    TopDown,
+}
+
+impl WorldPreset {
+    /// All presets, in UI order
+    pub const ALL: [WorldPreset; 4] = [
+        WorldPreset::Default,
+        WorldPreset::Platformer,
+        WorldPreset::Space,
+        WorldPreset::TopDown,
+    ];
+
+    /// Translation key for the preset name
+    pub fn key(self) -> &'static str {
+        match self {
+            WorldPreset::Default => "world_preset_default",
+            WorldPreset::Platformer => "world_preset_platformer",
+            WorldPreset::Space => "world_preset_space",
+            WorldPreset::TopDown => "world_preset_top_down",
+        }
+    }
+
+    /// Write the preset into the world's physics resources
+    ///
+    /// The game presets assume pixel-sized scenes with 100 pixels per meter. [`Self::Default`]
+    /// is the exception: it restores Avian's own defaults, one unit per meter with gravity of
+    /// 9.81, along with its solver and sleeping settings.
+    pub fn apply(self, world: &mut World) {
+        let (gravity, length_unit, substeps, friction, restitution) = match self {
+            WorldPreset::Default => (Vec2::new(0.0, -9.81), 1.0, 6, 0.5, 0.0),
+            WorldPreset::Platformer => (Vec2::new(0.0, -2000.0), 100.0, 8, 0.6, 0.0),
+            WorldPreset::Space => (Vec2::ZERO, 100.0, 6, 0.1, 0.5),
+            WorldPreset::TopDown => (Vec2::ZERO, 100.0, 4, 0.8, 0.1),
+        };
+
+        world.insert_resource(Gravity(gravity));
+        world.insert_resource(PhysicsLengthUnit(length_unit));
+        world.insert_resource(SubstepCount(substeps));
+        world.insert_resource(DefaultFriction(Friction::new(friction)));
+        world.insert_resource(DefaultRestitution(Restitution::new(restitution)));
+        world.insert_resource(PhysicsTimestep::default());
+
+        if self == WorldPreset::Default {
+            world.insert_resource(SolverConfig::default());
+            world.insert_resource(SleepingThreshold::default());
+            world.insert_resource(DeactivationTime::default());
+        }
+
+        info!("Applied world preset {:?}", self);
+    }
+}
+
+/// Plugin keeping scene-level physics settings in sync
+#[derive(Default)]
+pub struct WorldSettingsPlugin;
+
+impl Plugin for WorldSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsTimestep>()
+            .register_type::<PhysicsTimestep>()
+            .add_systems(
+                Update,
+                apply_physics_timestep.run_if(resource_changed::<PhysicsTimestep>),
+            );
+    }
+}
+
+/// Push the saved timestep into the fixed clock that drives physics
+fn apply_physics_timestep(timestep: Res<PhysicsTimestep>, mut fixed_time: ResMut<Time<Fixed>>) {
+    if timestep.hz > 0.0 {
+        fixed_time.set_timestep_hz(timestep.hz);
+    }
+}
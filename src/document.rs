@@ -0,0 +1,918 @@
+//! Current document, autosave and crash recovery
+//!
+//! The editor remembers which file the scene was opened from or last saved to, so saving
+//! writes in place, and whether the scene changed since. Changes are found with component
+//! change detection on the entities the user authored.
+//!
+//! While it runs, the scene is periodically written to a recovery directory. Each session keeps
+//! a lock file there that a clean exit removes; a lock left behind on the next start means that
+//! session crashed, and its latest autosave is offered for recovery.
+
+use avian2d::prelude::*;
+use bevy::app::AppExit;
+use bevy::ecs::component::{ComponentId, Tick};
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowCloseRequested};
+use bevy_egui::input::egui_wants_any_keyboard_input;
+use rfd::FileDialog;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+use std::path::{Path, PathBuf};
+
+use crate::collider_tools::debug_render::joint::JointConfig;
+use crate::interaction_standards::InteractionStateComponent;
+use crate::prefab::{PrefabInstance, rebuilt_components};
+use crate::scene_export_import::{SceneImportMode, clear_scene, read_scene_import, save_scene};
+use crate::selection::Selectable;
+use crate::sub_scene::SubScene;
+use crate::{CollisionLayerPresets, tr};
+
+/// Document tracking, autosave and recovery plugin
+pub struct DocumentPlugin;
+
+impl Plugin for DocumentPlugin {
+    fn build(&self, app: &mut App) {
+        let data_dir = editor_data_dir();
+        app.register_type::<AutosaveSettings>()
+            .insert_resource(load_data::<AutosaveSettings>(&data_dir))
+            .insert_resource(load_data::<RecentFiles>(&data_dir))
+            .insert_resource(RecoverySession::new(data_dir.join("recovery")))
+            .init_resource::<EditorDocument>()
+            .init_resource::<ChangeTracking>()
+            .add_event::<DocumentEvent>()
+            .add_systems(Startup, start_session)
+            .add_systems(
+                Update,
+                (
+                    handle_document_shortcuts.run_if(not(egui_wants_any_keyboard_input)),
+                    handle_close_requests,
+                    handle_document_events,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    autosave,
+                    store_data::<AutosaveSettings>.run_if(resource_changed::<AutosaveSettings>),
+                    store_data::<RecentFiles>.run_if(resource_changed::<RecentFiles>),
+                    (update_session_lock, update_window_title)
+                        .run_if(resource_changed::<EditorDocument>),
+                ),
+            )
+            .add_systems(
+                Last,
+                (track_modifications, end_session.run_if(on_event::<AppExit>)),
+            );
+    }
+}
+
+/// The scene file being edited
+#[derive(Resource, Debug, Clone, Default)]
+pub struct EditorDocument {
+    /// File that saving writes to; `None` until the scene is saved or opened
+    pub path: Option<PathBuf>,
+    /// Whether the scene changed since it was created, opened or saved
+    pub modified: bool,
+}
+
+impl EditorDocument {
+    /// File name of the document, if it has one
+    pub fn name(&self) -> Option<String> {
+        let name = self.path.as_ref()?.file_name()?;
+        Some(name.to_string_lossy().into_owned())
+    }
+}
+
+/// Document commands
+#[derive(Event, Debug, Clone)]
+pub enum DocumentEvent {
+    /// Start an empty scene
+    New,
+    /// Replace the scene with a file, asking for one if no path is given
+    Open(Option<PathBuf>),
+    /// Save to the current document, asking for a path if there is none
+    Save,
+    /// Ask for a path and save the scene there
+    SaveAs,
+    /// Quit the editor
+    Exit,
+    /// Answer the unsaved changes prompt
+    ResolveUnsaved(UnsavedChoice),
+    /// Replace the scene with the autosave of the crashed session
+    Recover,
+    /// Dismiss the crashed session and let its autosaves expire
+    DiscardRecovery,
+}
+
+/// Command that would drop the current scene
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocumentAction {
+    /// See [`DocumentEvent::New`]
+    New,
+    /// See [`DocumentEvent::Open`]
+    Open(Option<PathBuf>),
+    /// See [`DocumentEvent::Exit`]
+    Exit,
+}
+
+/// How to go on with a command held back by unsaved changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsavedChoice {
+    /// Save the document first
+    Save,
+    /// Go ahead and lose the changes
+    Discard,
+    /// Keep editing and drop the command
+    Cancel,
+}
+
+/// Command waiting for the user to decide what happens to unsaved changes
+#[derive(Resource, Debug, Clone)]
+pub struct UnsavedChanges {
+    pub action: DocumentAction,
+}
+
+/// Recently opened or saved documents, newest first, kept between sessions
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecentFiles {
+    pub paths: Vec<PathBuf>,
+}
+
+impl RecentFiles {
+    const LIMIT: usize = 10;
+
+    /// Move a path to the front of the list
+    pub fn add(&mut self, path: PathBuf) {
+        self.paths.retain(|recent| *recent != path);
+        self.paths.insert(0, path);
+        self.paths.truncate(Self::LIMIT);
+    }
+}
+
+/// Change detection state behind [`EditorDocument::modified`]
+#[derive(Resource, Debug, Default)]
+struct ChangeTracking {
+    /// Tick up to which changes are accounted for
+    last_check: Option<Tick>,
+    /// Number of authored entities at the last check, to notice despawns
+    authored: usize,
+    /// Frames left in which changes are not counted, while a loaded scene finishes spawning
+    settling: u8,
+    /// Whether physics time was running at the last check
+    simulating: bool,
+}
+
+/// Periodic autosave options, kept between sessions
+#[derive(Resource, Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[reflect(Resource, Default)]
+#[serde(default)]
+pub struct AutosaveSettings {
+    pub enabled: bool,
+    /// Seconds between autosaves
+    pub interval: f32,
+    /// Number of autosave files kept in the recovery directory
+    pub retention: usize,
+}
+
+impl Default for AutosaveSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: 120.0,
+            retention: 5,
+        }
+    }
+}
+
+/// Autosave state of this editor session
+#[derive(Resource, Debug)]
+pub struct RecoverySession {
+    /// Directory holding autosaves and session locks
+    pub directory: PathBuf,
+    id: String,
+    sequence: u32,
+    elapsed: f32,
+}
+
+impl RecoverySession {
+    fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            id: chrono::Local::now().format("%Y%m%d-%H%M%S").to_string(),
+            sequence: 0,
+            elapsed: 0.0,
+        }
+    }
+}
+
+/// Autosave of a session that did not exit cleanly, waiting for the user to recover or discard it
+#[derive(Resource, Debug, Clone)]
+pub struct Recovery {
+    /// Latest autosave of the crashed session
+    pub autosave: PathBuf,
+    /// Document that session was editing
+    pub document: Option<PathBuf>,
+    /// Local time the autosave was written
+    pub saved_at: String,
+    sessions: Vec<String>,
+}
+
+/// Directory for editor data kept between sessions
+pub fn editor_data_dir() -> PathBuf {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+        .unwrap_or_default()
+        .join("avian_2d_editor")
+}
+
+fn lock_path(directory: &Path, session: &str) -> PathBuf {
+    directory.join(format!("session-{session}.lock"))
+}
+
+fn autosave_prefix(session: &str) -> String {
+    format!("autosave-{session}-")
+}
+
+/// Names of files in a directory that start with a prefix and end with a suffix
+fn file_names(directory: &Path, prefix: &str, suffix: &str) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with(prefix) && name.ends_with(suffix))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Find the latest autosave of sessions whose lock was left behind
+///
+/// Locks of crashed sessions that never autosaved are removed, since there is nothing to recover.
+fn find_recovery(directory: &Path, own_session: &str) -> Option<Recovery> {
+    let sessions: Vec<String> = file_names(directory, "session-", ".lock")
+        .into_iter()
+        .filter_map(|name| {
+            Some(
+                name.strip_prefix("session-")?
+                    .strip_suffix(".lock")?
+                    .to_string(),
+            )
+        })
+        .filter(|session| session != own_session)
+        .collect();
+
+    // Session ids and sequence numbers both sort chronologically
+    let latest = sessions
+        .iter()
+        .filter_map(|session| {
+            let name = file_names(directory, &autosave_prefix(session), ".scn.ron").pop()?;
+            Some((session, name))
+        })
+        .max_by(|a, b| a.1.cmp(&b.1));
+    let Some((session, name)) = latest else {
+        for session in &sessions {
+            let _ = std::fs::remove_file(lock_path(directory, session));
+        }
+        return None;
+    };
+
+    let autosave = directory.join(name);
+    let document = std::fs::read_to_string(lock_path(directory, session))
+        .ok()
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from);
+    let saved_at = std::fs::metadata(&autosave)
+        .and_then(|metadata| metadata.modified())
+        .map(|time| {
+            chrono::DateTime::<chrono::Local>::from(time)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_default();
+    Some(Recovery {
+        autosave,
+        document,
+        saved_at,
+        sessions,
+    })
+}
+
+/// Delete all but the newest `retention` autosaves, sparing those of the given sessions
+fn prune_autosaves(directory: &Path, retention: usize, spared: &[String]) {
+    let mut names: Vec<String> = file_names(directory, "autosave-", ".scn.ron")
+        .into_iter()
+        .filter(|name| {
+            !spared
+                .iter()
+                .any(|session| name.starts_with(&autosave_prefix(session)))
+        })
+        .collect();
+    names.reverse();
+    for name in names.into_iter().skip(retention) {
+        if let Err(e) = std::fs::remove_file(directory.join(&name)) {
+            warn!("Failed to remove old autosave {}: {}", name, e);
+        }
+    }
+}
+
+/// Look for a crashed session to recover, then lock this one
+fn start_session(
+    mut commands: Commands,
+    session: Res<RecoverySession>,
+    document: Res<EditorDocument>,
+) {
+    if let Err(e) = std::fs::create_dir_all(&session.directory) {
+        warn!(
+            "Autosave directory {:?} is unavailable: {}",
+            session.directory, e
+        );
+        return;
+    }
+    if let Some(recovery) = find_recovery(&session.directory, &session.id) {
+        info!(
+            "Found an autosave of an unfinished session: {:?}",
+            recovery.autosave
+        );
+        commands.insert_resource(recovery);
+    }
+    write_session_lock(&session, &document);
+}
+
+/// Record the current document in the session lock, so a recovery can restore it
+fn write_session_lock(session: &RecoverySession, document: &EditorDocument) {
+    let contents = document
+        .path
+        .as_ref()
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if let Err(e) = std::fs::write(lock_path(&session.directory, &session.id), contents) {
+        warn!("Failed to write session lock: {}", e);
+    }
+}
+
+fn update_session_lock(session: Res<RecoverySession>, document: Res<EditorDocument>) {
+    write_session_lock(&session, &document);
+}
+
+/// A clean exit leaves no lock, so the next start does not offer recovery
+fn end_session(session: Res<RecoverySession>) {
+    let _ = std::fs::remove_file(lock_path(&session.directory, &session.id));
+}
+
+/// Preferences stored as a RON file in the editor data directory
+trait DataFile: Resource + Default + Serialize + DeserializeOwned {
+    const FILE_NAME: &'static str;
+}
+
+impl DataFile for AutosaveSettings {
+    const FILE_NAME: &'static str = "autosave.ron";
+}
+
+impl DataFile for RecentFiles {
+    const FILE_NAME: &'static str = "recent.ron";
+}
+
+/// Read a data file, falling back to defaults
+fn load_data<T: DataFile>(data_dir: &Path) -> T {
+    std::fs::read_to_string(data_dir.join(T::FILE_NAME))
+        .ok()
+        .and_then(|text| ron::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn store_data<T: DataFile>(data: Res<T>) {
+    let data_dir = editor_data_dir();
+    let result = ron::ser::to_string_pretty(&*data, ron::ser::PrettyConfig::default())
+        .map_err(|e| e.to_string())
+        .and_then(|text| {
+            std::fs::create_dir_all(&data_dir)
+                .and_then(|()| std::fs::write(data_dir.join(T::FILE_NAME), text))
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        warn!("Failed to store {}: {}", T::FILE_NAME, e);
+    }
+}
+
+/// Show the document name in the window title, marked while it has unsaved changes
+fn update_window_title(
+    document: Res<EditorDocument>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut app_title: Local<Option<String>>,
+) {
+    let Ok(mut window) = window_query.single_mut() else {
+        return;
+    };
+    let app_title = app_title.get_or_insert_with(|| window.title.clone());
+    let name = document.name().unwrap_or_else(|| tr!("untitled"));
+    let marker = if document.modified { "*" } else { "" };
+    window.title = format!("{name}{marker} - {app_title}");
+}
+
+/// Ctrl+N, Ctrl+O and Ctrl+S run the document commands, Ctrl+Shift+S saves under a new path
+fn handle_document_shortcuts(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut events: EventWriter<DocumentEvent>,
+) {
+    let control = keyboard.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    if !control {
+        return;
+    }
+    let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if keyboard.just_pressed(KeyCode::KeyN) {
+        events.write(DocumentEvent::New);
+    } else if keyboard.just_pressed(KeyCode::KeyO) {
+        events.write(DocumentEvent::Open(None));
+    } else if keyboard.just_pressed(KeyCode::KeyS) && shift {
+        events.write(DocumentEvent::SaveAs);
+    } else if keyboard.just_pressed(KeyCode::KeyS) {
+        events.write(DocumentEvent::Save);
+    }
+}
+
+/// Closing the main window exits through the document, so unsaved changes can be kept
+///
+/// Only has a say when the window plugin leaves close requests to the app
+/// (`close_when_requested: false`).
+fn handle_close_requests(
+    mut close_requests: EventReader<WindowCloseRequested>,
+    primary_window: Query<(), With<PrimaryWindow>>,
+    mut events: EventWriter<DocumentEvent>,
+) {
+    for request in close_requests.read() {
+        if primary_window.contains(request.window) {
+            events.write(DocumentEvent::Exit);
+        }
+    }
+}
+
+fn handle_document_events(world: &mut World) {
+    let events: Vec<DocumentEvent> = world
+        .resource_mut::<Events<DocumentEvent>>()
+        .drain()
+        .collect();
+
+    for event in events {
+        match event {
+            DocumentEvent::New => request(world, DocumentAction::New),
+            DocumentEvent::Open(path) => request(world, DocumentAction::Open(path)),
+            DocumentEvent::Exit => request(world, DocumentAction::Exit),
+            DocumentEvent::Save => {
+                save_document(world);
+            }
+            DocumentEvent::SaveAs => {
+                save_document_as(world);
+            }
+            DocumentEvent::ResolveUnsaved(choice) => {
+                let Some(UnsavedChanges { action }) = world.remove_resource::<UnsavedChanges>()
+                else {
+                    continue;
+                };
+                match choice {
+                    UnsavedChoice::Save => {
+                        if save_document(world) {
+                            perform(world, action);
+                        }
+                    }
+                    UnsavedChoice::Discard => perform(world, action),
+                    UnsavedChoice::Cancel => {}
+                }
+            }
+            DocumentEvent::Recover => {
+                if let Some(recovery) = world.remove_resource::<Recovery>() {
+                    recover(world, recovery);
+                }
+            }
+            DocumentEvent::DiscardRecovery => {
+                if let Some(recovery) = world.remove_resource::<Recovery>() {
+                    let directory = world.resource::<RecoverySession>().directory.clone();
+                    for session in &recovery.sessions {
+                        let _ = std::fs::remove_file(lock_path(&directory, session));
+                    }
+                    info!("Discarded autosave {:?}", recovery.autosave);
+                }
+            }
+        }
+    }
+}
+
+/// Run a command right away, or hold it until the user decides about unsaved changes
+fn request(world: &mut World, action: DocumentAction) {
+    if world.resource::<EditorDocument>().modified {
+        world.insert_resource(UnsavedChanges { action });
+    } else {
+        perform(world, action);
+    }
+}
+
+fn perform(world: &mut World, action: DocumentAction) {
+    match action {
+        DocumentAction::New => {
+            clear_scene(world);
+            set_document(world, None, true);
+            info!("Started a new scene");
+        }
+        DocumentAction::Open(path) => open_document(world, path),
+        DocumentAction::Exit => {
+            world.send_event(AppExit::Success);
+        }
+    }
+}
+
+fn open_document(world: &mut World, path: Option<PathBuf>) {
+    let Some(path) = path.or_else(|| {
+        FileDialog::new()
+            .add_filter("Scene files", &["ron", "scn"])
+            .add_filter("All files", &["*"])
+            .set_title("Open Scene")
+            .pick_file()
+    }) else {
+        return;
+    };
+    let import = read_scene_import(
+        &path,
+        SceneImportMode::Replace,
+        world.resource::<AppTypeRegistry>(),
+        world.resource::<CollisionLayerPresets>(),
+    );
+    match import {
+        Ok(import) => {
+            // Replacing the scene makes the file the current document
+            import.apply(world);
+            world.resource_mut::<RecentFiles>().add(path);
+        }
+        Err(e) => {
+            error!("Opening {:?} failed: {}", path, e);
+            if !path.exists() {
+                world
+                    .resource_mut::<RecentFiles>()
+                    .paths
+                    .retain(|recent| *recent != path);
+            }
+        }
+    }
+}
+
+/// Save to the current document or a new path; returns whether the scene was written
+fn save_document(world: &mut World) -> bool {
+    match world.resource::<EditorDocument>().path.clone() {
+        Some(path) => save_document_to(world, path),
+        None => save_document_as(world),
+    }
+}
+
+fn save_document_as(world: &mut World) -> bool {
+    let dialog = FileDialog::new()
+        .add_filter("Scene files", &["ron", "scn"])
+        .add_filter("All files", &["*"])
+        .set_title("Save Scene As");
+    match dialog.save_file() {
+        Some(path) => save_document_to(world, path),
+        None => false,
+    }
+}
+
+fn save_document_to(world: &mut World, path: PathBuf) -> bool {
+    match save_scene(world, &path) {
+        Ok(count) => {
+            info!("Saved {:?} with {} entities", path, count);
+            world.resource_mut::<RecentFiles>().add(path.clone());
+            set_document(world, Some(path), false);
+            true
+        }
+        Err(e) => {
+            error!("Saving {:?} failed: {}", path, e);
+            false
+        }
+    }
+}
+
+/// Make the scene as it is now the unmodified document at `path`
+///
+/// A `loaded` scene also ignores changes for a few frames, while its prefab members,
+/// sub-scenes and joints finish spawning.
+pub(crate) fn set_document(world: &mut World, path: Option<PathBuf>, loaded: bool) {
+    if let Some(mut document) = world.get_resource_mut::<EditorDocument>() {
+        document.path = path;
+        document.modified = false;
+    }
+    let tick = world.change_tick();
+    if let Some(mut tracking) = world.get_resource_mut::<ChangeTracking>() {
+        tracking.last_check = Some(tick);
+        if loaded {
+            tracking.settling = 3;
+        }
+    }
+}
+
+/// Whether an entity belongs to the document rather than to the editor
+fn is_authored(entity: &EntityRef) -> bool {
+    entity.contains::<Selectable>()
+        || entity.contains::<JointConfig>()
+        || entity.contains::<PrefabInstance>()
+        || entity.contains::<SubScene>()
+}
+
+/// Components whose changes modify the document
+///
+/// The editor's own and Avian's, plus the Bevy ones users edit. Derived state, simulation
+/// bookkeeping and hover feedback are left out.
+fn tracked_components(world: &World) -> Vec<ComponentId> {
+    let crate_prefix = concat!(env!("CARGO_CRATE_NAME"), "::");
+    let edited = [
+        TypeId::of::<Transform>(),
+        TypeId::of::<Sprite>(),
+        TypeId::of::<Name>(),
+    ];
+    let ignored = rebuilt_components();
+    let registry = world.resource::<AppTypeRegistry>().read();
+    registry
+        .iter()
+        .filter(|registration| registration.data::<ReflectComponent>().is_some())
+        .filter(|registration| {
+            let type_id = registration.type_id();
+            let type_path = registration.type_info().type_path();
+            (type_path.starts_with(crate_prefix)
+                || type_path.starts_with("avian2d::")
+                || edited.contains(&type_id))
+                && !ignored.contains(&type_id)
+                && type_id != TypeId::of::<InteractionStateComponent>()
+                && type_id != TypeId::of::<TimeSleeping>()
+                && type_id != TypeId::of::<CollidingEntities>()
+        })
+        .filter_map(|registration| world.components().get_id(registration.type_id()))
+        .collect()
+}
+
+/// Mark the document modified when an authored entity is added, changed or despawned
+///
+/// The solver writes transforms, velocities and sleep state of dynamic bodies every step, so
+/// component changes are only compared while physics is paused. The frame in which it was
+/// paused is skipped too, which leaves the paused state as the new baseline.
+fn track_modifications(world: &mut World) {
+    let this_run = world.change_tick();
+    let simulating = world
+        .get_resource::<Time<Physics>>()
+        .is_some_and(|time| !time.is_paused());
+    let tracking = world.resource::<ChangeTracking>();
+    let last_check = tracking
+        .last_check
+        .filter(|_| !simulating && !tracking.simulating);
+    let tracked = tracked_components(world);
+
+    let mut authored = 0;
+    let mut changed = false;
+    for entity in world.iter_entities().filter(is_authored) {
+        authored += 1;
+        changed = changed
+            || last_check.is_some_and(|last_check| {
+                tracked.iter().any(|&id| {
+                    entity
+                        .get_change_ticks_by_id(id)
+                        .is_some_and(|ticks| ticks.is_changed(last_check, this_run))
+                })
+            });
+    }
+
+    let mut tracking = world.resource_mut::<ChangeTracking>();
+    let counted = tracking.last_check.is_some() && tracking.settling == 0;
+    let modified = counted && (changed || authored != tracking.authored);
+    tracking.last_check = Some(this_run);
+    tracking.authored = authored;
+    tracking.settling = tracking.settling.saturating_sub(1);
+    tracking.simulating = simulating;
+
+    if modified {
+        let mut document = world.resource_mut::<EditorDocument>();
+        if !document.modified {
+            document.modified = true;
+        }
+    }
+}
+
+/// Replace the scene with the autosave and go back to editing the crashed session's document
+fn recover(world: &mut World, recovery: Recovery) {
+    let import = read_scene_import(
+        &recovery.autosave,
+        SceneImportMode::Replace,
+        world.resource::<AppTypeRegistry>(),
+        world.resource::<CollisionLayerPresets>(),
+    );
+    let import = match import {
+        Ok(import) => import,
+        Err(e) => {
+            error!("Recovering {:?} failed: {}", recovery.autosave, e);
+            return;
+        }
+    };
+    import.apply(world);
+    // The recovered changes were never saved to the document
+    set_document(world, recovery.document, true);
+    world.resource_mut::<EditorDocument>().modified = true;
+
+    let directory = world.resource::<RecoverySession>().directory.clone();
+    for session in &recovery.sessions {
+        let _ = std::fs::remove_file(lock_path(&directory, session));
+    }
+    info!("Recovered {:?}", recovery.autosave);
+}
+
+/// Write the scene to the recovery directory every interval
+fn autosave(world: &mut World) {
+    let settings = world.resource::<AutosaveSettings>().clone();
+    if !settings.enabled {
+        return;
+    }
+    let delta = world.resource::<Time<Real>>().delta_secs();
+    let mut session = world.resource_mut::<RecoverySession>();
+    session.elapsed += delta;
+    if session.elapsed < settings.interval {
+        return;
+    }
+    session.elapsed = 0.0;
+
+    // A saved scene has nothing worth recovering
+    if !world.resource::<EditorDocument>().modified {
+        return;
+    }
+
+    let mut session = world.resource_mut::<RecoverySession>();
+    session.sequence += 1;
+    let directory = session.directory.clone();
+    let path = directory.join(format!(
+        "{}{:04}.scn.ron",
+        autosave_prefix(&session.id),
+        session.sequence
+    ));
+    match save_scene(world, &path) {
+        Ok(count) => {
+            debug!("Autosaved {} entities to {:?}", count, path);
+            // Autosaves still offered for recovery are kept until the prompt is answered
+            let spared = world
+                .get_resource::<Recovery>()
+                .map(|recovery| recovery.sessions.clone())
+                .unwrap_or_default();
+            prune_autosaves(&directory, settings.retention.max(1), &spared);
+        }
+        Err(e) => {
+            warn!("Autosave to {:?} failed: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crashed_session_autosave_is_recovered_and_old_ones_pruned() {
+        let directory =
+            std::env::temp_dir().join(format!("avian_editor_recovery_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+
+        // A crashed session editing level.scn.ron and the running one
+        std::fs::write(lock_path(&directory, "20260101-100000"), "level.scn.ron").unwrap();
+        std::fs::write(lock_path(&directory, "20260102-100000"), "").unwrap();
+        for name in [
+            "autosave-20260101-100000-0001.scn.ron",
+            "autosave-20260101-100000-0002.scn.ron",
+            "autosave-20260102-100000-0001.scn.ron",
+            "autosave-20260102-100000-0002.scn.ron",
+            "autosave-20260102-100000-0003.scn.ron",
+        ] {
+            std::fs::write(directory.join(name), "").unwrap();
+        }
+
+        let recovery = find_recovery(&directory, "20260102-100000").unwrap();
+        assert_eq!(
+            recovery.autosave,
+            directory.join("autosave-20260101-100000-0002.scn.ron")
+        );
+        assert_eq!(recovery.document, Some(PathBuf::from("level.scn.ron")));
+        assert_eq!(recovery.sessions, vec!["20260101-100000".to_string()]);
+
+        // The crashed session's autosaves survive pruning while recovery is pending
+        prune_autosaves(&directory, 2, &recovery.sessions);
+        assert_eq!(
+            file_names(&directory, "autosave-", ".scn.ron"),
+            vec![
+                "autosave-20260101-100000-0001.scn.ron",
+                "autosave-20260101-100000-0002.scn.ron",
+                "autosave-20260102-100000-0002.scn.ron",
+                "autosave-20260102-100000-0003.scn.ron",
+            ]
+        );
+        prune_autosaves(&directory, 2, &[]);
+        assert_eq!(
+            file_names(&directory, "autosave-", ".scn.ron"),
+            vec![
+                "autosave-20260102-100000-0002.scn.ron",
+                "autosave-20260102-100000-0003.scn.ron",
+            ]
+        );
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn test_scene_edits_mark_document_modified() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Transform>();
+        world.init_resource::<EditorDocument>();
+        world.init_resource::<ChangeTracking>();
+        let body = world
+            .spawn((Transform::default(), Selectable::default()))
+            .id();
+        let camera = world.spawn(Transform::default()).id();
+
+        // The first check only records the scene as it is
+        world.run_system_once(track_modifications).unwrap();
+        assert!(!world.resource::<EditorDocument>().modified);
+
+        // Editor entities are not part of the document
+        world.get_mut::<Transform>(camera).unwrap().translation.x = 10.0;
+        world.run_system_once(track_modifications).unwrap();
+        assert!(!world.resource::<EditorDocument>().modified);
+
+        world.get_mut::<Transform>(body).unwrap().translation.x = 10.0;
+        world.run_system_once(track_modifications).unwrap();
+        assert!(world.resource::<EditorDocument>().modified);
+
+        // Saving makes the current state the baseline
+        set_document(&mut world, Some(PathBuf::from("level.scn.ron")), false);
+        world.run_system_once(track_modifications).unwrap();
+        assert!(!world.resource::<EditorDocument>().modified);
+
+        world.despawn(body);
+        world.run_system_once(track_modifications).unwrap();
+        assert!(world.resource::<EditorDocument>().modified);
+    }
+
+    #[test]
+    fn test_simulation_does_not_mark_document_modified() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Transform>();
+            registry.register::<LinearVelocity>();
+            registry.register::<AngularVelocity>();
+        }
+        world.init_resource::<EditorDocument>();
+        world.init_resource::<ChangeTracking>();
+        world.insert_resource(Time::<Physics>::default());
+        let body = world
+            .spawn((
+                Transform::default(),
+                RigidBody::Dynamic,
+                LinearVelocity::ZERO,
+                AngularVelocity::ZERO,
+                Selectable::default(),
+            ))
+            .id();
+        world.run_system_once(track_modifications).unwrap();
+
+        // What a physics step writes to a falling body
+        let step = |world: &mut World| {
+            world.get_mut::<LinearVelocity>(body).unwrap().y -= 0.16;
+            world.get_mut::<AngularVelocity>(body).unwrap().0 += 0.01;
+            world.get_mut::<Transform>(body).unwrap().translation.y -= 0.0027;
+        };
+        for _ in 0..3 {
+            step(&mut world);
+            world.run_system_once(track_modifications).unwrap();
+        }
+        assert!(!world.resource::<EditorDocument>().modified);
+
+        // The last step before pausing lands in the same frame as the pause
+        step(&mut world);
+        world.resource_mut::<Time<Physics>>().pause();
+        world.run_system_once(track_modifications).unwrap();
+        assert!(!world.resource::<EditorDocument>().modified);
+
+        // Edits to the paused scene still count
+        world.get_mut::<Transform>(body).unwrap().translation.x = 10.0;
+        world.run_system_once(track_modifications).unwrap();
+        assert!(world.resource::<EditorDocument>().modified);
+    }
+}
@@ -0,0 +1,998 @@
+//! Import and write-back of collision data in LDtk projects
+//!
+//! IntGrid layers become [`TileLayer`]s, one for each value used in the layer, so painted terrain
+//! turns into merged boxes or outlines as chosen in [`LdtkSettings::merge`]. Entity instances
+//! become colliders when they have a `collider` field naming their shape:
+//!
+//! | Field | Type | Meaning |
+//! |---|---|---|
+//! | `collider` | String or Enum | `rectangle`, `circle`, `capsule`, `triangle` or `polygon` |
+//! | `rotation` | Float | clockwise rotation in degrees |
+//! | `vertices` | String | `x,y x,y x,y` in pixels from the entity center, contours separated by `;` |
+//!
+//! Rectangles and capsules fill the entity's size and circles fit inside it. Triangles and
+//! polygons read their `vertices`; the first polygon contour is the outline and the rest are
+//! holes. Without vertices, triangles point up inside the entity's box and polygons cover it.
+//! Level fields and entity fields set up the bodies with the names listed in
+//! [`crate::level_import`]; entity fields override the level's.
+//!
+//! LDtk works in pixels with y pointing down. Positions are multiplied by
+//! [`LdtkSettings::scale`] and y is negated, like the Tiled importer does.
+//!
+//! Imported bodies keep an [`LdtkEntity`] or [`LdtkIntGrid`] link. Writing back reloads the
+//! project, updates the linked entities' position, size and existing fields and repaints the
+//! linked IntGrid values from the tile layer cells, then saves the project and its external
+//! level files in place.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use avian2d::parry::shape::TypedShape;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rfd::FileDialog;
+use serde_json::{Value, json};
+use thiserror::Error;
+
+use crate::collider_tools::polygon::signed_area;
+use crate::level_import::{
+    ImportedBody, ImportedShape, ImportedShapeKind, spawn_imported_shape, spawn_imported_tile_layer,
+};
+use crate::{
+    ColliderCreationState, ColliderType, CreationProperties, PolygonSource, TileLayer,
+    TileMergeMode,
+};
+
+/// LDtk import and write-back plugin
+pub struct LdtkPlugin;
+
+impl Plugin for LdtkPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LdtkSettings>()
+            .init_resource::<LdtkProjectLink>()
+            .register_type::<LdtkSettings>()
+            .register_type::<LdtkEntity>()
+            .register_type::<LdtkIntGrid>()
+            .add_event::<LdtkImportEvent>()
+            .add_event::<LdtkExportEvent>()
+            .add_systems(Update, (handle_ldtk_import, handle_ldtk_export));
+    }
+}
+
+/// LDtk import event
+#[derive(Event)]
+pub enum LdtkImportEvent {
+    /// Show file dialog and import
+    FromDialog,
+    /// Import from specific path
+    FromPath(PathBuf),
+}
+
+/// Write the linked bodies back into the last imported LDtk project
+#[derive(Event)]
+pub struct LdtkExportEvent;
+
+/// Options for LDtk imports
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct LdtkSettings {
+    /// World units per LDtk pixel, also used when writing back
+    pub scale: f32,
+    /// How IntGrid cells are merged into colliders
+    pub merge: TileMergeMode,
+}
+
+impl Default for LdtkSettings {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            merge: TileMergeMode::Rectangles,
+        }
+    }
+}
+
+/// Project written back to by [`LdtkExportEvent`]
+#[derive(Resource, Default, Debug)]
+pub struct LdtkProjectLink {
+    /// Path of the last imported project
+    pub path: Option<PathBuf>,
+}
+
+/// Link from a collider to the LDtk entity instance it was imported from
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct LdtkEntity {
+    /// Instance id of the entity
+    pub iid: String,
+    /// Level position plus layer offset, in LDtk pixels
+    pub layer_origin: Vec2,
+}
+
+/// Link from a tile layer to the IntGrid value it was imported from
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct LdtkIntGrid {
+    /// Instance id of the IntGrid layer
+    pub layer_iid: String,
+    /// IntGrid value painted by the tile layer's cells
+    pub value: i64,
+    /// Level position plus layer offset, in LDtk pixels
+    pub layer_origin: Vec2,
+}
+
+/// LDtk errors
+#[derive(Error, Debug)]
+pub enum LdtkError {
+    #[error("JSON error: {0}")]
+    JsonError(String),
+    #[error("File error: {0}")]
+    FileError(String),
+    #[error("Invalid field: {0}")]
+    InvalidField(String),
+}
+
+/// LDtk project with its external level files
+#[derive(Debug, Clone)]
+pub struct LdtkProject {
+    pub root: Value,
+    /// Levels saved in separate files, by their path relative to the project
+    pub external_levels: Vec<(String, Value)>,
+}
+
+impl LdtkProject {
+    /// Parse a project, loading external levels through `load_level`
+    pub fn parse(
+        json: &str,
+        load_level: &dyn Fn(&str) -> Result<String, LdtkError>,
+    ) -> Result<Self, LdtkError> {
+        let root: Value =
+            serde_json::from_str(json).map_err(|e| LdtkError::JsonError(e.to_string()))?;
+        let mut external_levels = Vec::new();
+        for path in level_objects(&root).filter_map(external_path) {
+            let level = serde_json::from_str(&load_level(path)?)
+                .map_err(|e| LdtkError::JsonError(format!("{path}: {e}")))?;
+            external_levels.push((path.to_string(), level));
+        }
+        Ok(Self {
+            root,
+            external_levels,
+        })
+    }
+
+    /// Load a project file and its external levels
+    pub fn load(path: &Path) -> Result<Self, LdtkError> {
+        let read = |path: &Path| {
+            std::fs::read_to_string(path)
+                .map_err(|e| LdtkError::FileError(format!("{}: {e}", path.display())))
+        };
+        let dir = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&read(path)?, &|relative| read(&dir.join(relative)))
+    }
+
+    /// Save the project and its external levels
+    pub fn save(&self, path: &Path) -> Result<(), LdtkError> {
+        let write = |path: &Path, value: &Value| {
+            let json = serde_json::to_string_pretty(value)
+                .map_err(|e| LdtkError::JsonError(e.to_string()))?;
+            std::fs::write(path, json)
+                .map_err(|e| LdtkError::FileError(format!("{}: {e}", path.display())))
+        };
+        write(path, &self.root)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for (relative, level) in &self.external_levels {
+            write(&dir.join(relative), level)?;
+        }
+        Ok(())
+    }
+
+    /// Levels with their layers, whether stored inline or in external files
+    fn levels(&self) -> Vec<&Value> {
+        let mut levels: Vec<&Value> = level_objects(&self.root)
+            .filter(|level| external_path(level).is_none())
+            .collect();
+        levels.extend(self.external_levels.iter().map(|(_, level)| level));
+        levels
+    }
+
+    fn levels_mut(&mut self) -> Vec<&mut Value> {
+        let mut levels = Vec::new();
+        for (key, value) in self.root.as_object_mut().into_iter().flatten() {
+            match (key.as_str(), value) {
+                ("levels", Value::Array(inline)) => levels.extend(inline),
+                ("worlds", Value::Array(worlds)) => {
+                    for world in worlds {
+                        if let Some(Value::Array(inline)) = world.get_mut("levels") {
+                            levels.extend(inline);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        levels.retain(|level| external_path(level).is_none());
+        levels.extend(self.external_levels.iter_mut().map(|(_, level)| level));
+        levels
+    }
+}
+
+/// Level objects of the project file, including those of multi-world projects
+fn level_objects(root: &Value) -> impl Iterator<Item = &Value> {
+    let worlds = root["worlds"].as_array().into_iter().flatten();
+    root["levels"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .chain(worlds.flat_map(|world| world["levels"].as_array().into_iter().flatten()))
+}
+
+fn external_path(level: &Value) -> Option<&str> {
+    level["externalRelPath"].as_str()
+}
+
+/// Colliders read from an LDtk project
+#[derive(Debug, Default)]
+pub struct LdtkImport {
+    /// Entity instances with a shape
+    pub shapes: Vec<(ImportedShape, LdtkEntity)>,
+    /// IntGrid values, with the world position of their layer's top-left corner
+    pub int_grids: Vec<(Vec2, TileLayer, ImportedBody, LdtkIntGrid)>,
+}
+
+fn number(value: &Value) -> f32 {
+    value.as_f64().unwrap_or_default() as f32
+}
+
+fn pair(value: &Value) -> Vec2 {
+    Vec2::new(number(&value[0]), number(&value[1]))
+}
+
+/// Fields of a level or entity as property text, skipping null and compound values
+fn field_properties(owner: &Value) -> Vec<(String, String)> {
+    let fields = owner["fieldInstances"].as_array().into_iter().flatten();
+    fields
+        .filter_map(|field| {
+            let name = field["__identifier"].as_str()?;
+            let text = match &field["__value"] {
+                Value::String(text) => text.clone(),
+                Value::Number(number) => number.to_string(),
+                Value::Bool(flag) => flag.to_string(),
+                _ => return None,
+            };
+            Some((name.to_string(), text))
+        })
+        .collect()
+}
+
+fn with_fields(
+    body: &ImportedBody,
+    fields: &[(String, String)],
+    owner: &str,
+) -> Result<ImportedBody, LdtkError> {
+    body.with_properties(fields.iter().map(|(n, v)| (n.as_str(), v.as_str())))
+        .map_err(|e| LdtkError::InvalidField(format!("{owner}: {e}")))
+}
+
+/// Parse a `vertices` field into contours of points
+pub(crate) fn parse_vertices(text: &str) -> Result<Vec<Vec<Vec2>>, String> {
+    let point = |pair: &str| {
+        let (x, y) = pair.split_once(',')?;
+        Some(Vec2::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+    };
+    text.split(';')
+        .filter(|contour| !contour.trim().is_empty())
+        .map(|contour| {
+            contour
+                .split_whitespace()
+                .map(|pair| point(pair).ok_or_else(|| format!("invalid vertex \"{pair}\"")))
+                .collect()
+        })
+        .collect()
+}
+
+/// Format contours as a `vertices` field
+pub(crate) fn format_vertices(contours: &[Vec<Vec2>]) -> String {
+    let round = |v: f32| (v * 100.0).round() / 100.0;
+    contours
+        .iter()
+        .map(|contour| {
+            contour
+                .iter()
+                .map(|p| format!("{},{}", round(p.x), round(p.y)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Read the colliders of every level in a project
+pub fn import_ldtk_project(
+    project: &LdtkProject,
+    scale: f32,
+    merge: TileMergeMode,
+) -> Result<LdtkImport, LdtkError> {
+    let to_world = |p: Vec2| Vec2::new(p.x * scale, -p.y * scale);
+    let mut import = LdtkImport::default();
+
+    for level in project.levels() {
+        let level_name = level["identifier"].as_str().unwrap_or("level");
+        let level_body = with_fields(
+            &ImportedBody::default(),
+            &field_properties(level),
+            level_name,
+        )?;
+        let level_origin = Vec2::new(number(&level["worldX"]), number(&level["worldY"]));
+
+        for layer in level["layerInstances"].as_array().into_iter().flatten() {
+            let origin = level_origin
+                + Vec2::new(
+                    number(&layer["__pxTotalOffsetX"]),
+                    number(&layer["__pxTotalOffsetY"]),
+                );
+            let grid_size = number(&layer["__gridSize"]);
+
+            if layer["__type"] == "IntGrid" {
+                let columns = layer["__cWid"].as_i64().unwrap_or_default().max(1);
+                let mut values: BTreeMap<i64, Vec<IVec2>> = BTreeMap::new();
+                let csv = layer["intGridCsv"].as_array().into_iter().flatten();
+                for (index, value) in csv.enumerate() {
+                    let value = value.as_i64().unwrap_or_default();
+                    if value != 0 {
+                        let (x, y) = (index as i64 % columns, index as i64 / columns);
+                        // Rows count down from the layer's top edge
+                        let cell = IVec2::new(x as i32, -(y as i32) - 1);
+                        values.entry(value).or_default().push(cell);
+                    }
+                }
+                for (value, mut cells) in values {
+                    cells.sort_by_key(|cell| (cell.y, cell.x));
+                    let mut tile_layer = TileLayer::new(grid_size * scale, merge);
+                    tile_layer.cells = cells;
+                    let link = LdtkIntGrid {
+                        layer_iid: layer["iid"].as_str().unwrap_or_default().to_string(),
+                        value,
+                        layer_origin: origin,
+                    };
+                    import
+                        .int_grids
+                        .push((to_world(origin), tile_layer, level_body.clone(), link));
+                }
+            }
+
+            for entity in layer["entityInstances"].as_array().into_iter().flatten() {
+                let iid = entity["iid"].as_str().unwrap_or_default();
+                let fields = field_properties(entity);
+                let field = |name: &str| {
+                    fields
+                        .iter()
+                        .find(|(field_name, _)| field_name == name)
+                        .map(|(_, value)| value.as_str())
+                };
+                let Some(collider) = field("collider") else {
+                    continue;
+                };
+                let owner = format!("{} {iid}", entity["__identifier"].as_str().unwrap_or(""));
+
+                let size = Vec2::new(number(&entity["width"]), number(&entity["height"]));
+                let top_left = origin + pair(&entity["px"]) - pair(&entity["__pivot"]) * size;
+                let degrees = match field("rotation") {
+                    Some(text) => text
+                        .parse::<f32>()
+                        .map_err(|_| LdtkError::InvalidField(format!("{owner}: rotation")))?,
+                    None => 0.0,
+                };
+                let contours = match field("vertices") {
+                    Some(text) => parse_vertices(text)
+                        .map_err(|e| LdtkError::InvalidField(format!("{owner}: {e}")))?,
+                    None => Vec::new(),
+                };
+                // Vertices are relative to the center, so only y flips
+                let local = |p: Vec2| Vec2::new(p.x * scale, -p.y * scale);
+                let half = size * 0.5;
+
+                let kind = match collider.to_ascii_lowercase().as_str() {
+                    "rectangle" => ImportedShapeKind::Rectangle(size * scale),
+                    "circle" => ImportedShapeKind::Circle(size.min_element() * 0.5 * scale),
+                    "capsule" => ImportedShapeKind::Capsule {
+                        radius: half.x * scale,
+                        length: (size.y - size.x).max(0.0) * scale,
+                    },
+                    "triangle" => {
+                        let points = match contours.first() {
+                            Some(points) if points.len() == 3 => points.clone(),
+                            _ => vec![
+                                Vec2::new(0.0, -half.y),
+                                Vec2::new(-half.x, half.y),
+                                Vec2::new(half.x, half.y),
+                            ],
+                        };
+                        let [a, mut b, mut c] =
+                            [local(points[0]), local(points[1]), local(points[2])];
+                        if signed_area(&[a, b, c]) < 0.0 {
+                            std::mem::swap(&mut b, &mut c);
+                        }
+                        ImportedShapeKind::Triangle([a, b, c])
+                    }
+                    "polygon" => {
+                        let contours = if contours.is_empty() {
+                            vec![vec![
+                                Vec2::new(-half.x, -half.y),
+                                Vec2::new(half.x, -half.y),
+                                Vec2::new(half.x, half.y),
+                                Vec2::new(-half.x, half.y),
+                            ]]
+                        } else {
+                            contours
+                        };
+                        let contours = contours
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, contour)| {
+                                let mut contour: Vec<Vec2> =
+                                    contour.into_iter().map(local).collect();
+                                // Outline counter-clockwise, holes clockwise
+                                if (signed_area(&contour) < 0.0) == (index == 0) {
+                                    contour.reverse();
+                                }
+                                contour
+                            })
+                            .collect();
+                        ImportedShapeKind::Polygon(contours)
+                    }
+                    other => {
+                        return Err(LdtkError::InvalidField(format!(
+                            "{owner}: unknown collider \"{other}\""
+                        )));
+                    }
+                };
+
+                let shape = ImportedShape {
+                    kind,
+                    position: to_world(top_left + half),
+                    rotation: -degrees.to_radians(),
+                    body: with_fields(&level_body, &fields, &owner)?,
+                };
+                let link = LdtkEntity {
+                    iid: iid.to_string(),
+                    layer_origin: origin,
+                };
+                import.shapes.push((shape, link));
+            }
+        }
+    }
+    Ok(import)
+}
+
+/// Value written into an entity field
+#[derive(Debug, Clone, PartialEq)]
+pub enum LdtkFieldValue {
+    Text(String),
+    Number(f32),
+}
+
+/// Edited state of an imported entity, in LDtk pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct LdtkEntityEdit {
+    pub iid: String,
+    /// Center of the entity relative to its layer
+    pub center: Vec2,
+    pub size: Vec2,
+    /// New field values; fields the entity doesn't define, or of another type, are left alone
+    pub fields: Vec<(&'static str, LdtkFieldValue)>,
+}
+
+/// Write an edited entity into the project, returning whether the entity was found
+pub fn write_ldtk_entity(project: &mut LdtkProject, edit: &LdtkEntityEdit) -> bool {
+    for level in project.levels_mut() {
+        let layers = level
+            .get_mut("layerInstances")
+            .and_then(Value::as_array_mut);
+        for layer in layers.into_iter().flatten() {
+            let grid_size = layer["__gridSize"].as_i64().unwrap_or(1).max(1);
+            let entities = layer
+                .get_mut("entityInstances")
+                .and_then(Value::as_array_mut);
+            let Some(entity) = entities
+                .into_iter()
+                .flatten()
+                .find(|entity| entity["iid"] == edit.iid.as_str())
+            else {
+                continue;
+            };
+
+            let size = edit.size.round().max(Vec2::ONE);
+            let old_px = pair(&entity["px"]).as_ivec2();
+            let px = (edit.center - size * 0.5 + pair(&entity["__pivot"]) * size)
+                .round()
+                .as_ivec2();
+            entity["px"] = json!([px.x, px.y]);
+            entity["__grid"] = json!([
+                (px.x as i64).div_euclid(grid_size),
+                (px.y as i64).div_euclid(grid_size)
+            ]);
+            entity["width"] = json!(size.x as i64);
+            entity["height"] = json!(size.y as i64);
+            // World positions only exist in some layouts, so shift whatever is there
+            for (key, delta) in [("__worldX", px.x - old_px.x), ("__worldY", px.y - old_px.y)] {
+                if let Some(world) = entity[key].as_i64() {
+                    entity[key] = json!(world + delta as i64);
+                }
+            }
+
+            let fields = entity
+                .get_mut("fieldInstances")
+                .and_then(Value::as_array_mut);
+            for field in fields.into_iter().flatten() {
+                let Some((_, value)) = edit
+                    .fields
+                    .iter()
+                    .find(|(name, _)| field["__identifier"] == *name)
+                else {
+                    continue;
+                };
+                let (value, editor_value) = match (value, field["__type"].as_str()) {
+                    (LdtkFieldValue::Text(text), Some("String" | "Multilines")) => {
+                        (json!(text), json!({ "id": "V_String", "params": [text] }))
+                    }
+                    (LdtkFieldValue::Number(number), Some("Float")) => {
+                        let number = (*number as f64 * 1e4).round() / 1e4;
+                        (
+                            json!(number),
+                            json!({ "id": "V_Float", "params": [number] }),
+                        )
+                    }
+                    (LdtkFieldValue::Number(number), Some("Int")) => {
+                        let number = number.round() as i64;
+                        (json!(number), json!({ "id": "V_Int", "params": [number] }))
+                    }
+                    _ => continue,
+                };
+                field["__value"] = value;
+                field["realEditorValues"] = json!([editor_value]);
+            }
+            return true;
+        }
+    }
+    false
+}
+
+/// Repaint one value of an IntGrid layer with the given cells
+///
+/// Cells are LDtk grid coordinates with rows counting down. Cells that held the value before
+/// are cleared first. Returns the number of cells dropped for falling outside the layer, or
+/// `None` if the layer wasn't found.
+pub fn write_ldtk_int_grid(
+    project: &mut LdtkProject,
+    layer_iid: &str,
+    value: i64,
+    cells: &[IVec2],
+) -> Option<usize> {
+    for level in project.levels_mut() {
+        let layers = level
+            .get_mut("layerInstances")
+            .and_then(Value::as_array_mut);
+        for layer in layers.into_iter().flatten() {
+            if layer["iid"] != layer_iid {
+                continue;
+            }
+            let columns = layer["__cWid"].as_i64().unwrap_or_default() as i32;
+            let rows = layer["__cHei"].as_i64().unwrap_or_default() as i32;
+            let csv = layer.get_mut("intGridCsv")?.as_array_mut()?;
+            csv.resize((columns * rows).max(0) as usize, json!(0));
+            for entry in csv.iter_mut() {
+                if *entry == value {
+                    *entry = json!(0);
+                }
+            }
+            let mut dropped = 0;
+            for cell in cells {
+                if (0..columns).contains(&cell.x) && (0..rows).contains(&cell.y) {
+                    csv[(cell.y * columns + cell.x) as usize] = json!(value);
+                } else {
+                    dropped += 1;
+                }
+            }
+            return Some(dropped);
+        }
+    }
+    None
+}
+
+/// Shape name, size and vertices of a collider as written to an entity, in world units
+fn exported_shape(
+    collider_type: ColliderType,
+    collider: &Collider,
+    source: Option<&PolygonSource>,
+    scale: Vec2,
+) -> Option<(&'static str, Vec2, Option<Vec<Vec<Vec2>>>)> {
+    let vec = |p: &avian2d::parry::math::Point<f32>| Vec2::new(p.x, p.y);
+    let with_vertices = |name, contours: Vec<Vec<Vec2>>| {
+        let extent = contours
+            .iter()
+            .flatten()
+            .fold(Vec2::ZERO, |extent, p| extent.max(p.abs()));
+        Some((name, extent * 2.0, Some(contours)))
+    };
+    match (collider_type, collider.shape_scaled().as_typed_shape()) {
+        (ColliderType::Rectangle, TypedShape::Cuboid(cuboid)) => Some((
+            "rectangle",
+            Vec2::new(cuboid.half_extents.x, cuboid.half_extents.y) * 2.0,
+            None,
+        )),
+        (ColliderType::Circle, TypedShape::Ball(ball)) => {
+            Some(("circle", Vec2::splat(ball.radius * 2.0), None))
+        }
+        (ColliderType::Capsule, TypedShape::Capsule(capsule)) => {
+            let length = vec(&capsule.segment.a).distance(vec(&capsule.segment.b));
+            let width = capsule.radius * 2.0;
+            Some(("capsule", Vec2::new(width, length + width), None))
+        }
+        (ColliderType::Triangle, TypedShape::Triangle(triangle)) => with_vertices(
+            "triangle",
+            vec![vec![vec(&triangle.a), vec(&triangle.b), vec(&triangle.c)]],
+        ),
+        (ColliderType::Polygon, shape) => match (source, shape) {
+            (Some(source), _) => with_vertices(
+                "polygon",
+                source
+                    .contours
+                    .iter()
+                    .map(|contour| contour.iter().map(|&p| p * scale).collect())
+                    .collect(),
+            ),
+            (None, TypedShape::ConvexPolygon(polygon)) => {
+                with_vertices("polygon", vec![polygon.points().iter().map(vec).collect()])
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Import LDtk projects
+fn handle_ldtk_import(
+    mut events: EventReader<LdtkImportEvent>,
+    mut commands: Commands,
+    mut state: ResMut<ColliderCreationState>,
+    mut link: ResMut<LdtkProjectLink>,
+    settings: Res<LdtkSettings>,
+    properties: Res<CreationProperties>,
+) {
+    for event in events.read() {
+        let path = match event {
+            LdtkImportEvent::FromDialog => {
+                let dialog = FileDialog::new()
+                    .add_filter("LDtk projects", &["ldtk"])
+                    .add_filter("All files", &["*"])
+                    .set_title("Import LDtk Project");
+                match dialog.pick_file() {
+                    Some(path) => path,
+                    None => continue,
+                }
+            }
+            LdtkImportEvent::FromPath(path) => path.clone(),
+        };
+
+        let import = LdtkProject::load(&path)
+            .and_then(|project| import_ldtk_project(&project, settings.scale, settings.merge));
+        match import {
+            Ok(import) => {
+                let shapes = import.shapes.len();
+                let layers = import.int_grids.len();
+                for (shape, entity_link) in import.shapes {
+                    if let Some(entity) =
+                        spawn_imported_shape(&mut commands, shape, properties.color)
+                    {
+                        commands.entity(entity).insert(entity_link);
+                        state.created_colliders.push(entity);
+                    }
+                }
+                for (origin, layer, body, grid_link) in import.int_grids {
+                    let entity = spawn_imported_tile_layer(
+                        &mut commands,
+                        origin,
+                        layer,
+                        &body,
+                        properties.color,
+                    );
+                    commands.entity(entity).insert(grid_link);
+                    state.created_colliders.push(entity);
+                }
+                info!(
+                    "Imported {} entities and {} IntGrid layers from {:?}",
+                    shapes, layers, path
+                );
+                link.path = Some(path);
+            }
+            Err(e) => {
+                error!("LDtk import failed: {}", e);
+            }
+        }
+    }
+}
+
+/// Write linked bodies back into the project they came from
+fn handle_ldtk_export(
+    mut events: EventReader<LdtkExportEvent>,
+    link: Res<LdtkProjectLink>,
+    settings: Res<LdtkSettings>,
+    entities: Query<(
+        &LdtkEntity,
+        &Transform,
+        &Collider,
+        &ColliderType,
+        Option<&PolygonSource>,
+        Option<&RigidBody>,
+        Option<&Friction>,
+        Option<&Restitution>,
+    )>,
+    int_grids: Query<(&LdtkIntGrid, &Transform, &TileLayer)>,
+) {
+    for _ in events.read() {
+        let Some(path) = &link.path else {
+            warn!("No LDtk project imported to write back to");
+            continue;
+        };
+        let mut project = match LdtkProject::load(path) {
+            Ok(project) => project,
+            Err(e) => {
+                error!("LDtk write-back failed: {}", e);
+                continue;
+            }
+        };
+        let s = settings.scale;
+        let to_ldtk = |p: Vec2| Vec2::new(p.x / s, -p.y / s);
+
+        let mut written = 0;
+        for (ldtk, transform, collider, collider_type, source, rigid_body, friction, restitution) in
+            &entities
+        {
+            let shape =
+                exported_shape(*collider_type, collider, source, transform.scale.truncate());
+            let Some((name, size, contours)) = shape else {
+                warn!(
+                    "Skipping LDtk entity {} with an unsupported shape",
+                    ldtk.iid
+                );
+                continue;
+            };
+            let angle = transform.rotation.to_euler(EulerRot::ZYX).0;
+            let mut fields = vec![
+                ("collider", LdtkFieldValue::Text(name.to_string())),
+                ("rotation", LdtkFieldValue::Number(-angle.to_degrees())),
+            ];
+            if let Some(contours) = contours {
+                let contours: Vec<Vec<Vec2>> = contours
+                    .iter()
+                    .map(|contour| contour.iter().map(|&p| to_ldtk(p)).collect())
+                    .collect();
+                fields.push(("vertices", LdtkFieldValue::Text(format_vertices(&contours))));
+            }
+            if let Some(rigid_body) = rigid_body {
+                let kind = match rigid_body {
+                    RigidBody::Dynamic => "dynamic",
+                    RigidBody::Kinematic => "kinematic",
+                    RigidBody::Static => "static",
+                };
+                fields.push(("rigid_body", LdtkFieldValue::Text(kind.to_string())));
+            }
+            if let Some(friction) = friction {
+                let coefficient = friction.dynamic_coefficient;
+                fields.push(("friction", LdtkFieldValue::Number(coefficient)));
+            }
+            if let Some(restitution) = restitution {
+                let coefficient = restitution.coefficient;
+                fields.push(("restitution", LdtkFieldValue::Number(coefficient)));
+            }
+
+            let edit = LdtkEntityEdit {
+                iid: ldtk.iid.clone(),
+                center: to_ldtk(transform.translation.truncate()) - ldtk.layer_origin,
+                size: size / s,
+                fields,
+            };
+            if write_ldtk_entity(&mut project, &edit) {
+                written += 1;
+            } else {
+                warn!("LDtk entity {} no longer exists in the project", ldtk.iid);
+            }
+        }
+
+        for (grid, transform, layer) in &int_grids {
+            // A moved tile layer shifts its cells by whole grid steps
+            let origin = Vec2::new(grid.layer_origin.x * s, -grid.layer_origin.y * s);
+            let shift = ((transform.translation.truncate() - origin) / layer.cell_size)
+                .round()
+                .as_ivec2();
+            let cells: Vec<IVec2> = layer
+                .cells
+                .iter()
+                .map(|&cell| {
+                    let cell = cell + shift;
+                    IVec2::new(cell.x, -cell.y - 1)
+                })
+                .collect();
+            match write_ldtk_int_grid(&mut project, &grid.layer_iid, grid.value, &cells) {
+                Some(0) => written += 1,
+                Some(dropped) => {
+                    warn!(
+                        "Dropped {} cells outside IntGrid layer {}",
+                        dropped, grid.layer_iid
+                    );
+                    written += 1;
+                }
+                None => warn!(
+                    "IntGrid layer {} no longer exists in the project",
+                    grid.layer_iid
+                ),
+            }
+        }
+
+        match project.save(path) {
+            Ok(()) => info!("Wrote {} bodies back to {:?}", written, path),
+            Err(e) => error!("LDtk write-back failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT: &str = r#"{
+        "jsonVersion": "1.5.3",
+        "levels": [{
+            "identifier": "Level_0",
+            "iid": "level-0",
+            "worldX": 0,
+            "worldY": 0,
+            "externalRelPath": null,
+            "fieldInstances": [{ "__identifier": "friction", "__type": "Float", "__value": 0.5 }],
+            "layerInstances": [
+                {
+                    "__identifier": "Entities",
+                    "__type": "Entities",
+                    "__gridSize": 16,
+                    "__pxTotalOffsetX": 0,
+                    "__pxTotalOffsetY": 0,
+                    "iid": "layer-entities",
+                    "entityInstances": [
+                        {
+                            "__identifier": "Crate",
+                            "iid": "crate",
+                            "__pivot": [0.5, 1],
+                            "__grid": [2, 2],
+                            "__worldX": 40,
+                            "__worldY": 48,
+                            "px": [40, 48],
+                            "width": 16,
+                            "height": 32,
+                            "fieldInstances": [
+                                { "__identifier": "collider", "__type": "String", "__value": "rectangle", "realEditorValues": [] },
+                                { "__identifier": "rotation", "__type": "Float", "__value": 90, "realEditorValues": [] },
+                                { "__identifier": "rigid_body", "__type": "String", "__value": "dynamic", "realEditorValues": [] }
+                            ]
+                        },
+                        {
+                            "__identifier": "Ramp",
+                            "iid": "ramp",
+                            "__pivot": [0, 0],
+                            "px": [0, 0],
+                            "width": 20,
+                            "height": 10,
+                            "fieldInstances": [
+                                { "__identifier": "collider", "__type": "String", "__value": "polygon" },
+                                { "__identifier": "vertices", "__type": "String", "__value": "-10,5 10,5 10,-5" }
+                            ]
+                        },
+                        { "__identifier": "Spawn", "iid": "spawn", "__pivot": [0, 0], "px": [0, 0], "width": 8, "height": 8, "fieldInstances": [] }
+                    ]
+                },
+                {
+                    "__identifier": "Collisions",
+                    "__type": "IntGrid",
+                    "__cWid": 3,
+                    "__cHei": 2,
+                    "__gridSize": 16,
+                    "__pxTotalOffsetX": 0,
+                    "__pxTotalOffsetY": 0,
+                    "iid": "layer-grid",
+                    "intGridCsv": [1, 1, 0, 0, 2, 1],
+                    "entityInstances": []
+                }
+            ]
+        }]
+    }"#;
+
+    fn project() -> LdtkProject {
+        LdtkProject::parse(PROJECT, &|path| Err(LdtkError::FileError(path.to_string()))).unwrap()
+    }
+
+    #[test]
+    fn test_ldtk_import() {
+        let import = import_ldtk_project(&project(), 2.0, TileMergeMode::Outline).unwrap();
+
+        // Entities without a collider field are left out
+        assert_eq!(import.shapes.len(), 2);
+        let (crate_shape, crate_link) = &import.shapes[0];
+        assert_eq!(crate_link.iid, "crate");
+        assert_eq!(
+            crate_shape.kind,
+            ImportedShapeKind::Rectangle(Vec2::new(32.0, 64.0))
+        );
+        // Bottom-center pivot at (40, 48) puts the center at (40, 32)
+        assert_eq!(crate_shape.position, Vec2::new(80.0, -64.0));
+        assert!((crate_shape.rotation + std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+        assert_eq!(crate_shape.body.rigid_body, RigidBody::Dynamic);
+        assert_eq!(crate_shape.body.friction, Some(0.5));
+
+        let (ramp, _) = &import.shapes[1];
+        assert_eq!(ramp.position, Vec2::new(20.0, -10.0));
+        let ImportedShapeKind::Polygon(contours) = &ramp.kind else {
+            panic!("expected a polygon, got {:?}", ramp.kind);
+        };
+        assert!(signed_area(&contours[0]) > 0.0);
+        assert!(contours[0].contains(&Vec2::new(20.0, 10.0)));
+
+        // One tile layer for each value, rows counting down from the top edge
+        let values: Vec<_> = import
+            .int_grids
+            .iter()
+            .map(|(origin, layer, _, link)| (*origin, link.value, layer.cells.clone()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                (
+                    Vec2::ZERO,
+                    1,
+                    vec![IVec2::new(2, -2), IVec2::new(0, -1), IVec2::new(1, -1)]
+                ),
+                (Vec2::ZERO, 2, vec![IVec2::new(1, -2)]),
+            ]
+        );
+        assert_eq!(import.int_grids[0].1.cell_size, 32.0);
+        assert_eq!(import.int_grids[0].1.merge, TileMergeMode::Outline);
+    }
+
+    #[test]
+    fn test_ldtk_write_back() {
+        let mut project = project();
+        let edit = LdtkEntityEdit {
+            iid: "crate".to_string(),
+            center: Vec2::new(56.0, 40.0),
+            size: Vec2::new(16.0, 16.0),
+            fields: vec![
+                ("rotation", LdtkFieldValue::Number(45.0)),
+                ("rigid_body", LdtkFieldValue::Text("static".to_string())),
+                ("friction", LdtkFieldValue::Number(0.1)),
+            ],
+        };
+        assert!(write_ldtk_entity(&mut project, &edit));
+        assert_eq!(
+            write_ldtk_int_grid(
+                &mut project,
+                "layer-grid",
+                1,
+                &[IVec2::new(2, 1), IVec2::new(5, 0)]
+            ),
+            Some(1)
+        );
+
+        let layers = &project.root["levels"][0]["layerInstances"];
+        let entity = &layers[0]["entityInstances"][0];
+        assert_eq!(entity["px"], json!([56, 48]));
+        assert_eq!(entity["__grid"], json!([3, 3]));
+        assert_eq!(entity["__worldX"], json!(56));
+        assert_eq!(entity["height"], json!(16));
+        let fields = &entity["fieldInstances"];
+        assert_eq!(fields[1]["__value"], json!(45.0));
+        assert_eq!(fields[1]["realEditorValues"][0]["id"], "V_Float");
+        assert_eq!(fields[2]["__value"], "static");
+        // Cells of other values stay put
+        assert_eq!(layers[1]["intGridCsv"], json!([0, 0, 0, 0, 2, 1]));
+
+        let square = vec![vec![Vec2::new(-1.5, 2.0), Vec2::new(3.0, -0.25)]];
+        assert_eq!(parse_vertices(&format_vertices(&square)), Ok(square));
+    }
+}
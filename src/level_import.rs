@@ -0,0 +1,246 @@
+//! Shared pieces of the level editor importers
+//!
+//! The Tiled and LDtk importers read their files into [`ImportedShape`]s and tile layers in world
+//! space, then spawn them through the functions here so imported bodies look and behave like
+//! ones drawn in the editor.
+//!
+//! Both formats attach custom properties to layers and objects, read with the same names:
+//! - `rigid_body`: `static` (default), `dynamic` or `kinematic`
+//! - `friction`, `restitution`: coefficients
+//! - `collision_layers`, `collision_filters`: layer bitmasks
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{ColliderType, PolygonSource, Selectable, TileLayer};
+
+/// Radius of the sensors created for point objects, in world units
+pub const POINT_RADIUS: f32 = 4.0;
+
+/// Body settings read from custom properties
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedBody {
+    /// Body type, static unless a `rigid_body` property says otherwise
+    pub rigid_body: RigidBody,
+    /// Friction coefficient; `None` keeps Avian's default
+    pub friction: Option<f32>,
+    /// Restitution coefficient; `None` keeps Avian's default
+    pub restitution: Option<f32>,
+    /// Collision layers the body belongs to
+    pub memberships: Option<u32>,
+    /// Collision layers the body interacts with
+    pub filters: Option<u32>,
+}
+
+impl Default for ImportedBody {
+    fn default() -> Self {
+        Self {
+            rigid_body: RigidBody::Static,
+            friction: None,
+            restitution: None,
+            memberships: None,
+            filters: None,
+        }
+    }
+}
+
+impl ImportedBody {
+    /// Copy of these settings with custom properties applied
+    ///
+    /// Properties with other names are ignored. Returns a message naming the offending property
+    /// when a value cannot be read.
+    pub fn with_properties<'a>(
+        &self,
+        properties: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, String> {
+        let mut body = self.clone();
+        for (name, value) in properties {
+            let value = value.trim();
+            let invalid = || format!("invalid {name} \"{value}\"");
+            match name {
+                "rigid_body" => {
+                    body.rigid_body = match value.to_ascii_lowercase().as_str() {
+                        "static" => RigidBody::Static,
+                        "dynamic" => RigidBody::Dynamic,
+                        "kinematic" => RigidBody::Kinematic,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "friction" => body.friction = Some(value.parse().map_err(|_| invalid())?),
+                "restitution" => body.restitution = Some(value.parse().map_err(|_| invalid())?),
+                "collision_layers" => {
+                    body.memberships = Some(value.parse().map_err(|_| invalid())?)
+                }
+                "collision_filters" => body.filters = Some(value.parse().map_err(|_| invalid())?),
+                _ => {}
+            }
+        }
+        Ok(body)
+    }
+}
+
+/// Shape of an imported collider
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportedShapeKind {
+    /// Rectangle centered on the body
+    Rectangle(Vec2),
+    /// Circle centered on the body
+    Circle(f32),
+    /// Vertical capsule centered on the body
+    Capsule { radius: f32, length: f32 },
+    /// Triangle around the body
+    Triangle([Vec2; 3]),
+    /// Outlines around the body, counter-clockwise for solids and clockwise for holes
+    Polygon(Vec<Vec<Vec2>>),
+    /// Open polyline around the body
+    Polyline(Vec<Vec2>),
+    /// Position marker
+    Point,
+}
+
+/// Collider read from a level file, in world space
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedShape {
+    pub kind: ImportedShapeKind,
+    /// World position of the body
+    pub position: Vec2,
+    /// Counter-clockwise rotation in radians
+    pub rotation: f32,
+    pub body: ImportedBody,
+}
+
+impl ImportedShape {
+    /// Polygon body from a world-space outline, positioned at the center of its bounds
+    pub fn polygon(mut outline: Vec<Vec2>, body: ImportedBody) -> Self {
+        // Level editors are y-down, so outlines usually arrive clockwise
+        if crate::collider_tools::polygon::signed_area(&outline) < 0.0 {
+            outline.reverse();
+        }
+        let center = bounds_center(&outline);
+        Self {
+            kind: ImportedShapeKind::Polygon(vec![outline.iter().map(|&p| p - center).collect()]),
+            position: center,
+            rotation: 0.0,
+            body,
+        }
+    }
+}
+
+/// Center of the bounding box of some points
+pub fn bounds_center(points: &[Vec2]) -> Vec2 {
+    let (min, max) = points
+        .iter()
+        .fold((Vec2::MAX, Vec2::MIN), |(min, max), &p| {
+            (min.min(p), max.max(p))
+        });
+    (min + max) * 0.5
+}
+
+/// Debug rendering shared by imported bodies
+fn imported_debug_render(color: Color) -> DebugRender {
+    DebugRender {
+        collider_color: Some(color),
+        axis_lengths: Some(avian2d::math::Vector::new(0.8, 0.8)),
+        ..default()
+    }
+}
+
+/// Spawn an imported collider
+///
+/// Returns `None` for polygons without area, which are skipped.
+pub fn spawn_imported_shape(
+    commands: &mut Commands,
+    shape: ImportedShape,
+    color: Color,
+) -> Option<Entity> {
+    let transform = Transform::from_translation(shape.position.extend(0.0))
+        .with_rotation(Quat::from_rotation_z(shape.rotation));
+    let sensor = shape.kind == ImportedShapeKind::Point;
+    let mut source = None;
+    let (collider, collider_type) = match shape.kind {
+        ImportedShapeKind::Rectangle(size) => {
+            (Collider::rectangle(size.x, size.y), ColliderType::Rectangle)
+        }
+        ImportedShapeKind::Circle(radius) => (Collider::circle(radius), ColliderType::Circle),
+        ImportedShapeKind::Capsule { radius, length } => {
+            (Collider::capsule(radius, length), ColliderType::Capsule)
+        }
+        ImportedShapeKind::Triangle([a, b, c]) => {
+            (Collider::triangle(a, b, c), ColliderType::Triangle)
+        }
+        ImportedShapeKind::Polygon(contours) => {
+            let polygon = PolygonSource::new(contours);
+            let Some(collider) = polygon.collider() else {
+                warn!("Skipping imported polygon without area");
+                return None;
+            };
+            source = Some(polygon);
+            (collider, ColliderType::Polygon)
+        }
+        ImportedShapeKind::Polyline(points) => {
+            (Collider::polyline(points, None), ColliderType::Polygon)
+        }
+        ImportedShapeKind::Point => (Collider::circle(POINT_RADIUS), ColliderType::Circle),
+    };
+
+    let mut entity_commands = commands.spawn((
+        transform,
+        collider,
+        collider_type,
+        imported_debug_render(color),
+        Selectable,
+        Pickable::default(),
+    ));
+    if let Some(source) = source {
+        entity_commands.insert(source);
+    }
+    if sensor {
+        entity_commands.insert(Sensor);
+    }
+    insert_imported_body(&mut entity_commands, &shape.body);
+    Some(entity_commands.id())
+}
+
+/// Spawn an imported tile layer with its origin at `origin`
+///
+/// The layer is spawned even when empty, so it can be painted into later.
+pub fn spawn_imported_tile_layer(
+    commands: &mut Commands,
+    origin: Vec2,
+    layer: TileLayer,
+    body: &ImportedBody,
+    color: Color,
+) -> Entity {
+    let collider = layer.collider();
+    let mut entity_commands = commands.spawn((
+        Transform::from_translation(origin.extend(0.0)),
+        layer,
+        ColliderType::Polygon,
+        imported_debug_render(color),
+        Selectable,
+        Pickable::default(),
+    ));
+    if let Some(collider) = collider {
+        entity_commands.insert(collider);
+    }
+    insert_imported_body(&mut entity_commands, body);
+    entity_commands.id()
+}
+
+/// Insert the rigid body and the material and layer overrides of a body
+fn insert_imported_body(entity_commands: &mut EntityCommands, body: &ImportedBody) {
+    entity_commands.insert(body.rigid_body);
+    if let Some(friction) = body.friction {
+        entity_commands.insert(Friction::new(friction));
+    }
+    if let Some(restitution) = body.restitution {
+        entity_commands.insert(Restitution::new(restitution));
+    }
+    if body.memberships.is_some() || body.filters.is_some() {
+        let default = CollisionLayers::default();
+        entity_commands.insert(CollisionLayers::from_bits(
+            body.memberships.unwrap_or(default.memberships.0),
+            body.filters.unwrap_or(default.filters.0),
+        ));
+    }
+}
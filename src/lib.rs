@@ -3,20 +3,39 @@ use bevy::prelude::*;
 
 pub mod camera;
 pub mod collider_tools;
+pub mod document;
 pub mod grid;
 pub mod interaction_standards;
+pub mod ldtk;
+pub mod level_import;
+pub mod prefab;
+pub mod rube_export;
+pub mod rust_export;
 pub mod scene_export_import;
 pub mod selection;
+pub mod sub_scene;
+pub mod svg_export;
+pub mod svg_import;
+pub mod tiled_import;
 pub mod transform_gizmos;
 pub mod ui;
 pub mod utils;
 
 pub use camera::*;
 pub use collider_tools::*;
+pub use document::*;
 pub use grid::*;
 pub use interaction_standards::*;
+pub use ldtk::*;
+pub use prefab::*;
+pub use rube_export::*;
+pub use rust_export::*;
 pub use scene_export_import::*;
 pub use selection::*;
+pub use sub_scene::*;
+pub use svg_export::*;
+pub use svg_import::*;
+pub use tiled_import::*;
 pub use transform_gizmos::*;
 pub use ui::*;
 pub use utils::*;
@@ -60,7 +79,19 @@ impl Plugin for AvianEditorPlugin {
             SelectionPlugin,
             InteractionStandardsPlugin,
             ColliderToolsPluginGroup,
-            SceneExportImportPlugin,
+            // Scene files, level imports and exports
+            (
+                SceneExportImportPlugin,
+                DocumentPlugin,
+                PrefabPlugin,
+                SubScenePlugin,
+                TiledImportPlugin,
+                LdtkPlugin,
+                SvgImportPlugin,
+                SvgExportPlugin,
+                RustExportPlugin,
+                RubeExportPlugin,
+            ),
             InfiniteGridPlugin,
             CameraControllerPlugin,
             TransformGizmoPlugin,
@@ -0,0 +1,1076 @@
+//! Prefabs: reusable groups of bodies, anchors and joints
+//!
+//! A prefab is a scene file holding bodies, their anchors and the joints between them, with body
+//! transforms relative to the prefab origin. Every entity in it carries a [`PrefabKey`] that is
+//! kept when the prefab is saved again, so instances can refer to their members across edits.
+//!
+//! Placing a prefab spawns an instance root with a [`PrefabInstance`] and a [`Transform`], and
+//! spawns the members from the file relative to it. Members link back to the root with
+//! [`PrefabMemberOf`]. Joints are keyed through their visualization, which keeps its identity
+//! when the physics joint is rebuilt. Moving the root moves its members. Scene export writes the
+//! root instead of the members, so the scene file refers to the prefab rather than copying it.
+//!
+//! Any member component that differs from the prefab is an override. Overrides are collected
+//! when the scene is exported and before a prefab changes, kept on the instance as RON, and
+//! applied on top of the prefab whenever the instance is spawned. Applying an instance to its
+//! prefab writes the instance to the file and respawns every other instance with its own
+//! overrides. Deleted or added members are not tracked; unpack an instance to change its makeup.
+
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use avian2d::prelude::*;
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+use bevy::reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use bevy::reflect::{FromReflect, ReflectFromReflect, ReflectRef, TypeRegistration, TypeRegistry};
+use bevy::scene::{DynamicEntity, DynamicScene, DynamicSceneBuilder, SceneFilter};
+use rfd::FileDialog;
+use serde::de::DeserializeSeed;
+
+use crate::ColliderCreationState;
+use crate::collider_tools::debug_render::anchor::AnchorPoint;
+use crate::collider_tools::debug_render::joint::{
+    AnchorUsedBy, JointConfig, JointVisualization, JointVisualizationOf, UsesAnchors, VisualizedBy,
+    joint_relationships,
+};
+use crate::collider_tools::utils::get_anchor_local_position;
+use crate::scene_export_import::{SceneError, find_reflected, parse_scene};
+use crate::selection::{EditorSelection, Selectable};
+use crate::sub_scene::SubSceneMemberOf;
+
+/// Prefab plugin
+pub struct PrefabPlugin;
+
+impl Plugin for PrefabPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PrefabLibrary>()
+            .register_type::<PrefabInstance>()
+            .register_type::<PrefabOverride>()
+            .register_type::<PrefabKey>()
+            .register_type::<PrefabMemberOf>()
+            .add_event::<PrefabEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_prefab_events,
+                    spawn_prefab_instances,
+                    follow_prefab_instances,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Placed copy of a prefab
+#[derive(Component, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Component, Default)]
+#[require(Transform)]
+pub struct PrefabInstance {
+    /// Prefab file the members are spawned from
+    pub source: String,
+    /// Member values that differ from the prefab
+    pub overrides: Vec<PrefabOverride>,
+}
+
+/// Component value of one instance member that differs from the prefab
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct PrefabOverride {
+    /// [`PrefabKey`] of the member
+    pub key: u32,
+    /// Type path of the component
+    pub component: String,
+    /// Component value in RON
+    pub value: String,
+}
+
+/// Identity of an entity within its prefab
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub struct PrefabKey(pub u32);
+
+/// Links a body, anchor or joint visualization to the prefab instance it belongs to
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+#[relationship(relationship_target = PrefabMembers)]
+pub struct PrefabMemberOf(pub Entity);
+
+/// Members of a prefab instance, despawned together with it.
+/// This component is updated reactively and should not be modified directly.
+#[derive(Component, Debug, Default)]
+#[relationship_target(relationship = PrefabMemberOf, linked_spawn)]
+pub struct PrefabMembers(Vec<Entity>);
+
+impl PrefabMembers {
+    /// Get the member entities
+    pub fn get(&self) -> &[Entity] {
+        &self.0
+    }
+}
+
+/// Root transform the members of an instance were placed with, present once they are spawned
+#[derive(Component, Debug, Clone, Copy)]
+struct PrefabPlacement(Transform);
+
+/// Prefab files read so far
+#[derive(Resource, Debug, Default)]
+pub struct PrefabLibrary {
+    /// Scene RON of each prefab, by path
+    pub prefabs: BTreeMap<String, String>,
+}
+
+impl PrefabLibrary {
+    /// Contents of a prefab, read from disk the first time it is used
+    pub fn load(&mut self, source: &str) -> Result<String, SceneError> {
+        if let Some(text) = self.prefabs.get(source) {
+            return Ok(text.clone());
+        }
+        let text =
+            std::fs::read_to_string(source).map_err(|e| SceneError::FileError(e.to_string()))?;
+        self.prefabs.insert(source.to_string(), text.clone());
+        Ok(text)
+    }
+}
+
+/// Prefab command
+#[derive(Event, Debug, Clone)]
+pub enum PrefabEvent {
+    /// Save bodies with their anchors and joints as a prefab (file dialog) and make them an
+    /// instance of it
+    Create(Vec<Entity>),
+    /// Place an instance at the view center, picking the file with a dialog when not given
+    Place(Option<PathBuf>),
+    /// Write an instance to its prefab and respawn the other instances
+    ApplyToPrefab(Entity),
+    /// Drop the overrides of an instance
+    Revert(Entity),
+    /// Turn the members of an instance into plain entities
+    Unpack(Entity),
+    /// Read a prefab file again and respawn its instances
+    Reload(String),
+}
+
+/// Handle prefab commands
+fn handle_prefab_events(world: &mut World) {
+    let events: Vec<PrefabEvent> = world
+        .resource_mut::<Events<PrefabEvent>>()
+        .drain()
+        .collect();
+
+    for event in events {
+        match event {
+            PrefabEvent::Create(entities) => {
+                let Some(path) = FileDialog::new()
+                    .add_filter("Prefab files", &["ron"])
+                    .add_filter("All files", &["*"])
+                    .set_title("Save Prefab As")
+                    .save_file()
+                else {
+                    continue;
+                };
+                match create_prefab(world, &entities, &path) {
+                    Ok(root) => info!("Prefab saved to {:?} with instance {}", path, root),
+                    Err(e) => error!("Prefab creation failed: {}", e),
+                }
+            }
+            PrefabEvent::Place(path) => {
+                let Some(path) = path.or_else(|| {
+                    FileDialog::new()
+                        .add_filter("Prefab files", &["ron"])
+                        .add_filter("All files", &["*"])
+                        .set_title("Place Prefab")
+                        .pick_file()
+                }) else {
+                    continue;
+                };
+                let position = world
+                    .query_filtered::<&GlobalTransform, With<Camera2d>>()
+                    .iter(world)
+                    .next()
+                    .map(|transform| transform.translation().truncate())
+                    .unwrap_or_default();
+                match place_prefab(world, &path.to_string_lossy(), position) {
+                    Ok(bodies) => {
+                        info!("Placed prefab {:?} with {} bodies", path, bodies.len());
+                        if let Some(mut selection) = world.get_resource_mut::<EditorSelection>() {
+                            *selection = EditorSelection::from_iter(bodies);
+                        }
+                    }
+                    Err(e) => error!("Prefab placement failed: {}", e),
+                }
+            }
+            PrefabEvent::ApplyToPrefab(root) => match apply_to_prefab(world, root) {
+                Ok(()) => info!("Applied instance {} to its prefab", root),
+                Err(e) => error!("Applying instance {} failed: {}", root, e),
+            },
+            PrefabEvent::Revert(root) => {
+                if let Some(mut instance) = world.get_mut::<PrefabInstance>(root) {
+                    instance.overrides.clear();
+                }
+                if let Err(e) = respawn_members(world, root) {
+                    error!("Reverting instance {} failed: {}", root, e);
+                }
+            }
+            PrefabEvent::Unpack(root) => {
+                let members = members_of(world, root);
+                for member in members {
+                    world
+                        .entity_mut(member)
+                        .remove::<(PrefabMemberOf, PrefabKey)>();
+                }
+                if let Ok(entity) = world.get_entity_mut(root) {
+                    entity.despawn();
+                }
+                info!("Unpacked instance {}", root);
+            }
+            PrefabEvent::Reload(source) => {
+                let result = std::fs::read_to_string(&source)
+                    .map_err(|e| SceneError::FileError(e.to_string()))
+                    .and_then(|text| update_instances(world, &source, text, None));
+                match result {
+                    Ok(()) => info!("Reloaded prefab {}", source),
+                    Err(e) => error!("Reloading prefab {} failed: {}", source, e),
+                }
+            }
+        }
+    }
+}
+
+/// Spawn the members of instances loaded from a scene file
+fn spawn_prefab_instances(
+    mut commands: Commands,
+    instance_query: Query<(Entity, &Transform), (With<PrefabInstance>, Without<PrefabPlacement>)>,
+) {
+    for (root, transform) in &instance_query {
+        commands.entity(root).insert(PrefabPlacement(*transform));
+        commands.queue(move |world: &mut World| {
+            if let Err(e) = spawn_members(world, root) {
+                error!("Spawning prefab instance {} failed: {}", root, e);
+            }
+        });
+    }
+}
+
+/// Carry the bodies of an instance along when its root is moved
+fn follow_prefab_instances(
+    mut instance_query: Query<
+        (&Transform, &mut PrefabPlacement, &PrefabMembers),
+        (With<PrefabInstance>, Changed<Transform>),
+    >,
+    mut body_query: Query<&mut Transform, (With<Collider>, Without<PrefabInstance>)>,
+) {
+    for (transform, mut placement, members) in &mut instance_query {
+        if placement.0 == *transform {
+            continue;
+        }
+        let delta = transform.compute_matrix() * placement.0.compute_matrix().inverse();
+        for &member in members.get() {
+            if let Ok(mut body) = body_query.get_mut(member) {
+                *body = Transform::from_matrix(delta * body.compute_matrix());
+            }
+        }
+        placement.0 = *transform;
+    }
+}
+
+/// Instance root of a member, or the entity itself when it is a root
+pub fn prefab_instance_of(world: &World, entity: Entity) -> Option<Entity> {
+    if world.get::<PrefabInstance>(entity).is_some() {
+        return Some(entity);
+    }
+    world
+        .get::<PrefabMemberOf>(key_holder(world, entity))
+        .map(|member| member.0)
+}
+
+/// Current overrides of a spawned instance, compared with its loaded prefab
+pub fn instance_overrides(world: &World, root: Entity) -> Option<Vec<PrefabOverride>> {
+    world.get::<PrefabPlacement>(root)?;
+    let source = &world.get::<PrefabInstance>(root)?.source;
+    let prefab = world.get_resource::<PrefabLibrary>()?.prefabs.get(source)?;
+    match prefab_overrides(world, root, prefab) {
+        Ok(overrides) => Some(overrides),
+        Err(e) => {
+            warn!("Comparing instance {} with {} failed: {}", root, source, e);
+            None
+        }
+    }
+}
+
+/// Anchors on `bodies` and joints between them
+pub(crate) fn attached_entities(world: &World, bodies: &[Entity]) -> (Vec<Entity>, Vec<Entity>) {
+    let body_set: HashSet<Entity> = bodies.iter().copied().collect();
+    let mut anchors = Vec::new();
+    let mut joints = Vec::new();
+    for entity in world.iter_entities() {
+        if let Some(anchor) = entity.get::<AnchorPoint>() {
+            if body_set.contains(&anchor.parent_entity) {
+                anchors.push(entity.id());
+            }
+        } else if let Some(config) = entity.get::<JointConfig>() {
+            if body_set.contains(&config.parent_entity) && body_set.contains(&config.child_entity) {
+                joints.push(entity.id());
+            }
+        }
+    }
+    (anchors, joints)
+}
+
+/// Entity holding the [`PrefabKey`] of a member; joints are keyed through their visualization
+pub(crate) fn key_holder(world: &World, entity: Entity) -> Entity {
+    world
+        .get::<VisualizedBy>(entity)
+        .and_then(VisualizedBy::get)
+        .unwrap_or(entity)
+}
+
+/// Serialize bodies with their anchors and the joints between them as prefab RON
+///
+/// Returns the RON and the key of each body, anchor and joint visualization.
+pub fn prefab_scene(
+    world: &World,
+    bodies: &[Entity],
+    frame: &Transform,
+) -> Result<(String, Vec<(Entity, u32)>), SceneError> {
+    let (scene, keys) = contents_scene(world, bodies, frame)?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let text = scene
+        .serialize(&registry)
+        .map_err(|e| SceneError::SerializationError(e.to_string()))?;
+    Ok((text, keys))
+}
+
+/// Extract bodies with their anchors and the joints between them, without resources
+///
+/// Body transforms are stored relative to `frame`. Entities that already have a [`PrefabKey`]
+/// keep it and the rest get unused ones. Returns the scene and the entity holding the key of
+/// each body, anchor and joint.
+pub(crate) fn contents_scene(
+    world: &World,
+    bodies: &[Entity],
+    frame: &Transform,
+) -> Result<(DynamicScene, Vec<(Entity, u32)>), SceneError> {
+    if bodies.is_empty() {
+        return Err(SceneError::NoEntitiesFound);
+    }
+    let (anchors, joints) = attached_entities(world, bodies);
+    let entities: Vec<Entity> = bodies
+        .iter()
+        .chain(&anchors)
+        .chain(&joints)
+        .copied()
+        .collect();
+
+    let mut next_key = entities
+        .iter()
+        .filter_map(|&entity| world.get::<PrefabKey>(key_holder(world, entity)))
+        .map(|key| key.0 + 1)
+        .max()
+        .unwrap_or(0);
+    let keys: HashMap<Entity, (Entity, u32)> = entities
+        .iter()
+        .map(|&entity| {
+            let holder = key_holder(world, entity);
+            let key = world.get::<PrefabKey>(holder).map_or_else(
+                || {
+                    next_key += 1;
+                    next_key - 1
+                },
+                |key| key.0,
+            );
+            (entity, (holder, key))
+        })
+        .collect();
+
+    let filter = rebuilt_components()
+        .into_iter()
+        .fold(SceneFilter::allow_all(), SceneFilter::deny_by_id);
+    let mut scene = DynamicSceneBuilder::from_world(world)
+        .with_component_filter(filter)
+        .extract_entities(entities.iter().copied())
+        .build();
+
+    let frame_inverse = frame.compute_matrix().inverse();
+    for entry in &mut scene.entities {
+        let id = entry.entity;
+        set_component(&mut entry.components, PrefabKey(keys[&id].1));
+        if bodies.contains(&id) {
+            if let Some(transform) = world.get::<Transform>(id) {
+                let local = Transform::from_matrix(frame_inverse * transform.compute_matrix());
+                set_component(&mut entry.components, local);
+            }
+        }
+    }
+    Ok((scene, keys.into_values().collect()))
+}
+
+/// Member values of an instance that differ from `prefab`
+pub fn prefab_overrides(
+    world: &World,
+    root: Entity,
+    prefab: &str,
+) -> Result<Vec<PrefabOverride>, SceneError> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let scene = parse_scene(prefab, &registry)?;
+    let frame_inverse = world
+        .get::<Transform>(root)
+        .copied()
+        .unwrap_or_default()
+        .compute_matrix()
+        .inverse();
+
+    let mut members = HashMap::new();
+    for member in members_of(world, root) {
+        if let Some(key) = world.get::<PrefabKey>(member) {
+            let live = world
+                .get::<JointVisualizationOf>(member)
+                .map_or(member, |of| of.0);
+            members.insert(key.0, live);
+        }
+    }
+
+    let mut overrides = Vec::new();
+    for entry in &scene.entities {
+        let Some(key) = entry_key(entry) else {
+            continue;
+        };
+        let Some(live) = members.get(&key).and_then(|&e| world.get_entity(e).ok()) else {
+            continue;
+        };
+        let is_joint = live.contains::<JointConfig>();
+        let is_body = !is_joint && !live.contains::<AnchorPoint>();
+
+        for stored in &entry.components {
+            let Some(registration) = stored
+                .get_represented_type_info()
+                .and_then(|info| registry.get(info.type_id()))
+            else {
+                continue;
+            };
+            let type_id = registration.type_id();
+            // Joints are rebuilt from their configuration alone
+            if type_id == TypeId::of::<PrefabKey>()
+                || (is_joint && type_id != TypeId::of::<JointConfig>())
+            {
+                continue;
+            }
+            let (Some(reflect_component), Some(from_reflect)) = (
+                registration.data::<ReflectComponent>(),
+                registration.data::<ReflectFromReflect>(),
+            ) else {
+                continue;
+            };
+            let Some(current) = reflect_component.reflect(live) else {
+                continue;
+            };
+            let (Some(mut current), Some(mut stored)) = (
+                from_reflect.from_reflect(current.as_partial_reflect()),
+                from_reflect.from_reflect(stored.as_ref()),
+            ) else {
+                continue;
+            };
+            if !detach(current.as_mut()) || !detach(stored.as_mut()) {
+                continue;
+            }
+            if is_body {
+                if let Some(transform) = current.downcast_mut::<Transform>() {
+                    *transform = Transform::from_matrix(frame_inverse * transform.compute_matrix());
+                }
+            }
+            if same_value(current.as_ref(), stored.as_ref(), &registry)? {
+                continue;
+            }
+            overrides.push(PrefabOverride {
+                key,
+                component: registration.type_info().type_path().to_string(),
+                value: to_ron(current.as_partial_reflect(), &registry)?,
+            });
+        }
+    }
+    Ok(overrides)
+}
+
+/// Spawn the members of an instance from its prefab and overrides, returning the bodies
+fn spawn_members(world: &mut World, root: Entity) -> Result<Vec<Entity>, SceneError> {
+    let instance = world
+        .get::<PrefabInstance>(root)
+        .cloned()
+        .ok_or(SceneError::NoEntitiesFound)?;
+    let frame = world.get::<Transform>(root).copied().unwrap_or_default();
+    let text = world
+        .resource_mut::<PrefabLibrary>()
+        .load(&instance.source)?;
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let mut scene = parse_scene(&text, &type_registry.read())?;
+    apply_overrides(&mut scene, &instance.overrides, &type_registry.read());
+    let bodies = spawn_contents(world, scene, &frame, PrefabMemberOf(root))?;
+
+    if let Some(mut state) = world.get_resource_mut::<ColliderCreationState>() {
+        state.created_colliders.extend(bodies.iter().copied());
+    }
+    world.entity_mut(root).insert(PrefabPlacement(frame));
+    Ok(bodies)
+}
+
+/// Components left out of prefab and sub-scene contents
+///
+/// World-space and derived state is recomputed for every copy, and hierarchy, relationships and
+/// physics joints are rebuilt when the contents are spawned.
+pub(crate) fn rebuilt_components() -> [TypeId; 17] {
+    [
+        TypeId::of::<GlobalTransform>(),
+        TypeId::of::<Position>(),
+        TypeId::of::<Rotation>(),
+        TypeId::of::<ColliderAabb>(),
+        TypeId::of::<ComputedMass>(),
+        TypeId::of::<ChildOf>(),
+        TypeId::of::<Children>(),
+        TypeId::of::<AnchorUsedBy>(),
+        TypeId::of::<UsesAnchors>(),
+        TypeId::of::<JointVisualizationOf>(),
+        TypeId::of::<VisualizedBy>(),
+        TypeId::of::<FixedJoint>(),
+        TypeId::of::<DistanceJoint>(),
+        TypeId::of::<RevoluteJoint>(),
+        TypeId::of::<PrismaticJoint>(),
+        TypeId::of::<PrefabMemberOf>(),
+        TypeId::of::<SubSceneMemberOf>(),
+    ]
+}
+
+/// Spawn bodies, anchors and joints from a scene with body transforms relative to `frame`
+///
+/// Resources and rebuilt components in the scene are ignored. Bodies, anchors and joint
+/// visualizations get `member`, anchors are attached to their spawned bodies and joints are
+/// created from their [`JointConfig`]. A joint's [`PrefabKey`] goes on its visualization.
+/// Returns the spawned entities that are not anchors.
+pub(crate) fn spawn_contents<M: Bundle + Clone>(
+    world: &mut World,
+    mut scene: DynamicScene,
+    frame: &Transform,
+    member: M,
+) -> Result<Vec<Entity>, SceneError> {
+    let rebuilt = rebuilt_components();
+    scene.resources.clear();
+    for entry in &mut scene.entities {
+        entry.components.retain(|component| {
+            component
+                .get_represented_type_info()
+                .is_none_or(|info| !rebuilt.contains(&info.type_id()))
+        });
+    }
+
+    // Joints are created from their configuration once the bodies and anchors exist
+    let mut joints = Vec::new();
+    scene.entities.retain(|entry| {
+        let config = find_reflected::<JointConfig>(&entry.components)
+            .and_then(|index| JointConfig::from_reflect(entry.components[index].as_ref()));
+        match config {
+            Some(config) => {
+                joints.push((entry_key(entry), config));
+                false
+            }
+            None => true,
+        }
+    });
+
+    let scene_ids: Vec<Entity> = scene.entities.iter().map(|entry| entry.entity).collect();
+    let mut entity_map = EntityHashMap::default();
+    scene
+        .write_to_world(world, &mut entity_map)
+        .map_err(|e| SceneError::SerializationError(e.to_string()))?;
+    let remap = |entity: Entity| entity_map.get(&entity).copied().unwrap_or(entity);
+
+    let mut placed = Vec::new();
+    for id in scene_ids {
+        let Some(&spawned) = entity_map.get(&id) else {
+            continue;
+        };
+        let mut entity = world.entity_mut(spawned);
+        entity.insert(member.clone());
+        if let Some(mut anchor) = entity.get_mut::<AnchorPoint>() {
+            anchor.parent_entity = remap(anchor.parent_entity);
+            let parent = anchor.parent_entity;
+            entity.insert(ChildOf(parent));
+        } else if let Some(mut transform) = entity.get_mut::<Transform>() {
+            *transform = frame.mul_transform(*transform);
+            placed.push(spawned);
+        }
+    }
+
+    let joints: Vec<_> = joints
+        .into_iter()
+        .map(|(key, config)| {
+            let config = JointConfig {
+                anchor_a: remap(config.anchor_a),
+                anchor_b: remap(config.anchor_b),
+                parent_entity: remap(config.parent_entity),
+                child_entity: remap(config.child_entity),
+                ..config
+            };
+            let offset = |anchor: Entity, is_anchor: bool| match world.get::<AnchorPoint>(anchor) {
+                Some(point) if is_anchor => get_anchor_local_position(point),
+                _ => Vec2::ZERO,
+            };
+            let anchor_a = offset(config.anchor_a, config.anchor_a_is_anchor);
+            let anchor_b = offset(config.anchor_b, config.anchor_b_is_anchor);
+            (key, config, anchor_a, anchor_b)
+        })
+        .collect();
+
+    let mut commands = world.commands();
+    for (key, config, anchor_a, anchor_b) in joints {
+        let joint = config.joint_config_details.create_physics_joint(
+            &mut commands,
+            anchor_a,
+            anchor_b,
+            config.parent_entity,
+            config.child_entity,
+        );
+        let anchors: Vec<Entity> = [
+            (config.anchor_a, config.anchor_a_is_anchor),
+            (config.anchor_b, config.anchor_b_is_anchor),
+        ]
+        .into_iter()
+        .filter_map(|(anchor, is_anchor)| is_anchor.then_some(anchor))
+        .collect();
+        joint_relationships::create_anchor_usage_relationships(&mut commands, joint, &anchors);
+
+        let visualization = commands
+            .spawn((
+                JointVisualization {
+                    anchor_a: config.anchor_a,
+                    anchor_b: config.anchor_b,
+                    joint_type: config.joint_config_details.joint_type(),
+                    selected: false,
+                },
+                Selectable,
+                JointVisualizationOf(joint),
+                member.clone(),
+            ))
+            .id();
+        if let Some(key) = key {
+            commands.entity(visualization).insert(PrefabKey(key));
+        }
+        commands.entity(joint).insert(config);
+    }
+    world.flush();
+    Ok(placed)
+}
+
+/// Members of an instance
+fn members_of(world: &World, root: Entity) -> Vec<Entity> {
+    world
+        .get::<PrefabMembers>(root)
+        .map(|members| members.get().to_vec())
+        .unwrap_or_default()
+}
+
+/// Despawn and spawn again the members of an instance
+fn respawn_members(world: &mut World, root: Entity) -> Result<Vec<Entity>, SceneError> {
+    let members = members_of(world, root);
+    if let Some(mut state) = world.get_resource_mut::<ColliderCreationState>() {
+        state
+            .created_colliders
+            .retain(|entity| !members.contains(entity));
+    }
+    // Anchors go with their bodies and joints with their visualizations
+    for member in members {
+        if let Ok(entity) = world.get_entity_mut(member) {
+            entity.despawn();
+        }
+    }
+    world.flush();
+    spawn_members(world, root)
+}
+
+/// Save bodies as a new prefab and make them its first instance
+fn create_prefab(
+    world: &mut World,
+    entities: &[Entity],
+    path: &Path,
+) -> Result<Entity, SceneError> {
+    let bodies: Vec<Entity> = entities
+        .iter()
+        .copied()
+        .filter(|&entity| {
+            world.get::<Collider>(entity).is_some() && world.get::<AnchorPoint>(entity).is_none()
+        })
+        .collect();
+    if bodies.is_empty() {
+        return Err(SceneError::NoEntitiesFound);
+    }
+
+    // Bodies taken from other instances start over with new keys
+    let (anchors, joints) = attached_entities(world, &bodies);
+    for entity in bodies.iter().chain(&anchors).chain(&joints) {
+        let holder = key_holder(world, *entity);
+        world
+            .entity_mut(holder)
+            .remove::<(PrefabMemberOf, PrefabKey)>();
+    }
+
+    let center = bodies
+        .iter()
+        .filter_map(|&entity| world.get::<Transform>(entity))
+        .map(|transform| transform.translation.truncate())
+        .sum::<Vec2>()
+        / bodies.len() as f32;
+    let frame = Transform::from_translation(center.extend(0.0));
+    let (text, keys) = prefab_scene(world, &bodies, &frame)?;
+    std::fs::write(path, &text).map_err(|e| SceneError::FileError(e.to_string()))?;
+
+    let source = path.to_string_lossy().into_owned();
+    world
+        .resource_mut::<PrefabLibrary>()
+        .prefabs
+        .insert(source.clone(), text);
+    let root = world
+        .spawn((
+            Name::new(format!("Prefab {}", prefab_name(&source))),
+            frame,
+            PrefabInstance {
+                source,
+                overrides: Vec::new(),
+            },
+            PrefabPlacement(frame),
+        ))
+        .id();
+    for (entity, key) in keys {
+        world
+            .entity_mut(entity)
+            .insert((PrefabKey(key), PrefabMemberOf(root)));
+    }
+    Ok(root)
+}
+
+/// Spawn an instance of a prefab at `position`, returning its bodies
+fn place_prefab(
+    world: &mut World,
+    source: &str,
+    position: Vec2,
+) -> Result<Vec<Entity>, SceneError> {
+    world.resource_mut::<PrefabLibrary>().load(source)?;
+    let root = world
+        .spawn((
+            Name::new(format!("Prefab {}", prefab_name(source))),
+            Transform::from_translation(position.extend(0.0)),
+            PrefabInstance {
+                source: source.to_string(),
+                overrides: Vec::new(),
+            },
+        ))
+        .id();
+    spawn_members(world, root).inspect_err(|_| {
+        world.despawn(root);
+    })
+}
+
+/// Write an instance to its prefab and respawn the other instances
+fn apply_to_prefab(world: &mut World, root: Entity) -> Result<(), SceneError> {
+    let source = world
+        .get::<PrefabInstance>(root)
+        .map(|instance| instance.source.clone())
+        .ok_or(SceneError::NoEntitiesFound)?;
+    let bodies: Vec<Entity> = members_of(world, root)
+        .into_iter()
+        .filter(|&member| world.get::<Collider>(member).is_some())
+        .collect();
+    let frame = world.get::<Transform>(root).copied().unwrap_or_default();
+
+    let (text, keys) = prefab_scene(world, &bodies, &frame)?;
+    std::fs::write(&source, &text).map_err(|e| SceneError::FileError(e.to_string()))?;
+    // Anchors and joints added to the instance become part of the prefab
+    for (entity, key) in keys {
+        world
+            .entity_mut(entity)
+            .insert((PrefabKey(key), PrefabMemberOf(root)));
+    }
+    if let Some(mut instance) = world.get_mut::<PrefabInstance>(root) {
+        instance.overrides.clear();
+    }
+    update_instances(world, &source, text, Some(root))
+}
+
+/// Replace the contents of a prefab and respawn its instances with their overrides
+fn update_instances(
+    world: &mut World,
+    source: &str,
+    text: String,
+    except: Option<Entity>,
+) -> Result<(), SceneError> {
+    let previous = world.resource_mut::<PrefabLibrary>().load(source)?;
+    let roots: Vec<Entity> = world
+        .iter_entities()
+        .filter(|entity| entity.contains::<PrefabPlacement>())
+        .filter(|entity| {
+            entity
+                .get::<PrefabInstance>()
+                .is_some_and(|instance| instance.source == source)
+        })
+        .map(|entity| entity.id())
+        .filter(|&root| Some(root) != except)
+        .collect();
+
+    // Overrides are measured against the previous contents before they are replaced
+    for &root in &roots {
+        let overrides = prefab_overrides(world, root, &previous)?;
+        if let Some(mut instance) = world.get_mut::<PrefabInstance>(root) {
+            instance.overrides = overrides;
+        }
+    }
+    world
+        .resource_mut::<PrefabLibrary>()
+        .prefabs
+        .insert(source.to_string(), text);
+    for root in roots {
+        respawn_members(world, root)?;
+    }
+    Ok(())
+}
+
+/// Put overrides in place of the prefab's values
+fn apply_overrides(
+    scene: &mut DynamicScene,
+    overrides: &[PrefabOverride],
+    registry: &TypeRegistry,
+) {
+    for prefab_override in overrides {
+        let Some(entry) = scene
+            .entities
+            .iter_mut()
+            .find(|entry| entry_key(entry) == Some(prefab_override.key))
+        else {
+            warn!("Prefab has no member {}", prefab_override.key);
+            continue;
+        };
+        let Some(registration) = registry.get_with_type_path(&prefab_override.component) else {
+            warn!("Unknown override type {}", prefab_override.component);
+            continue;
+        };
+        let mut value = match from_ron(&prefab_override.value, registration, registry) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!("Skipping override of {}: {}", prefab_override.component, e);
+                continue;
+            }
+        };
+        let index = entry.components.iter().position(|component| {
+            component
+                .get_represented_type_info()
+                .is_some_and(|info| info.type_id() == registration.type_id())
+        });
+        match index {
+            Some(index) => {
+                reattach(value.as_mut(), entry.components[index].as_ref());
+                entry.components[index] = value.into_partial_reflect();
+            }
+            None => entry.components.push(value.into_partial_reflect()),
+        }
+    }
+}
+
+/// Key stored on a prefab entity
+fn entry_key(entry: &DynamicEntity) -> Option<u32> {
+    let index = find_reflected::<PrefabKey>(&entry.components)?;
+    PrefabKey::from_reflect(entry.components[index].as_ref()).map(|key| key.0)
+}
+
+/// Put `value` in place of the component of the same type, or add it
+fn set_component<T: Reflect>(components: &mut Vec<Box<dyn PartialReflect>>, value: T) {
+    match find_reflected::<T>(components) {
+        Some(index) => components[index] = Box::new(value),
+        None => components.push(Box::new(value)),
+    }
+}
+
+/// Clear the entity references that differ between instances
+///
+/// Returns false for other values holding entities, which are not compared.
+fn detach(value: &mut dyn Reflect) -> bool {
+    if let Some(anchor) = value.downcast_mut::<AnchorPoint>() {
+        anchor.parent_entity = Entity::PLACEHOLDER;
+        true
+    } else if let Some(config) = value.downcast_mut::<JointConfig>() {
+        config.anchor_a = Entity::PLACEHOLDER;
+        config.anchor_b = Entity::PLACEHOLDER;
+        config.parent_entity = Entity::PLACEHOLDER;
+        config.child_entity = Entity::PLACEHOLDER;
+        true
+    } else {
+        !contains_entity(value.as_partial_reflect())
+    }
+}
+
+/// Give an override the entity references of the prefab value it replaces
+fn reattach(value: &mut dyn Reflect, stored: &dyn PartialReflect) {
+    if let Some(anchor) = value.downcast_mut::<AnchorPoint>() {
+        if let Some(stored) = AnchorPoint::from_reflect(stored) {
+            anchor.parent_entity = stored.parent_entity;
+        }
+    } else if let Some(config) = value.downcast_mut::<JointConfig>() {
+        if let Some(stored) = JointConfig::from_reflect(stored) {
+            *config = JointConfig {
+                joint_config_details: config.joint_config_details.clone(),
+                anchor_a_is_anchor: config.anchor_a_is_anchor,
+                anchor_b_is_anchor: config.anchor_b_is_anchor,
+                ..stored
+            };
+        }
+    }
+}
+
+/// Whether a reflected value holds an [`Entity`] anywhere
+fn contains_entity(value: &dyn PartialReflect) -> bool {
+    if value.try_downcast_ref::<Entity>().is_some() {
+        return true;
+    }
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value.iter_fields().any(contains_entity),
+        ReflectRef::TupleStruct(value) => value.iter_fields().any(contains_entity),
+        ReflectRef::Tuple(value) => value.iter_fields().any(contains_entity),
+        ReflectRef::List(value) => value.iter().any(contains_entity),
+        ReflectRef::Array(value) => value.iter().any(contains_entity),
+        ReflectRef::Map(value) => value
+            .iter()
+            .any(|(key, value)| contains_entity(key) || contains_entity(value)),
+        ReflectRef::Set(value) => value.iter().any(contains_entity),
+        ReflectRef::Enum(value) => value
+            .iter_fields()
+            .any(|field| contains_entity(field.value())),
+        _ => false,
+    }
+}
+
+/// Compare two values of the same type, allowing rounding in transforms
+fn same_value(
+    a: &dyn Reflect,
+    b: &dyn Reflect,
+    registry: &TypeRegistry,
+) -> Result<bool, SceneError> {
+    if let (Some(a), Some(b)) = (a.downcast_ref::<Transform>(), b.downcast_ref::<Transform>()) {
+        return Ok(a.translation.abs_diff_eq(b.translation, 1e-3)
+            && (a.rotation.abs_diff_eq(b.rotation, 1e-5)
+                || a.rotation.abs_diff_eq(-b.rotation, 1e-5))
+            && a.scale.abs_diff_eq(b.scale, 1e-5));
+    }
+    Ok(to_ron(a.as_partial_reflect(), registry)? == to_ron(b.as_partial_reflect(), registry)?)
+}
+
+/// Serialize a reflected value as RON
+fn to_ron(value: &dyn PartialReflect, registry: &TypeRegistry) -> Result<String, SceneError> {
+    ron::to_string(&TypedReflectSerializer::new(value, registry))
+        .map_err(|e| SceneError::SerializationError(e.to_string()))
+}
+
+/// Deserialize a RON value of the registered type
+fn from_ron(
+    text: &str,
+    registration: &TypeRegistration,
+    registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, SceneError> {
+    let mut deserializer = ron::de::Deserializer::from_str(text)
+        .map_err(|e| SceneError::SerializationError(e.to_string()))?;
+    let value = TypedReflectDeserializer::new(registration, registry)
+        .deserialize(&mut deserializer)
+        .map_err(|e| SceneError::SerializationError(e.to_string()))?;
+    registration
+        .data::<ReflectFromReflect>()
+        .and_then(|from_reflect| from_reflect.from_reflect(value.as_ref()))
+        .ok_or_else(|| {
+            SceneError::SerializationError(format!(
+                "cannot build {}",
+                registration.type_info().type_path()
+            ))
+        })
+}
+
+/// Display name of a prefab file
+pub fn prefab_name(source: &str) -> String {
+    let file_name = Path::new(source).file_name().map_or_else(
+        || source.to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    file_name
+        .strip_suffix(".ron")
+        .unwrap_or(&file_name)
+        .trim_end_matches(".prefab")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member_with_key(world: &World, root: Entity, key: u32) -> Entity {
+        members_of(world, root)
+            .into_iter()
+            .find(|&member| world.get::<PrefabKey>(member) == Some(&PrefabKey(key)))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_prefab_overrides_survive_prefab_changes() {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<Transform>();
+            registry.register::<PrefabKey>();
+            registry.register::<ColliderDensity>();
+        }
+        world.insert_resource(type_registry);
+        world.init_resource::<PrefabLibrary>();
+
+        let a = world
+            .spawn((Transform::from_xyz(110.0, 20.0, 0.0), ColliderDensity(1.0)))
+            .id();
+        let b = world
+            .spawn((Transform::from_xyz(90.0, 20.0, 0.0), ColliderDensity(2.0)))
+            .id();
+        let frame = Transform::from_xyz(100.0, 20.0, 0.0);
+        let (text, keys) = prefab_scene(&world, &[a, b], &frame).unwrap();
+        assert_eq!(keys.len(), 2);
+        let source = "crate.prefab.ron";
+        world
+            .resource_mut::<PrefabLibrary>()
+            .prefabs
+            .insert(source.to_string(), text.clone());
+
+        // An instance at the origin, with one body made heavier
+        let root = world
+            .spawn(PrefabInstance {
+                source: source.to_string(),
+                overrides: Vec::new(),
+            })
+            .id();
+        let bodies = spawn_members(&mut world, root).unwrap();
+        assert_eq!(bodies.len(), 2);
+        let first = member_with_key(&world, root, 0);
+        let position = world.get::<Transform>(first).unwrap().translation;
+        assert!(position.abs_diff_eq(Vec3::new(10.0, 0.0, 0.0), 1e-4));
+        assert_eq!(prefab_overrides(&world, root, &text).unwrap(), Vec::new());
+
+        let second = member_with_key(&world, root, 1);
+        world.get_mut::<ColliderDensity>(second).unwrap().0 = 5.0;
+        let overrides = prefab_overrides(&world, root, &text).unwrap();
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].key, 1);
+
+        // Editing the prefab reaches the instance without undoing its override
+        world.get_mut::<ColliderDensity>(a).unwrap().0 = 3.0;
+        let (changed, _) = prefab_scene(&world, &[a, b], &frame).unwrap();
+        update_instances(&mut world, source, changed, None).unwrap();
+
+        let first = member_with_key(&world, root, 0);
+        let second = member_with_key(&world, root, 1);
+        assert_eq!(world.get::<ColliderDensity>(first).unwrap().0, 3.0);
+        assert_eq!(world.get::<ColliderDensity>(second).unwrap().0, 5.0);
+        assert_eq!(
+            world.get::<PrefabInstance>(root).unwrap().overrides.len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,610 @@
+//! Export of the scene to the RUBE JSON format
+//!
+//! RUBE's JSON files are read by the b2dJson loader and most Box2D-based engines. Bodies become
+//! `body` entries with their fixtures, and joints become `revolute`, `prismatic`, `distance` or
+//! `weld` entries anchored in body space.
+//!
+//! Box2D works in meters, so lengths are divided by [`PhysicsLengthUnit`]. Shapes Box2D lacks
+//! are rebuilt from the ones it has: capsules become a box and two circles, and convex polygons
+//! with more than [`MAX_POLYGON_VERTICES`] vertices are cut into fans. Collision layers keep only
+//! their first 16 bits, which is all a Box2D filter has.
+
+use std::path::Path;
+
+use avian2d::parry::shape::TypedShape;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rfd::FileDialog;
+use serde_json::{Map, Value, json};
+
+use crate::SceneError;
+use crate::collider_tools::debug_render::joint::JointConfig;
+use crate::collider_tools::joint_config::JointConfigurationEnum;
+use crate::collider_tools::polygon::signed_area;
+use crate::debug_render::AnchorPoint;
+
+/// Most vertices a Box2D polygon can have
+pub const MAX_POLYGON_VERTICES: usize = 8;
+
+/// RUBE JSON export plugin
+pub struct RubeExportPlugin;
+
+impl Plugin for RubeExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RubeExportEvent>()
+            .add_systems(Update, handle_rube_export);
+    }
+}
+
+/// RUBE JSON export event
+#[derive(Event)]
+pub enum RubeExportEvent {
+    /// Export every body and joint
+    All,
+    /// Export these bodies and the joints between them
+    Entities(Vec<Entity>),
+}
+
+/// Box2D fixture shape in body space
+#[derive(Debug, Clone, PartialEq)]
+pub enum RubeShape {
+    Circle {
+        center: Vec2,
+        radius: f32,
+    },
+    /// Convex counter-clockwise polygon of at most [`MAX_POLYGON_VERTICES`] vertices
+    Polygon(Vec<Vec2>),
+    /// Connected line strip, closed when its last vertex repeats its first
+    Chain(Vec<Vec2>),
+    Edge(Vec2, Vec2),
+}
+
+impl RubeShape {
+    /// The shape with every length multiplied by `factor`
+    fn scaled(self, factor: f32) -> Self {
+        let scale = |points: Vec<Vec2>| points.into_iter().map(|p| p * factor).collect();
+        match self {
+            RubeShape::Circle { center, radius } => RubeShape::Circle {
+                center: center * factor,
+                radius: radius * factor,
+            },
+            RubeShape::Polygon(points) => RubeShape::Polygon(scale(points)),
+            RubeShape::Chain(points) => RubeShape::Chain(scale(points)),
+            RubeShape::Edge(a, b) => RubeShape::Edge(a * factor, b * factor),
+        }
+    }
+
+    /// Name and value of the shape field of a fixture
+    fn to_json(&self) -> (&'static str, Value) {
+        let vertices = |points: &[Vec2]| {
+            json!({
+                "x": points.iter().map(|p| p.x).collect::<Vec<_>>(),
+                "y": points.iter().map(|p| p.y).collect::<Vec<_>>(),
+            })
+        };
+        match self {
+            RubeShape::Circle { center, radius } => (
+                "circle",
+                json!({ "center": vector(*center), "radius": radius }),
+            ),
+            RubeShape::Polygon(points) => ("polygon", json!({ "vertices": vertices(points) })),
+            RubeShape::Chain(points) => ("chain", json!({ "vertices": vertices(points) })),
+            RubeShape::Edge(a, b) => (
+                "edge",
+                json!({ "vertex1": vector(*a), "vertex2": vector(*b) }),
+            ),
+        }
+    }
+}
+
+fn vector(value: Vec2) -> Value {
+    json!({ "x": value.x, "y": value.y })
+}
+
+/// Cut a convex polygon into counter-clockwise fans Box2D accepts
+///
+/// Every piece shares the first vertex, and neighbouring pieces share an edge.
+pub fn convex_pieces(points: &[Vec2]) -> Vec<Vec<Vec2>> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+    let mut points = points.to_vec();
+    if signed_area(&points) < 0.0 {
+        points.reverse();
+    }
+    if points.len() <= MAX_POLYGON_VERTICES {
+        return vec![points];
+    }
+    let mut pieces = Vec::new();
+    let mut start = 1;
+    while start < points.len() - 1 {
+        let end = (start + MAX_POLYGON_VERTICES - 2).min(points.len() - 1);
+        let mut piece = vec![points[0]];
+        piece.extend_from_slice(&points[start..=end]);
+        pieces.push(piece);
+        start = end;
+    }
+    pieces
+}
+
+/// A capsule around the segment from `a` to `b`, as a box and a circle at each end
+pub fn capsule_shapes(a: Vec2, b: Vec2, radius: f32) -> Vec<RubeShape> {
+    let mut shapes = vec![
+        RubeShape::Circle { center: a, radius },
+        RubeShape::Circle { center: b, radius },
+    ];
+    let Some(direction) = (b - a).try_normalize() else {
+        shapes.pop();
+        return shapes;
+    };
+    let side = direction.perp() * radius;
+    shapes.insert(
+        0,
+        RubeShape::Polygon(vec![a - side, b - side, b + side, a + side]),
+    );
+    shapes
+}
+
+/// Join the segments of a polyline into strips
+pub fn polyline_chains(vertices: &[Vec2], indices: &[[u32; 2]]) -> Vec<Vec<Vec2>> {
+    let mut chains: Vec<Vec<u32>> = Vec::new();
+    for &[a, b] in indices {
+        match chains.last_mut() {
+            Some(chain) if chain.last() == Some(&a) => chain.push(b),
+            _ => chains.push(vec![a, b]),
+        }
+    }
+    chains
+        .into_iter()
+        .map(|chain| chain.iter().map(|&i| vertices[i as usize]).collect())
+        .collect()
+}
+
+/// Box2D shapes of a collider shape, with `place` moving points into body space
+///
+/// Returns `false` for shapes that have no Box2D equivalent.
+fn collect_shapes(
+    shape: TypedShape,
+    place: &dyn Fn(Vec2) -> Vec2,
+    shapes: &mut Vec<RubeShape>,
+) -> bool {
+    let point = |p: &avian2d::parry::math::Point<f32>| place(Vec2::new(p.x, p.y));
+    match shape {
+        TypedShape::Cuboid(cuboid) => {
+            let h = Vec2::new(cuboid.half_extents.x, cuboid.half_extents.y);
+            shapes.push(RubeShape::Polygon(
+                [
+                    Vec2::new(-h.x, -h.y),
+                    Vec2::new(h.x, -h.y),
+                    h,
+                    Vec2::new(-h.x, h.y),
+                ]
+                .map(place)
+                .to_vec(),
+            ));
+        }
+        TypedShape::Ball(ball) => shapes.push(RubeShape::Circle {
+            center: place(Vec2::ZERO),
+            radius: ball.radius,
+        }),
+        TypedShape::Capsule(capsule) => shapes.extend(capsule_shapes(
+            point(&capsule.segment.a),
+            point(&capsule.segment.b),
+            capsule.radius,
+        )),
+        TypedShape::Triangle(triangle) => shapes.extend(
+            convex_pieces(&[point(&triangle.a), point(&triangle.b), point(&triangle.c)])
+                .into_iter()
+                .map(RubeShape::Polygon),
+        ),
+        TypedShape::ConvexPolygon(polygon) => shapes.extend(
+            convex_pieces(&polygon.points().iter().map(point).collect::<Vec<_>>())
+                .into_iter()
+                .map(RubeShape::Polygon),
+        ),
+        TypedShape::Segment(segment) => {
+            shapes.push(RubeShape::Edge(point(&segment.a), point(&segment.b)))
+        }
+        TypedShape::Polyline(polyline) => {
+            let vertices: Vec<Vec2> = polyline.vertices().iter().map(point).collect();
+            shapes.extend(
+                polyline_chains(&vertices, polyline.indices())
+                    .into_iter()
+                    .map(RubeShape::Chain),
+            );
+        }
+        TypedShape::Compound(compound) => {
+            for (isometry, part) in compound.shapes() {
+                let translation =
+                    Vec2::new(isometry.translation.vector.x, isometry.translation.vector.y);
+                let rotation = Vec2::from_angle(isometry.rotation.angle());
+                let place_part = |p: Vec2| place(rotation.rotate(p) + translation);
+                if !collect_shapes(part.as_typed_shape(), &place_part, shapes) {
+                    return false;
+                }
+            }
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// RUBE entry of a joint between bodies `body_a` and `body_b`
+///
+/// Anchors, lengths and limits are in meters already; `length` is the distance the joint holds,
+/// used by distance joints. Forces and torques are converted with `meters_per_unit`.
+pub fn rube_joint(
+    details: &JointConfigurationEnum,
+    body_a: usize,
+    body_b: usize,
+    anchor_a: Vec2,
+    anchor_b: Vec2,
+    length: f32,
+    meters_per_unit: f32,
+) -> Value {
+    let common = details.common();
+    let advanced = details.advanced();
+    let mut joint = json!({
+        "bodyA": body_a,
+        "bodyB": body_b,
+        "anchorA": vector(anchor_a),
+        "anchorB": vector(anchor_b),
+        "collideConnected": !common.disable_collision,
+    });
+    let fields = match details {
+        JointConfigurationEnum::Fixed { .. } => json!({
+            "type": "weld",
+            "refAngle": 0.0,
+            "frequency": 0.0,
+            "dampingRatio": 0.0,
+        }),
+        JointConfigurationEnum::Distance { config, .. } => {
+            let min = config.min_distance.map(|min| min * meters_per_unit);
+            let max = config.max_distance.map(|max| max * meters_per_unit);
+            let length = length.max(min.unwrap_or(length)).min(max.unwrap_or(length));
+            let mut fields = json!({
+                "type": "distance",
+                "length": length,
+                "frequency": 0.0,
+                "dampingRatio": 0.0,
+            });
+            // Box2D 2.4 names for the length range
+            if let Some(min) = min {
+                fields["minLength"] = json!(min);
+            }
+            if let Some(max) = max {
+                fields["maxLength"] = json!(max);
+            }
+            fields
+        }
+        JointConfigurationEnum::Prismatic { config, .. } => {
+            let (lower, upper) = config
+                .min_distance
+                .zip(config.max_distance)
+                .unwrap_or_default();
+            json!({
+                "type": "prismatic",
+                "localAxisA": vector(config.free_axis.normalize_or(Vec2::X)),
+                "refAngle": 0.0,
+                "enableLimit": config.min_distance.is_some() && config.max_distance.is_some(),
+                "lowerTranslation": lower * meters_per_unit,
+                "upperTranslation": upper * meters_per_unit,
+                "enableMotor": advanced.motor_enabled,
+                "motorSpeed": advanced.motor_target_velocity * meters_per_unit,
+                "maxMotorForce": advanced.motor_max_force * meters_per_unit,
+            })
+        }
+        JointConfigurationEnum::Revolute { config, .. } => {
+            let (lower, upper) = config.min_angle.zip(config.max_angle).unwrap_or_default();
+            json!({
+                "type": "revolute",
+                "refAngle": config.basis.unwrap_or(0.0),
+                "enableLimit": config.min_angle.is_some() && config.max_angle.is_some(),
+                "lowerLimit": lower,
+                "upperLimit": upper,
+                "enableMotor": advanced.motor_enabled,
+                "motorSpeed": advanced.motor_target_velocity,
+                "maxMotorTorque": advanced.motor_max_force * meters_per_unit * meters_per_unit,
+            })
+        }
+    };
+    if let (Value::Object(joint), Value::Object(fields)) = (&mut joint, fields) {
+        joint.extend(fields);
+    }
+    joint
+}
+
+/// RUBE entry of a body, or `None` when its collider has no Box2D equivalent
+fn rube_body(world: &World, entity: EntityRef, meters_per_unit: f32) -> Option<Value> {
+    let collider = entity.get::<Collider>()?;
+    let transform = entity.get::<Transform>()?;
+    let mut shapes = Vec::new();
+    // Box2D has no scale, so the scaled shape is baked in
+    if !collect_shapes(
+        collider.shape_scaled().as_typed_shape(),
+        &|p: Vec2| p,
+        &mut shapes,
+    ) {
+        return None;
+    }
+
+    let name = entity
+        .get::<Name>()
+        .map_or_else(|| entity.id().to_string(), |name| name.to_string());
+    let layers = entity.get::<CollisionLayers>().copied().unwrap_or_default();
+    // Filters with every high layer set, like the default, lose nothing
+    if layers.memberships.0 >> 16 != 0 || !matches!(layers.filters.0 >> 16, 0 | 0xffff) {
+        warn!("{name} uses collision layers above 16, which Box2D does not have");
+    }
+    let friction = entity.get::<Friction>().map_or_else(
+        || {
+            world
+                .get_resource::<DefaultFriction>()
+                .map_or(0.5, |friction| friction.0.dynamic_coefficient)
+        },
+        |friction| friction.dynamic_coefficient,
+    );
+    let restitution = entity.get::<Restitution>().map_or_else(
+        || {
+            world
+                .get_resource::<DefaultRestitution>()
+                .map_or(0.0, |restitution| restitution.0.coefficient)
+        },
+        |restitution| restitution.coefficient,
+    );
+    // Density is per square world unit
+    let density = entity
+        .get::<ColliderDensity>()
+        .map_or(1.0, |density| density.0)
+        / (meters_per_unit * meters_per_unit);
+    let fixtures: Vec<Value> = shapes
+        .into_iter()
+        .map(|shape| {
+            let (kind, shape) = shape.scaled(meters_per_unit).to_json();
+            json!({
+                "name": name,
+                "density": density,
+                "friction": friction,
+                "restitution": restitution,
+                "sensor": entity.contains::<Sensor>(),
+                "filter-categoryBits": layers.memberships.0 & 0xffff,
+                "filter-maskBits": layers.filters.0 & 0xffff,
+                "filter-groupIndex": 0,
+                kind: shape,
+            })
+        })
+        .collect();
+
+    let body_type = match entity.get::<RigidBody>() {
+        Some(RigidBody::Dynamic) => 2,
+        Some(RigidBody::Kinematic) => 1,
+        _ => 0,
+    };
+    let mut body = json!({
+        "name": name,
+        "type": body_type,
+        "position": vector(transform.translation.truncate() * meters_per_unit),
+        "angle": transform.rotation.to_euler(EulerRot::ZYX).0,
+        "awake": true,
+        "bullet": entity.contains::<SweptCcd>(),
+        "fixedRotation": entity
+            .get::<LockedAxes>()
+            .is_some_and(|axes| axes.is_rotation_locked()),
+        "linearDamping": entity.get::<LinearDamping>().map_or(0.0, |damping| damping.0),
+        "angularDamping": entity.get::<AngularDamping>().map_or(0.0, |damping| damping.0),
+        "gravityScale": entity.get::<GravityScale>().map_or(1.0, |scale| scale.0),
+        "linearVelocity": vector(
+            entity.get::<LinearVelocity>().map_or(Vec2::ZERO, |velocity| velocity.0)
+                * meters_per_unit
+        ),
+        "angularVelocity": entity.get::<AngularVelocity>().map_or(0.0, |velocity| velocity.0),
+        "fixture": fixtures,
+    });
+    if let Some(mass) = entity.get::<Mass>() {
+        let center = entity
+            .get::<CenterOfMass>()
+            .map_or(Vec2::ZERO, |center| center.0);
+        body["massData-mass"] = json!(mass.0);
+        body["massData-center"] = vector(center * meters_per_unit);
+        if let Some(inertia) = entity.get::<AngularInertia>() {
+            // Box2D takes the inertia about the body origin
+            let inertia = inertia.0 + mass.0 * center.length_squared();
+            body["massData-I"] = json!(inertia * meters_per_unit * meters_per_unit);
+        }
+    }
+    Some(body)
+}
+
+/// Build the RUBE document of the scene, or of `entities` and the joints between them
+pub fn scene_rube_json(world: &World, entities: Option<&[Entity]>) -> Result<Value, SceneError> {
+    let included = |entity: Entity| entities.is_none_or(|entities| entities.contains(&entity));
+    let meters_per_unit = 1.0
+        / world
+            .get_resource::<PhysicsLengthUnit>()
+            .map_or(1.0, |unit| unit.0);
+
+    let mut body_entities = Vec::new();
+    let mut bodies = Vec::new();
+    for entity in world.iter_entities() {
+        if !included(entity.id()) || !entity.contains::<Collider>() {
+            continue;
+        }
+        match rube_body(world, entity, meters_per_unit) {
+            Some(body) => {
+                body_entities.push(entity.id());
+                bodies.push(body);
+            }
+            None => warn!("Skipping {} with a shape Box2D does not have", entity.id()),
+        }
+    }
+    if bodies.is_empty() {
+        return Err(SceneError::NoEntitiesFound);
+    }
+
+    let local_anchor = |entity: Entity, is_anchor: bool| {
+        world
+            .get::<AnchorPoint>(entity)
+            .filter(|_| is_anchor)
+            .map_or(Vec2::ZERO, |anchor| anchor.local_anchor_position)
+    };
+    let world_point = |body: Entity, local: Vec2| {
+        world
+            .get::<GlobalTransform>(body)
+            .map_or(local, |transform| {
+                transform.transform_point(local.extend(0.0)).truncate()
+            })
+    };
+    let mut joints = Vec::new();
+    for entity in world.iter_entities() {
+        let Some(config) = entity.get::<JointConfig>() else {
+            continue;
+        };
+        let index = |body: Entity| body_entities.iter().position(|&entity| entity == body);
+        let (Some(body_a), Some(body_b)) =
+            (index(config.parent_entity), index(config.child_entity))
+        else {
+            continue;
+        };
+        let anchor_a = local_anchor(config.anchor_a, config.anchor_a_is_anchor);
+        let anchor_b = local_anchor(config.anchor_b, config.anchor_b_is_anchor);
+        let length = world_point(config.parent_entity, anchor_a)
+            .distance(world_point(config.child_entity, anchor_b));
+        joints.push(rube_joint(
+            &config.joint_config_details,
+            body_a,
+            body_b,
+            anchor_a * meters_per_unit,
+            anchor_b * meters_per_unit,
+            length * meters_per_unit,
+            meters_per_unit,
+        ));
+    }
+
+    let gravity = world
+        .get_resource::<Gravity>()
+        .map_or(Vec2::ZERO, |gravity| gravity.0);
+    let mut document = Map::new();
+    document.insert("gravity".into(), vector(gravity * meters_per_unit));
+    document.insert("allowSleep".into(), json!(true));
+    document.insert("autoClearForces".into(), json!(true));
+    document.insert("continuousPhysics".into(), json!(true));
+    document.insert("subStepping".into(), json!(false));
+    document.insert("warmStarting".into(), json!(true));
+    document.insert("positionIterations".into(), json!(3));
+    document.insert("velocityIterations".into(), json!(8));
+    document.insert("stepsPerSecond".into(), json!(60.0));
+    document.insert("body".into(), Value::Array(bodies));
+    document.insert("joint".into(), Value::Array(joints));
+    Ok(Value::Object(document))
+}
+
+/// Write the world as a RUBE JSON file
+pub fn export_scene_rube(
+    world: &World,
+    entities: Option<&[Entity]>,
+    path: &Path,
+) -> Result<(), SceneError> {
+    let document = scene_rube_json(world, entities)?;
+    let text = serde_json::to_string_pretty(&document)
+        .map_err(|e| SceneError::SerializationError(e.to_string()))?;
+    std::fs::write(path, text).map_err(|e| SceneError::FileError(e.to_string()))
+}
+
+/// Export RUBE JSON files
+fn handle_rube_export(mut events: EventReader<RubeExportEvent>, world: &World) {
+    for event in events.read() {
+        let entities = match event {
+            RubeExportEvent::All => None,
+            RubeExportEvent::Entities(entities) => Some(entities.as_slice()),
+        };
+        let dialog = FileDialog::new()
+            .add_filter("RUBE JSON", &["json"])
+            .add_filter("All files", &["*"])
+            .set_file_name("scene.json")
+            .set_title("Export RUBE JSON");
+        let Some(path) = dialog.save_file() else {
+            continue;
+        };
+        match export_scene_rube(world, entities, &path) {
+            Ok(()) => info!("Exported RUBE JSON to {:?}", path),
+            Err(e) => error!("RUBE export failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collider_tools::joint_config::{
+        AdvancedJointConfig, CommonJointConfig, RevoluteJointConfig,
+    };
+
+    #[test]
+    fn test_rube_shapes_and_joints() {
+        // A clockwise decagon is reversed and cut into two fans sharing an edge
+        let decagon: Vec<Vec2> = (0..10)
+            .map(|i| Vec2::from_angle(-(i as f32) * std::f32::consts::TAU / 10.0))
+            .collect();
+        let pieces = convex_pieces(&decagon);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].len(), MAX_POLYGON_VERTICES);
+        assert_eq!(pieces[1].len(), 4);
+        assert_eq!(pieces[0][7], pieces[1][1]);
+        assert!(pieces.iter().all(|piece| signed_area(piece) > 0.0));
+
+        let capsule = capsule_shapes(Vec2::new(0.0, -1.0), Vec2::new(0.0, 1.0), 0.5);
+        assert_eq!(
+            capsule[0],
+            RubeShape::Polygon(vec![
+                Vec2::new(0.5, -1.0),
+                Vec2::new(0.5, 1.0),
+                Vec2::new(-0.5, 1.0),
+                Vec2::new(-0.5, -1.0),
+            ])
+        );
+        assert_eq!(capsule.len(), 3);
+
+        let vertices = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y];
+        let chains = polyline_chains(&vertices, &[[0, 1], [1, 2], [3, 0]]);
+        assert_eq!(
+            chains,
+            vec![
+                vec![Vec2::ZERO, Vec2::X, Vec2::ONE],
+                vec![Vec2::Y, Vec2::ZERO]
+            ]
+        );
+        let (kind, shape) = RubeShape::Chain(chains[1].clone()).scaled(2.0).to_json();
+        assert_eq!(kind, "chain");
+        assert_eq!(
+            shape,
+            json!({ "vertices": { "x": [0.0, 0.0], "y": [2.0, 0.0] } })
+        );
+
+        let details = JointConfigurationEnum::Revolute {
+            common: CommonJointConfig {
+                disable_collision: true,
+                ..default()
+            },
+            config: RevoluteJointConfig {
+                min_angle: Some(-1.0),
+                max_angle: Some(0.5),
+                ..default()
+            },
+            advanced: AdvancedJointConfig {
+                motor_enabled: true,
+                motor_target_velocity: 2.0,
+                motor_max_force: 100.0,
+                ..default()
+            },
+        };
+        let joint = rube_joint(&details, 0, 1, Vec2::new(0.5, 0.0), Vec2::ZERO, 0.5, 0.1);
+        assert_eq!(joint["type"], "revolute");
+        assert_eq!(joint["bodyB"], 1);
+        assert_eq!(joint["anchorA"], json!({ "x": 0.5, "y": 0.0 }));
+        assert_eq!(joint["collideConnected"], false);
+        assert_eq!(joint["enableLimit"], true);
+        assert_eq!(joint["lowerLimit"], -1.0);
+        assert_eq!(joint["enableMotor"], true);
+        assert!((joint["maxMotorTorque"].as_f64().unwrap() - 1.0).abs() < 1e-5);
+    }
+}
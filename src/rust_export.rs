@@ -0,0 +1,647 @@
+//! Export of the scene as Rust source code
+//!
+//! The generated file holds a `spawn_scene` function that spawns every collider with its
+//! physics components and every joint with the builder calls the editor used to create it. It
+//! only depends on Bevy and avian2d, so it can be copied into a game or a test as is:
+//!
+//! ```ignore
+//! mod scene;
+//!
+//! fn setup(mut commands: Commands) {
+//!     scene::spawn_scene(&mut commands);
+//! }
+//! ```
+//!
+//! Editor-only joint features, breaking and motors, are written as comments.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use avian2d::parry::shape::TypedShape;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rfd::FileDialog;
+
+use crate::SceneError;
+use crate::collider_tools::debug_render::joint::JointConfig;
+use crate::collider_tools::joint_config::{
+    AdvancedJointConfig, CommonJointConfig, JointConfigurationEnum,
+};
+use crate::debug_render::AnchorPoint;
+
+/// Rust code export plugin
+pub struct RustExportPlugin;
+
+impl Plugin for RustExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RustExportEvent>()
+            .add_systems(Update, handle_rust_export);
+    }
+}
+
+/// Rust code export event
+#[derive(Event)]
+pub enum RustExportEvent {
+    /// Export every body and joint
+    All,
+    /// Export these bodies and the joints between them
+    Entities(Vec<Entity>),
+}
+
+/// Float literal that reads back as the same `f32`
+fn float(value: f32) -> String {
+    if value.is_nan() {
+        "f32::NAN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 {
+            "f32::INFINITY"
+        } else {
+            "f32::NEG_INFINITY"
+        }
+        .to_string()
+    } else {
+        // Debug formatting always keeps a decimal point or an exponent
+        format!("{value:?}")
+    }
+}
+
+fn vec2(value: Vec2) -> String {
+    if value == Vec2::ZERO {
+        "Vec2::ZERO".to_string()
+    } else {
+        format!("Vec2::new({}, {})", float(value.x), float(value.y))
+    }
+}
+
+fn point(point: &avian2d::parry::math::Point<f32>) -> String {
+    vec2(Vec2::new(point.x, point.y))
+}
+
+/// Indent every line after the first, for expressions nested in other expressions
+fn indent(text: &str, spaces: usize) -> String {
+    text.replace('\n', &format!("\n{}", " ".repeat(spaces)))
+}
+
+/// Constructor call for a collider shape, or `None` for shapes the editor does not create
+fn shape_expression(shape: TypedShape) -> Option<String> {
+    let points = |points: &[avian2d::parry::math::Point<f32>]| {
+        points.iter().map(point).collect::<Vec<_>>().join(", ")
+    };
+    Some(match shape {
+        TypedShape::Cuboid(cuboid) => format!(
+            "Collider::rectangle({}, {})",
+            float(cuboid.half_extents.x * 2.0),
+            float(cuboid.half_extents.y * 2.0)
+        ),
+        TypedShape::Ball(ball) => format!("Collider::circle({})", float(ball.radius)),
+        TypedShape::Capsule(capsule) => {
+            let (a, b) = (capsule.segment.a, capsule.segment.b);
+            if a.x == 0.0 && b.x == 0.0 && a.y == -b.y {
+                format!(
+                    "Collider::capsule({}, {})",
+                    float(capsule.radius),
+                    float((b.y - a.y).abs())
+                )
+            } else {
+                format!(
+                    "Collider::capsule_endpoints({}, {}, {})",
+                    float(capsule.radius),
+                    point(&a),
+                    point(&b)
+                )
+            }
+        }
+        TypedShape::Triangle(triangle) => format!(
+            "Collider::triangle({}, {}, {})",
+            point(&triangle.a),
+            point(&triangle.b),
+            point(&triangle.c)
+        ),
+        TypedShape::ConvexPolygon(polygon) => format!(
+            "Collider::convex_hull(vec![{}]).unwrap()",
+            points(polygon.points())
+        ),
+        TypedShape::Segment(segment) => format!(
+            "Collider::segment({}, {})",
+            point(&segment.a),
+            point(&segment.b)
+        ),
+        TypedShape::Polyline(polyline) => {
+            let indices = polyline
+                .indices()
+                .iter()
+                .map(|[a, b]| format!("[{a}, {b}]"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "Collider::polyline(vec![{}], Some(vec![{indices}]))",
+                points(polyline.vertices())
+            )
+        }
+        TypedShape::Compound(compound) => {
+            let mut parts = Vec::new();
+            for (isometry, shape) in compound.shapes() {
+                let translation =
+                    Vec2::new(isometry.translation.vector.x, isometry.translation.vector.y);
+                let shape = shape_expression(shape.as_typed_shape())?;
+                parts.push(format!(
+                    "    ({}, Rotation::radians({}), {}),",
+                    vec2(translation),
+                    float(isometry.rotation.angle()),
+                    indent(&shape, 4)
+                ));
+            }
+            format!("Collider::compound(vec![\n{}\n])", parts.join("\n"))
+        }
+        _ => return None,
+    })
+}
+
+/// Components of a joint, mirroring
+/// [`create_physics_joint`](JointConfigurationEnum::create_physics_joint)
+///
+/// The first component is the joint itself, built between the variables `body1` and `body2`.
+/// Editor-only components are returned separately as comment lines.
+pub fn joint_components(
+    details: &JointConfigurationEnum,
+    body1: &str,
+    body2: &str,
+    local_anchor_1: Vec2,
+    local_anchor_2: Vec2,
+) -> (Vec<String>, Vec<String>) {
+    let mut builder = Vec::new();
+    let (joint, common, advanced) = match details {
+        JointConfigurationEnum::Fixed {
+            common,
+            config,
+            advanced,
+        } => {
+            if config.point_compliance != 0.0 {
+                builder.push(format!(
+                    "with_point_compliance({})",
+                    float(config.point_compliance)
+                ));
+            }
+            if config.angle_compliance != 0.0 {
+                builder.push(format!(
+                    "with_angle_compliance({})",
+                    float(config.angle_compliance)
+                ));
+            }
+            ("FixedJoint", common, advanced)
+        }
+        JointConfigurationEnum::Distance {
+            common,
+            config,
+            advanced,
+        } => {
+            if config.compliance != 0.0 {
+                builder.push(format!("with_compliance({})", float(config.compliance)));
+            }
+            match (config.min_distance, config.max_distance) {
+                (Some(min), Some(max)) => {
+                    builder.push(format!("with_limits({}, {})", float(min), float(max)))
+                }
+                (Some(min), None) => builder.push(format!("with_min_distance({})", float(min))),
+                (None, Some(max)) => builder.push(format!("with_max_distance({})", float(max))),
+                (None, None) => {}
+            }
+            ("DistanceJoint", common, advanced)
+        }
+        JointConfigurationEnum::Prismatic {
+            common,
+            config,
+            advanced,
+        } => {
+            if config.free_axis != Vec2::X {
+                builder.push(format!("with_slider_axis({})", vec2(config.free_axis)));
+            }
+            if config.axis_compliance != 0.0 {
+                builder.push(format!(
+                    "with_align_compliance({})",
+                    float(config.axis_compliance)
+                ));
+            }
+            if config.limit_compliance != 0.0 {
+                builder.push(format!(
+                    "with_limit_compliance({})",
+                    float(config.limit_compliance)
+                ));
+            }
+            if config.angle_compliance != 0.0 {
+                builder.push(format!(
+                    "with_angle_compliance({})",
+                    float(config.angle_compliance)
+                ));
+            }
+            if let (Some(min), Some(max)) = (config.min_distance, config.max_distance) {
+                builder.push(format!("with_limits({}, {})", float(min), float(max)));
+            }
+            ("PrismaticJoint", common, advanced)
+        }
+        JointConfigurationEnum::Revolute {
+            common,
+            config,
+            advanced,
+        } => {
+            if let Some(basis) = config.basis {
+                builder.push(format!("with_basis({})", float(basis)));
+            }
+            if config.point_compliance != 0.0 {
+                builder.push(format!(
+                    "with_point_compliance({})",
+                    float(config.point_compliance)
+                ));
+            }
+            if config.limit_compliance != 0.0 {
+                builder.push(format!(
+                    "with_limit_compliance({})",
+                    float(config.limit_compliance)
+                ));
+            }
+            if let (Some(min), Some(max)) = (config.min_angle, config.max_angle) {
+                builder.push(format!("with_angle_limits({}, {})", float(min), float(max)));
+            }
+            ("RevoluteJoint", common, advanced)
+        }
+    };
+
+    let mut joint = format!(
+        "{joint}::new({body1}, {body2})\n    .with_local_anchor1({})\n    .with_local_anchor2({})",
+        vec2(local_anchor_1),
+        vec2(local_anchor_2)
+    );
+    for call in builder {
+        let _ = write!(joint, "\n    .{call}");
+    }
+    let mut components = vec![joint];
+    let (extra, comments) = advanced_components(common, advanced);
+    components.extend(extra);
+    (components, comments)
+}
+
+/// Damping and advanced components of a joint, and comments for the editor-only ones
+fn advanced_components(
+    common: &CommonJointConfig,
+    advanced: &AdvancedJointConfig,
+) -> (Vec<String>, Vec<String>) {
+    let mut components = Vec::new();
+    let mut comments = Vec::new();
+    if common.damping_linear != 1.0 || common.damping_angular != 1.0 {
+        components.push(format!(
+            "JointDamping {{\n    linear: {},\n    angular: {},\n}}",
+            float(common.damping_linear),
+            float(common.damping_angular)
+        ));
+    }
+    if common.disable_collision {
+        components.push("JointCollisionDisabled".to_string());
+    }
+    if advanced.disabled {
+        components.push("JointDisabled".to_string());
+    }
+    if advanced.track_forces {
+        components.push("JointForces::default()".to_string());
+    }
+    if advanced.breakable {
+        comments.push(format!(
+            "Breaks above a force of {} or a torque of {} in the editor",
+            float(advanced.break_force),
+            float(advanced.break_torque)
+        ));
+    }
+    if advanced.motor_enabled {
+        comments.push(format!(
+            "Motor in the editor: target velocity {}, max force {}, stiffness {}, damping {}",
+            float(advanced.motor_target_velocity),
+            float(advanced.motor_max_force),
+            float(advanced.motor_stiffness),
+            float(advanced.motor_damping)
+        ));
+    }
+    (components, comments)
+}
+
+/// Components of a body, or `None` when its collider cannot be written
+fn body_components(entity: EntityRef) -> Option<Vec<String>> {
+    let collider = entity.get::<Collider>()?;
+    let transform = entity.get::<Transform>()?;
+    let mut components = Vec::new();
+    if let Some(name) = entity.get::<Name>() {
+        components.push(format!("Name::new({:?})", name.as_str()));
+    }
+
+    let mut transform_code = format!(
+        "Transform::from_xyz({}, {}, {})",
+        float(transform.translation.x),
+        float(transform.translation.y),
+        float(transform.translation.z)
+    );
+    let angle = transform.rotation.to_euler(EulerRot::ZYX).0;
+    if angle != 0.0 {
+        let _ = write!(
+            transform_code,
+            "\n    .with_rotation(Quat::from_rotation_z({}))",
+            float(angle)
+        );
+    }
+    if transform.scale != Vec3::ONE {
+        let _ = write!(
+            transform_code,
+            "\n    .with_scale(Vec3::new({}, {}, {}))",
+            float(transform.scale.x),
+            float(transform.scale.y),
+            float(transform.scale.z)
+        );
+    }
+    components.push(transform_code);
+
+    if let Some(body) = entity.get::<RigidBody>() {
+        components.push(format!("RigidBody::{body:?}"));
+    }
+    // The transform scale is applied to the unscaled shape again when spawned
+    components.push(shape_expression(collider.shape().as_typed_shape())?);
+    if entity.contains::<Sensor>() {
+        components.push("Sensor".to_string());
+    }
+    if entity.contains::<CollisionEventsEnabled>() {
+        components.push("CollisionEventsEnabled".to_string());
+    }
+
+    if let Some(friction) = entity.get::<Friction>() {
+        if friction.static_coefficient == friction.dynamic_coefficient
+            && friction.combine_rule == CoefficientCombine::Average
+        {
+            components.push(format!(
+                "Friction::new({})",
+                float(friction.dynamic_coefficient)
+            ));
+        } else {
+            components.push(format!(
+                "Friction {{\n    dynamic_coefficient: {},\n    static_coefficient: {},\n    combine_rule: CoefficientCombine::{:?},\n}}",
+                float(friction.dynamic_coefficient),
+                float(friction.static_coefficient),
+                friction.combine_rule
+            ));
+        }
+    }
+    if let Some(restitution) = entity.get::<Restitution>() {
+        if restitution.combine_rule == CoefficientCombine::Average {
+            components.push(format!(
+                "Restitution::new({})",
+                float(restitution.coefficient)
+            ));
+        } else {
+            components.push(format!(
+                "Restitution {{\n    coefficient: {},\n    combine_rule: CoefficientCombine::{:?},\n}}",
+                float(restitution.coefficient),
+                restitution.combine_rule
+            ));
+        }
+    }
+    if let Some(layers) = entity.get::<CollisionLayers>() {
+        let default = CollisionLayers::default();
+        if layers.memberships != default.memberships || layers.filters != default.filters {
+            components.push(format!(
+                "CollisionLayers::from_bits({:#b}, {:#b})",
+                layers.memberships.0, layers.filters.0
+            ));
+        }
+    }
+
+    // Mass properties, as inserted from the creation settings
+    if let Some(density) = entity.get::<ColliderDensity>() {
+        if density.0 != 1.0 {
+            components.push(format!("ColliderDensity({})", float(density.0)));
+        }
+    }
+    if let Some(mass) = entity.get::<Mass>() {
+        components.push(format!("Mass({})", float(mass.0)));
+    }
+    if let Some(inertia) = entity.get::<AngularInertia>() {
+        components.push(format!("AngularInertia({})", float(inertia.0)));
+    }
+    if let Some(center) = entity.get::<CenterOfMass>() {
+        components.push(format!("CenterOfMass({})", vec2(center.0)));
+    }
+    if entity.contains::<NoAutoMass>() {
+        components.push("NoAutoMass".to_string());
+    }
+    if entity.contains::<NoAutoAngularInertia>() {
+        components.push("NoAutoAngularInertia".to_string());
+    }
+    if entity.contains::<NoAutoCenterOfMass>() {
+        components.push("NoAutoCenterOfMass".to_string());
+    }
+
+    if let Some(damping) = entity.get::<LinearDamping>() {
+        components.push(format!("LinearDamping({})", float(damping.0)));
+    }
+    if let Some(damping) = entity.get::<AngularDamping>() {
+        components.push(format!("AngularDamping({})", float(damping.0)));
+    }
+    if let Some(scale) = entity.get::<GravityScale>() {
+        components.push(format!("GravityScale({})", float(scale.0)));
+    }
+    Some(components)
+}
+
+/// Write a `spawn` call, binding the entity to `binding` when given
+fn write_spawn(code: &mut String, binding: Option<&str>, mut components: Vec<String>) {
+    // Bundles are tuples of at most 15 components
+    if components.len() > 15 {
+        let rest = components.split_off(14);
+        components.push(format!("(\n    {},\n)", indent(&rest.join(",\n"), 4)));
+    }
+    let depth = if binding.is_some() { 12 } else { 8 };
+    let pad = " ".repeat(depth);
+    let components = components
+        .iter()
+        .map(|component| format!("{pad}{},\n", indent(component, depth)))
+        .collect::<String>();
+    match binding {
+        Some(binding) => {
+            let _ = write!(
+                code,
+                "    let {binding} = commands\n        .spawn((\n{components}        ))\n        .id();\n"
+            );
+        }
+        None => {
+            let _ = write!(code, "    commands.spawn((\n{components}    ));\n");
+        }
+    }
+}
+
+/// Generate Rust code spawning the scene, or only `entities` and the joints between them
+pub fn scene_rust_code(world: &World, entities: Option<&[Entity]>) -> Result<String, SceneError> {
+    let included = |entity: Entity| entities.is_none_or(|entities| entities.contains(&entity));
+
+    let mut bodies = Vec::new();
+    for entity in world.iter_entities() {
+        if !included(entity.id()) || !entity.contains::<Collider>() {
+            continue;
+        }
+        match body_components(entity) {
+            Some(components) => bodies.push((entity.id(), components)),
+            None => warn!(
+                "Skipping {} with an unsupported collider shape",
+                entity.id()
+            ),
+        }
+    }
+    if bodies.is_empty() {
+        return Err(SceneError::NoEntitiesFound);
+    }
+
+    let anchor_offset = |entity: Entity, is_anchor: bool| {
+        world
+            .get::<AnchorPoint>(entity)
+            .filter(|_| is_anchor)
+            .map_or(Vec2::ZERO, |anchor| anchor.local_anchor_position)
+    };
+    let exported = |body: Entity| bodies.iter().any(|(entity, _)| *entity == body);
+    let configs: Vec<&JointConfig> = world
+        .iter_entities()
+        .filter_map(|entity| entity.get::<JointConfig>())
+        .filter(|config| exported(config.parent_entity) && exported(config.child_entity))
+        .collect();
+    // Only bodies used by joints are bound to variables, numbered in spawn order
+    let variables: HashMap<Entity, String> = bodies
+        .iter()
+        .map(|(entity, _)| *entity)
+        .filter(|&entity| {
+            configs
+                .iter()
+                .any(|config| config.parent_entity == entity || config.child_entity == entity)
+        })
+        .enumerate()
+        .map(|(index, entity)| (entity, format!("body_{index}")))
+        .collect();
+    let joints: Vec<_> = configs
+        .iter()
+        .map(|config| {
+            joint_components(
+                &config.joint_config_details,
+                &variables[&config.parent_entity],
+                &variables[&config.child_entity],
+                anchor_offset(config.anchor_a, config.anchor_a_is_anchor),
+                anchor_offset(config.anchor_b, config.anchor_b_is_anchor),
+            )
+        })
+        .collect();
+
+    let mut code = String::new();
+    code.push_str("//! Scene exported from the Avian 2D editor\n\n");
+    code.push_str("use avian2d::prelude::*;\nuse bevy::prelude::*;\n\n");
+    code.push_str("/// Spawn the bodies and joints of the scene\n");
+    code.push_str("pub fn spawn_scene(commands: &mut Commands) {\n");
+    for (entity, components) in bodies {
+        write_spawn(
+            &mut code,
+            variables.get(&entity).map(String::as_str),
+            components,
+        );
+    }
+    for (components, comments) in joints {
+        for comment in comments {
+            let _ = writeln!(code, "    // {comment}");
+        }
+        write_spawn(&mut code, None, components);
+    }
+    code.push_str("}\n");
+    Ok(code)
+}
+
+/// Write Rust code spawning the world to a file
+pub fn export_scene_rust(
+    world: &World,
+    entities: Option<&[Entity]>,
+    path: &Path,
+) -> Result<(), SceneError> {
+    let code = scene_rust_code(world, entities)?;
+    std::fs::write(path, code).map_err(|e| SceneError::FileError(e.to_string()))
+}
+
+/// Export Rust code
+fn handle_rust_export(mut events: EventReader<RustExportEvent>, world: &World) {
+    for event in events.read() {
+        let entities = match event {
+            RustExportEvent::All => None,
+            RustExportEvent::Entities(entities) => Some(entities.as_slice()),
+        };
+        let dialog = FileDialog::new()
+            .add_filter("Rust source", &["rs"])
+            .add_filter("All files", &["*"])
+            .set_file_name("scene.rs")
+            .set_title("Export as Rust");
+        let Some(path) = dialog.save_file() else {
+            continue;
+        };
+        match export_scene_rust(world, entities, &path) {
+            Ok(()) => info!("Exported Rust code to {:?}", path),
+            Err(e) => error!("Rust export failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collider_tools::joint_config::RevoluteJointConfig;
+
+    #[test]
+    fn test_rust_code() {
+        assert_eq!(float(2.0), "2.0");
+        assert_eq!(float(-0.25), "-0.25");
+        assert_eq!(float(1e-8), "1e-8");
+        assert_eq!(
+            shape_expression(Collider::rectangle(20.0, 10.0).shape().as_typed_shape()).as_deref(),
+            Some("Collider::rectangle(20.0, 10.0)")
+        );
+        assert_eq!(
+            shape_expression(Collider::capsule(5.0, 20.0).shape().as_typed_shape()).as_deref(),
+            Some("Collider::capsule(5.0, 20.0)")
+        );
+
+        let details = JointConfigurationEnum::Revolute {
+            common: CommonJointConfig {
+                damping_linear: 0.5,
+                damping_angular: 1.0,
+                disable_collision: true,
+            },
+            config: RevoluteJointConfig {
+                min_angle: Some(-1.5),
+                max_angle: Some(1.5),
+                ..default()
+            },
+            advanced: AdvancedJointConfig {
+                motor_enabled: true,
+                ..default()
+            },
+        };
+        let (components, comments) = joint_components(
+            &details,
+            "body_0",
+            "body_1",
+            Vec2::new(10.0, 0.0),
+            Vec2::ZERO,
+        );
+        assert_eq!(
+            components,
+            [
+                "RevoluteJoint::new(body_0, body_1)\n    .with_local_anchor1(Vec2::new(10.0, 0.0))\n    .with_local_anchor2(Vec2::ZERO)\n    .with_angle_limits(-1.5, 1.5)",
+                "JointDamping {\n    linear: 0.5,\n    angular: 1.0,\n}",
+                "JointCollisionDisabled",
+            ]
+        );
+        assert_eq!(comments.len(), 1);
+
+        let mut code = String::new();
+        write_spawn(&mut code, None, components);
+        assert!(code.starts_with(
+            "    commands.spawn((\n        RevoluteJoint::new(body_0, body_1)\n            .with_local_anchor1("
+        ));
+        assert!(code.ends_with("        JointCollisionDisabled,\n    ));\n"));
+    }
+}
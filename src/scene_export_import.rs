@@ -1,15 +1,34 @@
 use avian2d::prelude::*;
 use bevy::ecs::entity::EntityHashMap;
 use bevy::prelude::*;
+use bevy::reflect::{FromReflect, TypeRegistry};
 use bevy::scene::{DynamicScene, DynamicSceneBuilder, serde::SceneDeserializer};
+use bevy::window::FileDragAndDrop;
+use bevy_egui::input::EguiWantsInput;
 
 use rfd::FileDialog;
 use serde::de::DeserializeSeed;
-use std::path::PathBuf;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use crate::collider_tools::collision_layers::{LayerConflict, LayerResolution, remap_layer_mask};
+use crate::collider_tools::debug_render::anchor::AnchorPoint;
+use crate::collider_tools::debug_render::joint::JointConfig;
+use crate::collider_tools::joint::RebuildJoint;
+use crate::collider_tools::joint_config::JointConfigurationEnum;
+use crate::collider_tools::utils::get_mouse_world_position;
+use crate::document::set_document;
 use crate::panel_state::{EntityInspectorState, PanelState};
-use crate::{CollisionLayerPresets, CreationProperties};
+use crate::prefab::{PrefabInstance, instance_overrides, prefab_instance_of};
+use crate::selection::{EditorSelection, Selectable};
+use crate::sub_scene::{SubScene, sub_scene_of};
+use crate::{
+    ColliderCreationState, CollisionLayerPresets, CreationProperties, ForceField, ForceFieldKind,
+    InitialVelocity, KinematicPath, MeasureGuides, PhysicsTimestep, PolygonSource, SketchStroke,
+    TileLayer, ToolMode, UnitSettings,
+};
 
 /// Scene export/import plugin
 pub struct SceneExportImportPlugin;
@@ -19,7 +38,11 @@ impl Plugin for SceneExportImportPlugin {
         app.add_event::<SceneExportEvent>()
             .add_event::<SceneImportEvent>()
             .add_systems(Update, handle_scene_export)
-            .add_systems(Update, handle_scene_import);
+            .add_systems(Update, (handle_scene_import, handle_dropped_scene_files))
+            .add_systems(
+                Update,
+                place_imported_entities.run_if(resource_exists::<ImportPlacement>),
+            );
     }
 }
 
@@ -36,9 +59,47 @@ pub enum SceneExportEvent {
 #[derive(Event)]
 pub enum SceneImportEvent {
     /// Show file dialog and import
-    FromDialog,
+    FromDialog(SceneImportMode),
     /// Import from specific path
-    FromPath(PathBuf),
+    FromPath(PathBuf, SceneImportMode),
+}
+
+/// How an imported scene is combined with the current one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum SceneImportMode {
+    /// Clear the current scene and take the file's entities and resources
+    Replace,
+    /// Add the file's entities and keep the current resources
+    #[default]
+    Merge,
+    /// Like merge, then carry the entities with the cursor until a click drops and selects them
+    Place,
+}
+
+/// Scene import waiting for its collision layer conflicts to be resolved
+///
+/// Applying it as a command imports the scene with the chosen resolutions.
+#[derive(Resource)]
+pub struct SceneImport {
+    /// File the scene was read from
+    pub path: PathBuf,
+    /// How the scene is combined with the current one
+    pub mode: SceneImportMode,
+    /// Layer conflicts between the file and the current presets
+    pub conflicts: Vec<LayerConflict>,
+    /// Chosen resolution for each conflict
+    pub resolutions: Vec<LayerResolution>,
+    scene: DynamicScene,
+    layers: Option<CollisionLayerPresets>,
+}
+
+/// Imported entities following the cursor until they are dropped
+#[derive(Resource, Debug, Clone)]
+pub struct ImportPlacement {
+    /// Top-level imported entities, moved as a group
+    pub entities: Vec<Entity>,
+    /// Current center of the group
+    pub center: Vec2,
 }
 
 /// Scene export/import errors
@@ -68,6 +129,7 @@ impl Command for SpawnDynamicSceneCommand {
                     "Scene spawned successfully with {} entities",
                     entity_map.len()
                 );
+                remap_editor_references(world, &entity_map);
 
                 // You can access the entity_map to work with spawned entities
                 for (scene_entity, world_entity) in entity_map.iter() {
@@ -84,44 +146,43 @@ impl Command for SpawnDynamicSceneCommand {
     }
 }
 
+/// Point the entity fields of spawned anchors and joint configurations at the spawned entities
+///
+/// Scene loading only maps entities in components that declare them, which these plain fields
+/// do not. Joints are rebuilt from the mapped configuration.
+fn remap_editor_references(world: &mut World, entity_map: &EntityHashMap<Entity>) {
+    let remap = |entity: Entity| entity_map.get(&entity).copied().unwrap_or(entity);
+    for &spawned in entity_map.values() {
+        let Ok(mut entity) = world.get_entity_mut(spawned) else {
+            continue;
+        };
+        if let Some(mut anchor) = entity.get_mut::<AnchorPoint>() {
+            anchor.parent_entity = remap(anchor.parent_entity);
+        }
+        if let Some(mut config) = entity.get_mut::<JointConfig>() {
+            config.anchor_a = remap(config.anchor_a);
+            config.anchor_b = remap(config.anchor_b);
+            config.parent_entity = remap(config.parent_entity);
+            config.child_entity = remap(config.child_entity);
+            entity.insert(RebuildJoint);
+        }
+    }
+}
+
 /// Handle scene export
 fn handle_scene_export(
     mut events: EventReader<SceneExportEvent>,
     world: &World,
     type_registry: Res<AppTypeRegistry>,
-    collider_query: Query<Entity, With<Collider>>,
-    joint_query: Query<
-        Entity,
-        Or<(
-            With<FixedJoint>,
-            With<DistanceJoint>,
-            With<RevoluteJoint>,
-            With<PrismaticJoint>,
-        )>,
-    >,
 ) {
     for event in events.read() {
         // Get entities to export
         let entities = match event {
-            SceneExportEvent::All => {
-                let mut entities: Vec<Entity> = Vec::new();
-
-                // Add collider entities
-                for entity in collider_query.iter() {
-                    entities.push(entity);
-                }
-
-                // Add joint entities
-                for entity in joint_query.iter() {
-                    if !entities.contains(&entity) {
-                        entities.push(entity);
-                    }
-                }
-
-                entities
-            }
+            SceneExportEvent::All => all_scene_entities(world),
             SceneExportEvent::Entities(entities) => entities.clone(),
         };
+        // Prefab instances and sub-scenes are stored by reference
+        let entities = stored_entities(world, &entities);
 
         if entities.is_empty() {
             warn!("No entities to export");
@@ -156,58 +217,385 @@ fn handle_scene_import(
     mut events: EventReader<SceneImportEvent>,
     mut commands: Commands,
     type_registry: Res<AppTypeRegistry>,
+    presets: Res<CollisionLayerPresets>,
 ) {
     for event in events.read() {
-        match event {
-            SceneImportEvent::FromDialog => {
+        let (file_path, mode) = match event {
+            SceneImportEvent::FromDialog(mode) => {
                 let dialog = FileDialog::new()
                     .add_filter("Scene files", &["ron", "scn"])
                     .add_filter("All files", &["*"])
                     .set_title("Load Scene");
+                let Some(file_path) = dialog.pick_file() else {
+                    continue;
+                };
+                (file_path, *mode)
+            }
+            SceneImportEvent::FromPath(path, mode) => (path.clone(), *mode),
+        };
 
-                if let Some(file_path) = dialog.pick_file() {
-                    match import_scene_with_commands(&file_path, &type_registry, &mut commands) {
-                        Ok(()) => {
-                            info!("Scene import queued successfully from {:?}", file_path);
-                        }
-                        Err(e) => {
-                            error!("Scene import failed: {}", e);
-                        }
-                    }
-                }
+        match read_scene_import(&file_path, mode, &type_registry, &presets) {
+            Ok(import) if import.conflicts.is_empty() => commands.queue(import),
+            Ok(import) => {
+                info!(
+                    "Scene {:?} has {} collision layer conflicts to resolve",
+                    file_path,
+                    import.conflicts.len()
+                );
+                commands.insert_resource(import);
             }
-            SceneImportEvent::FromPath(path) => {
-                match import_scene_with_commands(path, &type_registry, &mut commands) {
-                    Ok(()) => {
-                        info!("Scene import queued successfully from {:?}", path);
-                    }
+            Err(e) => {
+                error!("Scene import failed: {}", e);
+            }
+        }
+    }
+}
+
+/// Place scene files dropped on the window at the cursor
+fn handle_dropped_scene_files(
+    mut drop_events: EventReader<FileDragAndDrop>,
+    mut import_events: EventWriter<SceneImportEvent>,
+) {
+    for event in drop_events.read() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = event {
+            let is_scene = path_buf
+                .extension()
+                .is_some_and(|extension| extension == "ron" || extension == "scn");
+            if is_scene {
+                import_events.write(SceneImportEvent::FromPath(
+                    path_buf.clone(),
+                    SceneImportMode::Place,
+                ));
+            }
+        }
+    }
+}
+
+/// Read a scene file and find its collision layer conflicts with the current presets
+pub(crate) fn read_scene_import(
+    file_path: &Path,
+    mode: SceneImportMode,
+    type_registry: &AppTypeRegistry,
+    presets: &CollisionLayerPresets,
+) -> Result<SceneImport, SceneError> {
+    let file_content =
+        std::fs::read_to_string(file_path).map_err(|e| SceneError::FileError(e.to_string()))?;
+    let scene = parse_scene(&file_content, &type_registry.read())?;
+    info!(
+        "Scene deserialized successfully with {} entities and {} resources",
+        scene.entities.len(),
+        scene.resources.len()
+    );
+
+    let layers = find_reflected::<CollisionLayerPresets>(&scene.resources)
+        .and_then(|index| CollisionLayerPresets::from_reflect(scene.resources[index].as_ref()));
+    // Replacing takes the file's layers as they are
+    let conflicts = match (&layers, mode) {
+        (Some(layers), SceneImportMode::Merge | SceneImportMode::Place) => {
+            presets.layer_conflicts(layers)
+        }
+        _ => Vec::new(),
+    };
+    Ok(SceneImport {
+        path: file_path.to_path_buf(),
+        mode,
+        resolutions: conflicts
+            .iter()
+            .map(LayerConflict::default_resolution)
+            .collect(),
+        conflicts,
+        scene,
+        layers,
+    })
+}
+
+impl Command for SceneImport {
+    fn apply(mut self, world: &mut World) {
+        if self.mode == SceneImportMode::Replace {
+            clear_scene(world);
+        } else {
+            // Only collision layers are taken from the file, merged into the current ones
+            self.scene.resources.clear();
+            if let Some(layers) = &self.layers {
+                let remap = world
+                    .resource_mut::<CollisionLayerPresets>()
+                    .merge_imported(layers, &self.resolutions);
+                match remap {
+                    Ok(remap) if !remap.is_empty() => remap_scene_layers(&mut self.scene, &remap),
+                    Ok(_) => {}
                     Err(e) => {
-                        error!("Scene import failed: {}", e);
+                        error!("Merging collision layers of {:?} failed: {}", self.path, e);
+                        return;
                     }
                 }
             }
         }
+
+        let mut entity_map = EntityHashMap::default();
+        if let Err(e) = self.scene.write_to_world(world, &mut entity_map) {
+            error!("Failed to spawn scene: {}", e);
+            return;
+        }
+        remap_editor_references(world, &entity_map);
+        info!(
+            "Imported {:?} with {} entities ({:?})",
+            self.path,
+            entity_map.len(),
+            self.mode
+        );
+
+        // A replaced scene is now edited as the file it came from
+        if self.mode == SceneImportMode::Replace {
+            set_document(world, Some(self.path.clone()), true);
+        }
+
+        if self.mode == SceneImportMode::Place {
+            let entities: Vec<Entity> = entity_map
+                .values()
+                .copied()
+                .filter(|&entity| {
+                    world.get::<Transform>(entity).is_some()
+                        && world.get::<ChildOf>(entity).is_none()
+                })
+                .collect();
+            if entities.is_empty() {
+                return;
+            }
+            let center = entities
+                .iter()
+                .filter_map(|&entity| world.get::<Transform>(entity))
+                .map(|transform| transform.translation.truncate())
+                .sum::<Vec2>()
+                / entities.len() as f32;
+            if let Some(mut mode) = world.get_resource_mut::<NextState<ToolMode>>() {
+                mode.set(ToolMode::Select);
+            }
+            world.insert_resource(ImportPlacement { entities, center });
+        }
+    }
+}
+
+/// Despawn everything the user authored, leaving cameras, grids and other editor entities
+pub(crate) fn clear_scene(world: &mut World) {
+    let entities: Vec<Entity> = world
+        .iter_entities()
+        .filter(|entity| {
+            entity.contains::<Selectable>()
+                || entity.contains::<JointConfig>()
+                || entity.contains::<PrefabInstance>()
+                || entity.contains::<SubScene>()
+                || entity.contains::<FixedJoint>()
+                || entity.contains::<DistanceJoint>()
+                || entity.contains::<RevoluteJoint>()
+                || entity.contains::<PrismaticJoint>()
+        })
+        .map(|entity| entity.id())
+        .collect();
+    // Children, members and visualizations may already be gone with their owners
+    for entity in entities {
+        if let Ok(entity) = world.get_entity_mut(entity) {
+            entity.despawn();
+        }
+    }
+    if let Some(mut state) = world.get_resource_mut::<ColliderCreationState>() {
+        state.created_colliders.clear();
+    }
+}
+
+/// Move the collision layer bits of imported colliders to where the merged layers put them
+fn remap_scene_layers(scene: &mut DynamicScene, remap: &HashMap<u8, u8>) {
+    for entry in &mut scene.entities {
+        let Some(index) = find_reflected::<CollisionLayers>(&entry.components) else {
+            continue;
+        };
+        if let Some(layers) = CollisionLayers::from_reflect(entry.components[index].as_ref()) {
+            entry.components[index] = Box::new(CollisionLayers {
+                memberships: remap_layer_mask(layers.memberships, remap),
+                filters: remap_layer_mask(layers.filters, remap),
+            });
+        }
     }
 }
 
-/// Export scene with entities and resources
+/// Carry placed entities with the cursor; a click drops and selects them, Escape cancels
+fn place_imported_entities(
+    mut commands: Commands,
+    mut placement: ResMut<ImportPlacement>,
+    mut transform_query: Query<&mut Transform>,
+    selectable_query: Query<(), With<Selectable>>,
+    window_query: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<SpritePickingCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    egui_wants_input: Res<EguiWantsInput>,
+    mut selection: ResMut<EditorSelection>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        for &entity in &placement.entities {
+            commands.entity(entity).try_despawn();
+        }
+        commands.remove_resource::<ImportPlacement>();
+        info!("Scene placement cancelled");
+        return;
+    }
+
+    let (Ok(window), Ok((camera, camera_transform))) =
+        (window_query.single(), camera_query.single())
+    else {
+        return;
+    };
+    if egui_wants_input.wants_any_input() {
+        return;
+    }
+    let Some(cursor) = get_mouse_world_position(window, camera, camera_transform) else {
+        return;
+    };
+
+    let delta = cursor - placement.center;
+    if delta != Vec2::ZERO {
+        for &entity in &placement.entities {
+            if let Ok(mut transform) = transform_query.get_mut(entity) {
+                transform.translation += delta.extend(0.0);
+            }
+        }
+        placement.center = cursor;
+    }
+
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        *selection = EditorSelection::from_iter(
+            placement
+                .entities
+                .iter()
+                .copied()
+                .filter(|&entity| selectable_query.contains(entity)),
+        );
+        commands.remove_resource::<ImportPlacement>();
+        info!("Placed {} imported entities", placement.entities.len());
+    }
+}
+
+/// Every entity a full scene export writes
+///
+/// Colliders, anchors and joints, plus prefab instances and sub-scenes, including ones whose
+/// members failed to spawn.
+pub(crate) fn all_scene_entities(world: &World) -> Vec<Entity> {
+    world
+        .iter_entities()
+        .filter(|entity| {
+            entity.contains::<Collider>()
+                || entity.contains::<AnchorPoint>()
+                || entity.contains::<FixedJoint>()
+                || entity.contains::<DistanceJoint>()
+                || entity.contains::<RevoluteJoint>()
+                || entity.contains::<PrismaticJoint>()
+                || entity.contains::<PrefabInstance>()
+                || entity.contains::<SubScene>()
+        })
+        .map(|entity| entity.id())
+        .collect()
+}
+
+/// Write the whole scene to a file without asking for a path
+///
+/// Documents and autosaves are read back by the editor, so they always keep the editor's
+/// units; the SI option only applies to [`SceneExportEvent`]s. Returns the number of stored
+/// entities.
+pub(crate) fn save_scene(world: &World, file_path: &Path) -> Result<usize, SceneError> {
+    let entities = stored_entities(world, &all_scene_entities(world));
+    let scene = extract_scene(&entities, world);
+    write_scene(&scene, world.resource::<AppTypeRegistry>(), file_path)?;
+    Ok(entities.len())
+}
+
+/// Entities to write to a scene file
+///
+/// Members of prefab instances and sub-scenes are replaced by their outermost root.
+fn stored_entities(world: &World, entities: &[Entity]) -> Vec<Entity> {
+    let mut stored = Vec::new();
+    for &entity in entities {
+        let mut entity = entity;
+        while let Some(root) = [prefab_instance_of, sub_scene_of]
+            .into_iter()
+            .filter_map(|root_of| root_of(world, entity))
+            .find(|&root| root != entity)
+        {
+            entity = root;
+        }
+        if !stored.contains(&entity) {
+            stored.push(entity);
+        }
+    }
+    stored
+}
+
+/// Export scene with entities and resources, in meters when the SI option is on
 fn export_scene(
     entities: &[Entity],
     world: &World,
     type_registry: &AppTypeRegistry,
-    file_path: &PathBuf,
+    file_path: &Path,
 ) -> Result<(), SceneError> {
+    let mut scene = extract_scene(entities, world);
+
+    if world
+        .get_resource::<UnitSettings>()
+        .is_some_and(|settings| settings.export_si)
+    {
+        let pixels_per_meter = world
+            .get_resource::<PhysicsLengthUnit>()
+            .map(|unit| unit.0)
+            .unwrap_or(1.0);
+        convert_scene_to_si(&mut scene, world, pixels_per_meter);
+    }
+
+    write_scene(&scene, type_registry, file_path)
+}
+
+/// Extract entities and the scene resources as they are in the world
+fn extract_scene(entities: &[Entity], world: &World) -> DynamicScene {
     // Create scene builder and extract both entities and resources
-    let scene = DynamicSceneBuilder::from_world(world)
+    let mut scene = DynamicSceneBuilder::from_world(world)
         .extract_entities(entities.iter().copied())
         .allow_resource::<CollisionLayerPresets>()
         .allow_resource::<PanelState>()
         .allow_resource::<EntityInspectorState>()
         .allow_resource::<CreationProperties>()
+        // Scene-level physics settings
+        .allow_resource::<Gravity>()
+        .allow_resource::<SubstepCount>()
+        .allow_resource::<SolverConfig>()
+        .allow_resource::<PhysicsLengthUnit>()
+        .allow_resource::<SleepingThreshold>()
+        .allow_resource::<DeactivationTime>()
+        .allow_resource::<DefaultFriction>()
+        .allow_resource::<DefaultRestitution>()
+        .allow_resource::<PhysicsTimestep>()
+        .allow_resource::<UnitSettings>()
+        .allow_resource::<MeasureGuides>()
         .allow_all_components()
         .extract_resources() // This will extract all resources with ReflectResource
         .build();
 
+    // Instances keep their members' current differences from the prefab
+    for entity in &mut scene.entities {
+        if let Some(overrides) = instance_overrides(world, entity.entity) {
+            replace_component::<PrefabInstance>(
+                &mut entity.components,
+                world,
+                entity.entity,
+                |instance| instance.overrides = overrides,
+            );
+        }
+    }
+    scene
+}
+
+/// Serialize a scene to a RON file
+fn write_scene(
+    scene: &DynamicScene,
+    type_registry: &AppTypeRegistry,
+    file_path: &Path,
+) -> Result<(), SceneError> {
     // Serialize to RON format
     let registry = type_registry.read();
     let scene_data = scene
@@ -220,37 +608,329 @@ fn export_scene(
     Ok(())
 }
 
-/// Import scene with entities and resources using commands.queue
-fn import_scene_with_commands(
-    file_path: &PathBuf,
-    type_registry: &AppTypeRegistry,
-    commands: &mut Commands,
-) -> Result<(), SceneError> {
-    // Read file content
-    let file_content =
-        std::fs::read_to_string(file_path).map_err(|e| SceneError::FileError(e.to_string()))?;
+/// Rewrite world lengths in an extracted scene as meters
+///
+/// Values are re-read from the world as concrete types, scaled and put back in place of the
+/// reflected copies. Forces and accelerations scale with length and torques with its square.
+/// Joint anchors, rest lengths and distance limits are lengths too; joint angles are left as
+/// they are.
+fn convert_scene_to_si(scene: &mut DynamicScene, world: &World, pixels_per_meter: f32) {
+    if pixels_per_meter <= 0.0 {
+        return;
+    }
+    let s = 1.0 / pixels_per_meter;
 
-    // Deserialize scene
-    let registry = type_registry.read();
-    let mut deserializer = ron::de::Deserializer::from_str(&file_content)
+    for entity in &mut scene.entities {
+        let id = entity.entity;
+        let components = &mut entity.components;
+
+        replace_component::<Transform>(components, world, id, |transform| {
+            transform.translation.x *= s;
+            transform.translation.y *= s;
+        });
+        replace_component::<Collider>(components, world, id, |collider| {
+            let mut scaled = Collider::from(collider.shape().clone());
+            scaled.set_scale(Vec2::splat(s), 10);
+            *collider = Collider::from(scaled.shape_scaled().clone());
+        });
+        replace_component::<LinearVelocity>(components, world, id, |v| v.0 *= s);
+        replace_component::<InitialVelocity>(components, world, id, |v| v.linear *= s);
+        replace_component::<MaxLinearSpeed>(components, world, id, |v| v.0 *= s);
+        replace_component::<Position>(components, world, id, |p| p.0 *= s);
+        replace_component::<ConstantForce>(components, world, id, |f| f.0 *= s);
+        replace_component::<ConstantLocalForce>(components, world, id, |f| f.0 *= s);
+        replace_component::<ConstantLinearAcceleration>(components, world, id, |a| a.0 *= s);
+        replace_component::<ConstantLocalLinearAcceleration>(components, world, id, |a| a.0 *= s);
+        replace_component::<ConstantTorque>(components, world, id, |t| t.0 *= s * s);
+        replace_component::<CenterOfMass>(components, world, id, |c| c.0 *= s);
+        replace_component::<AngularInertia>(components, world, id, |i| i.0 *= s * s);
+        // Density is mass per area
+        replace_component::<ColliderDensity>(components, world, id, |d| d.0 /= s * s);
+        replace_component::<CollisionMargin>(components, world, id, |m| m.0 *= s);
+        replace_component::<SpeculativeMargin>(components, world, id, |m| m.0 *= s);
+        replace_component::<PolygonSource>(components, world, id, |source| {
+            source
+                .contours
+                .iter_mut()
+                .flatten()
+                .for_each(|point| *point *= s);
+        });
+        replace_component::<SketchStroke>(components, world, id, |stroke| {
+            stroke.points.iter_mut().for_each(|point| *point *= s);
+            stroke.tolerance *= s;
+        });
+        replace_component::<TileLayer>(components, world, id, |layer| layer.cell_size *= s);
+        replace_component::<KinematicPath>(components, world, id, |path| {
+            for keyframe in &mut path.keyframes {
+                keyframe.position *= s;
+            }
+        });
+        replace_component::<ForceField>(components, world, id, |field| match &mut field.kind {
+            ForceFieldKind::Buoyancy { fluid_density, .. } => *fluid_density /= s * s,
+            ForceFieldKind::Gravity { gravity } => *gravity *= s,
+            _ => field.strength *= s,
+        });
+        replace_component::<AnchorPoint>(components, world, id, |anchor| {
+            anchor.local_anchor_position *= s;
+        });
+        replace_component::<JointConfig>(components, world, id, |config| {
+            scale_joint_lengths(&mut config.joint_config_details, s);
+        });
+        replace_component::<FixedJoint>(components, world, id, |joint| {
+            joint.local_anchor1 *= s;
+            joint.local_anchor2 *= s;
+        });
+        replace_component::<DistanceJoint>(components, world, id, |joint| {
+            joint.local_anchor1 *= s;
+            joint.local_anchor2 *= s;
+            joint.length_limits.min *= s;
+            joint.length_limits.max *= s;
+        });
+        replace_component::<PrismaticJoint>(components, world, id, |joint| {
+            joint.local_anchor1 *= s;
+            joint.local_anchor2 *= s;
+            if let Some(limits) = &mut joint.free_axis_limits {
+                limits.min *= s;
+                limits.max *= s;
+            }
+        });
+        replace_component::<RevoluteJoint>(components, world, id, |joint| {
+            joint.local_anchor1 *= s;
+            joint.local_anchor2 *= s;
+        });
+    }
+
+    let resources = &mut scene.resources;
+    replace_resource::<Gravity>(resources, world, |g| g.0 *= s);
+    replace_resource::<SleepingThreshold>(resources, world, |t| t.linear *= s);
+    replace_resource::<PhysicsLengthUnit>(resources, world, |unit| unit.0 = 1.0);
+    replace_resource::<UnitSettings>(resources, world, |settings| settings.export_si = false);
+    replace_resource::<MeasureGuides>(resources, world, |guides| {
+        guides.vertical.iter_mut().for_each(|x| *x *= s);
+        guides.horizontal.iter_mut().for_each(|y| *y *= s);
+    });
+}
+
+/// Scale the rest length and distance limits stored in a joint configuration
+fn scale_joint_lengths(details: &mut JointConfigurationEnum, s: f32) {
+    match details {
+        JointConfigurationEnum::Distance { config, .. } => {
+            config.rest_length *= s;
+            config.min_distance = config.min_distance.map(|min| min * s);
+            config.max_distance = config.max_distance.map(|max| max * s);
+        }
+        JointConfigurationEnum::Prismatic { config, .. } => {
+            config.min_distance = config.min_distance.map(|min| min * s);
+            config.max_distance = config.max_distance.map(|max| max * s);
+        }
+        JointConfigurationEnum::Fixed { .. } | JointConfigurationEnum::Revolute { .. } => {}
+    }
+}
+
+/// Index of the reflected value whose concrete type is `T`
+pub(crate) fn find_reflected<T: 'static>(values: &[Box<dyn PartialReflect>]) -> Option<usize> {
+    values.iter().position(|value| {
+        value
+            .get_represented_type_info()
+            .is_some_and(|info| info.type_id() == TypeId::of::<T>())
+    })
+}
+
+/// Replace an extracted component with a modified copy of the world's value
+pub(crate) fn replace_component<T: Component + Reflect + Clone>(
+    components: &mut [Box<dyn PartialReflect>],
+    world: &World,
+    entity: Entity,
+    modify: impl FnOnce(&mut T),
+) {
+    let (Some(index), Some(value)) = (find_reflected::<T>(components), world.get::<T>(entity))
+    else {
+        return;
+    };
+    let mut value = value.clone();
+    modify(&mut value);
+    components[index] = Box::new(value);
+}
+
+/// Replace an extracted resource with a modified copy of the world's value
+fn replace_resource<T: Resource + Reflect + Clone>(
+    resources: &mut [Box<dyn PartialReflect>],
+    world: &World,
+    modify: impl FnOnce(&mut T),
+) {
+    let (Some(index), Some(value)) = (find_reflected::<T>(resources), world.get_resource::<T>())
+    else {
+        return;
+    };
+    let mut value = value.clone();
+    modify(&mut value);
+    resources[index] = Box::new(value);
+}
+
+/// Deserialize a scene from RON
+pub(crate) fn parse_scene(text: &str, registry: &TypeRegistry) -> Result<DynamicScene, SceneError> {
+    let mut deserializer = ron::de::Deserializer::from_str(text)
         .map_err(|e| SceneError::SerializationError(e.to_string()))?;
     let scene_deserializer = SceneDeserializer {
-        type_registry: &registry,
+        type_registry: registry,
     };
-    let dynamic_scene = scene_deserializer
+    scene_deserializer
         .deserialize(&mut deserializer)
-        .map_err(|e| SceneError::SerializationError(e.to_string()))?;
+        .map_err(|e| SceneError::SerializationError(e.to_string()))
+}
 
-    info!(
-        "Scene deserialized successfully with {} entities and resources",
-        dynamic_scene.entities.len()
-    );
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collider_tools::joint_config::DistanceJointConfig;
 
-    // Queue the scene for spawning using commands
-    commands.queue(SpawnDynamicSceneCommand {
-        scene: dynamic_scene,
-        entity_map: None,
-    });
+    /// Concrete copy of a component stored for `entity` in an extracted scene
+    fn scene_component<T: FromReflect>(scene: &DynamicScene, entity: Entity) -> T {
+        let components = &scene
+            .entities
+            .iter()
+            .find(|stored| stored.entity == entity)
+            .unwrap()
+            .components;
+        T::from_reflect(components[find_reflected::<T>(components).unwrap()].as_ref()).unwrap()
+    }
 
-    Ok(())
+    #[test]
+    fn test_saved_scene_keeps_editor_units() {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<Transform>();
+            registry.register::<AnchorPoint>();
+        }
+        world.insert_resource(type_registry.clone());
+        world.insert_resource(UnitSettings {
+            export_si: true,
+            ..default()
+        });
+        world.insert_resource(PhysicsLengthUnit(100.0));
+        let body = world.spawn(Transform::default()).id();
+        let anchor = world
+            .spawn((
+                Transform::from_xyz(250.0, 0.0, 0.0),
+                AnchorPoint {
+                    parent_entity: body,
+                    ..default()
+                },
+            ))
+            .id();
+
+        // Only explicit exports are converted to meters
+        let path = std::env::temp_dir().join(format!("saved_scene_{}.scn.ron", std::process::id()));
+        assert_eq!(save_scene(&world, &path).unwrap(), 1);
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let scene = parse_scene(&text, &type_registry.read()).unwrap();
+        let transform: Transform = scene_component(&scene, anchor);
+        assert_eq!(transform.translation, Vec3::new(250.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_si_export_scales_joint_anchors_and_limits() {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<Transform>();
+            registry.register::<Position>();
+            registry.register::<ConstantLocalForce>();
+            registry.register::<ConstantLinearAcceleration>();
+            registry.register::<ConstantTorque>();
+            registry.register::<AnchorPoint>();
+            registry.register::<JointConfig>();
+            registry.register::<DistanceJoint>();
+        }
+        world.insert_resource(type_registry);
+        let body_a = world.spawn(Transform::from_xyz(100.0, 0.0, 0.0)).id();
+        let body_b = world
+            .spawn((
+                Transform::from_xyz(300.0, 0.0, 0.0),
+                Position(Vec2::new(300.0, 0.0)),
+                ConstantLocalForce(Vec2::new(50.0, 0.0)),
+                ConstantLinearAcceleration(Vec2::new(0.0, -980.0)),
+                ConstantTorque(2000.0),
+            ))
+            .id();
+        let anchor = world
+            .spawn(AnchorPoint {
+                local_anchor_position: Vec2::new(20.0, -10.0),
+                parent_entity: body_a,
+                ..default()
+            })
+            .id();
+        let joint = world
+            .spawn((
+                JointConfig {
+                    anchor_a: anchor,
+                    anchor_b: body_b,
+                    anchor_a_is_anchor: true,
+                    anchor_b_is_anchor: false,
+                    parent_entity: body_a,
+                    child_entity: body_b,
+                    joint_config_details: JointConfigurationEnum::Distance {
+                        common: default(),
+                        config: DistanceJointConfig {
+                            rest_length: 200.0,
+                            min_distance: Some(150.0),
+                            max_distance: Some(250.0),
+                            ..default()
+                        },
+                        advanced: default(),
+                    },
+                },
+                DistanceJoint::new(body_a, body_b)
+                    .with_local_anchor1(Vec2::new(20.0, -10.0))
+                    .with_limits(150.0, 250.0),
+            ))
+            .id();
+
+        let mut scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entities([body_a, body_b, anchor, joint].into_iter())
+            .build();
+        convert_scene_to_si(&mut scene, &world, 100.0);
+
+        let transform: Transform = scene_component(&scene, body_b);
+        assert!(
+            transform
+                .translation
+                .abs_diff_eq(Vec3::new(3.0, 0.0, 0.0), 1e-5)
+        );
+        let position: Position = scene_component(&scene, body_b);
+        assert!(position.0.abs_diff_eq(Vec2::new(3.0, 0.0), 1e-5));
+        let force: ConstantLocalForce = scene_component(&scene, body_b);
+        assert!(force.0.abs_diff_eq(Vec2::new(0.5, 0.0), 1e-5));
+        let gravity: ConstantLinearAcceleration = scene_component(&scene, body_b);
+        assert!(gravity.0.abs_diff_eq(Vec2::new(0.0, -9.8), 1e-4));
+        let torque: ConstantTorque = scene_component(&scene, body_b);
+        assert!((torque.0 - 0.2).abs() < 1e-5);
+        let anchor: AnchorPoint = scene_component(&scene, anchor);
+        assert!(
+            anchor
+                .local_anchor_position
+                .abs_diff_eq(Vec2::new(0.2, -0.1), 1e-5)
+        );
+
+        let config: JointConfig = scene_component(&scene, joint);
+        let JointConfigurationEnum::Distance { config, .. } = config.joint_config_details else {
+            panic!("joint type changed");
+        };
+        assert!((config.rest_length - 2.0).abs() < 1e-5);
+        assert!((config.min_distance.unwrap() - 1.5).abs() < 1e-5);
+        assert!((config.max_distance.unwrap() - 2.5).abs() < 1e-5);
+
+        let physics_joint: DistanceJoint = scene_component(&scene, joint);
+        assert!(
+            physics_joint
+                .local_anchor1
+                .abs_diff_eq(Vec2::new(0.2, -0.1), 1e-5)
+        );
+        assert!((physics_joint.length_limits.min - 1.5).abs() < 1e-5);
+        assert!((physics_joint.length_limits.max - 2.5).abs() < 1e-5);
+    }
 }
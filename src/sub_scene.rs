@@ -0,0 +1,783 @@
+//! Sub-scenes: other scene files placed in the current scene by reference
+//!
+//! A [`SubScene`] root names an exported scene file and places it with its [`Transform`],
+//! including scale. The bodies, anchors and joints of the file are spawned relative to the root
+//! as [`SubSceneMemberOf`] members, and scene export writes the root instead of the members.
+//! Sub-scenes can nest; a file that would end up inside itself is not spawned.
+//!
+//! Members are read-only: they cannot be picked, selected or transformed, and clicking one
+//! selects the root, which moves them as a whole. Opening a sub-scene for editing unlocks its
+//! members in place. Saving writes them back to the file relative to the root, keeping the
+//! file's resources, and respawns every sub-scene of that file; discarding respawns from the
+//! file as it is on disk.
+//!
+//! Members are identified by the [`PrefabKey`] stored in the file. Files that were never saved
+//! as a sub-scene get keys from their entity ids, which stay the same as long as the file does.
+//! A joint from a parent-scene body to a sub-scene body or anchor records the keys of its
+//! sub-scene ends in [`SubSceneJointEnds`], and is reconnected to the new members whenever the
+//! sub-scene is spawned again.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::reflect::FromReflect;
+use bevy::scene::{DynamicEntity, DynamicScene};
+use rfd::FileDialog;
+
+use crate::collider_tools::debug_render::EditorGizmoConfigGroup;
+use crate::collider_tools::debug_render::anchor::AnchorPoint;
+use crate::collider_tools::debug_render::joint::{JointConfig, JointVisualization, VisualizedBy};
+use crate::collider_tools::joint::{RebuildJoint, rebuild_marked_joints};
+use crate::collider_tools::utils::collider_world_aabb;
+use crate::prefab::{
+    PrefabInstance, PrefabKey, PrefabMemberOf, contents_scene, instance_overrides, key_holder,
+    spawn_contents,
+};
+use crate::scene_export_import::{SceneError, find_reflected, parse_scene, replace_component};
+use crate::selection::{EditorSelection, Selectable};
+use crate::transform_gizmos::GizmoTransformable;
+use crate::utils::DragCancelClick;
+
+/// Sub-scene plugin
+pub struct SubScenePlugin;
+
+impl Plugin for SubScenePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SubScene>()
+            .register_type::<SubSceneMemberOf>()
+            .register_type::<JointToSubScene>()
+            .register_type::<SubSceneJointEnds>()
+            .add_event::<SubSceneEvent>()
+            .add_observer(select_sub_scene_on_click)
+            .add_systems(
+                Update,
+                (
+                    handle_sub_scene_events,
+                    spawn_sub_scenes,
+                    follow_sub_scenes,
+                    update_member_locks,
+                    link_sub_scene_joints,
+                )
+                    .chain()
+                    .before(rebuild_marked_joints),
+            )
+            .add_systems(Update, draw_sub_scene_bounds);
+    }
+}
+
+/// Scene file placed by reference
+#[derive(Component, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Component, Default)]
+#[require(Transform, Selectable, GizmoTransformable)]
+pub struct SubScene {
+    /// Scene file the members are spawned from
+    pub source: String,
+}
+
+/// Links a body, anchor or joint visualization to the sub-scene it was spawned from
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+#[relationship(relationship_target = SubSceneMembers)]
+pub struct SubSceneMemberOf(pub Entity);
+
+/// Members of a sub-scene, despawned together with it.
+/// This component is updated reactively and should not be modified directly.
+#[derive(Component, Debug, Default)]
+#[relationship_target(relationship = SubSceneMemberOf, linked_spawn)]
+pub struct SubSceneMembers(Vec<Entity>);
+
+impl SubSceneMembers {
+    /// Get the member entities
+    pub fn get(&self) -> &[Entity] {
+        &self.0
+    }
+}
+
+/// Links a parent-scene joint to the sub-scene holding some of its ends
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+#[relationship(relationship_target = SubSceneJoints)]
+pub struct JointToSubScene(pub Entity);
+
+/// Parent-scene joints attached to a sub-scene.
+/// This component is updated reactively and should not be modified directly.
+#[derive(Component, Debug, Default)]
+#[relationship_target(relationship = JointToSubScene)]
+pub struct SubSceneJoints(Vec<Entity>);
+
+impl SubSceneJoints {
+    /// Get the joint entities
+    pub fn get(&self) -> &[Entity] {
+        &self.0
+    }
+}
+
+/// [`PrefabKey`]s of the joint ends that lie in the linked sub-scene
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct SubSceneJointEnds {
+    /// Key of [`JointConfig::anchor_a`]
+    pub anchor_a: Option<u32>,
+    /// Key of [`JointConfig::anchor_b`]
+    pub anchor_b: Option<u32>,
+    /// Key of [`JointConfig::parent_entity`]
+    pub parent: Option<u32>,
+    /// Key of [`JointConfig::child_entity`]
+    pub child: Option<u32>,
+}
+
+/// Sub-scene whose members are unlocked for editing
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SubSceneEditing;
+
+/// Root transform the members of a sub-scene were placed with, present once they are spawned
+#[derive(Component, Debug, Clone, Copy)]
+struct SubScenePlacement(Transform);
+
+/// Member made read-only, remembering whether it could be transformed
+#[derive(Component, Debug, Clone, Copy)]
+struct LockedMember {
+    transformable: bool,
+}
+
+/// Sub-scene command
+#[derive(Event, Debug, Clone)]
+pub enum SubSceneEvent {
+    /// Reference a scene file at the view center, picking it with a dialog when not given
+    Add(Option<PathBuf>),
+    /// Unlock the members for editing
+    Open(Entity),
+    /// Write the members back to the file and lock them again
+    Save(Entity),
+    /// Drop unsaved changes and spawn the members from the file again
+    Discard(Entity),
+}
+
+/// Handle sub-scene commands
+fn handle_sub_scene_events(world: &mut World) {
+    let events: Vec<SubSceneEvent> = world
+        .resource_mut::<Events<SubSceneEvent>>()
+        .drain()
+        .collect();
+
+    for event in events {
+        match event {
+            SubSceneEvent::Add(path) => {
+                let Some(path) = path.or_else(|| {
+                    FileDialog::new()
+                        .add_filter("Scene files", &["ron", "scn"])
+                        .add_filter("All files", &["*"])
+                        .set_title("Reference Scene")
+                        .pick_file()
+                }) else {
+                    continue;
+                };
+                let position = world
+                    .query_filtered::<&GlobalTransform, With<Camera2d>>()
+                    .iter(world)
+                    .next()
+                    .map(|transform| transform.translation().truncate())
+                    .unwrap_or_default();
+                match add_sub_scene(world, &path.to_string_lossy(), position) {
+                    Ok(root) => {
+                        info!("Referenced scene {:?} as {}", path, root);
+                        if let Some(mut selection) = world.get_resource_mut::<EditorSelection>() {
+                            selection.set(root);
+                        }
+                    }
+                    Err(e) => error!("Referencing scene {:?} failed: {}", path, e),
+                }
+            }
+            SubSceneEvent::Open(root) => {
+                if let Ok(mut entity) = world.get_entity_mut(root) {
+                    entity.insert(SubSceneEditing);
+                }
+            }
+            SubSceneEvent::Save(root) => match save_sub_scene(world, root) {
+                Ok(()) => info!("Saved sub-scene {}", root),
+                Err(e) => error!("Saving sub-scene {} failed: {}", root, e),
+            },
+            SubSceneEvent::Discard(root) => {
+                if let Ok(mut entity) = world.get_entity_mut(root) {
+                    entity.remove::<SubSceneEditing>();
+                }
+                if let Err(e) = respawn_sub_scene(world, root) {
+                    error!("Reloading sub-scene {} failed: {}", root, e);
+                }
+            }
+        }
+    }
+}
+
+/// Spawn the members of sub-scenes loaded from a scene file
+fn spawn_sub_scenes(
+    mut commands: Commands,
+    root_query: Query<(Entity, &Transform), (With<SubScene>, Without<SubScenePlacement>)>,
+) {
+    for (root, transform) in &root_query {
+        commands.entity(root).insert(SubScenePlacement(*transform));
+        commands.queue(move |world: &mut World| {
+            if let Err(e) = spawn_members(world, root) {
+                error!("Spawning sub-scene {} failed: {}", root, e);
+            }
+        });
+    }
+}
+
+/// Carry the members of a sub-scene along when its root is moved, rotated or scaled
+fn follow_sub_scenes(
+    mut transforms: ParamSet<(
+        Query<
+            (&Transform, &mut SubScenePlacement, &SubSceneMembers),
+            (With<SubScene>, Changed<Transform>),
+        >,
+        Query<&mut Transform, Without<ChildOf>>,
+    )>,
+) {
+    // Nested sub-scenes are members too, so the moves are collected before they are applied
+    let mut moves = Vec::new();
+    for (transform, mut placement, members) in &mut transforms.p0() {
+        if placement.0 == *transform {
+            continue;
+        }
+        let delta = transform.compute_matrix() * placement.0.compute_matrix().inverse();
+        moves.push((delta, members.get().to_vec()));
+        placement.0 = *transform;
+    }
+
+    let mut member_query = transforms.p1();
+    for (delta, members) in moves {
+        for member in members {
+            if let Ok(mut transform) = member_query.get_mut(member) {
+                *transform = Transform::from_matrix(delta * transform.compute_matrix());
+            }
+        }
+    }
+}
+
+/// Outermost sub-scene that keeps an entity read-only, if any
+///
+/// Looks through prefab instances, so the members of a prefab placed inside a sub-scene are
+/// locked along with it.
+fn locking_sub_scene(
+    entity: Entity,
+    owner_query: &Query<(
+        Option<&PrefabMemberOf>,
+        Option<&SubSceneMemberOf>,
+        Has<SubScene>,
+        Has<SubSceneEditing>,
+    )>,
+) -> Option<Entity> {
+    let mut locking = None;
+    let mut current = entity;
+    while let Ok((prefab, sub_scene, _, _)) = owner_query.get(current) {
+        let Some(owner) = prefab.map(|of| of.0).or(sub_scene.map(|of| of.0)) else {
+            break;
+        };
+        if let Ok((_, _, true, false)) = owner_query.get(owner) {
+            locking = Some(owner);
+        }
+        current = owner;
+    }
+    locking
+}
+
+/// Make the members of closed sub-scenes read-only and unlock those of open ones
+fn update_member_locks(
+    mut commands: Commands,
+    unlocked_query: Query<
+        (Entity, Has<GizmoTransformable>),
+        (
+            With<Selectable>,
+            Or<(With<PrefabMemberOf>, With<SubSceneMemberOf>)>,
+        ),
+    >,
+    locked_query: Query<(Entity, &LockedMember)>,
+    owner_query: Query<(
+        Option<&PrefabMemberOf>,
+        Option<&SubSceneMemberOf>,
+        Has<SubScene>,
+        Has<SubSceneEditing>,
+    )>,
+    mut selection: ResMut<EditorSelection>,
+) {
+    for (entity, transformable) in &unlocked_query {
+        if locking_sub_scene(entity, &owner_query).is_some() {
+            commands
+                .entity(entity)
+                .remove::<(Selectable, GizmoTransformable)>()
+                .insert(LockedMember { transformable });
+            if selection.contains(entity) {
+                selection.remove(entity);
+            }
+        }
+    }
+    for (entity, locked) in &locked_query {
+        if locking_sub_scene(entity, &owner_query).is_none() {
+            let mut entity = commands.entity(entity);
+            entity.remove::<LockedMember>().insert(Selectable);
+            if locked.transformable {
+                entity.insert(GizmoTransformable);
+            }
+        }
+    }
+}
+
+/// Select the sub-scene root when a read-only member is clicked
+fn select_sub_scene_on_click(
+    trigger: Trigger<Pointer<DragCancelClick>>,
+    mut selection: ResMut<EditorSelection>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    locked_query: Query<(), With<LockedMember>>,
+    owner_query: Query<(
+        Option<&PrefabMemberOf>,
+        Option<&SubSceneMemberOf>,
+        Has<SubScene>,
+        Has<SubSceneEditing>,
+    )>,
+) {
+    if trigger.button != PointerButton::Primary || !locked_query.contains(trigger.target()) {
+        return;
+    }
+    let Some(root) = locking_sub_scene(trigger.target(), &owner_query) else {
+        return;
+    };
+    if keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        selection.toggle(root);
+    } else {
+        selection.set(root);
+    }
+}
+
+/// Record which ends of parent-scene joints lie in a sub-scene
+fn link_sub_scene_joints(
+    mut commands: Commands,
+    joint_query: Query<(Entity, &JointConfig), Changed<JointConfig>>,
+    member_query: Query<(&SubSceneMemberOf, &PrefabKey)>,
+    visualized_by_query: Query<&VisualizedBy>,
+) {
+    for (joint, config) in &joint_query {
+        // Joints between members are stored in the sub-scene file itself
+        let visualization = visualized_by_query
+            .get(joint)
+            .ok()
+            .and_then(VisualizedBy::get);
+        if visualization.is_some_and(|visualization| member_query.contains(visualization)) {
+            continue;
+        }
+        let ends = [
+            config.anchor_a,
+            config.anchor_b,
+            config.parent_entity,
+            config.child_entity,
+        ]
+        .map(|end| member_query.get(end).ok());
+        let Some(root) = ends.iter().flatten().map(|(member, _)| member.0).next() else {
+            commands
+                .entity(joint)
+                .remove::<(JointToSubScene, SubSceneJointEnds)>();
+            continue;
+        };
+        let key = |index: usize| {
+            ends[index]
+                .filter(|(member, _)| member.0 == root)
+                .map(|(_, key)| key.0)
+        };
+        commands.entity(joint).insert((
+            JointToSubScene(root),
+            SubSceneJointEnds {
+                anchor_a: key(0),
+                anchor_b: key(1),
+                parent: key(2),
+                child: key(3),
+            },
+        ));
+    }
+}
+
+/// Outline each sub-scene, highlighting the selected ones and those open for editing
+fn draw_sub_scene_bounds(
+    mut gizmos: Gizmos<EditorGizmoConfigGroup>,
+    root_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &SubSceneMembers,
+            Has<SubSceneEditing>,
+        ),
+        With<SubScene>,
+    >,
+    body_query: Query<(&Collider, &GlobalTransform), Without<AnchorPoint>>,
+    selection: Res<EditorSelection>,
+) {
+    for (root, transform, members, editing) in &root_query {
+        let bounds = members
+            .get()
+            .iter()
+            .filter_map(|&member| body_query.get(member).ok())
+            .map(|(collider, body_transform)| {
+                let aabb = collider_world_aabb(collider, body_transform);
+                Rect::from_corners(aabb.min, aabb.max)
+            })
+            .reduce(|bounds, rect| bounds.union(rect));
+        let color = if editing {
+            Color::srgb(1.0, 0.6, 0.2)
+        } else if selection.contains(root) {
+            Color::srgb(0.4, 0.8, 1.0)
+        } else {
+            Color::srgba(0.6, 0.6, 0.7, 0.6)
+        };
+        if let Some(bounds) = bounds {
+            gizmos.rect_2d(
+                Isometry2d::from_translation(bounds.center()),
+                bounds.size() + Vec2::splat(8.0),
+                color,
+            );
+        }
+        let origin = transform.translation().truncate();
+        gizmos.cross_2d(Isometry2d::from_translation(origin), 12.0, color);
+    }
+}
+
+/// Sub-scene an entity was spawned from; joints are found through their visualization
+pub fn sub_scene_of(world: &World, entity: Entity) -> Option<Entity> {
+    world
+        .get::<SubSceneMemberOf>(key_holder(world, entity))
+        .map(|member| member.0)
+}
+
+/// Members of a sub-scene
+fn members_of(world: &World, root: Entity) -> Vec<Entity> {
+    world
+        .get::<SubSceneMembers>(root)
+        .map(|members| members.get().to_vec())
+        .unwrap_or_default()
+}
+
+/// Spawn a sub-scene root for a scene file at `position`, with its members
+fn add_sub_scene(world: &mut World, source: &str, position: Vec2) -> Result<Entity, SceneError> {
+    let root = world
+        .spawn((
+            Name::new(format!("Sub-scene {}", sub_scene_name(source))),
+            Transform::from_translation(position.extend(0.0)),
+            SubScene {
+                source: source.to_string(),
+            },
+        ))
+        .id();
+    spawn_members(world, root).inspect_err(|_| {
+        world.despawn(root);
+    })?;
+    Ok(root)
+}
+
+/// Spawn the members of a sub-scene from its file and reconnect the joints attached to it
+fn spawn_members(world: &mut World, root: Entity) -> Result<(), SceneError> {
+    let source = world
+        .get::<SubScene>(root)
+        .map(|sub_scene| sub_scene.source.clone())
+        .ok_or(SceneError::NoEntitiesFound)?;
+    let mut outer = world.get::<SubSceneMemberOf>(root).map(|member| member.0);
+    while let Some(entity) = outer {
+        if world
+            .get::<SubScene>(entity)
+            .is_some_and(|sub_scene| sub_scene.source == source)
+        {
+            return Err(SceneError::FileError(format!("{source} contains itself")));
+        }
+        outer = world.get::<SubSceneMemberOf>(entity).map(|member| member.0);
+    }
+
+    let text =
+        std::fs::read_to_string(&source).map_err(|e| SceneError::FileError(e.to_string()))?;
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let mut scene = parse_scene(&text, &type_registry.read())?;
+    assign_missing_keys(&mut scene);
+
+    let frame = world.get::<Transform>(root).copied().unwrap_or_default();
+    spawn_contents(world, scene, &frame, SubSceneMemberOf(root))?;
+    reconnect_joints(world, root);
+    world.entity_mut(root).insert(SubScenePlacement(frame));
+    Ok(())
+}
+
+/// Despawn and spawn again the members of a sub-scene
+fn respawn_sub_scene(world: &mut World, root: Entity) -> Result<(), SceneError> {
+    // Anchors go with their bodies and joints with their visualizations
+    for member in members_of(world, root) {
+        if let Ok(entity) = world.get_entity_mut(member) {
+            entity.despawn();
+        }
+    }
+    world.flush();
+    spawn_members(world, root)
+}
+
+/// Give every entity of a scene without a [`PrefabKey`] one derived from its id in the file
+fn assign_missing_keys(scene: &mut DynamicScene) {
+    let key_of = |entry: &DynamicEntity| {
+        find_reflected::<PrefabKey>(&entry.components)
+            .and_then(|index| PrefabKey::from_reflect(entry.components[index].as_ref()))
+    };
+    let first_free = scene
+        .entities
+        .iter()
+        .filter_map(key_of)
+        .map(|key| key.0 + 1)
+        .max()
+        .unwrap_or(0);
+    for entry in &mut scene.entities {
+        if key_of(entry).is_none() {
+            let key = PrefabKey(first_free + entry.entity.index());
+            entry.components.push(Box::new(key));
+        }
+    }
+}
+
+/// Point the joints attached to a sub-scene at its current members
+fn reconnect_joints(world: &mut World, root: Entity) {
+    let keyed: HashMap<u32, Entity> = members_of(world, root)
+        .into_iter()
+        .filter_map(|member| Some((world.get::<PrefabKey>(member)?.0, member)))
+        .collect();
+    let joints = world
+        .get::<SubSceneJoints>(root)
+        .map(|joints| joints.get().to_vec())
+        .unwrap_or_default();
+
+    for joint in joints {
+        let (Some(ends), Some(config)) = (
+            world.get::<SubSceneJointEnds>(joint).copied(),
+            world.get::<JointConfig>(joint).cloned(),
+        ) else {
+            continue;
+        };
+        let find = |key: Option<u32>, current: Entity| match key {
+            Some(key) => keyed.get(&key).copied().unwrap_or_else(|| {
+                warn!(
+                    "Sub-scene {} has no member {} for joint {}",
+                    root, key, joint
+                );
+                current
+            }),
+            None => current,
+        };
+        let config = JointConfig {
+            anchor_a: find(ends.anchor_a, config.anchor_a),
+            anchor_b: find(ends.anchor_b, config.anchor_b),
+            parent_entity: find(ends.parent, config.parent_entity),
+            child_entity: find(ends.child, config.child_entity),
+            ..config
+        };
+
+        for (anchor, is_anchor) in [
+            (config.anchor_a, config.anchor_a_is_anchor),
+            (config.anchor_b, config.anchor_b_is_anchor),
+        ] {
+            if let Some(mut point) = world.get_mut::<AnchorPoint>(anchor).filter(|_| is_anchor) {
+                point.in_joint = true;
+            }
+        }
+        let visualization = world.get::<VisualizedBy>(joint).and_then(VisualizedBy::get);
+        if let Some(mut visualization) =
+            visualization.and_then(|entity| world.get_mut::<JointVisualization>(entity))
+        {
+            visualization.anchor_a = config.anchor_a;
+            visualization.anchor_b = config.anchor_b;
+        }
+        world.entity_mut(joint).insert((config, RebuildJoint));
+    }
+}
+
+/// Write the members of a sub-scene to its file and respawn every sub-scene of that file
+fn save_sub_scene(world: &mut World, root: Entity) -> Result<(), SceneError> {
+    let source = world
+        .get::<SubScene>(root)
+        .map(|sub_scene| sub_scene.source.clone())
+        .ok_or(SceneError::NoEntitiesFound)?;
+    let frame = world.get::<Transform>(root).copied().unwrap_or_default();
+    // Bodies and nested roots; anchors and joints come along with the bodies
+    let placed: Vec<Entity> = members_of(world, root)
+        .into_iter()
+        .filter(|&member| {
+            world.get::<Transform>(member).is_some()
+                && world.get::<AnchorPoint>(member).is_none()
+                && world.get::<JointVisualization>(member).is_none()
+        })
+        .collect();
+
+    let (mut scene, keys) = contents_scene(world, &placed, &frame)?;
+    for entry in &mut scene.entities {
+        if let Some(overrides) = instance_overrides(world, entry.entity) {
+            replace_component::<PrefabInstance>(
+                &mut entry.components,
+                world,
+                entry.entity,
+                |instance| instance.overrides = overrides,
+            );
+        }
+    }
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = type_registry.read();
+    // Scene settings stay as the file had them
+    if let Some(previous) = std::fs::read_to_string(&source)
+        .ok()
+        .and_then(|text| parse_scene(&text, &registry).ok())
+    {
+        scene.resources = previous.resources;
+    }
+    let text = scene
+        .serialize(&registry)
+        .map_err(|e| SceneError::SerializationError(e.to_string()))?;
+    drop(registry);
+    std::fs::write(&source, text).map_err(|e| SceneError::FileError(e.to_string()))?;
+
+    // Anchors and joints added while editing became part of the file
+    for (entity, key) in keys {
+        world
+            .entity_mut(entity)
+            .insert((PrefabKey(key), SubSceneMemberOf(root)));
+    }
+    world.entity_mut(root).remove::<SubSceneEditing>();
+
+    let roots: Vec<Entity> = world
+        .iter_entities()
+        .filter(|entity| entity.contains::<SubScenePlacement>())
+        .filter(|entity| {
+            entity
+                .get::<SubScene>()
+                .is_some_and(|sub_scene| sub_scene.source == source)
+        })
+        .map(|entity| entity.id())
+        .collect();
+    for other in roots {
+        if other != root && world.get::<SubSceneEditing>(other).is_some() {
+            warn!(
+                "Sub-scene {} is open for editing and was not reloaded",
+                other
+            );
+            continue;
+        }
+        respawn_sub_scene(world, other)?;
+    }
+    Ok(())
+}
+
+/// Display name of a sub-scene file
+pub fn sub_scene_name(source: &str) -> String {
+    Path::new(source).file_stem().map_or_else(
+        || source.to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn test_sub_scene_joint_reconnects_after_respawn() {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<Transform>();
+            registry.register::<PrefabKey>();
+            registry.register::<ColliderDensity>();
+        }
+        world.insert_resource(type_registry.clone());
+
+        // A sub-scene file with a body at its origin, saved without keys
+        let mut file_world = World::new();
+        file_world.insert_resource(type_registry.clone());
+        let body = file_world
+            .spawn((Transform::default(), ColliderDensity(2.0)))
+            .id();
+        let text = DynamicScene::from_world(&file_world)
+            .serialize(&type_registry.read())
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("sub_scene_{}.scn.ron", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+
+        let root = world
+            .spawn((
+                SubScene {
+                    source: path.to_string_lossy().into_owned(),
+                },
+                Transform::from_xyz(50.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
+            ))
+            .id();
+        spawn_members(&mut world, root).unwrap();
+        let member = members_of(&world, root)[0];
+        assert_eq!(
+            world.get::<PrefabKey>(member),
+            Some(&PrefabKey(body.index()))
+        );
+        let position = world.get::<Transform>(member).unwrap();
+        assert!(
+            position
+                .translation
+                .abs_diff_eq(Vec3::new(50.0, 0.0, 0.0), 1e-4)
+        );
+        assert!(position.scale.abs_diff_eq(Vec3::splat(2.0), 1e-4));
+
+        // A parent-scene joint to the member survives the member being spawned again
+        let parent_body = world.spawn(Transform::default()).id();
+        let joint = world
+            .spawn((
+                JointConfig {
+                    anchor_a: parent_body,
+                    anchor_b: member,
+                    anchor_a_is_anchor: false,
+                    anchor_b_is_anchor: false,
+                    parent_entity: parent_body,
+                    child_entity: member,
+                    joint_config_details: default(),
+                },
+                JointToSubScene(root),
+                SubSceneJointEnds {
+                    anchor_b: Some(body.index()),
+                    child: Some(body.index()),
+                    ..default()
+                },
+            ))
+            .id();
+        respawn_sub_scene(&mut world, root).unwrap();
+
+        let respawned = members_of(&world, root)[0];
+        assert_ne!(respawned, member);
+        let config = world.get::<JointConfig>(joint).unwrap();
+        assert_eq!(config.parent_entity, parent_body);
+        assert_eq!(config.child_entity, respawned);
+        assert_eq!(config.anchor_b, respawned);
+        assert!(world.get::<RebuildJoint>(joint).is_some());
+
+        // The rebuilt joint is a new entity that stays linked to the sub-scene
+        world.run_system_once(rebuild_marked_joints).unwrap();
+        assert!(world.get_entity(joint).is_err());
+        let rebuilt = world.get::<SubSceneJoints>(root).unwrap().get().to_vec();
+        assert_eq!(rebuilt.len(), 1);
+        let rebuilt = rebuilt[0];
+        assert_eq!(
+            world.get::<SubSceneJointEnds>(rebuilt),
+            Some(&SubSceneJointEnds {
+                anchor_b: Some(body.index()),
+                child: Some(body.index()),
+                ..default()
+            })
+        );
+
+        respawn_sub_scene(&mut world, root).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let respawned_again = members_of(&world, root)[0];
+        assert_ne!(respawned_again, respawned);
+        let config = world.get::<JointConfig>(rebuilt).unwrap();
+        assert_eq!(config.parent_entity, parent_body);
+        assert_eq!(config.child_entity, respawned_again);
+        assert_eq!(config.anchor_b, respawned_again);
+    }
+}
@@ -0,0 +1,563 @@
+//! Export of the scene as an SVG diagram
+//!
+//! The diagram shows collider outlines in their [`DebugRender`] colors, anchor points, joint
+//! connections labelled with [`JointType::display_name`], an optional grid and a legend of the
+//! collision layers the bodies belong to. Hovering a body in a browser shows its name and layers.
+//!
+//! [`scene_diagram`] only reads components and resources, so the diagram can also be produced in
+//! a headless app, such as a CI job without a GPU:
+//!
+//! ```ignore
+//! let diagram = scene_diagram(app.world(), None);
+//! std::fs::write("scene.svg", diagram.to_svg(&SvgExportSettings::default()))?;
+//! ```
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use avian2d::parry::shape::TypedShape;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rfd::FileDialog;
+
+use crate::collider_tools::boolean::collider_world_pieces;
+use crate::collider_tools::polygon::outline_contours;
+use crate::collider_tools::utils::calculate_anchor_world_position_from_anchor;
+use crate::debug_render::{AnchorPoint, JointType, JointVisualization};
+use crate::grid::InfiniteGridSettings;
+use crate::ui::theme_colors::EditorThemeColors;
+use crate::{CollisionLayerPresets, SceneError};
+
+/// SVG export plugin
+pub struct SvgExportPlugin;
+
+impl Plugin for SvgExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SvgExportSettings>()
+            .register_type::<SvgExportSettings>()
+            .add_event::<SvgExportEvent>()
+            .add_systems(Update, handle_svg_export);
+    }
+}
+
+/// SVG diagram export event
+#[derive(Event)]
+pub enum SvgExportEvent {
+    /// Export every body, anchor and joint
+    All,
+    /// Export these bodies with their anchors and the joints between them
+    Entities(Vec<Entity>),
+}
+
+/// Options for SVG diagrams
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct SvgExportSettings {
+    /// Draw the editor grid behind the bodies
+    pub grid: bool,
+    /// List the collision layers below the drawing
+    pub legend: bool,
+    /// Space around the drawing, in world units
+    pub margin: f32,
+}
+
+impl Default for SvgExportSettings {
+    fn default() -> Self {
+        Self {
+            grid: true,
+            legend: true,
+            margin: 32.0,
+        }
+    }
+}
+
+/// Collider outline in world space
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramBody {
+    pub name: Option<String>,
+    /// Closed outlines, counter-clockwise for solids and clockwise for holes
+    pub contours: Vec<Vec<Vec2>>,
+    /// Open line segments, for polylines and segments
+    pub lines: Vec<[Vec2; 2]>,
+    pub color: Color,
+    /// Collision layers the body belongs to
+    pub memberships: u32,
+}
+
+/// Joint connection in world space
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramJoint {
+    pub joint_type: JointType,
+    pub a: Vec2,
+    pub b: Vec2,
+}
+
+/// Collision layer listed in the legend
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramLayer {
+    pub bit: u8,
+    pub name: String,
+    /// Number of bodies in the layer
+    pub bodies: usize,
+}
+
+/// Everything drawn in a diagram, gathered from the world
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SceneDiagram {
+    pub bodies: Vec<DiagramBody>,
+    pub anchors: Vec<Vec2>,
+    pub joints: Vec<DiagramJoint>,
+    pub layers: Vec<DiagramLayer>,
+    /// Grid line spacing in world units
+    pub grid_spacing: f32,
+}
+
+/// Gather the bodies, anchors and joints of the scene, or only those of `entities`
+///
+/// With `entities`, anchors are included when their body is, and joints when both of their ends
+/// are or the joint itself is listed.
+pub fn scene_diagram(world: &World, entities: Option<&[Entity]>) -> SceneDiagram {
+    let included = |entity: Entity| entities.is_none_or(|entities| entities.contains(&entity));
+    let mut diagram = SceneDiagram {
+        grid_spacing: world
+            .iter_entities()
+            .find_map(|entity| entity.get::<InfiniteGridSettings>().map(|grid| grid.scale))
+            .unwrap_or(100.0),
+        ..default()
+    };
+
+    for entity in world.iter_entities() {
+        let (Some(collider), Some(transform)) =
+            (entity.get::<Collider>(), entity.get::<GlobalTransform>())
+        else {
+            continue;
+        };
+        if !included(entity.id()) {
+            continue;
+        }
+        let mut body = DiagramBody {
+            name: entity.get::<Name>().map(|name| name.to_string()),
+            contours: Vec::new(),
+            lines: Vec::new(),
+            color: entity
+                .get::<DebugRender>()
+                .and_then(|render| render.collider_color)
+                .unwrap_or(Color::srgb(0.5, 0.5, 0.5)),
+            memberships: entity
+                .get::<CollisionLayers>()
+                .map_or(CollisionLayers::default().memberships.0, |layers| {
+                    layers.memberships.0
+                }),
+        };
+        if let Some(pieces) = collider_world_pieces(collider, transform) {
+            body.contours = outline_contours(&pieces);
+        } else {
+            let to_world = |p: &avian2d::parry::math::Point<f32>| {
+                transform
+                    .transform_point(Vec3::new(p.x, p.y, 0.0))
+                    .truncate()
+            };
+            match collider.shape_scaled().as_typed_shape() {
+                TypedShape::Polyline(polyline) => {
+                    let vertices = polyline.vertices();
+                    body.lines = polyline
+                        .indices()
+                        .iter()
+                        .map(|[a, b]| {
+                            [
+                                to_world(&vertices[*a as usize]),
+                                to_world(&vertices[*b as usize]),
+                            ]
+                        })
+                        .collect();
+                }
+                TypedShape::Segment(segment) => {
+                    body.lines = vec![[to_world(&segment.a), to_world(&segment.b)]];
+                }
+                _ => continue,
+            }
+        }
+        diagram.bodies.push(body);
+    }
+
+    // Anchors are positioned through their body, like the anchor gizmos
+    let anchor_position = |entity: Entity| {
+        let anchor = world.get::<AnchorPoint>(entity)?;
+        let transform = world.get::<GlobalTransform>(anchor.parent_entity)?;
+        Some(calculate_anchor_world_position_from_anchor(
+            anchor, transform,
+        ))
+    };
+    // A joint end is an anchor or the origin of a body
+    let joint_end = |entity: Entity| match world.get::<AnchorPoint>(entity) {
+        Some(anchor) => Some((anchor.parent_entity, anchor_position(entity)?)),
+        None => {
+            let transform = world.get::<GlobalTransform>(entity)?;
+            Some((entity, transform.translation().truncate()))
+        }
+    };
+    for entity in world.iter_entities() {
+        if let Some(anchor) = entity.get::<AnchorPoint>() {
+            if included(anchor.parent_entity) || included(entity.id()) {
+                diagram.anchors.extend(anchor_position(entity.id()));
+            }
+        }
+        if let Some(joint) = entity.get::<JointVisualization>() {
+            let (Some((body_a, a)), Some((body_b, b))) =
+                (joint_end(joint.anchor_a), joint_end(joint.anchor_b))
+            else {
+                continue;
+            };
+            if included(entity.id()) || (included(body_a) && included(body_b)) {
+                diagram.joints.push(DiagramJoint {
+                    joint_type: joint.joint_type,
+                    a,
+                    b,
+                });
+            }
+        }
+    }
+
+    let presets = world.get_resource::<CollisionLayerPresets>();
+    for bit in 0..32u8 {
+        let bodies = diagram
+            .bodies
+            .iter()
+            .filter(|body| body.memberships & (1 << bit) != 0)
+            .count();
+        if bodies == 0 {
+            continue;
+        }
+        let name = presets
+            .and_then(|presets| presets.layers.iter().find(|layer| layer.bit == bit))
+            .map_or_else(|| format!("Layer {bit}"), |layer| layer.name.clone());
+        diagram.layers.push(DiagramLayer { bit, name, bodies });
+    }
+    diagram
+}
+
+/// Number written with at most two decimals
+fn num(value: f32) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    // Avoid "-0"
+    format!("{}", rounded + 0.0)
+}
+
+/// Hex color and opacity for SVG attributes
+fn svg_color(color: Color) -> (String, f32) {
+    let srgba = color.to_srgba();
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let hex = format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(srgba.red),
+        channel(srgba.green),
+        channel(srgba.blue)
+    );
+    (hex, srgba.alpha)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl SceneDiagram {
+    /// Smallest box holding everything drawn, or `None` for an empty diagram
+    pub fn bounds(&self) -> Option<Rect> {
+        let points = self
+            .bodies
+            .iter()
+            .flat_map(|body| {
+                body.contours
+                    .iter()
+                    .flatten()
+                    .chain(body.lines.iter().flatten())
+            })
+            .chain(&self.anchors)
+            .chain(self.joints.iter().flat_map(|joint| [&joint.a, &joint.b]));
+        points.fold(None, |bounds: Option<Rect>, &point| {
+            Some(bounds.map_or(Rect::from_corners(point, point), |bounds| {
+                bounds.union_point(point)
+            }))
+        })
+    }
+
+    /// Write the diagram as an SVG document
+    ///
+    /// World y points up, so the drawing is flipped to SVG's y-down space.
+    pub fn to_svg(&self, settings: &SvgExportSettings) -> String {
+        let colors = EditorThemeColors::light();
+        let frame = self
+            .bounds()
+            .unwrap_or_default()
+            .inflate(settings.margin.max(0.0));
+        let size = frame.size().max(Vec2::ONE);
+        let legend_rows = if settings.legend {
+            self.layers.len()
+        } else {
+            0
+        };
+        let legend_height = if legend_rows > 0 {
+            24.0 + legend_rows as f32 * 18.0
+        } else {
+            0.0
+        };
+        let coords = |p: Vec2| (num(p.x - frame.min.x), num(frame.max.y - p.y));
+        let point = |p: Vec2| {
+            let (x, y) = coords(p);
+            format!("{x},{y}")
+        };
+
+        let mut svg = String::new();
+        let (width, height) = (num(size.x), num(size.y + legend_height));
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="12">"#
+        );
+        let _ = writeln!(svg, r#"  <rect width="100%" height="100%" fill="white"/>"#);
+
+        if settings.grid && self.grid_spacing > 0.0 {
+            let spacing = self.grid_spacing;
+            let _ = writeln!(
+                svg,
+                r##"  <g id="grid" stroke="#e0e0e0" stroke-width="0.5">"##
+            );
+            let mut x = (frame.min.x / spacing).ceil() * spacing;
+            while x <= frame.max.x {
+                let (top, bottom) = (
+                    point(Vec2::new(x, frame.max.y)),
+                    point(Vec2::new(x, frame.min.y)),
+                );
+                let _ = writeln!(svg, r#"    <path d="M{top} L{bottom}"/>"#);
+                x += spacing;
+            }
+            let mut y = (frame.min.y / spacing).ceil() * spacing;
+            while y <= frame.max.y {
+                let (left, right) = (
+                    point(Vec2::new(frame.min.x, y)),
+                    point(Vec2::new(frame.max.x, y)),
+                );
+                let _ = writeln!(svg, r#"    <path d="M{left} L{right}"/>"#);
+                y += spacing;
+            }
+            let _ = writeln!(svg, "  </g>");
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"  <g id="bodies" stroke-width="1.5" stroke-linejoin="round">"#
+        );
+        for body in &self.bodies {
+            let (color, opacity) = svg_color(body.color);
+            let mut data = String::new();
+            for contour in &body.contours {
+                for (i, &p) in contour.iter().enumerate() {
+                    let _ = write!(data, "{}{} ", if i == 0 { "M" } else { "L" }, point(p));
+                }
+                data.push_str("Z ");
+            }
+            for [a, b] in &body.lines {
+                let _ = write!(data, "M{} L{} ", point(*a), point(*b));
+            }
+            let layers: Vec<&str> = self
+                .layers
+                .iter()
+                .filter(|layer| body.memberships & (1 << layer.bit) != 0)
+                .map(|layer| layer.name.as_str())
+                .collect();
+            let title = match &body.name {
+                Some(name) => format!("{} ({})", name, layers.join(", ")),
+                None => layers.join(", "),
+            };
+            let _ = writeln!(
+                svg,
+                r#"    <path d="{}" fill="{color}" fill-opacity="{}" fill-rule="evenodd" stroke="{color}" stroke-opacity="{}"><title>{}</title></path>"#,
+                data.trim_end(),
+                num(opacity * 0.2),
+                num(opacity),
+                escape(&title)
+            );
+        }
+        let _ = writeln!(svg, "  </g>");
+
+        let _ = writeln!(svg, r#"  <g id="joints" stroke-width="1.5">"#);
+        for joint in &self.joints {
+            let color = match joint.joint_type {
+                JointType::Distance => colors.joint_distance,
+                JointType::Revolute => colors.joint_revolute,
+                JointType::Prismatic => colors.joint_prismatic,
+                JointType::Fixed => colors.joint_fixed,
+            };
+            let (color, _) = svg_color(color);
+            let dash = if joint.joint_type == JointType::Distance {
+                r#" stroke-dasharray="6 4""#
+            } else {
+                ""
+            };
+            let (x, y) = coords((joint.a + joint.b) * 0.5 + Vec2::Y * 6.0);
+            let _ = writeln!(
+                svg,
+                r#"    <path d="M{} L{}" stroke="{color}"{dash}/>"#,
+                point(joint.a),
+                point(joint.b)
+            );
+            let _ = writeln!(
+                svg,
+                r#"    <text x="{x}" y="{y}" fill="{color}" text-anchor="middle">{}</text>"#,
+                joint.joint_type.display_name()
+            );
+        }
+        let _ = writeln!(svg, "  </g>");
+
+        let (anchor_color, _) = svg_color(colors.anchor_in_joint);
+        let _ = writeln!(svg, r#"  <g id="anchors" fill="{anchor_color}">"#);
+        for &anchor in &self.anchors {
+            let (x, y) = coords(anchor);
+            let _ = writeln!(svg, r#"    <circle cx="{x}" cy="{y}" r="3"/>"#);
+        }
+        let _ = writeln!(svg, "  </g>");
+
+        if legend_rows > 0 {
+            let top = size.y + 18.0;
+            let _ = writeln!(svg, r#"  <g id="legend">"#);
+            let _ = writeln!(
+                svg,
+                r#"    <text x="8" y="{}" font-weight="bold">Collision layers</text>"#,
+                num(top)
+            );
+            for (row, layer) in self.layers.iter().enumerate() {
+                let _ = writeln!(
+                    svg,
+                    r#"    <text x="8" y="{}">{} (bit {}): {} bodies</text>"#,
+                    num(top + 18.0 * (row + 1) as f32),
+                    escape(&layer.name),
+                    layer.bit,
+                    layer.bodies
+                );
+            }
+            let _ = writeln!(svg, "  </g>");
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Write a diagram of the world to a file
+pub fn export_scene_svg(
+    world: &World,
+    entities: Option<&[Entity]>,
+    settings: &SvgExportSettings,
+    path: &Path,
+) -> Result<(), SceneError> {
+    let diagram = scene_diagram(world, entities);
+    if diagram.bodies.is_empty() {
+        return Err(SceneError::NoEntitiesFound);
+    }
+    std::fs::write(path, diagram.to_svg(settings)).map_err(|e| SceneError::FileError(e.to_string()))
+}
+
+/// Export SVG diagrams
+fn handle_svg_export(
+    mut events: EventReader<SvgExportEvent>,
+    world: &World,
+    settings: Res<SvgExportSettings>,
+) {
+    for event in events.read() {
+        let entities = match event {
+            SvgExportEvent::All => None,
+            SvgExportEvent::Entities(entities) => Some(entities.as_slice()),
+        };
+        let dialog = FileDialog::new()
+            .add_filter("SVG drawings", &["svg"])
+            .add_filter("All files", &["*"])
+            .set_file_name("scene.svg")
+            .set_title("Export SVG Diagram");
+        let Some(path) = dialog.save_file() else {
+            continue;
+        };
+        match export_scene_svg(world, entities, &settings, &path) {
+            Ok(()) => info!("Exported SVG diagram to {:?}", path),
+            Err(e) => error!("SVG export failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagram_svg() {
+        let diagram = SceneDiagram {
+            bodies: vec![
+                DiagramBody {
+                    name: Some("Crate <A>".to_string()),
+                    contours: vec![vec![
+                        Vec2::new(0.0, 0.0),
+                        Vec2::new(10.0, 0.0),
+                        Vec2::new(10.0, 10.0),
+                    ]],
+                    lines: Vec::new(),
+                    color: Color::srgb(1.0, 0.0, 0.0),
+                    memberships: 0b10,
+                },
+                DiagramBody {
+                    name: None,
+                    contours: Vec::new(),
+                    lines: vec![[Vec2::new(0.0, -20.0), Vec2::new(30.0, -20.0)]],
+                    color: Color::srgb(0.0, 0.0, 1.0),
+                    memberships: 0b1,
+                },
+            ],
+            anchors: vec![Vec2::new(10.0, 10.0)],
+            joints: vec![DiagramJoint {
+                joint_type: JointType::Revolute,
+                a: Vec2::new(10.0, 10.0),
+                b: Vec2::new(30.0, -20.0),
+            }],
+            layers: vec![
+                DiagramLayer {
+                    bit: 0,
+                    name: "Ground".to_string(),
+                    bodies: 1,
+                },
+                DiagramLayer {
+                    bit: 1,
+                    name: "Props".to_string(),
+                    bodies: 1,
+                },
+            ],
+            grid_spacing: 10.0,
+        };
+        assert_eq!(diagram.bounds(), Some(Rect::new(0.0, -20.0, 30.0, 10.0)));
+
+        let settings = SvgExportSettings {
+            margin: 5.0,
+            ..default()
+        };
+        let svg = diagram.to_svg(&settings);
+        // 40 by 40 drawing plus a legend of two rows
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="100""#)
+        );
+        // Flipped into SVG space with the margin as origin
+        assert!(svg.contains(r#"d="M5,15 L15,15 L15,5 Z""#));
+        assert!(svg.contains(r##"fill="#ff0000""##));
+        assert!(svg.contains("<title>Crate &lt;A&gt; (Props)</title>"));
+        assert!(svg.contains(r#"d="M5,35 L35,35""#));
+        assert!(svg.contains(">Revolute Joint</text>"));
+        assert!(svg.contains(r#"<circle cx="15" cy="5" r="3"/>"#));
+        assert!(svg.contains("Ground (bit 0): 1 bodies"));
+        assert!(svg.ends_with("</svg>\n"));
+
+        let bare = diagram.to_svg(&SvgExportSettings {
+            grid: false,
+            legend: false,
+            margin: 0.0,
+        });
+        assert!(!bare.contains(r#"id="grid""#));
+        assert!(!bare.contains("Collision layers"));
+    }
+}
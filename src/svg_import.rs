@@ -0,0 +1,963 @@
+//! Import of collider outlines from SVG drawings
+//!
+//! Reads `<rect>`, `<circle>`, `<ellipse>`, `<polygon>`, `<polyline>` and `<path>` elements,
+//! walking into groups, links and nested `<svg>` elements. Anything else, including the contents
+//! of `<defs>`, is skipped, as are elements hidden with `display="none"`.
+//!
+//! Element and group `transform`s and the `viewBox` mapping of each `<svg>` element are applied,
+//! then positions are multiplied by [`SvgImportSettings::scale`] with y negated, so the drawing's
+//! top-left corner lands on the world origin like the other level importers.
+//!
+//! Rectangles and circles stay exact colliders as long as the transforms keep them rectangles and
+//! circles. Everything else is flattened into polygons: curves and arcs are split into line
+//! segments straying at most [`SvgImportSettings::tolerance`] from the curve. Closed outlines
+//! become polygons with a [`PolygonSource`](crate::PolygonSource), using the even-odd rule to
+//! tell holes from solids; open ones become polylines.
+//!
+//! Element ids become the entities' [`Name`]s.
+
+use std::path::{Path, PathBuf};
+
+use bevy::math::Affine2;
+use bevy::prelude::*;
+use rfd::FileDialog;
+use roxmltree::Node;
+use thiserror::Error;
+
+use crate::collider_tools::polygon::{point_in_polygon, signed_area, simplify_contour};
+use crate::level_import::{
+    ImportedBody, ImportedShape, ImportedShapeKind, bounds_center, spawn_imported_shape,
+};
+use crate::{ColliderCreationState, CreationProperties};
+
+/// Limit on the segments a single curve is split into
+const MAX_CURVE_SEGMENTS: usize = 256;
+
+/// SVG import plugin
+pub struct SvgImportPlugin;
+
+impl Plugin for SvgImportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SvgImportSettings>()
+            .register_type::<SvgImportSettings>()
+            .add_event::<SvgImportEvent>()
+            .add_systems(Update, handle_svg_import);
+    }
+}
+
+/// SVG import event
+#[derive(Event)]
+pub enum SvgImportEvent {
+    /// Show file dialog and import
+    FromDialog,
+    /// Import from specific path
+    FromPath(PathBuf),
+}
+
+/// Options for SVG imports
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct SvgImportSettings {
+    /// World units per SVG user unit
+    pub scale: f32,
+    /// Largest distance a flattened curve may stray from the original, in world units
+    pub tolerance: f32,
+}
+
+impl Default for SvgImportSettings {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            tolerance: 0.5,
+        }
+    }
+}
+
+/// SVG import errors
+#[derive(Error, Debug)]
+pub enum SvgImportError {
+    #[error("XML error: {0}")]
+    XmlError(String),
+    #[error("File error: {0}")]
+    FileError(String),
+    #[error("Invalid {element}: {message}")]
+    InvalidElement { element: String, message: String },
+}
+
+/// Collider read from an SVG element
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgShape {
+    /// Id of the element
+    pub id: Option<String>,
+    pub shape: ImportedShape,
+}
+
+/// Piece of a subpath, ending at its last point
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Segment {
+    Line(Vec2),
+    Quadratic(Vec2, Vec2),
+    Cubic(Vec2, Vec2, Vec2),
+}
+
+/// Connected run of segments in an element's local space
+#[derive(Debug, Clone, PartialEq)]
+struct Subpath {
+    start: Vec2,
+    segments: Vec<Segment>,
+    closed: bool,
+}
+
+impl Subpath {
+    fn new(start: Vec2) -> Self {
+        Self {
+            start,
+            segments: Vec::new(),
+            closed: false,
+        }
+    }
+
+    fn end(&self) -> Vec2 {
+        match self.segments.last() {
+            Some(Segment::Line(p) | Segment::Quadratic(_, p) | Segment::Cubic(_, _, p)) => *p,
+            None => self.start,
+        }
+    }
+
+    /// Straight-edged subpath through points
+    fn polygon(points: &[Vec2], closed: bool) -> Option<Self> {
+        let (&start, rest) = points.split_first()?;
+        Some(Self {
+            start,
+            segments: rest.iter().map(|&p| Segment::Line(p)).collect(),
+            closed,
+        })
+    }
+
+    /// Points along the subpath after `transform`, curves split to within `tolerance`
+    fn flatten(&self, transform: Affine2, tolerance: f32) -> Vec<Vec2> {
+        let segments_for = |deviation: f32, factor: f32| {
+            ((factor * deviation / tolerance).sqrt().ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS)
+        };
+        let mut from = transform.transform_point2(self.start);
+        let mut points = vec![from];
+        for segment in &self.segments {
+            match *segment {
+                Segment::Line(p) => points.push(transform.transform_point2(p)),
+                Segment::Quadratic(c, p) => {
+                    let [c, p] = [c, p].map(|q| transform.transform_point2(q));
+                    // Wang's formula bounds the distance between the curve and its chords
+                    let n = segments_for((from - 2.0 * c + p).length(), 0.25);
+                    points.extend((1..n).map(|i| {
+                        let t = i as f32 / n as f32;
+                        from.lerp(c, t).lerp(c.lerp(p, t), t)
+                    }));
+                    points.push(p);
+                }
+                Segment::Cubic(c1, c2, p) => {
+                    let [c1, c2, p] = [c1, c2, p].map(|q| transform.transform_point2(q));
+                    let deviation = (from - 2.0 * c1 + c2)
+                        .length()
+                        .max((c1 - 2.0 * c2 + p).length());
+                    let n = segments_for(deviation, 0.75);
+                    points.extend((1..n).map(|i| {
+                        let t = i as f32 / n as f32;
+                        let (a, b, c) = (from.lerp(c1, t), c1.lerp(c2, t), c2.lerp(p, t));
+                        let (d, e) = (a.lerp(b, t), b.lerp(c, t));
+                        d.lerp(e, t)
+                    }));
+                    points.push(p);
+                }
+            }
+            from = *points.last().unwrap();
+        }
+        points.dedup_by(|a, b| a.distance(*b) <= f32::EPSILON);
+        if self.closed && points.len() > 1 && points[0].distance(points[points.len() - 1]) <= 1e-4 {
+            points.pop();
+        }
+        points
+    }
+}
+
+/// Read the colliders drawn in an SVG document
+pub fn parse_svg(text: &str, scale: f32, tolerance: f32) -> Result<Vec<SvgShape>, SvgImportError> {
+    let document =
+        roxmltree::Document::parse(text).map_err(|e| SvgImportError::XmlError(e.to_string()))?;
+    let root = document.root_element();
+    if root.tag_name().name() != "svg" {
+        return Err(SvgImportError::XmlError(
+            "root element is not <svg>".to_string(),
+        ));
+    }
+    let to_world = Affine2::from_scale(Vec2::new(scale, -scale));
+    let mut shapes = Vec::new();
+    visit(root, to_world, tolerance.max(1e-3), &mut shapes)?;
+    Ok(shapes)
+}
+
+fn invalid(node: Node, message: impl Into<String>) -> SvgImportError {
+    SvgImportError::InvalidElement {
+        element: node.tag_name().name().to_string(),
+        message: message.into(),
+    }
+}
+
+fn hidden(node: Node) -> bool {
+    let style = node.attribute("style").unwrap_or_default().replace(' ', "");
+    node.attribute("display") == Some("none") || style.contains("display:none")
+}
+
+/// Read an element and its children with `parent` mapping their space to the world
+fn visit(
+    node: Node,
+    parent: Affine2,
+    tolerance: f32,
+    shapes: &mut Vec<SvgShape>,
+) -> Result<(), SvgImportError> {
+    if !node.is_element() || hidden(node) {
+        return Ok(());
+    }
+    let mut transform = parent;
+    if let Some(text) = node.attribute("transform") {
+        transform *= parse_transform(text).map_err(|message| invalid(node, message))?;
+    }
+    let name = node.tag_name().name();
+    if name == "svg" {
+        transform *= viewport_transform(node).map_err(|message| invalid(node, message))?;
+    }
+    if matches!(name, "svg" | "g" | "a" | "switch") {
+        for child in node.children() {
+            visit(child, transform, tolerance, shapes)?;
+        }
+        return Ok(());
+    }
+
+    let id = node.attribute("id").map(str::to_string);
+    let length = |attribute: &str| {
+        node.attribute(attribute)
+            .map(|text| {
+                parse_length(text).ok_or_else(|| invalid(node, format!("invalid {attribute}")))
+            })
+            .transpose()
+            .map(|value| value.unwrap_or(0.0))
+    };
+    let (x_axis, y_axis) = (transform.matrix2.x_axis, transform.matrix2.y_axis);
+    let orthogonal = x_axis.dot(y_axis).abs() <= 1e-4 * x_axis.length() * y_axis.length();
+    let exact = |shape: ImportedShapeKind, center: Vec2| SvgShape {
+        id: id.clone(),
+        shape: ImportedShape {
+            kind: shape,
+            position: transform.transform_point2(center),
+            rotation: x_axis.to_angle(),
+            body: ImportedBody::default(),
+        },
+    };
+
+    let subpaths = match name {
+        "rect" => {
+            let (x, y) = (length("x")?, length("y")?);
+            let size = Vec2::new(length("width")?, length("height")?);
+            if size.min_element() <= 0.0 {
+                return Ok(());
+            }
+            // A missing corner radius takes the other one
+            let (rx, ry) = match (node.attribute("rx"), node.attribute("ry")) {
+                (None, None) => (0.0, 0.0),
+                (Some(_), None) => (length("rx")?, length("rx")?),
+                (None, Some(_)) => (length("ry")?, length("ry")?),
+                _ => (length("rx")?, length("ry")?),
+            };
+            let radius = Vec2::new(rx, ry).min(size * 0.5);
+            let min = Vec2::new(x, y);
+            if radius.min_element() > 0.0 {
+                vec![rounded_rect(min, size, radius)]
+            } else if orthogonal {
+                let extent = Vec2::new(size.x * x_axis.length(), size.y * y_axis.length());
+                shapes.push(exact(
+                    ImportedShapeKind::Rectangle(extent),
+                    min + size * 0.5,
+                ));
+                return Ok(());
+            } else {
+                let corners = [
+                    min,
+                    min.with_x(x + size.x),
+                    min + size,
+                    min.with_y(y + size.y),
+                ];
+                Subpath::polygon(&corners, true).into_iter().collect()
+            }
+        }
+        "circle" | "ellipse" => {
+            let center = Vec2::new(length("cx")?, length("cy")?);
+            let radii = if name == "circle" {
+                Vec2::splat(length("r")?)
+            } else {
+                Vec2::new(length("rx")?, length("ry")?)
+            };
+            if radii.min_element() <= 0.0 {
+                return Ok(());
+            }
+            let world_radii = Vec2::new(radii.x * x_axis.length(), radii.y * y_axis.length());
+            if orthogonal && (world_radii.x - world_radii.y).abs() <= 1e-4 * world_radii.x {
+                shapes.push(exact(ImportedShapeKind::Circle(world_radii.x), center));
+                return Ok(());
+            }
+            vec![ellipse(center, radii)]
+        }
+        "polygon" | "polyline" => {
+            let numbers = parse_numbers(node.attribute("points").unwrap_or_default())
+                .map_err(|message| invalid(node, message))?;
+            let points: Vec<Vec2> = numbers
+                .chunks_exact(2)
+                .map(|pair| Vec2::new(pair[0], pair[1]))
+                .collect();
+            Subpath::polygon(&points, name == "polygon")
+                .into_iter()
+                .collect()
+        }
+        "path" => parse_path(node.attribute("d").unwrap_or_default())
+            .map_err(|message| invalid(node, message))?,
+        _ => return Ok(()),
+    };
+
+    for shape in outline_shapes(&subpaths, transform, tolerance) {
+        shapes.push(SvgShape {
+            id: id.clone(),
+            shape,
+        });
+    }
+    Ok(())
+}
+
+/// Colliders for flattened subpaths: closed ones grouped into polygons with holes, open ones as
+/// polylines
+fn outline_shapes(subpaths: &[Subpath], transform: Affine2, tolerance: f32) -> Vec<ImportedShape> {
+    let mut shapes = Vec::new();
+    let mut loops = Vec::new();
+    for subpath in subpaths {
+        let points = subpath.flatten(transform, tolerance);
+        if subpath.closed {
+            loops.extend(simplify_contour(points, tolerance * 1e-3));
+        } else if points.len() >= 2 {
+            let center = bounds_center(&points);
+            shapes.push(ImportedShape {
+                kind: ImportedShapeKind::Polyline(points.iter().map(|&p| p - center).collect()),
+                position: center,
+                rotation: 0.0,
+                body: ImportedBody::default(),
+            });
+        }
+    }
+
+    // Even-odd rule: a loop inside an odd number of others is a hole in the innermost of them
+    let inside = |inner: &Vec<Vec2>, outer: &Vec<Vec2>| point_in_polygon(inner[0], outer);
+    let depths: Vec<usize> = loops
+        .iter()
+        .enumerate()
+        .map(|(i, inner)| {
+            loops
+                .iter()
+                .enumerate()
+                .filter(|&(j, outer)| i != j && inside(inner, outer))
+                .count()
+        })
+        .collect();
+    for (i, outline) in loops.iter().enumerate() {
+        if depths[i] % 2 == 1 {
+            continue;
+        }
+        let mut contours = vec![oriented(outline, true)];
+        for (j, hole) in loops.iter().enumerate() {
+            if depths[j] == depths[i] + 1 && inside(hole, outline) {
+                contours.push(oriented(hole, false));
+            }
+        }
+        let center = bounds_center(outline);
+        for contour in &mut contours {
+            contour.iter_mut().for_each(|point| *point -= center);
+        }
+        shapes.push(ImportedShape {
+            kind: ImportedShapeKind::Polygon(contours),
+            position: center,
+            rotation: 0.0,
+            body: ImportedBody::default(),
+        });
+    }
+    shapes
+}
+
+/// Copy of a loop running counter-clockwise, or clockwise for holes
+fn oriented(points: &[Vec2], counter_clockwise: bool) -> Vec<Vec2> {
+    let mut points = points.to_vec();
+    if (signed_area(&points) > 0.0) != counter_clockwise {
+        points.reverse();
+    }
+    points
+}
+
+/// Rectangle with elliptical corners
+fn rounded_rect(min: Vec2, size: Vec2, radius: Vec2) -> Subpath {
+    let max = min + size;
+    let mut subpath = Subpath::new(Vec2::new(min.x + radius.x, min.y));
+    let corners = [
+        (
+            Vec2::new(max.x - radius.x, min.y),
+            Vec2::new(max.x, min.y + radius.y),
+        ),
+        (
+            Vec2::new(max.x, max.y - radius.y),
+            Vec2::new(max.x - radius.x, max.y),
+        ),
+        (
+            Vec2::new(min.x + radius.x, max.y),
+            Vec2::new(min.x, max.y - radius.y),
+        ),
+        (
+            Vec2::new(min.x, min.y + radius.y),
+            Vec2::new(min.x + radius.x, min.y),
+        ),
+    ];
+    for (edge_end, arc_end) in corners {
+        subpath.segments.push(Segment::Line(edge_end));
+        subpath
+            .segments
+            .extend(arc_segments(edge_end, radius, 0.0, false, true, arc_end));
+    }
+    subpath.closed = true;
+    subpath
+}
+
+/// Ellipse as two half arcs
+fn ellipse(center: Vec2, radii: Vec2) -> Subpath {
+    let (right, left) = (center + Vec2::X * radii.x, center - Vec2::X * radii.x);
+    let mut subpath = Subpath::new(right);
+    subpath
+        .segments
+        .extend(arc_segments(right, radii, 0.0, false, true, left));
+    subpath
+        .segments
+        .extend(arc_segments(left, radii, 0.0, false, true, right));
+    subpath.closed = true;
+    subpath
+}
+
+/// Elliptical arc from `from` to `to` as cubic curves, following the SVG arc parameters
+///
+/// Radii too small to reach are scaled up, and a zero radius gives a straight line.
+fn arc_segments(
+    from: Vec2,
+    radii: Vec2,
+    x_rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: Vec2,
+) -> Vec<Segment> {
+    if from.distance(to) <= f32::EPSILON {
+        return Vec::new();
+    }
+    let mut radii = radii.abs();
+    if radii.min_element() <= f32::EPSILON {
+        return vec![Segment::Line(to)];
+    }
+    // Center parameterization from the SVG implementation notes
+    let rotation = Vec2::from_angle(x_rotation.to_radians());
+    let half = Vec2::from_angle(-x_rotation.to_radians()).rotate((from - to) * 0.5);
+    let lambda = (half / radii).length_squared();
+    if lambda > 1.0 {
+        radii *= lambda.sqrt();
+    }
+    let (r2, h2) = (radii * radii, half * half);
+    let numerator = r2.x * r2.y - r2.x * h2.y - r2.y * h2.x;
+    let denominator = r2.x * h2.y + r2.y * h2.x;
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+    let center_local =
+        coefficient * Vec2::new(radii.x * half.y / radii.y, -radii.y * half.x / radii.x);
+    let center = rotation.rotate(center_local) + (from + to) * 0.5;
+
+    let start = (half - center_local) / radii;
+    let end = (-half - center_local) / radii;
+    let mut sweep_angle = start.angle_to(end);
+    if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= std::f32::consts::TAU;
+    } else if sweep && sweep_angle < 0.0 {
+        sweep_angle += std::f32::consts::TAU;
+    }
+
+    // Quarter turns or less keep the cubic approximation tight
+    let count = (sweep_angle.abs() / std::f32::consts::FRAC_PI_2)
+        .ceil()
+        .max(1.0) as usize;
+    let step = sweep_angle / count as f32;
+    let handle = 4.0 / 3.0 * (step / 4.0).tan();
+    let map = |unit: Vec2| center + rotation.rotate(unit * radii);
+    let start_angle = start.to_angle();
+    (0..count)
+        .map(|i| {
+            let a = Vec2::from_angle(start_angle + step * i as f32);
+            let b = Vec2::from_angle(start_angle + step * (i + 1) as f32);
+            let end = if i + 1 == count { to } else { map(b) };
+            Segment::Cubic(map(a + a.perp() * handle), map(b - b.perp() * handle), end)
+        })
+        .collect()
+}
+
+/// Scanner over the numbers and letters of path data and attribute lists
+struct Scanner<'a> {
+    text: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text: text.as_bytes(),
+            position: 0,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while self
+            .text
+            .get(self.position)
+            .is_some_and(|c| c.is_ascii_whitespace() || *c == b',')
+        {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.text.get(self.position).copied()
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.peek().is_none()
+    }
+
+    fn letter(&mut self) -> Option<u8> {
+        let c = self.peek().filter(u8::is_ascii_alphabetic)?;
+        self.position += 1;
+        Some(c)
+    }
+
+    fn number(&mut self) -> Result<f32, String> {
+        self.skip_separators();
+        let start = self.position;
+        let digits = |scanner: &mut Self| {
+            while scanner
+                .text
+                .get(scanner.position)
+                .is_some_and(u8::is_ascii_digit)
+            {
+                scanner.position += 1;
+            }
+        };
+        if matches!(self.text.get(self.position), Some(b'+' | b'-')) {
+            self.position += 1;
+        }
+        digits(self);
+        if self.text.get(self.position) == Some(&b'.') {
+            self.position += 1;
+            digits(self);
+        }
+        // Only an exponent followed by digits belongs to the number
+        if matches!(self.text.get(self.position), Some(b'e' | b'E')) {
+            let mark = self.position;
+            self.position += 1;
+            if matches!(self.text.get(self.position), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+            if self.text.get(self.position).is_some_and(u8::is_ascii_digit) {
+                digits(self);
+            } else {
+                self.position = mark;
+            }
+        }
+        let token = std::str::from_utf8(&self.text[start..self.position]).unwrap_or_default();
+        token.parse().map_err(|_| {
+            let rest = String::from_utf8_lossy(&self.text[start..]);
+            format!(
+                "expected a number at \"{}\"",
+                rest.chars().take(12).collect::<String>()
+            )
+        })
+    }
+
+    fn point(&mut self) -> Result<Vec2, String> {
+        Ok(Vec2::new(self.number()?, self.number()?))
+    }
+
+    /// Arc flags may be written without separators, as in `a5 5 0 01 10 10`
+    fn flag(&mut self) -> Result<bool, String> {
+        match self.peek() {
+            Some(b'0') => {
+                self.position += 1;
+                Ok(false)
+            }
+            Some(b'1') => {
+                self.position += 1;
+                Ok(true)
+            }
+            _ => Err("expected an arc flag".to_string()),
+        }
+    }
+}
+
+/// Parse a list of numbers such as a `points` attribute
+fn parse_numbers(text: &str) -> Result<Vec<f32>, String> {
+    let mut scanner = Scanner::new(text);
+    let mut numbers = Vec::new();
+    while !scanner.at_end() {
+        numbers.push(scanner.number()?);
+    }
+    Ok(numbers)
+}
+
+/// Parse a length, converting absolute units to user units
+fn parse_length(text: &str) -> Option<f32> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| c.is_ascii_alphabetic() || c == '%')
+        .unwrap_or(text.len());
+    let value: f32 = text[..split].trim().parse().ok()?;
+    let unit = match &text[split..] {
+        "" | "px" => 1.0,
+        "pt" => 96.0 / 72.0,
+        "pc" => 16.0,
+        "mm" => 96.0 / 25.4,
+        "cm" => 96.0 / 2.54,
+        "in" => 96.0,
+        _ => return None,
+    };
+    Some(value * unit)
+}
+
+/// Parse a `transform` attribute
+fn parse_transform(text: &str) -> Result<Affine2, String> {
+    let mut transform = Affine2::IDENTITY;
+    for item in text.split(')') {
+        let Some((name, arguments)) = item.split_once('(') else {
+            if item
+                .trim_matches(|c: char| c.is_whitespace() || c == ',')
+                .is_empty()
+            {
+                continue;
+            }
+            return Err(format!("invalid transform \"{}\"", item.trim()));
+        };
+        let name = name.trim_matches(|c: char| c.is_whitespace() || c == ',');
+        let values = parse_numbers(arguments)?;
+        let value = |index: usize| values.get(index).copied().unwrap_or_default();
+        transform *= match (name, values.len()) {
+            ("matrix", 6) => Affine2::from_cols_array(&[
+                value(0),
+                value(1),
+                value(2),
+                value(3),
+                value(4),
+                value(5),
+            ]),
+            ("translate", 1 | 2) => Affine2::from_translation(Vec2::new(value(0), value(1))),
+            ("scale", 1) => Affine2::from_scale(Vec2::splat(value(0))),
+            ("scale", 2) => Affine2::from_scale(Vec2::new(value(0), value(1))),
+            ("rotate", 1 | 3) => {
+                let pivot = Vec2::new(value(1), value(2));
+                Affine2::from_translation(pivot)
+                    * Affine2::from_angle(value(0).to_radians())
+                    * Affine2::from_translation(-pivot)
+            }
+            ("skewX", 1) => {
+                Affine2::from_cols_array(&[1.0, 0.0, value(0).to_radians().tan(), 1.0, 0.0, 0.0])
+            }
+            ("skewY", 1) => {
+                Affine2::from_cols_array(&[1.0, value(0).to_radians().tan(), 0.0, 1.0, 0.0, 0.0])
+            }
+            _ => return Err(format!("invalid transform \"{}\"", item.trim())),
+        };
+    }
+    Ok(transform)
+}
+
+/// Map from the user space of an `<svg>` element to its parent's, through its position and
+/// `viewBox`
+///
+/// Aspect ratios other than `none` are treated as the default centered fit.
+fn viewport_transform(node: Node) -> Result<Affine2, String> {
+    let length = |name: &str| node.attribute(name).and_then(parse_length);
+    let origin = Vec2::new(length("x").unwrap_or(0.0), length("y").unwrap_or(0.0));
+    let view_box = match node.attribute("viewBox") {
+        Some(text) => parse_numbers(text)?,
+        None => Vec::new(),
+    };
+    let [min_x, min_y, width, height] = view_box[..] else {
+        return Ok(Affine2::from_translation(origin));
+    };
+    if width <= 0.0 || height <= 0.0 {
+        return Ok(Affine2::from_translation(origin));
+    }
+    let view_size = Vec2::new(width, height);
+    let size = Vec2::new(
+        length("width").unwrap_or(width),
+        length("height").unwrap_or(height),
+    );
+    let mut scale = size / view_size;
+    let mut offset = Vec2::ZERO;
+    let stretch = node
+        .attribute("preserveAspectRatio")
+        .is_some_and(|value| value.trim_start().starts_with("none"));
+    if !stretch {
+        scale = Vec2::splat(scale.min_element());
+        offset = (size - view_size * scale) * 0.5;
+    }
+    Ok(
+        Affine2::from_translation(origin + offset - Vec2::new(min_x, min_y) * scale)
+            * Affine2::from_scale(scale),
+    )
+}
+
+/// Parse path data into subpaths
+fn parse_path(data: &str) -> Result<Vec<Subpath>, String> {
+    let mut scanner = Scanner::new(data);
+    let mut subpaths: Vec<Subpath> = Vec::new();
+    let mut command = None;
+    let mut current = Vec2::ZERO;
+    let mut start = Vec2::ZERO;
+    // Control points reflected by the smooth curve commands
+    let mut last_cubic = None;
+    let mut last_quadratic = None;
+
+    while !scanner.at_end() {
+        if let Some(letter) = scanner.letter() {
+            command = Some(letter);
+        }
+        let Some(letter) = command else {
+            return Err("path data must start with a command".to_string());
+        };
+        let base = if letter.is_ascii_lowercase() {
+            current
+        } else {
+            Vec2::ZERO
+        };
+        let upper = letter.to_ascii_uppercase();
+        if upper == b'M' {
+            current = base + scanner.point()?;
+            start = current;
+            subpaths.push(Subpath::new(current));
+            // Further coordinate pairs after a move are lines
+            command = Some(if letter == b'm' { b'l' } else { b'L' });
+            (last_cubic, last_quadratic) = (None, None);
+            continue;
+        }
+        if upper == b'Z' {
+            if let Some(subpath) = subpaths.last_mut() {
+                subpath.closed = true;
+            }
+            current = start;
+            command = None;
+            (last_cubic, last_quadratic) = (None, None);
+            continue;
+        }
+
+        let segment = match upper {
+            b'L' => Segment::Line(base + scanner.point()?),
+            b'H' => Segment::Line(Vec2::new(base.x + scanner.number()?, current.y)),
+            b'V' => Segment::Line(Vec2::new(current.x, base.y + scanner.number()?)),
+            b'C' => Segment::Cubic(
+                base + scanner.point()?,
+                base + scanner.point()?,
+                base + scanner.point()?,
+            ),
+            b'S' => Segment::Cubic(
+                last_cubic.map_or(current, |control: Vec2| 2.0 * current - control),
+                base + scanner.point()?,
+                base + scanner.point()?,
+            ),
+            b'Q' => Segment::Quadratic(base + scanner.point()?, base + scanner.point()?),
+            b'T' => Segment::Quadratic(
+                last_quadratic.map_or(current, |control: Vec2| 2.0 * current - control),
+                base + scanner.point()?,
+            ),
+            b'A' => {
+                let radii = scanner.point()?;
+                let rotation = scanner.number()?;
+                let (large_arc, sweep) = (scanner.flag()?, scanner.flag()?);
+                let to = base + scanner.point()?;
+                let segments = arc_segments(current, radii, rotation, large_arc, sweep, to);
+                subpath_at(&mut subpaths, current).segments.extend(segments);
+                current = to;
+                (last_cubic, last_quadratic) = (None, None);
+                continue;
+            }
+            _ => return Err(format!("unknown path command '{}'", letter as char)),
+        };
+
+        (last_cubic, last_quadratic) = match segment {
+            Segment::Cubic(_, control, _) => (Some(control), None),
+            Segment::Quadratic(control, _) => (None, Some(control)),
+            Segment::Line(_) => (None, None),
+        };
+        let subpath = subpath_at(&mut subpaths, current);
+        subpath.segments.push(segment);
+        current = subpath.end();
+    }
+    Ok(subpaths)
+}
+
+/// Subpath to draw into, starting a new one at `current` after a close
+fn subpath_at(subpaths: &mut Vec<Subpath>, current: Vec2) -> &mut Subpath {
+    if subpaths.last().is_none_or(|subpath| subpath.closed) {
+        subpaths.push(Subpath::new(current));
+    }
+    subpaths.last_mut().unwrap()
+}
+
+/// Load and parse an SVG file
+pub fn load_svg(path: &Path, scale: f32, tolerance: f32) -> Result<Vec<SvgShape>, SvgImportError> {
+    let text =
+        std::fs::read_to_string(path).map_err(|e| SvgImportError::FileError(e.to_string()))?;
+    parse_svg(&text, scale, tolerance)
+}
+
+/// Import SVG drawings
+fn handle_svg_import(
+    mut events: EventReader<SvgImportEvent>,
+    mut commands: Commands,
+    mut state: ResMut<ColliderCreationState>,
+    settings: Res<SvgImportSettings>,
+    properties: Res<CreationProperties>,
+) {
+    for event in events.read() {
+        let path = match event {
+            SvgImportEvent::FromDialog => {
+                let dialog = FileDialog::new()
+                    .add_filter("SVG drawings", &["svg"])
+                    .add_filter("All files", &["*"])
+                    .set_title("Import SVG");
+                match dialog.pick_file() {
+                    Some(path) => path,
+                    None => continue,
+                }
+            }
+            SvgImportEvent::FromPath(path) => path.clone(),
+        };
+
+        match load_svg(&path, settings.scale, settings.tolerance) {
+            Ok(shapes) => {
+                let count = shapes.len();
+                for SvgShape { id, shape } in shapes {
+                    let Some(entity) = spawn_imported_shape(&mut commands, shape, properties.color)
+                    else {
+                        continue;
+                    };
+                    if let Some(id) = id {
+                        commands.entity(entity).insert(Name::new(id));
+                    }
+                    state.created_colliders.push(entity);
+                }
+                info!("Imported {} colliders from {:?}", count, path);
+            }
+            Err(e) => {
+                error!("SVG import failed: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DRAWING: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="0 0 100 50">
+  <defs><rect id="template" width="5" height="5"/></defs>
+  <g transform="translate(10 5)">
+    <rect id="floor" x="0" y="20" width="40" height="10"/>
+    <circle id="ball" cx="50" cy="10" r="4"/>
+    <ellipse id="egg" cx="70" cy="10" rx="6" ry="3"/>
+  </g>
+  <path id="donut" d="M0,0 h20 v20 h-20 z m5,5 v10 h10 v-10 z"/>
+  <path id="arch" d="M0 40 Q 10 30 20 40 T 40 40"/>
+  <polygon id="hidden" points="0,0 1,0 1,1" display="none"/>
+</svg>"#;
+
+    fn find<'a>(shapes: &'a [SvgShape], id: &str) -> Vec<&'a ImportedShape> {
+        shapes
+            .iter()
+            .filter(|shape| shape.id.as_deref() == Some(id))
+            .map(|shape| &shape.shape)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_svg() {
+        let shapes = parse_svg(DRAWING, 1.0, 0.1).unwrap();
+        assert_eq!(shapes.len(), 5);
+        assert!(find(&shapes, "template").is_empty());
+        assert!(find(&shapes, "hidden").is_empty());
+
+        // The viewBox doubles everything, then y is flipped
+        let floor = find(&shapes, "floor")[0];
+        assert_eq!(
+            floor.kind,
+            ImportedShapeKind::Rectangle(Vec2::new(80.0, 20.0))
+        );
+        assert_eq!(floor.position, Vec2::new(60.0, -60.0));
+        let ball = find(&shapes, "ball")[0];
+        assert_eq!(ball.kind, ImportedShapeKind::Circle(8.0));
+        assert_eq!(ball.position, Vec2::new(120.0, -30.0));
+
+        // Ellipses are flattened within the tolerance
+        let egg = find(&shapes, "egg")[0];
+        let ImportedShapeKind::Polygon(contours) = &egg.kind else {
+            panic!("expected a polygon, got {:?}", egg.kind);
+        };
+        assert!((egg.position - Vec2::new(160.0, -30.0)).length() < 1e-3);
+        for point in &contours[0] {
+            let unit = *point / Vec2::new(12.0, 6.0);
+            assert!((unit.length() - 1.0).abs() * 12.0 < 0.2);
+        }
+
+        // The inner square of the second subpath is a hole
+        let donut = find(&shapes, "donut")[0];
+        let ImportedShapeKind::Polygon(contours) = &donut.kind else {
+            panic!("expected a polygon, got {:?}", donut.kind);
+        };
+        assert_eq!(contours.len(), 2);
+        assert!(signed_area(&contours[0]) > 0.0);
+        assert!(signed_area(&contours[1]) < 0.0);
+        assert!((signed_area(&contours[0]) + signed_area(&contours[1]) - 1200.0).abs() < 1e-2);
+
+        // The smooth quadratic mirrors the first control point into a second bump
+        let arch = find(&shapes, "arch")[0];
+        let ImportedShapeKind::Polyline(points) = &arch.kind else {
+            panic!("expected a polyline, got {:?}", arch.kind);
+        };
+        let world: Vec<Vec2> = points.iter().map(|&p| p + arch.position).collect();
+        assert!(world[0].distance(Vec2::new(0.0, -80.0)) < 1e-4);
+        assert!(world[world.len() - 1].distance(Vec2::new(80.0, -80.0)) < 1e-4);
+        assert!(world.iter().any(|p| (p.y + 70.0).abs() < 0.2));
+        assert!(world.iter().any(|p| (p.y + 90.0).abs() < 0.2));
+    }
+
+    #[test]
+    fn test_svg_transforms_and_arcs() {
+        // Transforms apply right to left, rotating about the given pivot
+        let transform = parse_transform("rotate(90 10 0) scale(2, 1)").unwrap();
+        let point = transform.transform_point2(Vec2::new(5.0, 1.0));
+        assert!(point.distance(Vec2::new(9.0, 0.0)) < 1e-4);
+
+        // A half circle arc ends exactly at its end point and bulges by its radius
+        let path = parse_path("M0 0 A5 5 0 0 1 10 0").unwrap();
+        let points = path[0].flatten(Affine2::IDENTITY, 0.01);
+        assert_eq!(points.last(), Some(&Vec2::new(10.0, 0.0)));
+        let bulge = points.iter().map(|p| p.y).fold(f32::MAX, f32::min);
+        assert!((bulge + 5.0).abs() < 0.02);
+
+        assert_eq!(
+            parse_numbers("1-2.5.5e1,3").unwrap(),
+            vec![1.0, -2.5, 5.0, 3.0]
+        );
+        assert_eq!(parse_length("1in"), Some(96.0));
+    }
+}
@@ -0,0 +1,884 @@
+//! Import of collision shapes from Tiled maps
+//!
+//! Reads orthogonal TMX maps and the TSX tilesets they reference. Object layers become
+//! colliders, and tiles placed in tile layers bring along the collision shapes drawn for them in
+//! the tileset's collision editor.
+//!
+//! Tiled works in pixels with y pointing down. Positions are multiplied by
+//! [`TiledImportSettings::scale`] and y is negated, so the map's top-left corner lands on the
+//! world origin and the map extends below it. Rotations are clockwise in Tiled and become
+//! counter-clockwise angles here.
+//!
+//! | Tiled object | Collider |
+//! |---|---|
+//! | rectangle | rectangle |
+//! | ellipse | circle, or a polygon when the axes differ |
+//! | polygon | polygon with a [`PolygonSource`](crate::PolygonSource), so concave shapes work |
+//! | polyline | polyline |
+//! | point | small circle sensor marking the position |
+//!
+//! Tiles whose collision is a single rectangle filling the whole tile are gathered into a
+//! [`TileLayer`] per tile layer instead of one body each, so a painted floor becomes a merged
+//! collider without seams.
+//!
+//! Custom properties on layers, tiles and objects set up the bodies, using the names listed in
+//! [`crate::level_import`]. Objects inherit from their layer, and tile collision shapes from
+//! their tile.
+
+use std::path::{Path, PathBuf};
+
+use base64::Engine as _;
+use bevy::math::Affine2;
+use bevy::prelude::*;
+use rfd::FileDialog;
+use roxmltree::Node;
+use std::io::Read as _;
+use thiserror::Error;
+
+use crate::level_import::{
+    ImportedBody, ImportedShape, ImportedShapeKind, bounds_center, spawn_imported_shape,
+    spawn_imported_tile_layer,
+};
+use crate::{ColliderCreationState, CreationProperties, TileLayer, TileMergeMode};
+
+/// Points used to approximate an ellipse
+const ELLIPSE_RESOLUTION: usize = 32;
+
+/// Tile ids carry their flip flags in the top bits
+const FLIP_HORIZONTAL: u32 = 0x8000_0000;
+const FLIP_VERTICAL: u32 = 0x4000_0000;
+const FLIP_DIAGONAL: u32 = 0x2000_0000;
+const FLIP_HEXAGONAL: u32 = 0x1000_0000;
+
+/// Tiled import plugin
+pub struct TiledImportPlugin;
+
+impl Plugin for TiledImportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TiledImportSettings>()
+            .register_type::<TiledImportSettings>()
+            .add_event::<TiledImportEvent>()
+            .add_systems(Update, handle_tiled_import);
+    }
+}
+
+/// Tiled import event
+#[derive(Event)]
+pub enum TiledImportEvent {
+    /// Show file dialog and import
+    FromDialog,
+    /// Import from specific path
+    FromPath(PathBuf),
+}
+
+/// Options for Tiled imports
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct TiledImportSettings {
+    /// World units per Tiled pixel
+    pub scale: f32,
+}
+
+impl Default for TiledImportSettings {
+    fn default() -> Self {
+        Self { scale: 1.0 }
+    }
+}
+
+/// Tiled import errors
+#[derive(Error, Debug)]
+pub enum TiledImportError {
+    #[error("XML error: {0}")]
+    XmlError(String),
+    #[error("File error: {0}")]
+    FileError(String),
+    #[error("Unsupported map: {0}")]
+    Unsupported(String),
+}
+
+/// Colliders read from a Tiled map
+#[derive(Debug, Default)]
+pub struct TiledMap {
+    /// Individual colliders from object layers and tiles
+    pub shapes: Vec<ImportedShape>,
+    /// Full-tile collisions of tile layers, merged per layer and body settings
+    pub tile_layers: Vec<(Vec2, TileLayer, ImportedBody)>,
+}
+
+/// Tile of a tileset that has collision shapes
+struct TilesetTile {
+    /// Size of the tile image in pixels
+    size: Vec2,
+    /// Body settings of the tile, applied over the layer's
+    properties: Vec<(String, String)>,
+    /// Collision objects in tile pixels
+    objects: Vec<TiledObject>,
+}
+
+/// Tileset with the tiles that have collision shapes
+struct Tileset {
+    first_gid: u32,
+    offset: Vec2,
+    tiles: Vec<(u32, TilesetTile)>,
+}
+
+impl Tileset {
+    fn tile(&self, id: u32) -> Option<&TilesetTile> {
+        self.tiles
+            .iter()
+            .find(|(tile_id, _)| *tile_id == id)
+            .map(|(_, tile)| tile)
+    }
+}
+
+/// Geometry of an object, in its own pixel space
+#[derive(Debug, Clone)]
+enum TiledGeometry {
+    Rectangle(Vec2),
+    Ellipse(Vec2),
+    Polygon(Vec<Vec2>),
+    Polyline(Vec<Vec2>),
+    Point,
+}
+
+/// Object of an object layer or tile collision group
+#[derive(Debug, Clone)]
+struct TiledObject {
+    /// Pixel space of the object relative to its layer or tile
+    transform: Affine2,
+    geometry: TiledGeometry,
+    /// Tile drawn by a tile object, with flip flags
+    gid: Option<u32>,
+    /// Size of a tile object, in pixels
+    size: Vec2,
+    /// Raw `<properties>` of the object
+    properties: Vec<(String, String)>,
+}
+
+/// Parse a TMX map
+///
+/// `load_tileset` reads the external tilesets the map references by their `source` path.
+pub fn parse_tiled_map(
+    tmx: &str,
+    scale: f32,
+    load_tileset: &dyn Fn(&str) -> Result<String, TiledImportError>,
+) -> Result<TiledMap, TiledImportError> {
+    let document =
+        roxmltree::Document::parse(tmx).map_err(|e| TiledImportError::XmlError(e.to_string()))?;
+    let map = document.root_element();
+    if !map.has_tag_name("map") {
+        return Err(TiledImportError::XmlError("missing <map> element".into()));
+    }
+    let orientation = map.attribute("orientation").unwrap_or("orthogonal");
+    if orientation != "orthogonal" {
+        return Err(TiledImportError::Unsupported(format!(
+            "{orientation} maps are not supported"
+        )));
+    }
+    let tile_size = Vec2::new(
+        parse_attribute(map, "tilewidth", 0.0)?,
+        parse_attribute(map, "tileheight", 0.0)?,
+    );
+
+    let mut tilesets = Vec::new();
+    for node in map.children().filter(|n| n.has_tag_name("tileset")) {
+        let first_gid = parse_attribute(node, "firstgid", 1)?;
+        let tileset = match node.attribute("source") {
+            Some(source) => {
+                let tsx = load_tileset(source)?;
+                let document = roxmltree::Document::parse(&tsx)
+                    .map_err(|e| TiledImportError::XmlError(format!("{source}: {e}")))?;
+                parse_tileset(document.root_element(), first_gid)?
+            }
+            None => parse_tileset(node, first_gid)?,
+        };
+        tilesets.push(tileset);
+    }
+    tilesets.sort_by_key(|tileset| tileset.first_gid);
+
+    let importer = Importer {
+        to_world: Affine2::from_scale(Vec2::new(scale, -scale)),
+        scale,
+        tile_size,
+        tilesets,
+    };
+    let mut tiled_map = TiledMap::default();
+    importer.layers(map, Vec2::ZERO, &ImportedBody::default(), &mut tiled_map)?;
+    Ok(tiled_map)
+}
+
+/// Map-wide data needed while walking the layers of a map
+struct Importer {
+    /// Map pixels to world units
+    to_world: Affine2,
+    scale: f32,
+    /// Grid size of the map, in pixels
+    tile_size: Vec2,
+    tilesets: Vec<Tileset>,
+}
+
+impl Importer {
+    /// Import the layers below a map or group, shifted by the accumulated layer offsets
+    fn layers(
+        &self,
+        parent: Node,
+        offset: Vec2,
+        body: &ImportedBody,
+        map: &mut TiledMap,
+    ) -> Result<(), TiledImportError> {
+        for layer in parent.children().filter(Node::is_element) {
+            let offset = offset
+                + Vec2::new(
+                    parse_attribute(layer, "offsetx", 0.0)?,
+                    parse_attribute(layer, "offsety", 0.0)?,
+                );
+            match layer.tag_name().name() {
+                "group" => {
+                    let body = with_properties(body, &raw_properties(layer))?;
+                    self.layers(layer, offset, &body, map)?;
+                }
+                "objectgroup" => {
+                    let body = with_properties(body, &raw_properties(layer))?;
+                    for node in layer.children().filter(|n| n.has_tag_name("object")) {
+                        let object = parse_object(node)?;
+                        let frame = Affine2::from_translation(offset);
+                        self.object(&object, frame, &body, map)?;
+                    }
+                }
+                "layer" => {
+                    let body = with_properties(body, &raw_properties(layer))?;
+                    self.tile_layer(layer, offset, &body, map)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Import the tiles of a tile layer
+    fn tile_layer(
+        &self,
+        layer: Node,
+        offset: Vec2,
+        body: &ImportedBody,
+        map: &mut TiledMap,
+    ) -> Result<(), TiledImportError> {
+        let Some(data) = layer.children().find(|n| n.has_tag_name("data")) else {
+            return Ok(());
+        };
+        let square = self.tile_size.x > 0.0 && self.tile_size.x == self.tile_size.y;
+        let layer_origin = self.to_world.transform_point2(offset);
+        let mut merged: Vec<(TileLayer, ImportedBody)> = Vec::new();
+
+        for (cell, gid) in parse_tile_data(layer, data)? {
+            let Some((tileset, tile)) = self.find_tile(gid) else {
+                continue;
+            };
+            let tile_body = with_properties(body, &tile.properties)?;
+
+            // Tiles are drawn bottom-aligned to their cell
+            let cell_origin = cell.as_vec2() * self.tile_size;
+            let top_left = offset
+                + tileset.offset
+                + Vec2::new(
+                    cell_origin.x,
+                    cell_origin.y + self.tile_size.y - tile.size.y,
+                );
+            let full_cell = square
+                && tile.size == self.tile_size
+                && tileset.offset == Vec2::ZERO
+                && tile.objects.len() == 1
+                && tile.objects[0].fills(tile.size);
+            if full_cell {
+                let body = with_properties(&tile_body, &tile.objects[0].properties)?;
+                let index = match merged.iter().position(|(_, b)| *b == body) {
+                    Some(index) => index,
+                    None => {
+                        let cell_size = self.tile_size.x * self.scale;
+                        merged.push((TileLayer::new(cell_size, TileMergeMode::Rectangles), body));
+                        merged.len() - 1
+                    }
+                };
+                // Tiled rows grow downwards, world rows upwards
+                merged[index].0.set(IVec2::new(cell.x, -cell.y - 1), true);
+                continue;
+            }
+
+            let frame = Affine2::from_translation(top_left) * flip_transform(gid, tile.size);
+            for object in &tile.objects {
+                self.object(object, frame, &tile_body, map)?;
+            }
+        }
+
+        map.tile_layers.extend(
+            merged
+                .into_iter()
+                .map(|(layer, body)| (layer_origin, layer, body)),
+        );
+        Ok(())
+    }
+
+    /// Tileset and collision data of a tile id with flip flags
+    fn find_tile(&self, gid: u32) -> Option<(&Tileset, &TilesetTile)> {
+        let gid = gid & !(FLIP_HORIZONTAL | FLIP_VERTICAL | FLIP_DIAGONAL | FLIP_HEXAGONAL);
+        let tileset = self
+            .tilesets
+            .iter()
+            .rev()
+            .find(|tileset| tileset.first_gid <= gid)?;
+        tileset
+            .tile(gid - tileset.first_gid)
+            .map(|tile| (tileset, tile))
+    }
+
+    /// Import one object placed through `frame`, a pixel space relative to the map
+    fn object(
+        &self,
+        object: &TiledObject,
+        frame: Affine2,
+        parent: &ImportedBody,
+        map: &mut TiledMap,
+    ) -> Result<(), TiledImportError> {
+        let body = with_properties(parent, &object.properties)?;
+        let transform = self.to_world * frame * object.transform;
+
+        // Tile objects stretch the tile's collision shapes over the object's size
+        if let Some(gid) = object.gid {
+            let Some((tileset, tile)) = self.find_tile(gid) else {
+                return Ok(());
+            };
+            // Properties set on the placed object override those of its tile
+            let tile_body = with_properties(parent, &tile.properties)?;
+            let body = with_properties(&tile_body, &object.properties)?;
+            let tile_frame = frame
+                * object.transform
+                * Affine2::from_translation(Vec2::new(0.0, -object.size.y) + tileset.offset)
+                * Affine2::from_scale(object.size / tile.size)
+                * flip_transform(gid, tile.size);
+            for tile_object in &tile.objects {
+                self.object(tile_object, tile_frame, &body, map)?;
+            }
+            return Ok(());
+        }
+
+        let shape = |kind, position, rotation| ImportedShape {
+            kind,
+            position,
+            rotation,
+            body: body.clone(),
+        };
+        let shape = match &object.geometry {
+            TiledGeometry::Rectangle(size) | TiledGeometry::Ellipse(size) => {
+                let center = transform.transform_point2(*size * 0.5);
+                let x_axis = transform.transform_vector2(Vec2::new(size.x, 0.0));
+                let y_axis = transform.transform_vector2(Vec2::new(0.0, size.y));
+                let rotation = x_axis.to_angle();
+                let orthogonal =
+                    x_axis.dot(y_axis).abs() <= 1e-3 * x_axis.length() * y_axis.length();
+                let extent = Vec2::new(x_axis.length(), y_axis.length());
+                let is_ellipse = matches!(object.geometry, TiledGeometry::Ellipse(_));
+                if extent.min_element() <= f32::EPSILON {
+                    warn!("Skipping Tiled object without area");
+                    return Ok(());
+                } else if !orthogonal
+                    || (is_ellipse && (extent.x - extent.y).abs() > 1e-3 * extent.x)
+                {
+                    // Skewed rectangles and true ellipses become outlines
+                    let outline: Vec<Vec2> = if is_ellipse {
+                        (0..ELLIPSE_RESOLUTION)
+                            .map(|i| {
+                                let angle =
+                                    i as f32 / ELLIPSE_RESOLUTION as f32 * std::f32::consts::TAU;
+                                *size * 0.5 * (Vec2::ONE + Vec2::from_angle(angle))
+                            })
+                            .collect()
+                    } else {
+                        vec![
+                            Vec2::ZERO,
+                            Vec2::new(size.x, 0.0),
+                            *size,
+                            Vec2::new(0.0, size.y),
+                        ]
+                    };
+                    polygon_shape(&outline, transform, body.clone())
+                } else if is_ellipse {
+                    shape(ImportedShapeKind::Circle(extent.x * 0.5), center, rotation)
+                } else {
+                    shape(ImportedShapeKind::Rectangle(extent), center, rotation)
+                }
+            }
+            TiledGeometry::Polygon(points) => polygon_shape(points, transform, body.clone()),
+            TiledGeometry::Polyline(points) => {
+                let world: Vec<Vec2> = points
+                    .iter()
+                    .map(|&p| transform.transform_point2(p))
+                    .collect();
+                let center = bounds_center(&world);
+                shape(
+                    ImportedShapeKind::Polyline(world.iter().map(|&p| p - center).collect()),
+                    center,
+                    0.0,
+                )
+            }
+            TiledGeometry::Point => shape(
+                ImportedShapeKind::Point,
+                transform.transform_point2(Vec2::ZERO),
+                0.0,
+            ),
+        };
+        map.shapes.push(shape);
+        Ok(())
+    }
+}
+
+impl TiledObject {
+    /// Whether this is an unrotated rectangle covering a whole tile of `size`
+    fn fills(&self, size: Vec2) -> bool {
+        let TiledGeometry::Rectangle(extent) = self.geometry else {
+            return false;
+        };
+        self.gid.is_none()
+            && self.transform.matrix2 == Mat2::IDENTITY
+            && self.transform.translation.abs().max_element() <= 1e-3
+            && (extent - size).abs().max_element() <= 1e-3
+    }
+}
+
+/// Polygon collider from an outline in object pixels
+fn polygon_shape(points: &[Vec2], transform: Affine2, body: ImportedBody) -> ImportedShape {
+    let outline = points
+        .iter()
+        .map(|&p| transform.transform_point2(p))
+        .collect();
+    ImportedShape::polygon(outline, body)
+}
+
+/// Body settings with the raw properties of an element applied
+fn with_properties(
+    body: &ImportedBody,
+    properties: &[(String, String)],
+) -> Result<ImportedBody, TiledImportError> {
+    body.with_properties(
+        properties
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str())),
+    )
+    .map_err(TiledImportError::XmlError)
+}
+
+/// Pixel space of a tile drawn with the flip flags of `gid`
+///
+/// Tiled applies the diagonal flip first, then the horizontal and vertical ones.
+fn flip_transform(gid: u32, size: Vec2) -> Affine2 {
+    let mut flip = Affine2::IDENTITY;
+    if gid & FLIP_DIAGONAL != 0 {
+        flip = Affine2::from_mat2(Mat2::from_cols(Vec2::Y, Vec2::X)) * flip;
+    }
+    if gid & FLIP_HORIZONTAL != 0 {
+        flip = Affine2::from_scale_angle_translation(
+            Vec2::new(-1.0, 1.0),
+            0.0,
+            Vec2::new(size.x, 0.0),
+        ) * flip;
+    }
+    if gid & FLIP_VERTICAL != 0 {
+        flip = Affine2::from_scale_angle_translation(
+            Vec2::new(1.0, -1.0),
+            0.0,
+            Vec2::new(0.0, size.y),
+        ) * flip;
+    }
+    flip
+}
+
+/// Parse a `<tileset>` element, keeping the tiles that have collision shapes
+fn parse_tileset(node: Node, first_gid: u32) -> Result<Tileset, TiledImportError> {
+    if !node.has_tag_name("tileset") {
+        return Err(TiledImportError::XmlError(
+            "missing <tileset> element".into(),
+        ));
+    }
+    let tile_size = Vec2::new(
+        parse_attribute(node, "tilewidth", 0.0)?,
+        parse_attribute(node, "tileheight", 0.0)?,
+    );
+    let offset = match node.children().find(|n| n.has_tag_name("tileoffset")) {
+        Some(tile_offset) => Vec2::new(
+            parse_attribute(tile_offset, "x", 0.0)?,
+            parse_attribute(tile_offset, "y", 0.0)?,
+        ),
+        None => Vec2::ZERO,
+    };
+
+    let mut tiles = Vec::new();
+    for tile in node.children().filter(|n| n.has_tag_name("tile")) {
+        let Some(group) = tile.children().find(|n| n.has_tag_name("objectgroup")) else {
+            continue;
+        };
+        // Tiles of image collections have their own size
+        let size = match tile.children().find(|n| n.has_tag_name("image")) {
+            Some(image) => Vec2::new(
+                parse_attribute(image, "width", tile_size.x)?,
+                parse_attribute(image, "height", tile_size.y)?,
+            ),
+            None => tile_size,
+        };
+        let objects = group
+            .children()
+            .filter(|n| n.has_tag_name("object"))
+            .map(parse_object)
+            .collect::<Result<Vec<_>, _>>()?;
+        tiles.push((
+            parse_attribute(tile, "id", 0)?,
+            TilesetTile {
+                size,
+                properties: raw_properties(tile),
+                objects,
+            },
+        ));
+    }
+    Ok(Tileset {
+        first_gid,
+        offset,
+        tiles,
+    })
+}
+
+/// Parse an `<object>` element
+fn parse_object(node: Node) -> Result<TiledObject, TiledImportError> {
+    let position = Vec2::new(
+        parse_attribute(node, "x", 0.0)?,
+        parse_attribute(node, "y", 0.0)?,
+    );
+    let size = Vec2::new(
+        parse_attribute(node, "width", 0.0)?,
+        parse_attribute(node, "height", 0.0)?,
+    );
+    // Clockwise in Tiled's y-down space is the positive angle
+    let rotation = parse_attribute(node, "rotation", 0.0f32)?.to_radians();
+    let gid = node
+        .attribute("gid")
+        .map(|gid| parse_number::<u32>(gid, "gid"))
+        .transpose()?;
+
+    let child = |name: &str| node.children().find(|n| n.has_tag_name(name));
+    let geometry = if child("ellipse").is_some() {
+        TiledGeometry::Ellipse(size)
+    } else if child("point").is_some() {
+        TiledGeometry::Point
+    } else if let Some(polygon) = child("polygon") {
+        TiledGeometry::Polygon(parse_points(polygon)?)
+    } else if let Some(polyline) = child("polyline") {
+        TiledGeometry::Polyline(parse_points(polyline)?)
+    } else if child("text").is_some() {
+        // Text objects only label the map
+        TiledGeometry::Point
+    } else {
+        TiledGeometry::Rectangle(size)
+    };
+
+    Ok(TiledObject {
+        transform: Affine2::from_angle_translation(rotation, position),
+        geometry,
+        gid,
+        size,
+        properties: raw_properties(node),
+    })
+}
+
+/// Cells and tile ids of a tile layer, skipping empty cells
+fn parse_tile_data(layer: Node, data: Node) -> Result<Vec<(IVec2, u32)>, TiledImportError> {
+    let width: i32 = parse_attribute(layer, "width", 0)?;
+    let mut cells = Vec::new();
+    // Infinite maps split the data into chunks
+    let chunks: Vec<Node> = data
+        .children()
+        .filter(|n| n.has_tag_name("chunk"))
+        .collect();
+    if chunks.is_empty() {
+        push_tiles(data, IVec2::ZERO, width, &mut cells)?;
+    }
+    for chunk in chunks {
+        let origin = IVec2::new(
+            parse_attribute(chunk, "x", 0)?,
+            parse_attribute(chunk, "y", 0)?,
+        );
+        push_tiles(
+            chunk,
+            origin,
+            parse_attribute(chunk, "width", 0)?,
+            &mut cells,
+        )?;
+    }
+    Ok(cells)
+}
+
+/// Decode the tile ids of a `<data>` or `<chunk>` element
+fn push_tiles(
+    node: Node,
+    origin: IVec2,
+    width: i32,
+    cells: &mut Vec<(IVec2, u32)>,
+) -> Result<(), TiledImportError> {
+    let data = node
+        .ancestors()
+        .find(|n| n.has_tag_name("data"))
+        .unwrap_or(node);
+    let text = node.text().unwrap_or_default().trim();
+    let gids: Vec<u32> = match data.attribute("encoding") {
+        Some("csv") => text
+            .split(',')
+            .map(str::trim)
+            .filter(|gid| !gid.is_empty())
+            .map(|gid| parse_number(gid, "tile"))
+            .collect::<Result<_, _>>()?,
+        Some("base64") => {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(text)
+                .map_err(|e| TiledImportError::XmlError(e.to_string()))?;
+            let bytes = decompress(bytes, data.attribute("compression"))?;
+            bytes
+                .chunks_exact(4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect()
+        }
+        Some(encoding) => {
+            return Err(TiledImportError::Unsupported(format!(
+                "{encoding} tile data encoding"
+            )));
+        }
+        None => node
+            .children()
+            .filter(|n| n.has_tag_name("tile"))
+            .map(|tile| parse_attribute(tile, "gid", 0))
+            .collect::<Result<_, _>>()?,
+    };
+
+    let width = width.max(1);
+    cells.extend(
+        gids.into_iter()
+            .enumerate()
+            .filter(|&(_, gid)| gid != 0)
+            .map(|(i, gid)| (origin + IVec2::new(i as i32 % width, i as i32 / width), gid)),
+    );
+    Ok(())
+}
+
+/// Undo the compression of base64 tile data
+fn decompress(bytes: Vec<u8>, compression: Option<&str>) -> Result<Vec<u8>, TiledImportError> {
+    let mut decoded = Vec::new();
+    let result = match compression {
+        None => return Ok(bytes),
+        Some("zlib") => flate2::read::ZlibDecoder::new(&bytes[..]).read_to_end(&mut decoded),
+        Some("gzip") => flate2::read::GzDecoder::new(&bytes[..]).read_to_end(&mut decoded),
+        Some(other) => {
+            return Err(TiledImportError::Unsupported(format!(
+                "{other} tile data compression"
+            )));
+        }
+    };
+    result.map_err(|e| TiledImportError::XmlError(e.to_string()))?;
+    Ok(decoded)
+}
+
+/// Points of a `<polygon>` or `<polyline>` element
+fn parse_points(node: Node) -> Result<Vec<Vec2>, TiledImportError> {
+    node.attribute("points")
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|pair| {
+            let (x, y) = pair
+                .split_once(',')
+                .ok_or_else(|| TiledImportError::XmlError(format!("bad point \"{pair}\"")))?;
+            Ok(Vec2::new(parse_number(x, "x")?, parse_number(y, "y")?))
+        })
+        .collect()
+}
+
+/// Name and value of each custom property of an element
+fn raw_properties(node: Node) -> Vec<(String, String)> {
+    node.children()
+        .filter(|child| child.has_tag_name("properties"))
+        .flat_map(|properties| properties.children())
+        .filter(|child| child.has_tag_name("property"))
+        .map(|property| {
+            (
+                property.attribute("name").unwrap_or_default().to_string(),
+                property.attribute("value").unwrap_or_default().to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Attribute parsed as a number, or `default` when missing
+fn parse_attribute<T: std::str::FromStr>(
+    node: Node,
+    name: &str,
+    default: T,
+) -> Result<T, TiledImportError> {
+    node.attribute(name)
+        .map_or(Ok(default), |value| parse_number(value, name))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str, name: &str) -> Result<T, TiledImportError> {
+    value
+        .parse()
+        .map_err(|_| TiledImportError::XmlError(format!("invalid {name} \"{value}\"")))
+}
+
+/// Read a TMX file, resolving tilesets relative to it
+fn load_tiled_map(path: &Path, scale: f32) -> Result<TiledMap, TiledImportError> {
+    let tmx =
+        std::fs::read_to_string(path).map_err(|e| TiledImportError::FileError(e.to_string()))?;
+    let directory = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    parse_tiled_map(&tmx, scale, &|source| {
+        std::fs::read_to_string(directory.join(source))
+            .map_err(|e| TiledImportError::FileError(format!("{source}: {e}")))
+    })
+}
+
+/// Handle Tiled import
+fn handle_tiled_import(
+    mut events: EventReader<TiledImportEvent>,
+    mut commands: Commands,
+    mut state: ResMut<ColliderCreationState>,
+    settings: Res<TiledImportSettings>,
+    properties: Res<CreationProperties>,
+) {
+    for event in events.read() {
+        let path = match event {
+            TiledImportEvent::FromDialog => {
+                let dialog = FileDialog::new()
+                    .add_filter("Tiled maps", &["tmx"])
+                    .add_filter("All files", &["*"])
+                    .set_title("Import Tiled Map");
+                match dialog.pick_file() {
+                    Some(path) => path,
+                    None => continue,
+                }
+            }
+            TiledImportEvent::FromPath(path) => path.clone(),
+        };
+
+        match load_tiled_map(&path, settings.scale) {
+            Ok(map) => {
+                let shapes = map.shapes.len();
+                let layers = map.tile_layers.len();
+                for shape in map.shapes {
+                    state.created_colliders.extend(spawn_imported_shape(
+                        &mut commands,
+                        shape,
+                        properties.color,
+                    ));
+                }
+                for (origin, layer, body) in map.tile_layers {
+                    let entity = spawn_imported_tile_layer(
+                        &mut commands,
+                        origin,
+                        layer,
+                        &body,
+                        properties.color,
+                    );
+                    state.created_colliders.push(entity);
+                }
+                info!(
+                    "Imported {} colliders and {} tile layers from {:?}",
+                    shapes, layers, path
+                );
+            }
+            Err(e) => {
+                error!("Tiled import failed: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collider_tools::polygon::signed_area;
+    use avian2d::prelude::RigidBody;
+
+    const MAP: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="3" height="2" tilewidth="16" tileheight="16">
+ <tileset firstgid="1" name="terrain" tilewidth="16" tileheight="16" tilecount="2" columns="2">
+  <tile id="0">
+   <objectgroup><object id="1" x="0" y="0" width="16" height="16"/></objectgroup>
+  </tile>
+  <tile id="1">
+   <objectgroup><object id="1" x="0" y="0"><polygon points="0,16 16,16 16,0"/></object></objectgroup>
+  </tile>
+ </tileset>
+ <layer id="1" name="ground" width="3" height="2">
+  <data encoding="csv">
+1,1,0,
+0,2,1
+</data>
+ </layer>
+ <objectgroup id="2" name="bodies">
+  <properties><property name="rigid_body" value="dynamic"/></properties>
+  <object id="1" x="10" y="20" width="30" height="10">
+   <properties><property name="friction" type="float" value="0.25"/></properties>
+  </object>
+  <object id="2" x="0" y="0" width="10" height="10" rotation="90"/>
+  <object id="3" x="40" y="40" width="8" height="8"><ellipse/></object>
+  <object id="4" x="5" y="5"><point/></object>
+ </objectgroup>
+</map>"#;
+
+    #[test]
+    fn test_parse_tiled_map() {
+        let map = parse_tiled_map(MAP, 2.0, &|source| {
+            Err(TiledImportError::FileError(source.to_string()))
+        })
+        .unwrap();
+
+        // Full-cell tiles merge into one layer, with rows flipped upwards
+        assert_eq!(map.tile_layers.len(), 1);
+        let (origin, layer, body) = &map.tile_layers[0];
+        assert_eq!(*origin, Vec2::ZERO);
+        assert_eq!(layer.cell_size, 32.0);
+        assert_eq!(
+            layer.cells,
+            vec![IVec2::new(2, -2), IVec2::new(0, -1), IVec2::new(1, -1)]
+        );
+        assert_eq!(body.rigid_body, RigidBody::Static);
+
+        // The slope tile keeps its own polygon, wound counter-clockwise after the y flip
+        let slope = &map.shapes[0];
+        assert_eq!(slope.position, Vec2::new(48.0, -48.0));
+        let ImportedShapeKind::Polygon(contours) = &slope.kind else {
+            panic!("expected a polygon, got {:?}", slope.kind);
+        };
+        assert!(signed_area(&contours[0]) > 0.0);
+
+        let bodies = &map.shapes[1..];
+        assert_eq!(bodies.len(), 4);
+        assert!(
+            bodies
+                .iter()
+                .all(|s| s.body.rigid_body == RigidBody::Dynamic)
+        );
+        assert_eq!(
+            bodies[0].kind,
+            ImportedShapeKind::Rectangle(Vec2::new(60.0, 20.0))
+        );
+        assert_eq!(bodies[0].position, Vec2::new(50.0, -50.0));
+        assert_eq!(bodies[0].body.friction, Some(0.25));
+
+        // Clockwise in Tiled is clockwise on screen, a negative angle here
+        assert!((bodies[1].rotation + std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+        assert!(bodies[1].position.distance(Vec2::new(-10.0, -10.0)) < 1e-4);
+
+        assert_eq!(bodies[2].kind, ImportedShapeKind::Circle(8.0));
+        assert_eq!(bodies[2].position, Vec2::new(88.0, -88.0));
+        assert_eq!(bodies[3].kind, ImportedShapeKind::Point);
+        assert_eq!(bodies[3].position, Vec2::new(10.0, -10.0));
+
+        let isometric = MAP.replace("orthogonal", "isometric");
+        assert!(matches!(
+            parse_tiled_map(&isometric, 1.0, &|_| unreachable!()),
+            Err(TiledImportError::Unsupported(_))
+        ));
+    }
+}
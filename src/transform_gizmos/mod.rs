@@ -11,12 +11,17 @@
 //! Then, when these entities are selected via [`bevy_editor_core::selection`] the
 //! transform gizmo will appear and allow you to move and rotate your selection.
 
+use avian2d::prelude::Collider;
 use bevy::picking::{backend::ray::RayMap, pointer::PointerId};
 use bevy::{prelude::*, render::camera::Projection, transform::TransformSystem};
+use bevy_egui::input::egui_wants_any_input;
 use mesh::{RotationGizmo, ViewTranslateGizmo};
 
 use normalization::*;
 
+use crate::collider_tools::measure::{GUIDE_SNAP_DISTANCE, MeasureGuides};
+use crate::collider_tools::object_snap::{ObjectSnap, ObjectSnapState, find_object_snap};
+use crate::collider_tools::utils::get_mouse_world_position;
 use crate::grid::InfiniteGridSettings;
 use crate::selection::EditorSelection;
 
@@ -89,6 +94,40 @@ impl Default for GizmoMode {
     }
 }
 
+/// Point a selection is rotated and scaled around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PivotMode {
+    /// Center of the box bounding the selected origins.
+    BoundsCenter,
+    /// Average of the selected origins.
+    #[default]
+    Median,
+    /// Origin of the primary selection.
+    Active,
+    /// Point placed at the cursor with the P key.
+    Custom,
+}
+
+impl PivotMode {
+    /// All pivot modes, in UI order.
+    pub const ALL: [PivotMode; 4] = [
+        PivotMode::BoundsCenter,
+        PivotMode::Median,
+        PivotMode::Active,
+        PivotMode::Custom,
+    ];
+
+    /// Translation key of the mode name.
+    pub fn key(self) -> &'static str {
+        match self {
+            PivotMode::BoundsCenter => "pivot_bounds_center",
+            PivotMode::Median => "pivot_median",
+            PivotMode::Active => "pivot_active",
+            PivotMode::Custom => "pivot_custom",
+        }
+    }
+}
+
 /// Settings for the [`TransformGizmoPlugin`].
 #[derive(Resource, Clone, Debug)]
 pub struct TransformGizmoSettings {
@@ -105,8 +144,16 @@ pub struct TransformGizmoSettings {
     pub scale_snap: f32,
     /// Whether snapping is currently enabled (can be toggled with Ctrl key).
     pub snap_enabled: bool,
+    /// Whether translated colliders snap to nearby colliders (hold Alt to skip).
+    pub object_snap: bool,
+    /// Distance in world units within which object snapping engages.
+    pub object_snap_distance: f32,
     /// Current gizmo mode.
     pub mode: GizmoMode,
+    /// Point the selection rotates and scales around.
+    pub pivot_mode: PivotMode,
+    /// World position of the custom pivot, used with [`PivotMode::Custom`].
+    pub custom_pivot: Vec3,
     /// Whether to enable Z-axis components (for 3D mode).
     pub enable_z_axis: bool,
     /// Whether to enable rotation gizmos.
@@ -126,7 +173,11 @@ impl Default for TransformGizmoSettings {
             angle_snap: 15.0,   // 15 degree angle snapping
             scale_snap: 0.1,    // 0.1 scale increment snapping
             snap_enabled: true, // Enable snapping by default
+            object_snap: true,
+            object_snap_distance: 10.0,
             mode: GizmoMode::default(),
+            pivot_mode: PivotMode::default(),
+            custom_pivot: Vec3::ZERO,
             enable_z_axis: true,
             enable_rotation_gizmos: true,
             enable_scale_gizmos: true,
@@ -190,7 +241,10 @@ impl Plugin for TransformGizmoPlugin {
         // Input Set
         app.add_systems(
             PreUpdate,
-            handle_gizmo_hotkeys
+            (
+                handle_gizmo_hotkeys,
+                place_custom_pivot.run_if(not(egui_wants_any_input)),
+            )
                 .in_set(TransformGizmoSystems::Input)
                 .run_if(|settings: Res<TransformGizmoSettings>| settings.enabled),
         );
@@ -291,7 +345,6 @@ pub enum InteractionKind {
 #[derive(Component, Clone, Debug)]
 struct InitialTransform {
     transform: Transform,
-    rotation_offset: Vec3,
 }
 
 /// Marker component for the camera that display and control the transform gizmo.
@@ -303,7 +356,7 @@ fn on_transform_gizmo_pointer_press(
     target_query: Query<(&InteractionKind, &ChildOf)>,
     mut query: Query<(&mut TransformGizmo, &GlobalTransform)>,
     selection: Res<EditorSelection>,
-    items_query: Query<(&GlobalTransform, Entity, Option<&TransformGizmoOffset>)>,
+    items_query: Query<(&GlobalTransform, Entity)>,
     mut commands: Commands,
 ) {
     if trigger.button != PointerButton::Primary {
@@ -324,13 +377,10 @@ fn on_transform_gizmo_pointer_press(
     });
 
     // Dragging has started, store the initial position of all selected meshes
-    for (transform, entity, rotation_origin_offset) in items_query.iter() {
+    for (transform, entity) in items_query.iter() {
         if selection.contains(entity) {
             commands.entity(entity).insert(InitialTransform {
                 transform: transform.compute_transform(),
-                rotation_offset: rotation_origin_offset
-                    .map(|offset| offset.0)
-                    .unwrap_or(Vec3::ZERO),
             });
         }
     }
@@ -373,6 +423,10 @@ fn drag_gizmo(
     selection: Res<EditorSelection>,
     settings: Res<TransformGizmoSettings>,
     grid_settings: Query<&InfiniteGridSettings>,
+    guides: Option<Res<MeasureGuides>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut object_snap_state: Option<ResMut<ObjectSnapState>>,
+    collider_query: Query<(Entity, &Collider, &GlobalTransform)>,
     mut transform_query: Query<
         (Entity, Option<&ChildOf>, &mut Transform, &InitialTransform),
         Without<TransformGizmo>,
@@ -380,6 +434,8 @@ fn drag_gizmo(
     parent_query: Query<&GlobalTransform>,
     mut gizmo_query: Query<(&mut TransformGizmo, &GlobalTransform)>,
 ) {
+    let mut active_snap = None;
+
     // Gizmo handle should project mouse motion onto the axis of the handle. Perpendicular motion
     // should have no effect on the handle. We can do this by projecting the vector from the handle
     // click point to mouse's current position, onto the axis of the direction we are dragging. See
@@ -392,6 +448,19 @@ fn drag_gizmo(
         }) = gizmo.interaction
         && let Some((_, &ray)) = raymap.iter().find(|(id, _)| id.pointer == pointer_id)
     {
+        // Poses of the moved and stationary colliders, read before the selection is borrowed
+        let object_snap_enabled = settings.object_snap
+            && !keyboard_input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+            && matches!(
+                kind,
+                InteractionKind::TranslateAxis { .. } | InteractionKind::TranslatePlane { .. }
+            );
+        let snap_sources = if object_snap_enabled {
+            ObjectSnapSources::collect(&selection, &collider_query, &transform_query)
+        } else {
+            ObjectSnapSources::default()
+        };
+
         let selected_iter = transform_query
             .iter_mut()
             .filter(|(entity, ..)| selection.contains(*entity))
@@ -434,6 +503,18 @@ fn drag_gizmo(
                         translation = snap_to_grid(translation, grid_settings.scale);
                     }
                 }
+                if let Some(guides) = &guides {
+                    translation = snap_to_guides(origin, translation, Some(axis), guides);
+                }
+                if let Some((snapped, snap)) = snap_to_objects(
+                    translation,
+                    Some(axis),
+                    &snap_sources,
+                    settings.object_snap_distance,
+                ) {
+                    translation = snapped;
+                    active_snap = Some(snap);
+                }
                 selected_iter.for_each(
                     |(inverse_parent, mut local_transform, initial_global_transform)| {
                         let new_transform = Transform {
@@ -464,6 +545,18 @@ fn drag_gizmo(
                         translation_delta = snap_to_grid(translation_delta, grid_settings.scale);
                     }
                 }
+                if let Some(guides) = &guides {
+                    translation_delta = snap_to_guides(origin, translation_delta, None, guides);
+                }
+                if let Some((snapped, snap)) = snap_to_objects(
+                    translation_delta,
+                    None,
+                    &snap_sources,
+                    settings.object_snap_distance,
+                ) {
+                    translation_delta = snapped;
+                    active_snap = Some(snap);
+                }
 
                 selected_iter.for_each(
                     |(inverse_parent, mut local_transform, initial_transform)| {
@@ -498,15 +591,15 @@ fn drag_gizmo(
                     angle = snap_angle(angle, settings.angle_snap);
                 }
 
+                // Every selected entity orbits the pivot the gizmo was placed at, so a
+                // multi-selection turns as one rigid group and joints between its bodies keep
+                // their relative anchors
                 let rotation = Quat::from_axis_angle(axis, angle);
                 selected_iter.for_each(
                     |(inverse_parent, mut local_transform, initial_transform)| {
-                        let world_space_offset = initial_transform.transform.rotation
-                            * initial_transform.rotation_offset;
-                        let offset_rotated = rotation * world_space_offset;
-                        let offset = world_space_offset - offset_rotated;
                         let new_transform = Transform {
-                            translation: initial_transform.transform.translation + offset,
+                            translation: origin
+                                + rotation * (initial_transform.transform.translation - origin),
                             rotation: rotation * initial_transform.transform.rotation,
                             scale: initial_transform.transform.scale,
                         };
@@ -565,7 +658,15 @@ fn drag_gizmo(
                         }
 
                         let new_transform = Transform {
-                            translation: initial_transform.transform.translation,
+                            translation: scale_about_pivot(
+                                initial_transform.transform.translation,
+                                origin,
+                                Vec3::select(
+                                    axis.abs().cmpgt(Vec3::splat(0.9)),
+                                    Vec3::splat(scale_factor),
+                                    Vec3::ONE,
+                                ),
+                            ),
                             rotation: initial_transform.transform.rotation,
                             scale: new_scale,
                         };
@@ -605,7 +706,11 @@ fn drag_gizmo(
                     |(inverse_parent, mut local_transform, initial_transform)| {
                         let new_scale = initial_transform.transform.scale * scale_factor;
                         let new_transform = Transform {
-                            translation: initial_transform.transform.translation,
+                            translation: scale_about_pivot(
+                                initial_transform.transform.translation,
+                                origin,
+                                Vec3::splat(scale_factor),
+                            ),
                             rotation: initial_transform.transform.rotation,
                             scale: new_scale,
                         };
@@ -616,6 +721,12 @@ fn drag_gizmo(
             }
         }
     }
+
+    if let Some(state) = object_snap_state.as_mut() {
+        if state.active != active_snap {
+            state.active = active_snap;
+        }
+    }
 }
 
 fn intersect_plane(ray: Ray3d, plane_normal: Vec3, plane_origin: Vec3) -> Option<Vec3> {
@@ -643,6 +754,90 @@ fn snap_to_grid(position: Vec3, grid_size: f32) -> Vec3 {
     )
 }
 
+/// Colliders taking part in object snapping during a translation drag.
+#[derive(Default)]
+struct ObjectSnapSources {
+    /// Selected colliders with their global transforms at the start of the drag.
+    moving: Vec<(Collider, Transform)>,
+    /// Unselected colliders.
+    targets: Vec<(Collider, GlobalTransform)>,
+}
+
+impl ObjectSnapSources {
+    fn collect(
+        selection: &EditorSelection,
+        collider_query: &Query<(Entity, &Collider, &GlobalTransform)>,
+        transform_query: &Query<
+            (Entity, Option<&ChildOf>, &mut Transform, &InitialTransform),
+            Without<TransformGizmo>,
+        >,
+    ) -> Self {
+        let mut sources = Self::default();
+        for (entity, collider, global_transform) in collider_query {
+            if !selection.contains(entity) {
+                sources.targets.push((collider.clone(), *global_transform));
+            } else if let Ok((.., initial)) = transform_query.get(entity) {
+                sources.moving.push((collider.clone(), initial.transform));
+            }
+        }
+        sources
+    }
+}
+
+/// Adjust a translation so a moved collider touches a nearby collider.
+///
+/// When dragging along an axis, only the part of the correction along that axis is applied.
+fn snap_to_objects(
+    translation: Vec3,
+    axis: Option<Vec3>,
+    sources: &ObjectSnapSources,
+    threshold: f32,
+) -> Option<(Vec3, ObjectSnap)> {
+    if sources.moving.is_empty() || sources.targets.is_empty() {
+        return None;
+    }
+    let moving: Vec<(Collider, GlobalTransform)> = sources
+        .moving
+        .iter()
+        .map(|(collider, initial)| {
+            let moved = Transform {
+                translation: initial.translation + translation,
+                ..*initial
+            };
+            (collider.clone(), GlobalTransform::from(moved))
+        })
+        .collect();
+
+    let snap = find_object_snap(&moving, &sources.targets, threshold)?;
+    let correction = snap.offset.extend(0.0);
+    let correction = match axis.and_then(Vec3::try_normalize) {
+        Some(axis) => axis * correction.dot(axis),
+        None => correction,
+    };
+    Some((translation + correction, snap))
+}
+
+/// Adjust a translation so the gizmo origin lands on nearby guide lines.
+///
+/// When dragging along an axis, only the part of the correction along that axis is applied.
+fn snap_to_guides(
+    origin: Vec3,
+    translation: Vec3,
+    axis: Option<Vec3>,
+    guides: &MeasureGuides,
+) -> Vec3 {
+    let target = (origin + translation).truncate();
+    let (snapped, moved) = guides.snap_point(target, GUIDE_SNAP_DISTANCE);
+    if !moved {
+        return translation;
+    }
+    let correction = (snapped - target).extend(0.0);
+    match axis.and_then(Vec3::try_normalize) {
+        Some(axis) => translation + axis * correction.dot(axis),
+        None => translation + correction,
+    }
+}
+
 /// Snap an angle to the nearest increment.
 fn snap_angle(angle: f32, snap_increment: f32) -> f32 {
     if snap_increment <= 0.0 {
@@ -660,6 +855,37 @@ fn snap_scale(scale: f32, snap_increment: f32) -> f32 {
     (scale / snap_increment).round() * snap_increment
 }
 
+/// Move a position away from or towards a pivot by a per-axis factor.
+fn scale_about_pivot(position: Vec3, pivot: Vec3, factor: Vec3) -> Vec3 {
+    pivot + (position - pivot) * factor
+}
+
+/// Pivot of a selection given the gizmo origins of the selected entities.
+///
+/// `active` is the origin of the primary selection; when it is missing the median is used.
+/// Returns `None` for an empty selection.
+pub(crate) fn selection_pivot(
+    mode: PivotMode,
+    origins: &[Vec3],
+    active: Option<Vec3>,
+    custom: Vec3,
+) -> Option<Vec3> {
+    if origins.is_empty() {
+        return None;
+    }
+    let median = origins.iter().sum::<Vec3>() / origins.len() as f32;
+    Some(match mode {
+        PivotMode::Median => median,
+        PivotMode::BoundsCenter => {
+            let min = origins.iter().copied().reduce(Vec3::min)?;
+            let max = origins.iter().copied().reduce(Vec3::max)?;
+            (min + max) / 2.0
+        }
+        PivotMode::Active => active.unwrap_or(median),
+        PivotMode::Custom => custom,
+    })
+}
+
 /// Offsets where the origin is for an entity transformed by the transform gizmo.
 #[derive(Component)]
 pub struct TransformGizmoOffset(pub Vec3);
@@ -673,31 +899,39 @@ fn place_gizmo(
         Query<(&mut GlobalTransform, &mut Transform, &mut Visibility), With<TransformGizmo>>,
     )>,
 ) {
+    let mut active = None;
     let selected: Vec<_> = queries
         .p0()
         .iter()
         .filter(|(entity, ..)| selection.contains(*entity))
-        .map(|(_s, t, offset)| {
-            t.translation()
+        .map(|(entity, t, offset)| {
+            let origin = t.translation()
                 + offset
                     .map(|o| t.compute_transform().rotation * o.0)
-                    .unwrap_or(Vec3::ZERO)
+                    .unwrap_or(Vec3::ZERO);
+            if selection.primary() == Some(entity) {
+                active = Some(origin);
+            }
+            origin
         })
         .collect();
-    let n_selected = selected.len();
-    let transform_sum = selected.iter().fold(Vec3::ZERO, |acc, t| acc + *t);
-    let centroid = transform_sum / n_selected as f32;
+    let pivot = selection_pivot(
+        plugin_settings.pivot_mode,
+        &selected,
+        active,
+        plugin_settings.custom_pivot,
+    );
     // Set the gizmo's position and visibility
     if let Ok((mut g_transform, mut transform, mut visible)) = queries.p1().single_mut() {
-        if n_selected > 0 {
+        if let Some(pivot) = pivot {
             let gt = g_transform.compute_transform();
             *g_transform = Transform {
-                translation: centroid,
+                translation: pivot,
                 rotation: plugin_settings.alignment_rotation,
                 ..gt
             }
             .into();
-            transform.translation = centroid;
+            transform.translation = pivot;
             transform.rotation = plugin_settings.alignment_rotation;
             *visible = Visibility::Inherited;
         } else {
@@ -852,6 +1086,29 @@ fn handle_gizmo_hotkeys(
     }
 }
 
+/// Place the custom pivot at the cursor with P and switch to it.
+fn place_custom_pivot(
+    mut settings: ResMut<TransformGizmoSettings>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<SpritePickingCamera>>,
+    window_query: Query<&Window>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyP) {
+        return;
+    }
+    let (Ok((camera, camera_transform)), Ok(window)) =
+        (camera_query.single(), window_query.single())
+    else {
+        return;
+    };
+    let Some(cursor) = get_mouse_world_position(window, camera, camera_transform) else {
+        return;
+    };
+    settings.custom_pivot = cursor.extend(0.0);
+    settings.pivot_mode = PivotMode::Custom;
+    info!("Custom pivot placed at {:?}", cursor);
+}
+
 fn update_gizmo_visibility(
     settings: Res<TransformGizmoSettings>,
     mut translation_gizmos: Query<
@@ -0,0 +1,129 @@
+//! Floating toolbar with the align, distribute and stack commands
+//!
+//! The toolbar sits at the top of the viewport while more than one entity is selected in
+//! Select mode. The tool panel shows the same buttons together with the arrange options.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::collider_tools::arrange::{
+    AlignEdge, AlignReference, ArrangeAxis, ArrangeEvent, ArrangeSettings,
+};
+use crate::collider_tools::units::UnitConverter;
+use crate::selection::EditorSelection;
+use crate::tr;
+use crate::ui::unit_widgets::length_drag_value;
+
+pub(super) fn ui(ctx: &egui::Context, world: &mut World) {
+    let selected = world
+        .get_resource::<EditorSelection>()
+        .map(|selection| selection.len())
+        .unwrap_or(0);
+    if selected < 2 {
+        return;
+    }
+
+    let area = ctx.available_rect();
+    egui::Area::new(egui::Id::new("arrange_toolbar"))
+        .pivot(egui::Align2::CENTER_TOP)
+        .fixed_pos(egui::pos2(area.center().x, area.top() + 24.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    arrange_buttons(ui, world);
+                });
+            });
+        });
+}
+
+/// Arrange options: alignment reference and stacking gap
+pub(super) fn arrange_settings_ui(ui: &mut egui::Ui, world: &mut World) {
+    let units = UnitConverter::from_world(world);
+    let Some(mut settings) = world.get_resource_mut::<ArrangeSettings>() else {
+        return;
+    };
+
+    let mut edited = settings.clone();
+    ui.horizontal(|ui| {
+        ui.label(tr!("align_to"));
+        ui.selectable_value(
+            &mut edited.reference,
+            AlignReference::SelectionBounds,
+            tr!("align_to_selection"),
+        );
+        ui.selectable_value(
+            &mut edited.reference,
+            AlignReference::Active,
+            tr!("align_to_active"),
+        );
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr!("stack_gap"));
+        ui.add(length_drag_value(&mut edited.stack_gap, units, 0.5));
+    });
+
+    if edited != *settings {
+        *settings = edited;
+    }
+}
+
+/// One button per arrange command, sending an [`ArrangeEvent`]
+pub(super) fn arrange_buttons(ui: &mut egui::Ui, world: &mut World) {
+    let mut command = None;
+
+    for edge in AlignEdge::ALL {
+        if ui
+            .button(tr!(edge.key()))
+            .on_hover_text(align_hotkey(edge))
+            .clicked()
+        {
+            command = Some(ArrangeEvent::Align(edge));
+        }
+    }
+    ui.separator();
+    if ui
+        .button(tr!("distribute_horizontal"))
+        .on_hover_text("Alt + Shift + H")
+        .clicked()
+    {
+        command = Some(ArrangeEvent::Distribute(ArrangeAxis::Horizontal));
+    }
+    if ui
+        .button(tr!("distribute_vertical"))
+        .on_hover_text("Alt + Shift + V")
+        .clicked()
+    {
+        command = Some(ArrangeEvent::Distribute(ArrangeAxis::Vertical));
+    }
+    ui.separator();
+    if ui
+        .button(tr!("stack_horizontal"))
+        .on_hover_text("Alt + Shift + J")
+        .clicked()
+    {
+        command = Some(ArrangeEvent::Stack(ArrangeAxis::Horizontal));
+    }
+    if ui
+        .button(tr!("stack_vertical"))
+        .on_hover_text("Alt + Shift + K")
+        .clicked()
+    {
+        command = Some(ArrangeEvent::Stack(ArrangeAxis::Vertical));
+    }
+
+    if let Some(command) = command {
+        world.send_event(command);
+    }
+}
+
+/// Shortcut shown in the tooltip of an align button
+fn align_hotkey(edge: AlignEdge) -> &'static str {
+    match edge {
+        AlignEdge::Left => "Alt + J",
+        AlignEdge::Right => "Alt + L",
+        AlignEdge::Top => "Alt + I",
+        AlignEdge::Bottom => "Alt + K",
+        AlignEdge::CenterX => "Alt + H",
+        AlignEdge::CenterY => "Alt + V",
+    }
+}
@@ -0,0 +1,98 @@
+//! 诊断面板
+//!
+//! 列出场景检查发现的问题，点击条目选中相关实体，并可一键修复。
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::collider_tools::ToolMode;
+use crate::collider_tools::debug_render::anchor::AnchorPoint;
+use crate::collider_tools::debug_render::joint::JointVisualization;
+use crate::collider_tools::lint::{LintFixEvent, LintSettings, SceneLint};
+use crate::selection::EditorSelection;
+use crate::tr;
+use crate::ui::panel_state::PanelState;
+
+pub(super) fn ui(ctx: &egui::Context, world: &mut World) {
+    let mut open = true;
+
+    egui::Window::new(tr!("diagnostics"))
+        .open(&mut open)
+        .default_width(320.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            // 检查设置
+            if let Some(mut settings) = world.get_resource_mut::<LintSettings>() {
+                let mut edited = settings.clone();
+                ui.checkbox(&mut edited.enabled, tr!("diagnostics_enabled"));
+                ui.horizontal(|ui| {
+                    ui.label(tr!("diagnostics_max_mass_ratio"));
+                    ui.add(
+                        egui::DragValue::new(&mut edited.max_mass_ratio)
+                            .range(1.0..=f32::MAX)
+                            .speed(1.0),
+                    );
+                });
+                if edited != *settings {
+                    *settings = edited;
+                }
+            }
+
+            ui.separator();
+
+            let findings = world
+                .get_resource::<SceneLint>()
+                .map(|lint| lint.findings.clone())
+                .unwrap_or_default();
+            if findings.is_empty() {
+                ui.label(egui::RichText::new(tr!("diagnostics_none")).weak());
+                return;
+            }
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for finding in findings {
+                    ui.horizontal_wrapped(|ui| {
+                        let text = format!("{}: {}", tr!(finding.kind.key()), finding.detail);
+                        if ui
+                            .link(text)
+                            .on_hover_text(tr!("diagnostics_select"))
+                            .clicked()
+                        {
+                            select(world, finding.entity);
+                        }
+                        if let Some(fix) = finding.fix {
+                            if ui.small_button(tr!(fix.key())).clicked() {
+                                world.send_event(LintFixEvent(fix));
+                            }
+                        }
+                    });
+                }
+            });
+        });
+
+    if !open {
+        if let Some(mut panel_state) = world.get_resource_mut::<PanelState>() {
+            panel_state.diagnostics_visible = false;
+        }
+    }
+}
+
+/// 切换到能编辑该实体的工具模式并选中它
+fn select(world: &mut World, entity: Entity) {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return;
+    };
+    let mode = if entity_ref.contains::<JointVisualization>() {
+        ToolMode::Joint
+    } else if entity_ref.contains::<AnchorPoint>() {
+        ToolMode::Anchor
+    } else {
+        ToolMode::Select
+    };
+    if let Some(mut next_state) = world.get_resource_mut::<NextState<ToolMode>>() {
+        next_state.set(mode);
+    }
+    if let Some(mut selection) = world.get_resource_mut::<EditorSelection>() {
+        selection.set(entity);
+    }
+}
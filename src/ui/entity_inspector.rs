@@ -4,12 +4,14 @@ use bevy_egui::egui::{self, Context};
 
 use crate::collider_tools;
 use crate::collider_tools::edit;
+use crate::collider_tools::units::UnitConverter;
 use crate::collider_tools::visualization;
 use crate::ui::asset_management::{
     ImageAssetChannel, SelectedImageAsset, get_available_images, get_supported_image_extensions,
     open_load_image_dialog,
 };
 use crate::ui::panel_state::{EntityInspectorMode, EntityInspectorState};
+use crate::ui::unit_widgets::{bounded_length_drag_value, length_drag_value};
 use crate::{EditorSelection, tr};
 
 /// Triangle lock state for angles and sides
@@ -350,6 +352,14 @@ fn get_available_component_categories() -> Vec<(&'static str, Vec<ComponentInfo>
                 },
             ],
         ),
+        (
+            "Force Fields",
+            vec![ComponentInfo {
+                name: "ForceField".to_string(),
+                display_name: tr!("force_field"),
+                description: tr!("force_field_desc"),
+            }],
+        ),
         (
             "Rendering",
             vec![ComponentInfo {
@@ -670,6 +680,11 @@ fn get_current_components(world: &World, entity: Entity) -> Vec<String> {
         components.push("ConstantAngularAcceleration".to_string());
     }
 
+    // Force field components
+    if world.get::<collider_tools::ForceField>(entity).is_some() {
+        components.push("ForceField".to_string());
+    }
+
     // Rendering components
     if world.get::<Sprite>(entity).is_some() {
         components.push("Sprite".to_string());
@@ -809,6 +824,13 @@ fn add_component_to_entity(
                 .insert(ConstantAngularAcceleration(0.0));
         }
 
+        // Force field components - fields act as area effectors, so make the collider a sensor
+        "ForceField" => {
+            commands
+                .entity(entity)
+                .insert((collider_tools::ForceField::default(), Sensor));
+        }
+
         // Rendering components - Sprite with image support
         "Sprite" => {
             if let Some(image_handle) = selected_image_handle {
@@ -950,6 +972,13 @@ fn remove_component_from_entity(world: &mut World, entity: Entity, component_nam
                 .remove::<ConstantAngularAcceleration>();
         }
 
+        // Force field components
+        "ForceField" => {
+            commands
+                .entity(entity)
+                .remove::<collider_tools::ForceField>();
+        }
+
         // Rendering components
         "Sprite" => {
             commands.entity(entity).remove::<Sprite>();
@@ -1016,7 +1045,22 @@ fn shape_edit_ui(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
             triangle_shape_edit_ui(ui, world, entity, &collider, &transform);
         }
         crate::collider_tools::ColliderType::Polygon => {
-            polygon_shape_edit_ui(ui, world, entity, &collider, &transform);
+            if let Some(source) = world.get::<crate::PolygonSource>(entity) {
+                // Outlines from boolean operations are edited through their vertices in Edit mode
+                ui.label(tr!("polygon_properties"));
+                ui.label(format!(
+                    "{}: {}",
+                    tr!("contour_count"),
+                    source.contours.len()
+                ));
+                ui.label(format!(
+                    "{}: {}",
+                    tr!("vertex_count"),
+                    source.vertex_count()
+                ));
+            } else {
+                polygon_shape_edit_ui(ui, world, entity, &collider, &transform);
+            }
         }
     }
 
@@ -1035,6 +1079,7 @@ fn rectangle_shape_edit_ui(
     collider: &Collider,
     _transform: &Transform,
 ) {
+    let units = UnitConverter::from_world(world);
     ui.label(tr!("rectangle_properties"));
 
     // Extract current dimensions
@@ -1055,11 +1100,12 @@ fn rectangle_shape_edit_ui(
     ui.horizontal(|ui| {
         ui.label(tr!("width"));
         if ui
-            .add(
-                egui::DragValue::new(&mut width)
-                    .speed(0.1)
-                    .range(0.01..=f32::MAX),
-            )
+            .add(bounded_length_drag_value(
+                &mut width,
+                units,
+                0.1,
+                0.01..=f32::MAX,
+            ))
             .changed()
         {
             update_rectangle_collider(world, entity, width, height);
@@ -1070,11 +1116,12 @@ fn rectangle_shape_edit_ui(
     ui.horizontal(|ui| {
         ui.label(tr!("height"));
         if ui
-            .add(
-                egui::DragValue::new(&mut height)
-                    .speed(0.1)
-                    .range(0.01..=f32::MAX),
-            )
+            .add(bounded_length_drag_value(
+                &mut height,
+                units,
+                0.1,
+                0.01..=f32::MAX,
+            ))
             .changed()
         {
             update_rectangle_collider(world, entity, width, height);
@@ -1116,6 +1163,7 @@ fn circle_shape_edit_ui(
     collider: &Collider,
     _transform: &Transform,
 ) {
+    let units = UnitConverter::from_world(world);
     ui.label(tr!("circle_properties"));
 
     // Extract current radius
@@ -1133,11 +1181,12 @@ fn circle_shape_edit_ui(
     ui.horizontal(|ui| {
         ui.label(tr!("radius"));
         if ui
-            .add(
-                egui::DragValue::new(&mut radius)
-                    .speed(0.1)
-                    .range(0.01..=f32::MAX),
-            )
+            .add(bounded_length_drag_value(
+                &mut radius,
+                units,
+                0.1,
+                0.01..=f32::MAX,
+            ))
             .changed()
         {
             update_circle_collider(world, entity, radius);
@@ -1146,7 +1195,11 @@ fn circle_shape_edit_ui(
 
     // Diameter display
     let diameter = radius * 2.0;
-    ui.label(format!("{}: {:.2}", tr!("diameter"), diameter));
+    ui.label(format!(
+        "{}: {}",
+        tr!("diameter"),
+        units.format_length(diameter)
+    ));
 
     // Preset radii
     ui.label(tr!("preset_radii"));
@@ -1180,6 +1233,7 @@ fn capsule_shape_edit_ui(
     collider: &Collider,
     transform: &Transform,
 ) {
+    let units = UnitConverter::from_world(world);
     ui.label(tr!("capsule_properties"));
 
     // Extract current capsule properties
@@ -1207,11 +1261,12 @@ fn capsule_shape_edit_ui(
     ui.horizontal(|ui| {
         ui.label(tr!("radius"));
         if ui
-            .add(
-                egui::DragValue::new(&mut radius)
-                    .speed(0.05)
-                    .range(0.01..=f32::MAX),
-            )
+            .add(bounded_length_drag_value(
+                &mut radius,
+                units,
+                0.05,
+                0.01..=f32::MAX,
+            ))
             .changed()
         {
             update_capsule_collider(world, entity, radius, half_height, rotation);
@@ -1223,11 +1278,12 @@ fn capsule_shape_edit_ui(
     ui.horizontal(|ui| {
         ui.label(tr!("height"));
         if ui
-            .add(
-                egui::DragValue::new(&mut full_height)
-                    .speed(0.1)
-                    .range(0.02..=f32::MAX),
-            )
+            .add(bounded_length_drag_value(
+                &mut full_height,
+                units,
+                0.1,
+                0.02..=f32::MAX,
+            ))
             .changed()
         {
             update_capsule_collider(world, entity, radius, full_height / 2.0, rotation);
@@ -1275,6 +1331,7 @@ fn triangle_shape_edit_ui(
     collider: &Collider,
     transform: &Transform,
 ) {
+    let units = UnitConverter::from_world(world);
     ui.label(tr!("triangle_properties"));
 
     // Get lock state - extract before UI closures
@@ -1326,13 +1383,12 @@ fn triangle_shape_edit_ui(
                     }
                 }
                 let mut new_length = side_a;
-                let drag_value = egui::DragValue::new(&mut new_length)
-                    .speed(0.1)
-                    .range(0.001..=f32::MAX);
+                let drag_value =
+                    bounded_length_drag_value(&mut new_length, units, 0.1, 0.001..=f32::MAX);
                 if ui.add_enabled(!is_locked, drag_value).changed() {
                     update_triangle_side_length(world, entity, 0, new_length, transform);
                 }
-                ui.label(format!("(当前: {:.1})", side_a));
+                ui.label(format!("(当前: {})", units.format_length(side_a)));
             });
 
             // Side BC lockable label
@@ -1352,13 +1408,12 @@ fn triangle_shape_edit_ui(
                     }
                 }
                 let mut new_length = side_b;
-                let drag_value = egui::DragValue::new(&mut new_length)
-                    .speed(0.1)
-                    .range(0.001..=f32::MAX);
+                let drag_value =
+                    bounded_length_drag_value(&mut new_length, units, 0.1, 0.001..=f32::MAX);
                 if ui.add_enabled(!is_locked, drag_value).changed() {
                     update_triangle_side_length(world, entity, 1, new_length, transform);
                 }
-                ui.label(format!("(当前: {:.1})", side_b));
+                ui.label(format!("(当前: {})", units.format_length(side_b)));
             });
 
             // Side CA lockable label
@@ -1378,13 +1433,12 @@ fn triangle_shape_edit_ui(
                     }
                 }
                 let mut new_length = side_c;
-                let drag_value = egui::DragValue::new(&mut new_length)
-                    .speed(0.1)
-                    .range(0.001..=f32::MAX);
+                let drag_value =
+                    bounded_length_drag_value(&mut new_length, units, 0.1, 0.001..=f32::MAX);
                 if ui.add_enabled(!is_locked, drag_value).changed() {
                     update_triangle_side_length(world, entity, 2, new_length, transform);
                 }
-                ui.label(format!("(当前: {:.1})", side_c));
+                ui.label(format!("(当前: {})", units.format_length(side_c)));
             });
 
             ui.separator();
@@ -1395,16 +1449,17 @@ fn triangle_shape_edit_ui(
             ui.horizontal(|ui| {
                 let mut new_radius = circumradius;
                 if ui
-                    .add(
-                        egui::DragValue::new(&mut new_radius)
-                            .speed(0.1)
-                            .range(0.001..=f32::MAX),
-                    )
+                    .add(bounded_length_drag_value(
+                        &mut new_radius,
+                        units,
+                        0.1,
+                        0.001..=f32::MAX,
+                    ))
                     .changed()
                 {
                     scale_triangle_from_circumradius(world, entity, new_radius, transform);
                 }
-                ui.label(format!("(当前: {:.1})", circumradius));
+                ui.label(format!("(当前: {})", units.format_length(circumradius)));
             });
 
             ui.separator();
@@ -1549,8 +1604,8 @@ fn triangle_shape_edit_ui(
                         let mut x = world_vertices[i].x;
                         let mut y = world_vertices[i].y;
 
-                        let x_changed = ui.add(egui::DragValue::new(&mut x).speed(0.1)).changed();
-                        let y_changed = ui.add(egui::DragValue::new(&mut y).speed(0.1)).changed();
+                        let x_changed = ui.add(length_drag_value(&mut x, units, 0.1)).changed();
+                        let y_changed = ui.add(length_drag_value(&mut y, units, 0.1)).changed();
 
                         if x_changed || y_changed {
                             world_vertices[i] = Vec2::new(x, y);
@@ -1589,6 +1644,7 @@ fn polygon_shape_edit_ui(
     collider: &Collider,
     transform: &Transform,
 ) {
+    let units = UnitConverter::from_world(world);
     ui.label(tr!("polygon_properties"));
 
     match collider.shape_scaled().as_typed_shape() {
@@ -1621,11 +1677,12 @@ fn polygon_shape_edit_ui(
                 ui.label(tr!("radius"));
                 let mut radius = avg_radius;
                 if ui
-                    .add(
-                        egui::DragValue::new(&mut radius)
-                            .speed(0.1)
-                            .range(0.1..=100.0),
-                    )
+                    .add(bounded_length_drag_value(
+                        &mut radius,
+                        units,
+                        0.1,
+                        0.1..=100.0,
+                    ))
                     .changed()
                 {
                     scale_polygon_from_radius(world, entity, radius, transform);
@@ -1636,11 +1693,12 @@ fn polygon_shape_edit_ui(
                 ui.label(tr!("avg_side_length"));
                 let mut side_length = avg_side_length;
                 if ui
-                    .add(
-                        egui::DragValue::new(&mut side_length)
-                            .speed(0.1)
-                            .range(0.1..=100.0),
-                    )
+                    .add(bounded_length_drag_value(
+                        &mut side_length,
+                        units,
+                        0.1,
+                        0.1..=100.0,
+                    ))
                     .changed()
                 {
                     scale_polygon_from_side_length(world, entity, side_length, transform);
@@ -1657,11 +1715,12 @@ fn polygon_shape_edit_ui(
                             ui.label(format!("Side {}: ", i + 1));
                             let mut new_len = side_len;
                             if ui
-                                .add(
-                                    egui::DragValue::new(&mut new_len)
-                                        .speed(0.05)
-                                        .range(0.001..=f32::MAX),
-                                )
+                                .add(bounded_length_drag_value(
+                                    &mut new_len,
+                                    units,
+                                    0.05,
+                                    0.001..=f32::MAX,
+                                ))
                                 .changed()
                             {
                                 adjust_polygon_side(world, entity, i, new_len, transform);
@@ -1687,10 +1746,8 @@ fn polygon_shape_edit_ui(
                             let mut x = world_vertex.x;
                             let mut y = world_vertex.y;
 
-                            let x_changed =
-                                ui.add(egui::DragValue::new(&mut x).speed(0.1)).changed();
-                            let y_changed =
-                                ui.add(egui::DragValue::new(&mut y).speed(0.1)).changed();
+                            let x_changed = ui.add(length_drag_value(&mut x, units, 0.1)).changed();
+                            let y_changed = ui.add(length_drag_value(&mut y, units, 0.1)).changed();
 
                             if x_changed || y_changed {
                                 let new_world_vertices: Vec<Vec2> = world_vertices
@@ -1745,6 +1802,7 @@ fn polygon_shape_edit_ui(
 
 /// Transform editing interface
 fn transform_edit_ui(ui: &mut egui::Ui, world: &mut World, entity: Entity, transform: &Transform) {
+    let units = UnitConverter::from_world(world);
     let mut pos = transform.translation;
     let rot = transform.rotation.to_euler(EulerRot::XYZ).2; // Z-axis rotation
     let mut scale = transform.scale;
@@ -1752,12 +1810,8 @@ fn transform_edit_ui(ui: &mut egui::Ui, world: &mut World, entity: Entity, trans
     // Position editing
     ui.horizontal(|ui| {
         ui.label(tr!("position"));
-        let x_changed = ui
-            .add(egui::DragValue::new(&mut pos.x).speed(0.1))
-            .changed();
-        let y_changed = ui
-            .add(egui::DragValue::new(&mut pos.y).speed(0.1))
-            .changed();
+        let x_changed = ui.add(length_drag_value(&mut pos.x, units, 0.1)).changed();
+        let y_changed = ui.add(length_drag_value(&mut pos.y, units, 0.1)).changed();
 
         if x_changed || y_changed {
             let mut new_transform = *transform;
@@ -293,13 +293,46 @@ pub fn init_translations() {
     // 英文翻译
     let en_us = r#"
 app_title = Avian Physics Editor
+file_menu = 📄 File
+new_scene = New (Ctrl+N)
+open_scene = Open… (Ctrl+O)
+recent_files = Open Recent
+recent_files_clear = Clear Recent Files
+untitled = Untitled
+document_modified = Unsaved changes
+document_saved = No unsaved changes
+save = Save (Ctrl+S)
+save_as = Save As… (Ctrl+Shift+S)
+autosave = Autosave
+autosave_interval = Interval
+autosave_retention = Autosaves Kept
 export_scene = 📁 Export Scene
 export_all = Export All Physics Entities
 export_selected = Export Selected Entities
 export_colliders = Export Colliders Only
 export_joints = Export Joints Only
+export_svg_all = SVG Diagram of All
+export_svg_selected = SVG Diagram of Selection
+svg_include_grid = Grid
+svg_include_legend = Layer Legend
+export_rust_all = Export All as Rust
+export_rust_selected = Export Selection as Rust
+export_rube_all = Export All as RUBE JSON
+export_rube_selected = Export Selection as RUBE JSON
 import_scene = 📂 Import Scene
-import_from_file = Import from File
+import_merge = Merge into Scene…
+import_place = Place at Cursor…
+import_sub_scene = Reference Scene as Sub-scene
+import_tiled_map = Import Tiled Map (TMX)
+tiled_pixel_size = Tiled Pixel Size
+import_ldtk_project = Import LDtk Project
+ldtk_pixel_size = LDtk Pixel Size
+ldtk_int_grid_merge = IntGrid Merge
+export_ldtk_project = Write Back to LDtk Project
+import_svg = Import SVG
+svg_unit_size = SVG Unit Size
+svg_curve_tolerance = Curve Tolerance
+play_from_start = ⏮ Play from Start
 resume_physics = ▶ Resume Physics
 pause_physics = ⏸ Pause Physics
 tool_mode = Tool Mode
@@ -308,6 +341,9 @@ mode_create = Create
 mode_edit = Edit
 mode_anchor = Anchor
 mode_joint = Joint
+mode_velocity = Velocity
+mode_path = Path
+mode_measure = Measure
 transform_gizmo = Transform Gizmo
 collider_editor = Collider Editor
 anchor_tools = Anchor Tools
@@ -575,6 +611,16 @@ snap_settings = Snap Settings
 enable_snapping = Enable Snapping
 angle_snap = Angle Snap
 scale_snap = Scale Snap
+object_snap = Snap to Objects
+object_snap_desc = While moving with the gizmo, pull the selection onto nearby vertices, edges and centers of other colliders. Hold Alt to skip.
+object_snap_distance = Snap Distance
+pivot_point = Pivot Point
+pivot_bounds_center = Bounds Center
+pivot_median = Median Point
+pivot_active = Active Object
+pivot_custom = Custom
+pivot_custom_hint = press P to move it to the cursor
+place_custom_pivot = Place Custom Pivot
 center_origin = Center to Origin
 # Missing translations for hardcoded strings and tr! keys
 language_switcher = 🌐 Language
@@ -966,18 +1012,326 @@ image_loading = Image is loading
 image_unavailable = Image unavailable
 click_to_select = Click to select
 unknown_time = Unknown time
+# Velocity tool
+velocity_tools = Velocity Tools
+initial_linear_velocity = Initial Linear Velocity
+initial_angular_velocity = Initial Angular Velocity
+speed = Speed
+clear_velocity = Clear Velocity
+apply_to_selection = Apply to Selection
+select_dynamic_body = Select a dynamic body to author its initial velocity
+bodies_with_initial_velocity = Bodies with initial velocity
+velocity_controls = Velocity Controls
+linear_velocity_handle = Linear Velocity
+angular_velocity_handle = Angular Velocity
+reset_handle = Reset Handle
+velocity_play_note = Initial velocities are applied when physics resumes.
+
+# Force fields
+force_field_presets = Force Field Presets
+wind_zone = Wind Zone
+water_volume = Water Volume
+gravity_zone = Gravity Zone
+attractor_zone = Attractor
+vortex_zone = Vortex
+force_field = Force Field
+force_field_desc = Applies wind, attraction, vortex, buoyancy or custom gravity to dynamic bodies inside this collider
+use_force_field = Add Force Field
+force_field_sensor_hint = Force fields are usually sensors so bodies can pass through them
+force_field_enabled = Field Enabled
+force_field_directional = Directional
+force_field_point = Point
+force_field_vortex = Vortex
+force_field_buoyancy = Buoyancy
+force_field_gravity = Gravity
+direction = Direction
+falloff = Falloff
+falloff_constant = Constant
+falloff_linear = Linear
+vortex_inward = Inward Pull
+fluid_density = Fluid Density
+linear_drag = Linear Drag
+angular_drag = Angular Drag
+zone_gravity = Zone Gravity
+field_strength = Strength
+mass_dependent = Divide by Mass
+
+# Kinematic path tool
+path_tools = Path Tools
+path_controls = **Kinematic Path**
+add_keyframe = Add Keyframe
+move_keyframe = Move Keyframe
+axis_lock = Lock to Axis
+delete_keyframe = Delete Keyframe
+path_play_note = Paths restart from the first keyframe every time play starts. The body is driven by velocity so it pushes and carries other bodies.
+select_kinematic_body = Select a kinematic body to edit its path
+no_path_hint = This body has no path yet. Ctrl+Click in the viewport to add keyframes.
+create_path = Create Path
+path_enabled = Path Enabled
+path_once = Once
+path_loop = Loop
+path_ping_pong = Ping-Pong
+path_time_offset = Time Offset
+path_duration = Duration
+preview_time = Preview Time
+keyframes = Keyframes
+keyframe_time = Time
+easing = Easing
+easing_linear = Linear
+easing_ease_in = Ease In
+easing_ease_out = Ease Out
+easing_ease_in_out = Ease In-Out
+easing_step = Step
+add_keyframe_from_body = Add Keyframe at Body
+move_body_to_start = Move Body to Start
+remove_path = Remove Path
+
+# World settings
+world_settings = World Settings
+world_presets = Presets
+world_preset_default = Default
+world_preset_platformer = Platformer
+world_preset_space = Space
+world_preset_top_down = Top-Down
+gravity = Gravity
+zero_gravity = Zero
+physics_length_unit = Pixels per Meter
+physics_length_unit_desc = World units (pixels) per meter. Scales internal tolerances such as contact and sleeping thresholds, and converts lengths shown in m or cm.
+simulation = Simulation
+physics_timestep = Timestep
+substep_count = Substeps
+restitution_iterations = Restitution Iterations
+contact_damping_ratio = Contact Damping Ratio
+contact_frequency_factor = Contact Frequency Factor
+max_overlap_solve_speed = Max Overlap Solve Speed
+sleeping = Sleeping
+sleep_linear_threshold = Linear Threshold
+sleep_angular_threshold = Angular Threshold
+deactivation_time = Time Before Sleep
+default_material = Default Material
+world_settings_saved_note = Changes apply immediately and are saved with the scene on export.
+
+# Units
+units = Units
+display_unit = Display Unit
+grid_labels = Label Grid Lines
+grid_spacing = Grid
+export_si_units = Export in SI Units
+export_si_units_desc = Convert positions, shapes, velocities, forces and gravity to meters when exporting a scene
+
+# Measure
+measure_tools = Measure Tools
+measure_controls = **Measure**
+place_measure_point = Place point
+constrain_angle = 15° steps
+move_guide = Move guide
+add_guide = Add guide
+delete_measure = Delete dimension or guide
+measure_distance = Distance
+measure_angle = Angle
+dimensions = Dimensions
+no_dimensions = No dimensions yet. Click two points to measure a distance.
+show_dimensions_everywhere = Show in all modes
+clear_dimensions = Clear Dimensions
+guides = Guides
+snap_to_guides = Snap to guides
+clear_guides = Clear Guides
+
+# Arrange
+arrange = Arrange
+align_left = Left
+align_right = Right
+align_top = Top
+align_bottom = Bottom
+align_center_x = Center X
+align_center_y = Center Y
+align_to = Align To
+align_to_selection = Selection
+align_to_active = Active Object
+stack_gap = Stack Gap
+distribute_horizontal = Distribute X
+distribute_vertical = Distribute Y
+stack_horizontal = Stack X
+stack_vertical = Stack Y
+align_edges = Align Edges
+align_centers = Align Centers
+distribute = Distribute
+stack = Stack
+
+# Mirror
+mirror = Mirror
+flip = Flip
+mirror_copy = Mirror Copy
+mirror_line = Mirror Line
+mirror_line_selection = Selection Center
+mirror_line_origin = World Origin
+mirror_line_pivot = Custom Pivot
+flip_horizontal = Flip Horizontal
+flip_vertical = Flip Vertical
+mirror_copy_horizontal = Mirror Copy Horizontal
+mirror_copy_vertical = Mirror Copy Vertical
+mirror_skipped_title = Some Bodies Were Not Flipped
+mirror_skipped_message = {count} bodies have compound or polyline colliders that cannot be mirrored and were left unchanged:
+mirror_skipped_ok = OK
+
+# Boolean Operations
+boolean_operations = Boolean Operations
+boolean_union = Union
+boolean_subtract = Subtract
+boolean_intersect = Intersect
+contour_count = Contours
+boolean_hint = The first selected collider is kept and becomes an editable polygon; the others are removed
+
+# Prefabs
+prefabs = Prefabs
+prefab_create = Create from Selection
+prefab_place = Place Prefab…
+prefab_place_here = Place
+prefab_reload = Reload
+prefab_instance = Instance of
+prefab_overrides = Overrides
+prefab_apply = Apply to Prefab
+prefab_revert = Revert Overrides
+prefab_unpack = Unpack
+prefab_select_members = Select Members
+
+# Sub-scenes
+sub_scenes = Sub-scenes
+sub_scene_add = Reference Scene…
+sub_scene_source = Scene
+sub_scene_read_only = Read-only; open it to edit its contents
+sub_scene_editing = Editing; save to write the scene file
+sub_scene_open = Open for Editing
+sub_scene_save = Save
+sub_scene_discard = Discard Changes
+sub_scene_reload = Reload
+sub_scene_joints = Attached joints
+
+# Import conflicts
+import_conflicts = Collision Layer Conflicts
+import_conflicts_file = Importing
+import_conflict_bit = Bit
+import_conflict_current_bit = current bit
+import_conflict_imported_bit = imported bit
+import_conflict_use_current = Use current bit
+import_conflict_keep_both = Keep both
+import_conflict_same_layer = Same layer
+import_conflict_move_imported = Move imported layer
+import_conflicts_import = Import
+
+# Recovery
+recovery_title = Recover Unsaved Session
+recovery_message = The editor did not exit cleanly last time. Its latest autosave can be recovered.
+recovery_document = Document
+recovery_saved_at = Autosaved at
+recovery_recover = Recover
+recovery_discard = Discard
+
+# Unsaved changes
+unsaved_changes = Unsaved Changes
+unsaved_before_new = Save changes before starting a new scene?
+unsaved_before_open = Save changes before opening another scene?
+unsaved_before_exit = Save changes before quitting?
+unsaved_save = Save
+unsaved_discard = Don't Save
+
+# Sketch
+mode_sketch = Sketch
+sketch_tools = Sketch Tools
+sketch_controls = **Sketch**
+draw_stroke = Draw stroke
+sketch_close_note = End a stroke near its start to close it into a solid shape; other strokes become polylines.
+sketch_tolerance = Tolerance
+sketch_close_distance = Close Distance
+sketch_sample_spacing = Sample Spacing
+selected_sketch = Selected Sketch
+sketch_closed = Closed
+raw_samples = Samples
+simplified_vertices = Vertices
+no_sketch_selected = Select a sketched collider to change how it is simplified.
+
+# Tiles
+mode_tile = Tile
+tile_tools = Tile Tools
+tile_controls = **Tile**
+paint_tiles = Paint cells
+erase_tiles = Erase cells
+tile_layer_note = Painting goes into the selected tile layer, or the last one painted. The first stroke without a layer creates a static layer aligned to the grid.
+tile_cell_size = Cell Size
+tile_merge = Merge
+tile_merge_rectangles = Rectangles
+tile_merge_outline = Outline
+no_tile_layer = No tile layer yet. Paint on the grid to create one.
+tile_layer = Tile Layer
+tile_cells = Cells
+tile_parts = Collider Parts
+clear_tiles = Clear
+new_tile_layer = New Layer
+# Diagnostics
+diagnostics = Diagnostics
+diagnostics_enabled = Check scene
+diagnostics_max_mass_ratio = Max mass ratio
+diagnostics_none = No problems found.
+diagnostics_select = Click to select
+lint_dangling_joint = Joint body or anchor is missing
+lint_degenerate_collider = Collider has no area
+lint_static_joint = Joint between two static bodies
+lint_mass_ratio = Extreme mass ratio across joint
+lint_overlapping_bodies = Dynamic bodies overlap
+lint_sensor_without_events = Sensor without collision events
+lint_layers_never_collide = Collision layers never collide
+lint_anchor_outside_collider = Anchor outside its collider
+lint_fix_remove_joint = Remove joint
+lint_fix_delete_collider = Delete
+lint_fix_make_dynamic = Make dynamic
+lint_fix_enable_events = Enable events
+lint_fix_reset_layers = Reset layers
+lint_fix_move_anchor = Move onto collider
 "#;
 
     // 中文翻译
     let zh_cn = r#"
 app_title = Avian 物理编辑器
+file_menu = 📄 文件
+new_scene = 新建 (Ctrl+N)
+open_scene = 打开… (Ctrl+O)
+recent_files = 最近打开
+recent_files_clear = 清除最近文件
+untitled = 未命名
+document_modified = 有未保存的更改
+document_saved = 没有未保存的更改
+save = 保存 (Ctrl+S)
+save_as = 另存为… (Ctrl+Shift+S)
+autosave = 自动保存
+autosave_interval = 间隔
+autosave_retention = 保留份数
 export_scene = 📁 导出场景
 export_all = 导出所有物理实体
 export_selected = 导出选中实体
 export_colliders = 仅导出碰撞体
 export_joints = 仅导出关节
+export_svg_all = 全部导出为 SVG 图
+export_svg_selected = 选中项导出为 SVG 图
+svg_include_grid = 网格
+svg_include_legend = 碰撞层图例
+export_rust_all = 全部导出为 Rust 代码
+export_rust_selected = 选中项导出为 Rust 代码
+export_rube_all = 全部导出为 RUBE JSON
+export_rube_selected = 选中项导出为 RUBE JSON
 import_scene = 📂 导入场景
-import_from_file = 从文件导入
+import_merge = 合并到场景…
+import_place = 放置到光标处…
+import_sub_scene = 作为子场景引用
+import_tiled_map = 导入 Tiled 地图 (TMX)
+tiled_pixel_size = Tiled 像素大小
+import_ldtk_project = 导入 LDtk 项目
+ldtk_pixel_size = LDtk 像素大小
+ldtk_int_grid_merge = IntGrid 合并
+export_ldtk_project = 写回 LDtk 项目
+import_svg = 导入 SVG
+svg_unit_size = SVG 单位大小
+svg_curve_tolerance = 曲线容差
+play_from_start = ⏮ 从头播放
 resume_physics = ▶ 恢复物理
 pause_physics = ⏸ 暂停物理
 tool_mode = 工具模式
@@ -986,6 +1340,9 @@ mode_create = 创建
 mode_edit = 编辑
 mode_anchor = 锚点
 mode_joint = 关节
+mode_velocity = 速度
+mode_path = 路径
+mode_measure = 测量
 transform_gizmo = 变换手柄
 collider_editor = 碰撞体编辑器
 anchor_tools = 锚点工具
@@ -1191,6 +1548,16 @@ snap_settings = 吸附设置
 enable_snapping = 启用吸附
 angle_snap = 角度吸附
 scale_snap = 缩放吸附
+object_snap = 吸附到物体
+object_snap_desc = 使用变换控件移动时，将选中物体吸附到其它碰撞体的顶点、边和中心。按住 Alt 可暂时跳过。
+object_snap_distance = 吸附距离
+pivot_point = 轴心点
+pivot_bounds_center = 包围盒中心
+pivot_median = 中位点
+pivot_active = 活动对象
+pivot_custom = 自定义
+pivot_custom_hint = 按 P 移到光标处
+place_custom_pivot = 放置自定义轴心
 center_origin = 居中到原点
 # 缺失的翻译补充
 language_switcher = 🌐 语言
@@ -1583,6 +1950,281 @@ image_loading = 图片正在加载
 image_unavailable = 图片不可用
 click_to_select = 点击选择
 unknown_time = 未知时间
+# 速度工具
+velocity_tools = 速度工具
+initial_linear_velocity = 初始线速度
+initial_angular_velocity = 初始角速度
+speed = 速率
+clear_velocity = 清除速度
+apply_to_selection = 应用到选中对象
+select_dynamic_body = 选择一个动态刚体以设置初始速度
+bodies_with_initial_velocity = 设置了初始速度的刚体
+velocity_controls = 速度控制
+linear_velocity_handle = 线速度
+angular_velocity_handle = 角速度
+reset_handle = 重置手柄
+velocity_play_note = 恢复物理模拟时将应用初始速度。
+
+# 力场
+force_field_presets = 力场预设
+wind_zone = 风区
+water_volume = 水体
+gravity_zone = 重力区
+attractor_zone = 吸引点
+vortex_zone = 漩涡
+force_field = 力场
+force_field_desc = 对位于该碰撞体内的动态刚体施加风力、吸引力、漩涡、浮力或自定义重力
+use_force_field = 添加力场
+force_field_sensor_hint = 力场通常设为传感器，以便物体可以穿过
+force_field_enabled = 启用力场
+force_field_directional = 定向
+force_field_point = 点
+force_field_vortex = 漩涡
+force_field_buoyancy = 浮力
+force_field_gravity = 重力
+direction = 方向
+falloff = 衰减
+falloff_constant = 恒定
+falloff_linear = 线性
+vortex_inward = 向心拉力
+fluid_density = 流体密度
+linear_drag = 线性阻力
+angular_drag = 角阻力
+zone_gravity = 区域重力
+field_strength = 强度
+mass_dependent = 按质量缩放
+
+# 运动路径工具
+path_tools = 路径工具
+path_controls = **运动学路径**
+add_keyframe = 添加关键帧
+move_keyframe = 移动关键帧
+axis_lock = 锁定轴向
+delete_keyframe = 删除关键帧
+path_play_note = 每次开始播放时路径都会从第一个关键帧重新开始。刚体由速度驱动，因此可以推动和承载其他刚体。
+select_kinematic_body = 选择一个运动学刚体以编辑其路径
+no_path_hint = 该刚体尚无路径。在视口中按 Ctrl+点击 添加关键帧。
+create_path = 创建路径
+path_enabled = 启用路径
+path_once = 单次
+path_loop = 循环
+path_ping_pong = 往返
+path_time_offset = 时间偏移
+path_duration = 时长
+preview_time = 预览时间
+keyframes = 关键帧
+keyframe_time = 时间
+easing = 缓动
+easing_linear = 线性
+easing_ease_in = 缓入
+easing_ease_out = 缓出
+easing_ease_in_out = 缓入缓出
+easing_step = 阶跃
+add_keyframe_from_body = 在刚体处添加关键帧
+move_body_to_start = 移动刚体到起点
+remove_path = 移除路径
+
+# 世界设置
+world_settings = 世界设置
+world_presets = 预设
+world_preset_default = 默认
+world_preset_platformer = 平台跳跃
+world_preset_space = 太空
+world_preset_top_down = 俯视角
+gravity = 重力
+zero_gravity = 归零
+physics_length_unit = 每米像素数
+physics_length_unit_desc = 每米对应的世界单位（像素），用于缩放接触与休眠等内部容差，并换算以 m 或 cm 显示的长度。
+simulation = 模拟
+physics_timestep = 时间步长
+substep_count = 子步数
+restitution_iterations = 弹性迭代次数
+contact_damping_ratio = 接触阻尼比
+contact_frequency_factor = 接触频率系数
+max_overlap_solve_speed = 最大穿透修正速度
+sleeping = 休眠
+sleep_linear_threshold = 线性阈值
+sleep_angular_threshold = 角度阈值
+deactivation_time = 进入休眠前时间
+default_material = 默认材质
+world_settings_saved_note = 修改会立即生效，并在导出时随场景保存。
+
+# 单位
+units = 单位
+display_unit = 显示单位
+grid_labels = 标注网格线
+grid_spacing = 网格
+export_si_units = 以国际单位导出
+export_si_units_desc = 导出场景时将位置、形状、速度、力和重力换算为米
+
+# 测量
+measure_tools = 测量工具
+measure_controls = **测量**
+place_measure_point = 放置测量点
+constrain_angle = 按 15° 约束
+move_guide = 移动参考线
+add_guide = 添加参考线
+delete_measure = 删除标注或参考线
+measure_distance = 距离
+measure_angle = 角度
+dimensions = 尺寸标注
+no_dimensions = 暂无标注。点击两个点即可测量距离。
+show_dimensions_everywhere = 在所有模式中显示
+clear_dimensions = 清除标注
+guides = 参考线
+snap_to_guides = 吸附到参考线
+clear_guides = 清除参考线
+
+# 排列
+arrange = 排列
+align_left = 左对齐
+align_right = 右对齐
+align_top = 顶对齐
+align_bottom = 底对齐
+align_center_x = 水平居中
+align_center_y = 垂直居中
+align_to = 对齐到
+align_to_selection = 选区
+align_to_active = 活动对象
+stack_gap = 堆叠间距
+distribute_horizontal = 水平分布
+distribute_vertical = 垂直分布
+stack_horizontal = 水平堆叠
+stack_vertical = 垂直堆叠
+align_edges = 边缘对齐
+align_centers = 中心对齐
+distribute = 均匀分布
+stack = 堆叠
+
+# 镜像
+mirror = 镜像
+flip = 翻转
+mirror_copy = 镜像复制
+mirror_line = 镜像线
+mirror_line_selection = 选区中心
+mirror_line_origin = 世界原点
+mirror_line_pivot = 自定义轴心
+flip_horizontal = 水平翻转
+flip_vertical = 垂直翻转
+mirror_copy_horizontal = 水平镜像复制
+mirror_copy_vertical = 垂直镜像复制
+mirror_skipped_title = 部分物体未翻转
+mirror_skipped_message = 以下 {count} 个物体的碰撞体为复合或折线形状，无法镜像，已保持原样：
+mirror_skipped_ok = 确定
+
+# 布尔运算
+boolean_operations = 布尔运算
+boolean_union = 合并
+boolean_subtract = 减去
+boolean_intersect = 相交
+contour_count = 轮廓数
+boolean_hint = 保留最先选中的碰撞体并转为可编辑多边形，其余碰撞体将被移除
+
+# 预制体
+prefabs = 预制体
+prefab_create = 从选区创建
+prefab_place = 放置预制体…
+prefab_place_here = 放置
+prefab_reload = 重新加载
+prefab_instance = 实例来源
+prefab_overrides = 覆盖项
+prefab_apply = 应用到预制体
+prefab_revert = 还原覆盖
+prefab_unpack = 解除关联
+prefab_select_members = 选中成员
+
+# 子场景
+sub_scenes = 子场景
+sub_scene_add = 引用场景…
+sub_scene_source = 场景
+sub_scene_read_only = 只读；打开后可编辑其内容
+sub_scene_editing = 编辑中；保存以写回场景文件
+sub_scene_open = 打开编辑
+sub_scene_save = 保存
+sub_scene_discard = 放弃修改
+sub_scene_reload = 重新加载
+sub_scene_joints = 连接的关节
+
+# 导入冲突
+import_conflicts = 碰撞层冲突
+import_conflicts_file = 正在导入
+import_conflict_bit = 位
+import_conflict_current_bit = 当前位
+import_conflict_imported_bit = 导入位
+import_conflict_use_current = 使用当前位
+import_conflict_keep_both = 两者都保留
+import_conflict_same_layer = 视为同一层
+import_conflict_move_imported = 移动导入的层
+import_conflicts_import = 导入
+
+# 会话恢复
+recovery_title = 恢复未保存的会话
+recovery_message = 编辑器上次未正常退出，可以恢复其最后一次自动保存。
+recovery_document = 文档
+recovery_saved_at = 自动保存于
+recovery_recover = 恢复
+recovery_discard = 丢弃
+
+# 未保存更改
+unsaved_changes = 未保存的更改
+unsaved_before_new = 新建场景前是否保存更改？
+unsaved_before_open = 打开其他场景前是否保存更改？
+unsaved_before_exit = 退出前是否保存更改？
+unsaved_save = 保存
+unsaved_discard = 不保存
+
+# 草图
+mode_sketch = 草图
+sketch_tools = 草图工具
+sketch_controls = **草图**
+draw_stroke = 绘制笔画
+sketch_close_note = 在起点附近结束笔画会将其闭合为实心形状；其他笔画会成为折线。
+sketch_tolerance = 容差
+sketch_close_distance = 闭合距离
+sketch_sample_spacing = 采样间距
+selected_sketch = 选中的草图
+sketch_closed = 闭合
+raw_samples = 采样点
+simplified_vertices = 顶点
+no_sketch_selected = 选择一个草图碰撞体以调整其简化方式。
+
+# 瓦片
+mode_tile = 瓦片
+tile_tools = 瓦片工具
+tile_controls = **瓦片**
+paint_tiles = 绘制单元格
+erase_tiles = 擦除单元格
+tile_layer_note = 绘制会写入选中的瓦片图层，或上次绘制的图层。没有图层时，第一笔会创建一个与网格对齐的静态图层。
+tile_cell_size = 单元格大小
+tile_merge = 合并
+tile_merge_rectangles = 矩形
+tile_merge_outline = 轮廓
+no_tile_layer = 还没有瓦片图层。在网格上绘制即可创建。
+tile_layer = 瓦片图层
+tile_cells = 单元格
+tile_parts = 碰撞体部件
+clear_tiles = 清空
+new_tile_layer = 新建图层
+# 诊断
+diagnostics = 诊断
+diagnostics_enabled = 检查场景
+diagnostics_max_mass_ratio = 最大质量比
+diagnostics_none = 未发现问题。
+diagnostics_select = 点击选中
+lint_dangling_joint = 关节的刚体或锚点已不存在
+lint_degenerate_collider = 碰撞体没有面积
+lint_static_joint = 关节连接了两个静态刚体
+lint_mass_ratio = 关节两端质量比过大
+lint_overlapping_bodies = 动态刚体相互重叠
+lint_sensor_without_events = 传感器未启用碰撞事件
+lint_layers_never_collide = 碰撞层永远不会发生碰撞
+lint_anchor_outside_collider = 锚点位于碰撞体之外
+lint_fix_remove_joint = 移除关节
+lint_fix_delete_collider = 删除
+lint_fix_make_dynamic = 设为动态
+lint_fix_enable_events = 启用事件
+lint_fix_reset_layers = 重置碰撞层
+lint_fix_move_anchor = 移到碰撞体上
 "#;
 
     // 加载翻译
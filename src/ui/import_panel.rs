@@ -0,0 +1,93 @@
+//! 场景导入冲突面板
+//!
+//! 合并或放置导入的场景与当前碰撞层冲突时弹出，逐条选择合并为同一层或作为新层保留。
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::collider_tools::collision_layers::{LayerConflict, LayerResolution};
+use crate::scene_export_import::SceneImport;
+use crate::tr;
+
+pub(super) fn ui(ctx: &egui::Context, world: &mut World) {
+    let Some(mut pending) = world.get_resource_mut::<SceneImport>() else {
+        return;
+    };
+
+    let mut open = true;
+    let mut confirmed = false;
+    let mut cancelled = false;
+    let file_name = pending
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let import = &mut *pending;
+
+    egui::Window::new(tr!("import_conflicts"))
+        .open(&mut open)
+        .collapsible(false)
+        .default_width(360.0)
+        .show(ctx, |ui| {
+            ui.label(format!("{}: {}", tr!("import_conflicts_file"), file_name));
+            ui.separator();
+
+            egui::Grid::new("import_layer_conflicts")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (conflict, resolution) in
+                        import.conflicts.iter().zip(&mut import.resolutions)
+                    {
+                        let (description, merge, separate) = match conflict {
+                            LayerConflict::Bit {
+                                name,
+                                current,
+                                imported,
+                            } => (
+                                format!(
+                                    "\"{name}\": {} {current} / {} {imported}",
+                                    tr!("import_conflict_current_bit"),
+                                    tr!("import_conflict_imported_bit")
+                                ),
+                                tr!("import_conflict_use_current"),
+                                tr!("import_conflict_keep_both"),
+                            ),
+                            LayerConflict::Name {
+                                bit,
+                                current,
+                                imported,
+                            } => (
+                                format!(
+                                    "{} {bit}: \"{current}\" / \"{imported}\"",
+                                    tr!("import_conflict_bit")
+                                ),
+                                tr!("import_conflict_same_layer"),
+                                tr!("import_conflict_move_imported"),
+                            ),
+                        };
+                        ui.label(description);
+                        ui.horizontal(|ui| {
+                            ui.radio_value(resolution, LayerResolution::Merge, merge);
+                            ui.radio_value(resolution, LayerResolution::Separate, separate);
+                        });
+                        ui.end_row();
+                    }
+                });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                confirmed = ui.button(tr!("import_conflicts_import")).clicked();
+                cancelled = ui.button(tr!("cancel")).clicked();
+            });
+        });
+
+    // 确认后按所选方式导入，关闭则放弃导入
+    if confirmed {
+        if let Some(import) = world.remove_resource::<SceneImport>() {
+            world.commands().queue(import);
+        }
+    } else if cancelled || !open {
+        world.remove_resource::<SceneImport>();
+    }
+}
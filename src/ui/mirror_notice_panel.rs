@@ -0,0 +1,44 @@
+//! 镜像跳过提示
+//!
+//! 翻转或镜像复制遇到无法镜像形状的碰撞体（复合、折线）时弹出，列出保持原样的物体。
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::collider_tools::SkippedFlips;
+use crate::tr;
+
+pub(super) fn ui(ctx: &egui::Context, world: &mut World) {
+    let Some(skipped) = world.get_resource::<SkippedFlips>() else {
+        return;
+    };
+    let names: Vec<String> = skipped
+        .entities
+        .iter()
+        .map(|&entity| match world.get::<Name>(entity) {
+            Some(name) => name.to_string(),
+            None => format!("{entity}"),
+        })
+        .collect();
+
+    let mut open = true;
+    let mut dismissed = false;
+    egui::Window::new(tr!("mirror_skipped_title"))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(tr!("mirror_skipped_message", {count: names.len()}));
+            ui.separator();
+            for name in &names {
+                ui.label(name);
+            }
+            ui.separator();
+            dismissed = ui.button(tr!("mirror_skipped_ok")).clicked();
+        });
+
+    if !open || dismissed {
+        world.remove_resource::<SkippedFlips>();
+    }
+}
@@ -12,10 +12,20 @@ mod image_preview;
 pub mod panel_state;
 pub mod theme_colors;
 
+mod arrange_toolbar;
 mod collision_layer_ui;
+mod diagnostics_panel;
 mod entity_inspector;
+mod import_panel;
+mod mirror_notice_panel;
+mod recovery_panel;
+mod rulers;
 mod tool_panel;
 mod top_bar;
+mod unit_widgets;
+mod unsaved_changes_panel;
+mod viewport_labels;
+mod world_settings_panel;
 
 use crate::collider_tools::{PhysicsManager, ToolMode};
 use asset_management::AssetManagementPlugin;
@@ -25,7 +35,9 @@ use tool_panel::DuplicateEntityEvent;
 
 use crate::GizmoCamera;
 use crate::collider_tools::debug_render::joint::{JointConfig, joint_relationships};
+use crate::prefab::{PrefabKey, PrefabMemberOf};
 use crate::selection::EditorSelection;
+use crate::sub_scene::SubSceneMemberOf;
 use collision_layer_ui::CollisionLayerUIPlugin;
 
 pub struct EditorUIPlugin;
@@ -98,6 +110,8 @@ pub fn ui_main(world: &mut World) {
     let left_visible = panel_state.left_panel_visible;
     let right_visible = panel_state.right_panel_visible;
     let bottom_visible = panel_state.bottom_panel_visible;
+    let world_settings_visible = panel_state.world_settings_visible;
+    let diagnostics_visible = panel_state.diagnostics_visible;
 
     // Top bar (always visible)
     top_bar::ui(ctx, world, physics_paused);
@@ -116,6 +130,41 @@ pub fn ui_main(world: &mut World) {
     if bottom_visible {
         asset_management_ui(ctx, world);
     }
+
+    // Floating world settings window
+    if world_settings_visible {
+        world_settings_panel::ui(ctx, world);
+    }
+
+    // Floating diagnostics window
+    if diagnostics_visible {
+        diagnostics_panel::ui(ctx, world);
+    }
+
+    // Collision layer conflicts of a pending scene import
+    import_panel::ui(ctx, world);
+
+    // Bodies a flip had to leave unchanged
+    mirror_notice_panel::ui(ctx, world);
+
+    // Offer to recover the autosave of a crashed session
+    recovery_panel::ui(ctx, world);
+
+    // Ask what to do with unsaved changes before they would be lost
+    unsaved_changes_panel::ui(ctx, world);
+
+    // Align and distribute commands for multi-selections
+    if current_mode == ToolMode::Select {
+        arrange_toolbar::ui(ctx, world);
+    }
+
+    // Screen-edge rulers for dragging out guides
+    if current_mode == ToolMode::Measure {
+        rulers::ui(ctx, world);
+    }
+
+    // Numeric readouts next to viewport handles
+    viewport_labels::ui(ctx, world, current_mode);
 }
 
 /// Asset management UI for the bottom panel
@@ -260,8 +309,13 @@ fn handle_duplicate_entity(
     )>,
 ) {
     for event in events.read() {
-        // First, create a basic duplicate without linked_cloning to avoid crashes
-        let cloned_entity = commands.entity(event.original).clone_and_spawn().id();
+        // First, create a basic duplicate without linked_cloning to avoid crashes. A copy of a
+        // prefab or sub-scene member is a body of its own, or saving would fold it into the root
+        let cloned_entity = commands
+            .entity(event.original)
+            .clone_and_spawn()
+            .remove::<(PrefabMemberOf, PrefabKey, SubSceneMemberOf)>()
+            .id();
 
         // Find all anchor entities that belong to the duplicated entity
         let mut anchor_mapping = std::collections::HashMap::new();
@@ -270,7 +324,11 @@ fn handle_duplicate_entity(
         for (anchor_entity, anchor_point) in anchor_query.iter() {
             if anchor_point.parent_entity == event.original {
                 // Duplicate the anchor entity
-                let cloned_anchor = commands.entity(anchor_entity).clone_and_spawn().id();
+                let cloned_anchor = commands
+                    .entity(anchor_entity)
+                    .clone_and_spawn()
+                    .remove::<(PrefabMemberOf, PrefabKey, SubSceneMemberOf)>()
+                    .id();
                 anchor_mapping.insert(anchor_entity, cloned_anchor);
 
                 // Update the anchor's parent entity reference
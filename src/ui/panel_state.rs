@@ -7,6 +7,8 @@ pub struct PanelState {
     pub left_panel_visible: bool,
     pub right_panel_visible: bool,
     pub bottom_panel_visible: bool,
+    pub world_settings_visible: bool,
+    pub diagnostics_visible: bool,
 }
 
 /// 实体检查器页面模式
@@ -31,6 +33,8 @@ pub enum PanelControlEvent {
     ToggleLeftPanel,
     ToggleRightPanel,
     ToggleBottomPanel,
+    ToggleWorldSettings,
+    ToggleDiagnostics,
     MaximizeViewport,
 }
 
@@ -42,6 +46,8 @@ impl Plugin for PanelControlPlugin {
             left_panel_visible: true,
             right_panel_visible: true,
             bottom_panel_visible: false,
+            world_settings_visible: false,
+            diagnostics_visible: false,
         })
         .insert_resource(EntityInspectorState::default())
         .add_event::<PanelControlEvent>()
@@ -65,6 +71,12 @@ fn handle_panel_controls(
             PanelControlEvent::ToggleBottomPanel => {
                 panel_state.bottom_panel_visible = !panel_state.bottom_panel_visible;
             }
+            PanelControlEvent::ToggleWorldSettings => {
+                panel_state.world_settings_visible = !panel_state.world_settings_visible;
+            }
+            PanelControlEvent::ToggleDiagnostics => {
+                panel_state.diagnostics_visible = !panel_state.diagnostics_visible;
+            }
             PanelControlEvent::MaximizeViewport => {
                 panel_state.left_panel_visible = false;
                 panel_state.right_panel_visible = false;
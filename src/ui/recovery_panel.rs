@@ -0,0 +1,55 @@
+//! 会话恢复提示
+//!
+//! 上次编辑器未正常退出时弹出，可用其最后一次自动保存替换当前场景，或丢弃它。
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::document::{DocumentEvent, Recovery};
+use crate::tr;
+
+pub(super) fn ui(ctx: &egui::Context, world: &mut World) {
+    let Some(recovery) = world.get_resource::<Recovery>() else {
+        return;
+    };
+    let document = recovery
+        .document
+        .as_ref()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| tr!("untitled"));
+    let saved_at = recovery.saved_at.clone();
+
+    let mut event = None;
+    egui::Window::new(tr!("recovery_title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(tr!("recovery_message"));
+            ui.separator();
+            egui::Grid::new("recovery_details")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label(tr!("recovery_document"));
+                    ui.label(document);
+                    ui.end_row();
+                    ui.label(tr!("recovery_saved_at"));
+                    ui.label(saved_at);
+                    ui.end_row();
+                });
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button(tr!("recovery_recover")).clicked() {
+                    event = Some(DocumentEvent::Recover);
+                }
+                if ui.button(tr!("recovery_discard")).clicked() {
+                    event = Some(DocumentEvent::DiscardRecovery);
+                }
+            });
+        });
+
+    if let Some(event) = event {
+        world.send_event(event);
+    }
+}
@@ -0,0 +1,192 @@
+//! Screen-edge rulers for measure mode
+//!
+//! The rulers run along the top and left edges of the viewport with ticks at the grid lines.
+//! Dragging out of the top ruler places a horizontal guide, out of the left ruler a vertical
+//! one; releasing over the ruler again cancels the guide.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::collider_tools::measure::{GuideAxis, MeasureGuides, MeasureState};
+use crate::collider_tools::units::UnitConverter;
+use crate::ui::viewport_labels::{grid_label_text, major_grid_spacing, visible_world_bounds};
+
+/// Thickness of a ruler in screen pixels
+const RULER_SIZE: f32 = 18.0;
+
+/// Minor ticks between two labelled ticks
+const MINOR_TICKS: i32 = 10;
+
+pub(super) fn ui(ctx: &egui::Context, world: &mut World) {
+    let Ok((camera, camera_transform)) = world
+        .query_filtered::<(&Camera, &GlobalTransform), With<SpritePickingCamera>>()
+        .single(world)
+        .map(|(camera, transform)| (camera.clone(), *transform))
+    else {
+        return;
+    };
+    let Some(bounds) = visible_world_bounds(&camera, &camera_transform) else {
+        return;
+    };
+    let Some(spacing) = major_grid_spacing(world, bounds.height()) else {
+        return;
+    };
+
+    let area = ctx.available_rect();
+    let top = egui::Rect::from_min_size(area.min, egui::vec2(area.width(), RULER_SIZE));
+    let left = egui::Rect::from_min_size(
+        area.min + egui::vec2(0.0, RULER_SIZE),
+        egui::vec2(RULER_SIZE, (area.height() - RULER_SIZE).max(0.0)),
+    );
+
+    let ruler = Ruler {
+        camera: &camera,
+        camera_transform: &camera_transform,
+        bounds,
+        spacing,
+        units: UnitConverter::from_world(world),
+    };
+    ruler.show(ctx, world, top, GuideAxis::Horizontal);
+    ruler.show(ctx, world, left, GuideAxis::Vertical);
+}
+
+/// View information shared by both rulers
+struct Ruler<'a> {
+    camera: &'a Camera,
+    camera_transform: &'a GlobalTransform,
+    bounds: Rect,
+    spacing: f32,
+    units: UnitConverter,
+}
+
+impl Ruler<'_> {
+    /// Draw one ruler and handle dragging a guide out of it
+    ///
+    /// `guide_axis` is the orientation of the guides it creates: the top ruler measures x and
+    /// creates horizontal guides.
+    fn show(
+        &self,
+        ctx: &egui::Context,
+        world: &mut World,
+        rect: egui::Rect,
+        guide_axis: GuideAxis,
+    ) {
+        let id = match guide_axis {
+            GuideAxis::Horizontal => "measure_ruler_top",
+            GuideAxis::Vertical => "measure_ruler_left",
+        };
+
+        let response = egui::Area::new(egui::Id::new(id))
+            .fixed_pos(rect.min)
+            .show(ctx, |ui| {
+                let (rect, response) = ui.allocate_exact_size(rect.size(), egui::Sense::drag());
+                self.paint(ui, rect, guide_axis);
+                response
+            })
+            .inner;
+
+        let pointer_value = response.interact_pointer_pos().and_then(|pointer| {
+            let world_pos = self
+                .camera
+                .viewport_to_world_2d(self.camera_transform, Vec2::new(pointer.x, pointer.y))
+                .ok()?;
+            Some(match guide_axis {
+                GuideAxis::Horizontal => world_pos.y,
+                GuideAxis::Vertical => world_pos.x,
+            })
+        });
+
+        if response.dragged() {
+            if let Some(mut state) = world.get_resource_mut::<MeasureState>() {
+                state.ruler_drag = pointer_value.map(|value| (guide_axis, value));
+            }
+        }
+        if response.drag_stopped() {
+            let dropped = world
+                .get_resource_mut::<MeasureState>()
+                .and_then(|mut state| state.ruler_drag.take());
+            let over_ruler = ctx
+                .pointer_latest_pos()
+                .is_some_and(|pointer| response.rect.contains(pointer));
+            if let (Some((axis, value)), false) = (dropped, over_ruler) {
+                if let Some(mut guides) = world.get_resource_mut::<MeasureGuides>() {
+                    guides.positions_mut(axis).push(value);
+                    info!("Added {:?} guide at {}", axis, value);
+                }
+            }
+        }
+    }
+
+    /// Background, ticks and coordinate labels
+    fn paint(&self, ui: &egui::Ui, rect: egui::Rect, guide_axis: GuideAxis) {
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        painter.rect_filled(rect, 0.0, visuals.extreme_bg_color);
+        let stroke = egui::Stroke::new(1.0, visuals.weak_text_color());
+        let font = egui::FontId::monospace(9.0);
+
+        // The top ruler measures x, the left ruler measures y
+        let (min, max) = match guide_axis {
+            GuideAxis::Horizontal => (self.bounds.min.x, self.bounds.max.x),
+            GuideAxis::Vertical => (self.bounds.min.y, self.bounds.max.y),
+        };
+        let step = self.spacing / MINOR_TICKS as f32;
+        let first = (min / step).ceil() as i32;
+        let last = ((max / step).floor() as i32).min(first + 50 * MINOR_TICKS);
+
+        for index in first..=last {
+            let value = index as f32 * step;
+            let world_pos = match guide_axis {
+                GuideAxis::Horizontal => Vec3::new(value, 0.0, 0.0),
+                GuideAxis::Vertical => Vec3::new(0.0, value, 0.0),
+            };
+            let Ok(screen) = self
+                .camera
+                .world_to_viewport(self.camera_transform, world_pos)
+            else {
+                continue;
+            };
+
+            let major = index.rem_euclid(MINOR_TICKS) == 0;
+            let tick = if major { RULER_SIZE } else { RULER_SIZE * 0.3 };
+            match guide_axis {
+                GuideAxis::Horizontal => {
+                    painter.line_segment(
+                        [
+                            egui::pos2(screen.x, rect.bottom() - tick),
+                            egui::pos2(screen.x, rect.bottom()),
+                        ],
+                        stroke,
+                    );
+                    if major {
+                        painter.text(
+                            egui::pos2(screen.x + 2.0, rect.top()),
+                            egui::Align2::LEFT_TOP,
+                            grid_label_text(&self.units, value),
+                            font.clone(),
+                            visuals.text_color(),
+                        );
+                    }
+                }
+                GuideAxis::Vertical => {
+                    painter.line_segment(
+                        [
+                            egui::pos2(rect.right() - tick, screen.y),
+                            egui::pos2(rect.right(), screen.y),
+                        ],
+                        stroke,
+                    );
+                    if major {
+                        painter.text(
+                            egui::pos2(rect.left() + 1.0, screen.y + 2.0),
+                            egui::Align2::LEFT_TOP,
+                            grid_label_text(&self.units, value),
+                            font.clone(),
+                            visuals.text_color(),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -61,6 +61,32 @@ pub struct EditorThemeColors {
     pub gizmo_z_axis_selected: Color,
     /// TransformGizmo 视图控制颜色
     pub gizmo_view_control: Color,
+    /// 初始线速度箭头颜色
+    pub velocity_linear: Color,
+    /// 初始角速度弧线颜色
+    pub velocity_angular: Color,
+    /// 速度手柄悬停/拖拽颜色
+    pub velocity_handle_active: Color,
+    /// 力场箭头颜色
+    pub force_field_arrow: Color,
+    /// 禁用力场箭头颜色
+    pub force_field_disabled: Color,
+    /// 浮力区水面线颜色
+    pub force_field_surface: Color,
+    /// 运动路径线颜色
+    pub path_line: Color,
+    /// 路径关键帧颜色
+    pub path_keyframe: Color,
+    /// 路径关键帧悬停/选中颜色
+    pub path_keyframe_active: Color,
+    /// 路径预览虚影颜色
+    pub path_ghost: Color,
+    /// 测量尺寸线颜色
+    pub measure_line: Color,
+    /// 测量吸附点指示颜色
+    pub measure_snap: Color,
+    /// 参考线颜色
+    pub guide_line: Color,
 }
 
 impl EditorThemeColors {
@@ -93,6 +119,19 @@ impl EditorThemeColors {
             gizmo_y_axis_selected: Color::srgba(0.5, 0.9, 0.2, 1.0), // 亮绿色
             gizmo_z_axis_selected: Color::srgba(0.25, 0.65, 1.0, 1.0), // 亮蓝色
             gizmo_view_control: Color::srgba(0.9, 0.9, 0.9, 0.8), // 中性白/灰
+            velocity_linear: Color::srgb(0.3, 0.7, 1.0),   // 天蓝色
+            velocity_angular: Color::srgb(1.0, 0.5, 0.8),  // 粉色
+            velocity_handle_active: Color::srgb(1.0, 1.0, 1.0), // 白色
+            force_field_arrow: Color::srgba(0.4, 1.0, 0.7, 0.8), // 薄荷绿
+            force_field_disabled: Color::srgba(0.6, 0.6, 0.6, 0.4), // 半透明灰色
+            force_field_surface: Color::srgb(0.3, 0.6, 1.0), // 水蓝色
+            path_line: Color::srgb(1.0, 0.75, 0.3),        // 琥珀色
+            path_keyframe: Color::srgb(1.0, 0.9, 0.6),     // 浅琥珀色
+            path_keyframe_active: Color::srgb(1.0, 1.0, 1.0), // 白色
+            path_ghost: Color::srgba(1.0, 0.75, 0.3, 0.5), // 半透明琥珀色
+            measure_line: Color::srgb(0.4, 0.9, 1.0),      // 青色
+            measure_snap: Color::srgb(1.0, 0.4, 0.8),      // 粉色
+            guide_line: Color::srgba(0.2, 0.8, 1.0, 0.6),  // 半透明天蓝色
         }
     }
 
@@ -125,6 +164,19 @@ impl EditorThemeColors {
             gizmo_y_axis_selected: Color::srgba(0.4, 0.7, 0.15, 1.0), // 深亮绿色
             gizmo_z_axis_selected: Color::srgba(0.2, 0.5, 0.8, 1.0), // 深亮蓝色
             gizmo_view_control: Color::srgba(0.4, 0.4, 0.4, 0.8), // 深灰色
+            velocity_linear: Color::srgb(0.1, 0.4, 0.8),   // 深蓝色
+            velocity_angular: Color::srgb(0.8, 0.2, 0.5),  // 深粉色
+            velocity_handle_active: Color::srgb(0.0, 0.0, 0.0), // 黑色
+            force_field_arrow: Color::srgba(0.1, 0.6, 0.4, 0.8), // 深薄荷绿
+            force_field_disabled: Color::srgba(0.4, 0.4, 0.4, 0.4), // 半透明深灰色
+            force_field_surface: Color::srgb(0.1, 0.3, 0.7), // 深水蓝色
+            path_line: Color::srgb(0.7, 0.45, 0.0),        // 深琥珀色
+            path_keyframe: Color::srgb(0.55, 0.35, 0.0),   // 棕色
+            path_keyframe_active: Color::srgb(0.0, 0.0, 0.0), // 黑色
+            path_ghost: Color::srgba(0.7, 0.45, 0.0, 0.5), // 半透明深琥珀色
+            measure_line: Color::srgb(0.0, 0.45, 0.6),     // 深青色
+            measure_snap: Color::srgb(0.8, 0.0, 0.5),      // 洋红色
+            guide_line: Color::srgba(0.0, 0.5, 0.9, 0.6),  // 半透明蓝色
         }
     }
 
@@ -2,12 +2,23 @@ use avian2d::prelude::{LockedAxes, RigidBody};
 use bevy::prelude::*;
 use bevy_egui::egui::{self, Context};
 
+use crate::grid::InfiniteGridSettings;
 use crate::{
-    AnchorCreationState, AnchorPoint, ColliderEditState, ColliderType, CreationProperties,
-    EditorSelection, GizmoMode, GizmoTransformable, JointCreationState, JointType, ToolMode,
-    TransformGizmoSettings, joint_config::JointConfiguration, tr,
+    AnchorCreationState, AnchorPoint, BooleanEvent, BooleanOp, ColliderEditState, ColliderType,
+    CreationProperties, Dimension, EditorSelection, FlipDirection, ForceFalloff, ForceField,
+    ForceFieldKind, GizmoMode, GizmoTransformable, GuideAxis, InitialVelocity, JointCreationState,
+    JointType, KinematicPath, MeasureAnnotations, MeasureGuides, MeasureKind, MeasureState,
+    MirrorEvent, MirrorLine, MirrorSettings, PathEasing, PathEditState, PathLoopMode, PivotMode,
+    PrefabEvent, PrefabInstance, PrefabLibrary, PrefabMembers, SketchSettings, SketchStroke,
+    SubScene, SubSceneEditing, SubSceneEvent, SubSceneJoints, TileLayer, TileMergeMode,
+    TilePaintState, TileSettings, ToolMode, TransformGizmoSettings, UnitConverter,
+    VelocityAuthoringState, instance_overrides, joint_config::JointConfiguration,
+    prefab_instance_of, prefab_name, sub_scene_name, tr,
 };
 
+use super::arrange_toolbar::{arrange_buttons, arrange_settings_ui};
+use super::unit_widgets::length_drag_value;
+
 /// Event for duplicating an entity
 #[derive(Event, Reflect)]
 pub struct DuplicateEntityEvent {
@@ -67,6 +78,46 @@ pub(super) fn ui(
                         next_state.set(ToolMode::Joint);
                     }
                 }
+                if ui
+                    .selectable_label(current_mode == ToolMode::Velocity, tr!("mode_velocity"))
+                    .clicked()
+                {
+                    if let Some(mut next_state) = world.get_resource_mut::<NextState<ToolMode>>() {
+                        next_state.set(ToolMode::Velocity);
+                    }
+                }
+                if ui
+                    .selectable_label(current_mode == ToolMode::Path, tr!("mode_path"))
+                    .clicked()
+                {
+                    if let Some(mut next_state) = world.get_resource_mut::<NextState<ToolMode>>() {
+                        next_state.set(ToolMode::Path);
+                    }
+                }
+                if ui
+                    .selectable_label(current_mode == ToolMode::Measure, tr!("mode_measure"))
+                    .clicked()
+                {
+                    if let Some(mut next_state) = world.get_resource_mut::<NextState<ToolMode>>() {
+                        next_state.set(ToolMode::Measure);
+                    }
+                }
+                if ui
+                    .selectable_label(current_mode == ToolMode::Sketch, tr!("mode_sketch"))
+                    .clicked()
+                {
+                    if let Some(mut next_state) = world.get_resource_mut::<NextState<ToolMode>>() {
+                        next_state.set(ToolMode::Sketch);
+                    }
+                }
+                if ui
+                    .selectable_label(current_mode == ToolMode::Tile, tr!("mode_tile"))
+                    .clicked()
+                {
+                    if let Some(mut next_state) = world.get_resource_mut::<NextState<ToolMode>>() {
+                        next_state.set(ToolMode::Tile);
+                    }
+                }
             });
 
             // Mode-specific controls
@@ -974,6 +1025,186 @@ pub(super) fn ui(
                         }
                     });
                 }
+                ToolMode::Velocity => {
+                    ui.heading(tr!("velocity_tools"));
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        let target = world
+                            .get_resource::<VelocityAuthoringState>()
+                            .and_then(|state| state.target);
+
+                        if let Some(entity) = target {
+                            ui.label(format!("{}: {:?}", tr!("selected_entity"), entity));
+
+                            let has_initial_velocity =
+                                world.get::<InitialVelocity>(entity).is_some();
+                            let mut value = world
+                                .get::<InitialVelocity>(entity)
+                                .copied()
+                                .unwrap_or_default();
+                            let mut changed = false;
+                            let units = UnitConverter::from_world(world);
+
+                            ui.label(tr!("initial_linear_velocity"));
+                            ui.horizontal(|ui| {
+                                ui.label("X");
+                                changed = ui
+                                    .add(
+                                        length_drag_value(&mut value.linear.x, units, 1.0)
+                                            .suffix(format!(" {}/s", units.symbol())),
+                                    )
+                                    .changed()
+                                    || changed;
+                                ui.label("Y");
+                                changed = ui
+                                    .add(
+                                        length_drag_value(&mut value.linear.y, units, 1.0)
+                                            .suffix(format!(" {}/s", units.symbol())),
+                                    )
+                                    .changed()
+                                    || changed;
+                            });
+                            ui.label(format!(
+                                "{}: {}",
+                                tr!("speed"),
+                                units.format_speed(value.linear.length())
+                            ));
+
+                            ui.label(tr!("initial_angular_velocity"));
+                            ui.horizontal(|ui| {
+                                changed = ui
+                                    .add(
+                                        egui::DragValue::new(&mut value.angular)
+                                            .speed(0.05)
+                                            .suffix(" rad/s"),
+                                    )
+                                    .changed()
+                                    || changed;
+                                ui.label(format!("({:.1}°/s)", value.angular.to_degrees()));
+                            });
+
+                            if changed {
+                                world.entity_mut(entity).insert(value);
+                            }
+
+                            ui.separator();
+
+                            ui.horizontal_wrapped(|ui| {
+                                if ui
+                                    .add_enabled(
+                                        has_initial_velocity,
+                                        egui::Button::new(tr!("clear_velocity")),
+                                    )
+                                    .clicked()
+                                {
+                                    world.entity_mut(entity).remove::<InitialVelocity>();
+                                }
+                                if ui.button(tr!("apply_to_selection")).clicked() {
+                                    let selected: Vec<Entity> = world
+                                        .get_resource::<EditorSelection>()
+                                        .map(|selection| selection.iter().collect())
+                                        .unwrap_or_default();
+                                    for other in selected {
+                                        if matches!(
+                                            world.get::<RigidBody>(other),
+                                            Some(RigidBody::Dynamic)
+                                        ) {
+                                            world.entity_mut(other).insert(value);
+                                        }
+                                    }
+                                }
+                            });
+                        } else {
+                            ui.label(tr!("select_dynamic_body"));
+                        }
+
+                        ui.separator();
+
+                        let authored_count = world.query::<&InitialVelocity>().iter(world).count();
+                        ui.label(format!(
+                            "{}: {}",
+                            tr!("bodies_with_initial_velocity"),
+                            authored_count
+                        ));
+
+                        ui.separator();
+
+                        re_ui::Help::new_without_title()
+                            .markdown(tr!("velocity_controls"))
+                            .control(tr!("linear_velocity_handle"), "Drag Arrow")
+                            .control(tr!("angular_velocity_handle"), "Drag Arc")
+                            .control(tr!("snap"), ("Shift +", "Drag"))
+                            .control(tr!("reset_handle"), "Right Click")
+                            .control(tr!("cancel"), "Escape")
+                            .markdown(tr!("velocity_play_note"))
+                            .ui(ui);
+                    });
+                }
+                ToolMode::Path => {
+                    ui.heading(tr!("path_tools"));
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        kinematic_path_ui(ui, world);
+
+                        ui.separator();
+
+                        re_ui::Help::new_without_title()
+                            .markdown(tr!("path_controls"))
+                            .control(tr!("add_keyframe"), ("Ctrl +", "Click"))
+                            .control(tr!("move_keyframe"), "Drag Handle")
+                            .control(tr!("axis_lock"), ("Shift +", "Drag"))
+                            .control(tr!("delete_keyframe"), "Right Click")
+                            .control(tr!("cancel"), "Escape")
+                            .markdown(tr!("path_play_note"))
+                            .ui(ui);
+                    });
+                }
+                ToolMode::Measure => {
+                    ui.heading(tr!("measure_tools"));
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        measure_ui(ui, world);
+
+                        ui.separator();
+
+                        re_ui::Help::new_without_title()
+                            .markdown(tr!("measure_controls"))
+                            .control(tr!("place_measure_point"), "Click")
+                            .control(tr!("constrain_angle"), ("Shift +", "Click"))
+                            .control(tr!("move_guide"), "Drag Guide")
+                            .control(tr!("add_guide"), "Drag Ruler")
+                            .control(tr!("delete_measure"), "Right Click")
+                            .control(tr!("cancel"), "Escape")
+                            .ui(ui);
+                    });
+                }
+                ToolMode::Sketch => {
+                    ui.heading(tr!("sketch_tools"));
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        sketch_ui(ui, world);
+
+                        ui.separator();
+
+                        re_ui::Help::new_without_title()
+                            .markdown(tr!("sketch_controls"))
+                            .control(tr!("draw_stroke"), "Drag")
+                            .control(tr!("cancel"), "Escape")
+                            .markdown(tr!("sketch_close_note"))
+                            .ui(ui);
+                    });
+                }
+                ToolMode::Tile => {
+                    ui.heading(tr!("tile_tools"));
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        tile_ui(ui, world);
+
+                        ui.separator();
+
+                        re_ui::Help::new_without_title()
+                            .markdown(tr!("tile_controls"))
+                            .control(tr!("paint_tiles"), "Left Drag")
+                            .control(tr!("erase_tiles"), "Right Drag")
+                            .markdown(tr!("tile_layer_note"))
+                            .ui(ui);
+                    });
+                }
                 ToolMode::Select => {
                     ui.heading(tr!("transform_gizmo"));
                     egui::ScrollArea::vertical().show(ui, |ui| {
@@ -993,6 +1224,8 @@ pub(super) fn ui(
                         let mut snap_enabled = false;
                         let mut angle_snap = 15.0;
                         let mut scale_snap = 0.1;
+                        let mut object_snap = true;
+                        let mut object_snap_distance = 10.0;
 
                         world.resource_scope(
                             |_world, gizmo_settings: Mut<TransformGizmoSettings>| {
@@ -1000,6 +1233,8 @@ pub(super) fn ui(
                                 snap_enabled = gizmo_settings.snap_enabled;
                                 angle_snap = gizmo_settings.angle_snap;
                                 scale_snap = gizmo_settings.scale_snap;
+                                object_snap = gizmo_settings.object_snap;
+                                object_snap_distance = gizmo_settings.object_snap_distance;
                             },
                         );
 
@@ -1043,6 +1278,34 @@ pub(super) fn ui(
                             }
                         });
 
+                        // Pivot
+                        let (pivot_mode, custom_pivot) = {
+                            let settings = world.resource::<TransformGizmoSettings>();
+                            (settings.pivot_mode, settings.custom_pivot)
+                        };
+                        ui.label(tr!("pivot_point"));
+                        let mut pivot_mode_local = pivot_mode;
+                        ui.horizontal_wrapped(|ui| {
+                            for mode in PivotMode::ALL {
+                                ui.selectable_value(&mut pivot_mode_local, mode, tr!(mode.key()));
+                            }
+                        });
+                        if pivot_mode_local == PivotMode::Custom {
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "({:.1}, {:.1}) - {}",
+                                    custom_pivot.x,
+                                    custom_pivot.y,
+                                    tr!("pivot_custom_hint")
+                                ))
+                                .weak(),
+                            );
+                        }
+                        if pivot_mode_local != pivot_mode {
+                            world.resource_mut::<TransformGizmoSettings>().pivot_mode =
+                                pivot_mode_local;
+                        }
+
                         ui.separator();
 
                         // Snap Settings
@@ -1083,6 +1346,37 @@ pub(super) fn ui(
                             });
                         }
 
+                        // Object snapping
+                        let mut object_snap_local = object_snap;
+                        ui.checkbox(&mut object_snap_local, tr!("object_snap"))
+                            .on_hover_text(tr!("object_snap_desc"));
+                        if object_snap_local != object_snap {
+                            world.resource_scope(
+                                |_world, mut gizmo_settings: Mut<TransformGizmoSettings>| {
+                                    gizmo_settings.object_snap = object_snap_local;
+                                },
+                            );
+                        }
+                        if object_snap_local {
+                            ui.horizontal(|ui| {
+                                ui.label(tr!("object_snap_distance"));
+                                let mut distance_local = object_snap_distance;
+                                ui.add(
+                                    egui::DragValue::new(&mut distance_local)
+                                        .speed(0.5)
+                                        .range(1.0..=100.0)
+                                        .suffix(" px"),
+                                );
+                                if distance_local != object_snap_distance {
+                                    world.resource_scope(
+                                    |_world, mut gizmo_settings: Mut<TransformGizmoSettings>| {
+                                        gizmo_settings.object_snap_distance = distance_local;
+                                    },
+                                );
+                                }
+                            });
+                        }
+
                         ui.separator();
 
                         // Quick Actions
@@ -1138,6 +1432,36 @@ pub(super) fn ui(
 
                         ui.separator();
 
+                        // Arrange
+                        ui.collapsing(tr!("arrange"), |ui| {
+                            arrange_settings_ui(ui, world);
+                            ui.horizontal_wrapped(|ui| {
+                                arrange_buttons(ui, world);
+                            });
+                        });
+
+                        // Mirror
+                        ui.collapsing(tr!("mirror"), |ui| {
+                            mirror_ui(ui, world);
+                        });
+
+                        // Boolean operations
+                        ui.collapsing(tr!("boolean_operations"), |ui| {
+                            boolean_ui(ui, world);
+                        });
+
+                        // Prefabs
+                        ui.collapsing(tr!("prefabs"), |ui| {
+                            prefab_ui(ui, world, selected_entity);
+                        });
+
+                        // Sub-scenes
+                        ui.collapsing(tr!("sub_scenes"), |ui| {
+                            sub_scene_ui(ui, world, selected_entity);
+                        });
+
+                        ui.separator();
+
                         // Instructions
                         re_ui::Help::new_without_title()
                             .markdown(tr!("selection_controls"))
@@ -1152,6 +1476,15 @@ pub(super) fn ui(
                             .control(tr!("rotate"), "Drag Ring")
                             .control(tr!("scale"), "Drag Handle")
                             .control(tr!("snapping"), "Ctrl")
+                            .control(tr!("place_custom_pivot"), "P")
+                            .markdown(tr!("arrange"))
+                            .control(tr!("align_edges"), "Alt + J/L/I/K")
+                            .control(tr!("align_centers"), "Alt + H/V")
+                            .control(tr!("distribute"), "Alt + Shift + H/V")
+                            .control(tr!("stack"), "Alt + Shift + J/K")
+                            .markdown(tr!("mirror"))
+                            .control(tr!("flip"), "Shift + H/V")
+                            .control(tr!("mirror_copy"), "Ctrl + Shift + H/V")
                             .ui(ui);
                     });
                 }
@@ -1306,6 +1639,30 @@ fn create_presets_ui(ui: &mut egui::Ui, properties: &mut CreationProperties) ->
         }
     });
 
+    ui.label(tr!("force_field_presets"));
+    ui.horizontal_wrapped(|ui| {
+        if ui.button(tr!("wind_zone")).clicked() {
+            properties.wind_zone();
+            changed = true;
+        }
+        if ui.button(tr!("water_volume")).clicked() {
+            properties.water_volume();
+            changed = true;
+        }
+        if ui.button(tr!("gravity_zone")).clicked() {
+            properties.gravity_zone();
+            changed = true;
+        }
+        if ui.button(tr!("attractor_zone")).clicked() {
+            properties.attractor_zone();
+            changed = true;
+        }
+        if ui.button(tr!("vortex_zone")).clicked() {
+            properties.vortex_zone();
+            changed = true;
+        }
+    });
+
     changed
 }
 
@@ -2100,5 +2457,874 @@ fn create_advanced_physics_ui(ui: &mut egui::Ui, properties: &mut CreationProper
         }
     });
 
+    // 力场
+    ui.label(tr!("force_field"));
+    let mut use_force_field = advanced.force_field.is_some();
+    changed = ui
+        .checkbox(&mut use_force_field, tr!("use_force_field"))
+        .changed()
+        || changed;
+
+    if use_force_field {
+        let field = advanced.force_field.get_or_insert_with(ForceField::default);
+        changed = force_field_ui(ui, field) || changed;
+        if !properties.collision.is_sensor {
+            ui.label(
+                egui::RichText::new(tr!("force_field_sensor_hint"))
+                    .small()
+                    .weak(),
+            );
+        }
+    } else {
+        advanced.force_field = None;
+    }
+
     changed
 }
+
+/// 力场参数 UI
+fn force_field_ui(ui: &mut egui::Ui, field: &mut ForceField) -> bool {
+    let mut changed = false;
+
+    changed = ui
+        .checkbox(&mut field.enabled, tr!("force_field_enabled"))
+        .changed()
+        || changed;
+
+    // 力场类型，切换时保留默认参数
+    ui.horizontal_wrapped(|ui| {
+        let kinds = [
+            ForceFieldKind::Directional { direction: Vec2::X },
+            ForceFieldKind::Point {
+                falloff: ForceFalloff::Constant,
+            },
+            ForceFieldKind::Vortex { inward: 0.0 },
+            ForceField::water(1.0).kind,
+            ForceField::gravity_zone(Vec2::ZERO).kind,
+        ];
+        for kind in kinds {
+            let selected = std::mem::discriminant(&field.kind) == std::mem::discriminant(&kind);
+            let label = tr!(&format!("force_field_{}", kind.name()));
+            if ui.selectable_label(selected, label).clicked() && !selected {
+                field.kind = kind;
+                changed = true;
+            }
+        }
+    });
+
+    match &mut field.kind {
+        ForceFieldKind::Directional { direction } => {
+            ui.horizontal(|ui| {
+                ui.label(tr!("direction"));
+                changed = ui
+                    .add(
+                        egui::DragValue::new(&mut direction.x)
+                            .speed(0.01)
+                            .prefix("x: "),
+                    )
+                    .changed()
+                    || changed;
+                changed = ui
+                    .add(
+                        egui::DragValue::new(&mut direction.y)
+                            .speed(0.01)
+                            .prefix("y: "),
+                    )
+                    .changed()
+                    || changed;
+            });
+        }
+        ForceFieldKind::Point { falloff } => {
+            ui.horizontal(|ui| {
+                ui.label(tr!("falloff"));
+                changed = ui
+                    .selectable_value(falloff, ForceFalloff::Constant, tr!("falloff_constant"))
+                    .changed()
+                    || changed;
+                changed = ui
+                    .selectable_value(falloff, ForceFalloff::Linear, tr!("falloff_linear"))
+                    .changed()
+                    || changed;
+            });
+        }
+        ForceFieldKind::Vortex { inward } => {
+            ui.horizontal(|ui| {
+                ui.label(tr!("vortex_inward"));
+                changed = ui
+                    .add(egui::DragValue::new(inward).speed(0.01).range(-1.0..=1.0))
+                    .changed()
+                    || changed;
+            });
+        }
+        ForceFieldKind::Buoyancy {
+            fluid_density,
+            linear_drag,
+            angular_drag,
+        } => {
+            ui.horizontal(|ui| {
+                ui.label(tr!("fluid_density"));
+                changed = ui
+                    .add(
+                        egui::DragValue::new(fluid_density)
+                            .speed(0.01)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .changed()
+                    || changed;
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr!("linear_drag"));
+                changed = ui
+                    .add(
+                        egui::DragValue::new(linear_drag)
+                            .speed(0.01)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .changed()
+                    || changed;
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr!("angular_drag"));
+                changed = ui
+                    .add(
+                        egui::DragValue::new(angular_drag)
+                            .speed(0.01)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .changed()
+                    || changed;
+            });
+        }
+        ForceFieldKind::Gravity { gravity } => {
+            ui.horizontal(|ui| {
+                ui.label(tr!("zone_gravity"));
+                changed = ui
+                    .add(
+                        egui::DragValue::new(&mut gravity.x)
+                            .speed(1.0)
+                            .prefix("x: "),
+                    )
+                    .changed()
+                    || changed;
+                changed = ui
+                    .add(
+                        egui::DragValue::new(&mut gravity.y)
+                            .speed(1.0)
+                            .prefix("y: "),
+                    )
+                    .changed()
+                    || changed;
+            });
+        }
+    }
+
+    if !matches!(
+        field.kind,
+        ForceFieldKind::Buoyancy { .. } | ForceFieldKind::Gravity { .. }
+    ) {
+        ui.horizontal(|ui| {
+            ui.label(tr!("field_strength"));
+            changed = ui
+                .add(egui::DragValue::new(&mut field.strength).speed(1.0))
+                .changed()
+                || changed;
+        });
+        changed = ui
+            .checkbox(&mut field.mass_dependent, tr!("mass_dependent"))
+            .changed()
+            || changed;
+    }
+
+    changed
+}
+
+/// 运动路径面板
+fn kinematic_path_ui(ui: &mut egui::Ui, world: &mut World) {
+    let Some(target) = world
+        .get_resource::<PathEditState>()
+        .and_then(|state| state.target)
+    else {
+        ui.label(tr!("select_kinematic_body"));
+        return;
+    };
+    ui.label(format!("{}: {:?}", tr!("selected_entity"), target));
+
+    let body_pose = world.get::<GlobalTransform>(target).map(|transform| {
+        (
+            transform.translation().truncate(),
+            transform.rotation().to_euler(EulerRot::ZYX).0,
+        )
+    });
+
+    let Some(mut path) = world.get::<KinematicPath>(target).cloned() else {
+        ui.label(tr!("no_path_hint"));
+        if ui.button(tr!("create_path")).clicked() {
+            if let Some((position, rotation)) = body_pose {
+                world
+                    .entity_mut(target)
+                    .insert(KinematicPath::from_pose(position, rotation));
+            }
+        }
+        return;
+    };
+    let original = path.clone();
+
+    let (mut selected, mut preview_time) = world
+        .get_resource::<PathEditState>()
+        .map(|state| (state.selected, state.preview_time))
+        .unwrap_or_default();
+
+    // 播放设置
+    ui.checkbox(&mut path.enabled, tr!("path_enabled"));
+    ui.horizontal_wrapped(|ui| {
+        ui.selectable_value(&mut path.mode, PathLoopMode::Once, tr!("path_once"));
+        ui.selectable_value(&mut path.mode, PathLoopMode::Loop, tr!("path_loop"));
+        ui.selectable_value(
+            &mut path.mode,
+            PathLoopMode::PingPong,
+            tr!("path_ping_pong"),
+        );
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr!("path_time_offset"));
+        ui.add(
+            egui::DragValue::new(&mut path.time_offset)
+                .speed(0.01)
+                .suffix(" s"),
+        );
+    });
+    ui.label(format!(
+        "{}: {:.2} s",
+        tr!("path_duration"),
+        path.duration()
+    ));
+
+    // 预览时间
+    if let (Some(first), Some(last)) = (path.keyframes.first(), path.keyframes.last()) {
+        let range = first.time..=last.time.max(first.time);
+        ui.add(egui::Slider::new(&mut preview_time, range).text(tr!("preview_time")));
+    }
+
+    ui.separator();
+
+    // 关键帧列表
+    ui.label(tr!("keyframes"));
+    let keyframe_count = path.keyframes.len();
+    let mut remove = None;
+    egui::Grid::new("path_keyframes")
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("#");
+            ui.label(tr!("keyframe_time"));
+            ui.label(tr!("rotation"));
+            ui.label(tr!("easing"));
+            ui.end_row();
+
+            for (index, keyframe) in path.keyframes.iter_mut().enumerate() {
+                if ui
+                    .selectable_label(selected == Some(index), index.to_string())
+                    .clicked()
+                {
+                    selected = Some(index);
+                    preview_time = keyframe.time;
+                }
+                ui.add(
+                    egui::DragValue::new(&mut keyframe.time)
+                        .speed(0.01)
+                        .range(0.0..=f32::MAX)
+                        .suffix(" s"),
+                );
+                let mut degrees = keyframe.rotation.to_degrees();
+                if ui
+                    .add(egui::DragValue::new(&mut degrees).speed(1.0).suffix("°"))
+                    .changed()
+                {
+                    keyframe.rotation = degrees.to_radians();
+                }
+                egui::ComboBox::from_id_salt(("path_easing", index))
+                    .selected_text(tr!(keyframe.easing.key()))
+                    .show_ui(ui, |ui| {
+                        for easing in PathEasing::ALL {
+                            ui.selectable_value(&mut keyframe.easing, easing, tr!(easing.key()));
+                        }
+                    });
+                if ui
+                    .add_enabled(keyframe_count > 1, egui::Button::new(tr!("delete")))
+                    .clicked()
+                {
+                    remove = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+
+    if let Some(index) = remove {
+        path.keyframes.remove(index);
+        selected = None;
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        if let Some((position, rotation)) = body_pose {
+            if ui.button(tr!("add_keyframe_from_body")).clicked() {
+                let index = path.push_keyframe(position);
+                path.keyframes[index].rotation = rotation;
+                selected = Some(index);
+            }
+        }
+        if ui.button(tr!("move_body_to_start")).clicked() {
+            if let (Some(first), Some(mut transform)) = (
+                path.keyframes.first().copied(),
+                world.get_mut::<Transform>(target),
+            ) {
+                transform.translation = first.position.extend(transform.translation.z);
+                transform.rotation = Quat::from_rotation_z(first.rotation);
+            }
+        }
+        if ui.button(tr!("remove_path")).clicked() {
+            world.entity_mut(target).remove::<KinematicPath>();
+        }
+    });
+
+    if path != original && world.get::<KinematicPath>(target).is_some() {
+        path.sort_keyframes();
+        world.entity_mut(target).insert(path);
+    }
+
+    if let Some(mut state) = world.get_resource_mut::<PathEditState>() {
+        state.selected = selected;
+        state.preview_time = preview_time;
+    }
+}
+
+/// Measure tool settings, kept dimensions and guides
+fn measure_ui(ui: &mut egui::Ui, world: &mut World) {
+    let units = UnitConverter::from_world(world);
+
+    if let Some(mut state) = world.get_resource_mut::<MeasureState>() {
+        let mut kind = state.kind;
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut kind, MeasureKind::Distance, tr!("measure_distance"));
+            ui.selectable_value(&mut kind, MeasureKind::Angle, tr!("measure_angle"));
+        });
+        if kind != state.kind {
+            state.kind = kind;
+            state.points.clear();
+        }
+    }
+
+    ui.separator();
+
+    // 尺寸标注列表
+    ui.label(tr!("dimensions"));
+    let readouts: Vec<(Dimension, Option<String>)> = world
+        .get_resource::<MeasureAnnotations>()
+        .map(|annotations| {
+            annotations
+                .dimensions
+                .iter()
+                .map(|dimension| {
+                    let value = dimension
+                        .measure(|entity| world.get::<GlobalTransform>(entity).copied())
+                        .map(|value| value.format(&units));
+                    (*dimension, value)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let Some(mut annotations) = world.get_resource_mut::<MeasureAnnotations>() else {
+        return;
+    };
+    if readouts.is_empty() {
+        ui.label(egui::RichText::new(tr!("no_dimensions")).weak());
+    }
+    let mut remove = None;
+    for (index, (dimension, value)) in readouts.iter().enumerate() {
+        ui.horizontal(|ui| {
+            let kind = match dimension {
+                Dimension::Distance { .. } => tr!("measure_distance"),
+                Dimension::Angle { .. } => tr!("measure_angle"),
+            };
+            ui.label(format!("#{} {}", index, kind));
+            ui.monospace(value.as_deref().unwrap_or("-"));
+            if ui.button(tr!("delete")).clicked() {
+                remove = Some(index);
+            }
+        });
+    }
+    if let Some(index) = remove {
+        annotations.dimensions.remove(index);
+    }
+
+    let mut visible = annotations.visible;
+    ui.horizontal_wrapped(|ui| {
+        ui.checkbox(&mut visible, tr!("show_dimensions_everywhere"));
+        if ui.button(tr!("clear_dimensions")).clicked() {
+            annotations.dimensions.clear();
+        }
+    });
+    if visible != annotations.visible {
+        annotations.visible = visible;
+    }
+
+    ui.separator();
+
+    // 参考线
+    ui.label(tr!("guides"));
+    let Some(mut guides) = world.get_resource_mut::<MeasureGuides>() else {
+        return;
+    };
+    let mut edited = guides.clone();
+    let mut remove = None;
+    egui::Grid::new("measure_guides")
+        .striped(true)
+        .show(ui, |ui| {
+            for axis in [GuideAxis::Vertical, GuideAxis::Horizontal] {
+                let label = match axis {
+                    GuideAxis::Vertical => "x",
+                    GuideAxis::Horizontal => "y",
+                };
+                for (index, position) in edited.positions_mut(axis).iter_mut().enumerate() {
+                    ui.label(label);
+                    ui.add(length_drag_value(position, units, 1.0));
+                    if ui.button(tr!("delete")).clicked() {
+                        remove = Some((axis, index));
+                    }
+                    ui.end_row();
+                }
+            }
+        });
+    if let Some((axis, index)) = remove {
+        edited.positions_mut(axis).remove(index);
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        ui.checkbox(&mut edited.snap_enabled, tr!("snap_to_guides"));
+        if ui.button(tr!("clear_guides")).clicked() {
+            edited.vertical.clear();
+            edited.horizontal.clear();
+        }
+    });
+
+    if edited != *guides {
+        *guides = edited;
+    }
+}
+
+/// 镜像：镜像线位置、翻转与镜像复制
+fn mirror_ui(ui: &mut egui::Ui, world: &mut World) {
+    if let Some(mut settings) = world.get_resource_mut::<MirrorSettings>() {
+        let mut line = settings.line;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(tr!("mirror_line"));
+            for option in MirrorLine::ALL {
+                ui.selectable_value(&mut line, option, tr!(option.key()));
+            }
+        });
+        if line != settings.line {
+            settings.line = line;
+        }
+    }
+
+    let mut command = None;
+    ui.horizontal_wrapped(|ui| {
+        if ui.button(tr!("flip_horizontal")).clicked() {
+            command = Some(MirrorEvent::Flip(FlipDirection::Horizontal));
+        }
+        if ui.button(tr!("flip_vertical")).clicked() {
+            command = Some(MirrorEvent::Flip(FlipDirection::Vertical));
+        }
+    });
+    ui.horizontal_wrapped(|ui| {
+        if ui.button(tr!("mirror_copy_horizontal")).clicked() {
+            command = Some(MirrorEvent::MirrorCopy(FlipDirection::Horizontal));
+        }
+        if ui.button(tr!("mirror_copy_vertical")).clicked() {
+            command = Some(MirrorEvent::MirrorCopy(FlipDirection::Vertical));
+        }
+    });
+    if let Some(command) = command {
+        world.send_event(command);
+    }
+}
+
+/// 布尔运算：合并、减去、相交，需要至少两个选中的碰撞体
+fn boolean_ui(ui: &mut egui::Ui, world: &mut World) {
+    let selected = world
+        .get_resource::<EditorSelection>()
+        .map(|selection| selection.len())
+        .unwrap_or(0);
+
+    let mut command = None;
+    ui.add_enabled_ui(selected >= 2, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            for op in BooleanOp::ALL {
+                if ui.button(tr!(op.key())).clicked() {
+                    command = Some(BooleanEvent(op));
+                }
+            }
+        });
+    });
+    ui.label(egui::RichText::new(tr!("boolean_hint")).small().weak());
+
+    if let Some(command) = command {
+        world.send_event(command);
+    }
+}
+
+/// 预制体：从选区创建、放置、库列表，以及选中实例的位置与覆盖操作
+fn prefab_ui(ui: &mut egui::Ui, world: &mut World, selected_entity: Option<Entity>) {
+    let selection: Vec<Entity> = world
+        .get_resource::<EditorSelection>()
+        .map(|selection| selection.iter().collect())
+        .unwrap_or_default();
+
+    let mut command = None;
+    ui.horizontal_wrapped(|ui| {
+        if ui
+            .add_enabled(
+                !selection.is_empty(),
+                egui::Button::new(tr!("prefab_create")),
+            )
+            .clicked()
+        {
+            command = Some(PrefabEvent::Create(selection.clone()));
+        }
+        if ui.button(tr!("prefab_place")).clicked() {
+            command = Some(PrefabEvent::Place(None));
+        }
+    });
+
+    // 已加载的预制体
+    let sources: Vec<String> = world
+        .get_resource::<PrefabLibrary>()
+        .map(|library| library.prefabs.keys().cloned().collect())
+        .unwrap_or_default();
+    for source in sources {
+        ui.horizontal(|ui| {
+            ui.label(prefab_name(&source)).on_hover_text(&source);
+            if ui.small_button(tr!("prefab_place_here")).clicked() {
+                command = Some(PrefabEvent::Place(Some(source.clone().into())));
+            }
+            if ui.small_button(tr!("prefab_reload")).clicked() {
+                command = Some(PrefabEvent::Reload(source.clone()));
+            }
+        });
+    }
+
+    // 选中实体所属的实例
+    if let Some(root) = selected_entity.and_then(|entity| prefab_instance_of(world, entity)) {
+        ui.separator();
+        let source = world
+            .get::<PrefabInstance>(root)
+            .map(|instance| prefab_name(&instance.source))
+            .unwrap_or_default();
+        ui.label(format!("{}: {}", tr!("prefab_instance"), source));
+        if let Some(overrides) = instance_overrides(world, root) {
+            ui.label(format!("{}: {}", tr!("prefab_overrides"), overrides.len()));
+        }
+
+        placement_ui(ui, world, root, false);
+
+        ui.horizontal_wrapped(|ui| {
+            if ui.button(tr!("prefab_apply")).clicked() {
+                command = Some(PrefabEvent::ApplyToPrefab(root));
+            }
+            if ui.button(tr!("prefab_revert")).clicked() {
+                command = Some(PrefabEvent::Revert(root));
+            }
+            if ui.button(tr!("prefab_unpack")).clicked() {
+                command = Some(PrefabEvent::Unpack(root));
+            }
+            if ui.button(tr!("prefab_select_members")).clicked() {
+                let bodies: Vec<Entity> = world
+                    .get::<PrefabMembers>(root)
+                    .map(|members| members.get().to_vec())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|&member| world.get::<GizmoTransformable>(member).is_some())
+                    .collect();
+                if let Some(mut selection) = world.get_resource_mut::<EditorSelection>() {
+                    *selection = EditorSelection::from_iter(bodies);
+                }
+            }
+        });
+    }
+
+    if let Some(command) = command {
+        world.send_event(command);
+    }
+}
+
+/// 子场景：引用场景文件，以及选中子场景的摆放、打开编辑与保存
+fn sub_scene_ui(ui: &mut egui::Ui, world: &mut World, selected_entity: Option<Entity>) {
+    let mut command = None;
+    if ui.button(tr!("sub_scene_add")).clicked() {
+        command = Some(SubSceneEvent::Add(None));
+    }
+
+    // 选中的子场景根节点
+    let root = selected_entity.filter(|&entity| world.get::<SubScene>(entity).is_some());
+    if let Some(root) = root {
+        ui.separator();
+        let source = world
+            .get::<SubScene>(root)
+            .map(|sub_scene| sub_scene.source.clone())
+            .unwrap_or_default();
+        ui.label(format!(
+            "{}: {}",
+            tr!("sub_scene_source"),
+            sub_scene_name(&source)
+        ))
+        .on_hover_text(&source);
+        let joint_count = world
+            .get::<SubSceneJoints>(root)
+            .map_or(0, |joints| joints.get().len());
+        ui.label(format!("{}: {}", tr!("sub_scene_joints"), joint_count));
+
+        placement_ui(ui, world, root, true);
+
+        let editing = world.get::<SubSceneEditing>(root).is_some();
+        ui.label(
+            egui::RichText::new(if editing {
+                tr!("sub_scene_editing")
+            } else {
+                tr!("sub_scene_read_only")
+            })
+            .weak(),
+        );
+        ui.horizontal_wrapped(|ui| {
+            if editing {
+                if ui.button(tr!("sub_scene_save")).clicked() {
+                    command = Some(SubSceneEvent::Save(root));
+                }
+                if ui.button(tr!("sub_scene_discard")).clicked() {
+                    command = Some(SubSceneEvent::Discard(root));
+                }
+            } else {
+                if ui.button(tr!("sub_scene_open")).clicked() {
+                    command = Some(SubSceneEvent::Open(root));
+                }
+                if ui.button(tr!("sub_scene_reload")).clicked() {
+                    command = Some(SubSceneEvent::Discard(root));
+                }
+            }
+        });
+    }
+
+    if let Some(command) = command {
+        world.send_event(command);
+    }
+}
+
+/// 预制体实例或子场景根节点的位置、旋转与缩放
+fn placement_ui(ui: &mut egui::Ui, world: &mut World, root: Entity, with_scale: bool) {
+    let units = UnitConverter::from_world(world);
+    let Some(mut transform) = world.get_mut::<Transform>(root) else {
+        return;
+    };
+    let mut position = transform.translation.truncate();
+    let mut angle = transform.rotation.to_euler(EulerRot::ZYX).0.to_degrees();
+    let mut scale = transform.scale.truncate();
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label(tr!("position"));
+        changed |= ui
+            .add(length_drag_value(&mut position.x, units, 1.0))
+            .changed();
+        changed |= ui
+            .add(length_drag_value(&mut position.y, units, 1.0))
+            .changed();
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr!("rotation"));
+        changed |= ui
+            .add(egui::DragValue::new(&mut angle).speed(1.0).suffix("°"))
+            .changed();
+    });
+    if with_scale {
+        ui.horizontal(|ui| {
+            ui.label(tr!("scale"));
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut scale.x)
+                        .speed(0.01)
+                        .range(0.01..=100.0),
+                )
+                .changed();
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut scale.y)
+                        .speed(0.01)
+                        .range(0.01..=100.0),
+                )
+                .changed();
+        });
+    }
+    if changed {
+        transform.translation = position.extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(angle.to_radians());
+        transform.scale = scale.extend(transform.scale.z);
+    }
+}
+
+/// 草图：新笔画的简化参数，以及选中草图的重新简化
+fn sketch_ui(ui: &mut egui::Ui, world: &mut World) {
+    let units = UnitConverter::from_world(world);
+
+    if let Some(mut settings) = world.get_resource_mut::<SketchSettings>() {
+        let mut edited = settings.clone();
+        egui::Grid::new("sketch_settings")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(tr!("sketch_tolerance"));
+                ui.add(length_drag_value(&mut edited.tolerance, units, 0.1));
+                ui.end_row();
+                ui.label(tr!("sketch_close_distance"));
+                ui.add(length_drag_value(&mut edited.close_distance, units, 0.5));
+                ui.end_row();
+                ui.label(tr!("sketch_sample_spacing"));
+                ui.add(length_drag_value(&mut edited.sample_spacing, units, 0.1));
+                ui.end_row();
+            });
+        edited.tolerance = edited.tolerance.max(0.0);
+        edited.sample_spacing = edited.sample_spacing.max(0.1);
+        if edited != *settings {
+            *settings = edited;
+        }
+    }
+
+    ui.separator();
+
+    // 选中的草图可以用不同容差重新简化原始笔画
+    let selected = world
+        .get_resource::<EditorSelection>()
+        .and_then(|selection| selection.primary());
+    let Some(mut stroke) = selected.and_then(|entity| world.get_mut::<SketchStroke>(entity)) else {
+        ui.label(egui::RichText::new(tr!("no_sketch_selected")).weak());
+        return;
+    };
+
+    ui.label(tr!("selected_sketch"));
+    let mut edited = stroke.clone();
+    egui::Grid::new("selected_sketch")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label(tr!("sketch_tolerance"));
+            ui.add(length_drag_value(&mut edited.tolerance, units, 0.1));
+            ui.end_row();
+            ui.label(tr!("sketch_closed"));
+            ui.checkbox(&mut edited.closed, "");
+            ui.end_row();
+            ui.label(tr!("raw_samples"));
+            ui.label(edited.points.len().to_string());
+            ui.end_row();
+            ui.label(tr!("simplified_vertices"));
+            ui.label(edited.simplified().len().to_string());
+            ui.end_row();
+        });
+    edited.tolerance = edited.tolerance.max(0.0);
+    if edited != *stroke {
+        *stroke = edited;
+    }
+}
+
+/// 瓦片：新图层的合并方式，以及当前图层的单元格与合并结果
+fn tile_ui(ui: &mut egui::Ui, world: &mut World) {
+    let units = UnitConverter::from_world(world);
+    let grid_scale = world
+        .query::<&InfiniteGridSettings>()
+        .iter(world)
+        .next()
+        .map(|grid| grid.scale);
+
+    egui::Grid::new("tile_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label(tr!("tile_cell_size"));
+            match grid_scale {
+                Some(scale) => ui.label(units.format_length(scale)),
+                None => ui.label("-"),
+            };
+            ui.end_row();
+            if let Some(mut settings) = world.get_resource_mut::<TileSettings>() {
+                let mut merge = settings.merge;
+                ui.label(tr!("tile_merge"));
+                ui.horizontal(|ui| {
+                    for mode in TileMergeMode::ALL {
+                        ui.selectable_value(&mut merge, mode, tr!(mode.key()));
+                    }
+                });
+                ui.end_row();
+                if merge != settings.merge {
+                    settings.merge = merge;
+                }
+            }
+        });
+
+    ui.separator();
+
+    // 与绘制时相同：优先使用选中的图层，否则使用上次绘制的图层
+    let selected = world
+        .get_resource::<EditorSelection>()
+        .and_then(|selection| selection.primary())
+        .filter(|&entity| world.get::<TileLayer>(entity).is_some());
+    let active = world
+        .get_resource::<TilePaintState>()
+        .and_then(|state| state.active_layer)
+        .filter(|&entity| world.get::<TileLayer>(entity).is_some());
+    let Some(entity) = selected.or(active) else {
+        ui.label(egui::RichText::new(tr!("no_tile_layer")).weak());
+        return;
+    };
+    let Some(mut layer) = world.get_mut::<TileLayer>(entity) else {
+        return;
+    };
+
+    ui.label(tr!("tile_layer"));
+    let mut merge = layer.merge;
+    let mut clear = false;
+    egui::Grid::new("tile_layer").num_columns(2).show(ui, |ui| {
+        ui.label(tr!("tile_cells"));
+        ui.label(layer.cells.len().to_string());
+        ui.end_row();
+        ui.label(tr!("tile_merge"));
+        ui.horizontal(|ui| {
+            for mode in TileMergeMode::ALL {
+                ui.selectable_value(&mut merge, mode, tr!(mode.key()));
+            }
+        });
+        ui.end_row();
+        ui.label(tr!("tile_parts"));
+        ui.label(layer.part_count().to_string());
+        ui.end_row();
+    });
+    let mut new_layer = false;
+    ui.horizontal_wrapped(|ui| {
+        clear = ui
+            .add_enabled(
+                !layer.cells.is_empty(),
+                egui::Button::new(tr!("clear_tiles")),
+            )
+            .clicked();
+        new_layer = ui.button(tr!("new_tile_layer")).clicked();
+    });
+    if merge != layer.merge {
+        layer.merge = merge;
+    }
+    if clear {
+        layer.cells.clear();
+    }
+
+    // 下一笔将创建新图层
+    if new_layer {
+        if let Some(mut state) = world.get_resource_mut::<TilePaintState>() {
+            state.active_layer = None;
+        }
+        if selected.is_some() {
+            if let Some(mut selection) = world.get_resource_mut::<EditorSelection>() {
+                selection.clear();
+            }
+        }
+    }
+}
@@ -6,9 +6,16 @@ use re_ui::{
     icons::{PAUSE, PLAY},
 };
 
+use super::unit_widgets::length_drag_value;
 use crate::grid::InfiniteGridSettings;
 use crate::ui::panel_state::PanelControlEvent;
-use crate::{EditorSelection, PhysicsManager, SceneExportEvent, SceneImportEvent, tr, ui::i18n};
+use crate::{
+    AutosaveSettings, DocumentEvent, EditorDocument, EditorSelection, LdtkExportEvent,
+    LdtkImportEvent, LdtkProjectLink, LdtkSettings, PhysicsManager, PlayFromStart, RecentFiles,
+    RubeExportEvent, RustExportEvent, SceneExportEvent, SceneImportEvent, SceneImportMode,
+    SubSceneEvent, SvgExportEvent, SvgExportSettings, SvgImportEvent, SvgImportSettings,
+    TileMergeMode, TiledImportEvent, TiledImportSettings, UnitConverter, tr, ui::i18n,
+};
 
 pub(super) fn ui(ctx: &egui::Context, world: &mut World, physics_paused: bool) {
     // Top bar for physics controls and scene export
@@ -17,8 +24,91 @@ pub(super) fn ui(ctx: &egui::Context, world: &mut World, physics_paused: bool) {
         .show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.heading(tr!("app_title"));
+                if let Some(document) = world.get_resource::<EditorDocument>() {
+                    let name = document.name().unwrap_or_else(|| tr!("untitled"));
+                    let marker = if document.modified { "*" } else { "" };
+                    ui.label(format!("{name}{marker}"))
+                        .on_hover_text(if document.modified {
+                            tr!("document_modified")
+                        } else {
+                            tr!("document_saved")
+                        });
+                }
                 ui.separator();
 
+                // Document menu
+                ui.menu_button(tr!("file_menu"), |ui| {
+                    if ui.button(tr!("new_scene")).clicked() {
+                        world.send_event(DocumentEvent::New);
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    if ui.button(tr!("open_scene")).clicked() {
+                        world.send_event(DocumentEvent::Open(None));
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    let recent = world
+                        .get_resource::<RecentFiles>()
+                        .map(|recent| recent.paths.clone())
+                        .unwrap_or_default();
+                    ui.add_enabled_ui(!recent.is_empty(), |ui| {
+                        ui.menu_button(tr!("recent_files"), |ui| {
+                            for path in recent {
+                                let name = path
+                                    .file_name()
+                                    .map(|name| name.to_string_lossy().into_owned())
+                                    .unwrap_or_default();
+                                if ui
+                                    .button(name)
+                                    .on_hover_text(path.to_string_lossy())
+                                    .clicked()
+                                {
+                                    world.send_event(DocumentEvent::Open(Some(path)));
+                                    ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                                }
+                            }
+                            ui.separator();
+                            if ui.button(tr!("recent_files_clear")).clicked() {
+                                if let Some(mut recent) = world.get_resource_mut::<RecentFiles>() {
+                                    recent.paths.clear();
+                                }
+                                ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                            }
+                        });
+                    });
+                    ui.separator();
+                    if ui.button(tr!("save")).clicked() {
+                        world.send_event(DocumentEvent::Save);
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    if ui.button(tr!("save_as")).clicked() {
+                        world.send_event(DocumentEvent::SaveAs);
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    ui.separator();
+                    if let Some(mut settings) = world.get_resource_mut::<AutosaveSettings>() {
+                        let mut edited = settings.clone();
+                        ui.checkbox(&mut edited.enabled, tr!("autosave"));
+                        ui.add_enabled_ui(edited.enabled, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(tr!("autosave_interval"));
+                                ui.add(
+                                    egui::DragValue::new(&mut edited.interval)
+                                        .range(10.0..=3600.0)
+                                        .speed(1.0)
+                                        .suffix(" s"),
+                                );
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label(tr!("autosave_retention"));
+                                ui.add(egui::DragValue::new(&mut edited.retention).range(1..=100));
+                            });
+                        });
+                        if edited != *settings {
+                            *settings = edited;
+                        }
+                    }
+                });
+
                 // Scene export menu
                 ui.menu_button(tr!("export_scene"), |ui| {
                     if ui.button(tr!("export_all")).clicked() {
@@ -33,14 +123,147 @@ pub(super) fn ui(ctx: &egui::Context, world: &mut World, physics_paused: bool) {
                         world.send_event(SceneExportEvent::Entities(selected_entities));
                         ui.close_kind(bevy_egui::egui::UiKind::Menu);
                     }
+                    ui.separator();
+                    if ui.button(tr!("export_svg_all")).clicked() {
+                        world.send_event(SvgExportEvent::All);
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    if ui.button(tr!("export_svg_selected")).clicked() {
+                        let selected_entities: Vec<Entity> = world
+                            .get_resource::<EditorSelection>()
+                            .map(|selection| selection.iter().collect())
+                            .unwrap_or_default();
+                        world.send_event(SvgExportEvent::Entities(selected_entities));
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    if let Some(mut settings) = world.get_resource_mut::<SvgExportSettings>() {
+                        let (mut grid, mut legend) = (settings.grid, settings.legend);
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut grid, tr!("svg_include_grid"));
+                            ui.checkbox(&mut legend, tr!("svg_include_legend"));
+                        });
+                        if (grid, legend) != (settings.grid, settings.legend) {
+                            settings.grid = grid;
+                            settings.legend = legend;
+                        }
+                    }
+                    ui.separator();
+                    if ui.button(tr!("export_rust_all")).clicked() {
+                        world.send_event(RustExportEvent::All);
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    if ui.button(tr!("export_rust_selected")).clicked() {
+                        let selected_entities: Vec<Entity> = world
+                            .get_resource::<EditorSelection>()
+                            .map(|selection| selection.iter().collect())
+                            .unwrap_or_default();
+                        world.send_event(RustExportEvent::Entities(selected_entities));
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    if ui.button(tr!("export_rube_all")).clicked() {
+                        world.send_event(RubeExportEvent::All);
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    if ui.button(tr!("export_rube_selected")).clicked() {
+                        let selected_entities: Vec<Entity> = world
+                            .get_resource::<EditorSelection>()
+                            .map(|selection| selection.iter().collect())
+                            .unwrap_or_default();
+                        world.send_event(RubeExportEvent::Entities(selected_entities));
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    ui.separator();
+                    let linked = world
+                        .get_resource::<LdtkProjectLink>()
+                        .is_some_and(|link| link.path.is_some());
+                    if ui
+                        .add_enabled(linked, egui::Button::new(tr!("export_ldtk_project")))
+                        .clicked()
+                    {
+                        world.send_event(LdtkExportEvent);
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
                 });
 
                 // Scene import menu
                 ui.menu_button(tr!("import_scene"), |ui| {
-                    if ui.button(tr!("import_from_file")).clicked() {
-                        world.send_event(SceneImportEvent::FromDialog);
+                    // Replacing the scene is File > Open
+                    for (mode, label) in [
+                        (SceneImportMode::Merge, "import_merge"),
+                        (SceneImportMode::Place, "import_place"),
+                    ] {
+                        if ui.button(tr!(label)).clicked() {
+                            world.send_event(SceneImportEvent::FromDialog(mode));
+                            ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                        }
+                    }
+                    if ui.button(tr!("import_sub_scene")).clicked() {
+                        world.send_event(SubSceneEvent::Add(None));
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    ui.separator();
+                    if ui.button(tr!("import_tiled_map")).clicked() {
+                        world.send_event(TiledImportEvent::FromDialog);
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    let units = UnitConverter::from_world(world);
+                    if let Some(mut settings) = world.get_resource_mut::<TiledImportSettings>() {
+                        let mut scale = settings.scale;
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("tiled_pixel_size"));
+                            ui.add(length_drag_value(&mut scale, units, 0.01));
+                        });
+                        if scale != settings.scale && scale > 0.0 {
+                            settings.scale = scale;
+                        }
+                    }
+                    ui.separator();
+                    if ui.button(tr!("import_ldtk_project")).clicked() {
+                        world.send_event(LdtkImportEvent::FromDialog);
+                        ui.close_kind(bevy_egui::egui::UiKind::Menu);
+                    }
+                    if let Some(mut settings) = world.get_resource_mut::<LdtkSettings>() {
+                        let mut scale = settings.scale;
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("ldtk_pixel_size"));
+                            ui.add(length_drag_value(&mut scale, units, 0.01));
+                        });
+                        if scale != settings.scale && scale > 0.0 {
+                            settings.scale = scale;
+                        }
+                        let mut merge = settings.merge;
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("ldtk_int_grid_merge"));
+                            for mode in TileMergeMode::ALL {
+                                ui.selectable_value(&mut merge, mode, tr!(mode.key()));
+                            }
+                        });
+                        if merge != settings.merge {
+                            settings.merge = merge;
+                        }
+                    }
+                    ui.separator();
+                    if ui.button(tr!("import_svg")).clicked() {
+                        world.send_event(SvgImportEvent::FromDialog);
                         ui.close_kind(bevy_egui::egui::UiKind::Menu);
                     }
+                    if let Some(mut settings) = world.get_resource_mut::<SvgImportSettings>() {
+                        let (mut scale, mut tolerance) = (settings.scale, settings.tolerance);
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("svg_unit_size"));
+                            ui.add(length_drag_value(&mut scale, units, 0.01));
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("svg_curve_tolerance"));
+                            ui.add(length_drag_value(&mut tolerance, units, 0.01));
+                        });
+                        if scale != settings.scale && scale > 0.0 {
+                            settings.scale = scale;
+                        }
+                        if tolerance != settings.tolerance && tolerance > 0.0 {
+                            settings.tolerance = tolerance;
+                        }
+                    }
                 });
 
                 ui.separator();
@@ -112,6 +335,9 @@ pub(super) fn ui(ctx: &egui::Context, world: &mut World, physics_paused: bool) {
                 ui.separator();
 
                 // Physics controls
+                if ui.small_button(tr!("play_from_start")).clicked() {
+                    world.send_event(PlayFromStart);
+                }
                 if physics_paused {
                     if ui.small_icon_button(&PLAY, tr!("resume_physics")).clicked() {
                         // Use a closure to handle the resource borrowing
@@ -154,6 +380,17 @@ fn render_panel_controls(ui: &mut egui::Ui, world: &mut World) {
         .get_resource::<crate::ui::panel_state::PanelState>()
         .map(|state| state.bottom_panel_visible)
         .unwrap_or(false);
+    let world_settings_visible = world
+        .get_resource::<crate::ui::panel_state::PanelState>()
+        .map(|state| state.world_settings_visible)
+        .unwrap_or(false);
+    let diagnostics_visible = world
+        .get_resource::<crate::ui::panel_state::PanelState>()
+        .map(|state| state.diagnostics_visible)
+        .unwrap_or(false);
+    let finding_count = world
+        .get_resource::<crate::collider_tools::lint::SceneLint>()
+        .map_or(0, |lint| lint.findings.len());
 
     ui.horizontal(|ui| {
         // 左侧面板按钮
@@ -174,6 +411,24 @@ fn render_panel_controls(ui: &mut egui::Ui, world: &mut World) {
             world.send_event(PanelControlEvent::ToggleBottomPanel);
         }
 
+        // 世界设置窗口按钮
+        let world_settings_button =
+            ui.selectable_label(world_settings_visible, tr!("world_settings"));
+        if world_settings_button.clicked() {
+            world.send_event(PanelControlEvent::ToggleWorldSettings);
+        }
+
+        // 诊断窗口按钮，附带问题数量
+        let diagnostics_label = if finding_count > 0 {
+            format!("{} ({finding_count})", tr!("diagnostics"))
+        } else {
+            tr!("diagnostics").to_string()
+        };
+        let diagnostics_button = ui.selectable_label(diagnostics_visible, diagnostics_label);
+        if diagnostics_button.clicked() {
+            world.send_event(PanelControlEvent::ToggleDiagnostics);
+        }
+
         ui.separator();
 
         if ui.button(tr!("max_viewport")).clicked() {
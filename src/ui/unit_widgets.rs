@@ -0,0 +1,40 @@
+//! Widgets for editing lengths in the chosen display unit
+
+use std::ops::RangeInclusive;
+
+use bevy_egui::egui;
+
+use crate::collider_tools::units::UnitConverter;
+
+/// Drag value that edits a world length while showing it in display units
+///
+/// `world_speed` is the drag speed in world units. Use [`bounded_length_drag_value`] for
+/// limits; a `.range` added by the caller would clamp the displayed number instead.
+pub(super) fn length_drag_value(
+    value: &mut f32,
+    units: UnitConverter,
+    world_speed: f32,
+) -> egui::DragValue<'_> {
+    let factor = units.factor();
+    egui::DragValue::from_get_set(move |new_value: Option<f64>| {
+        if let Some(new_value) = new_value {
+            *value = new_value as f32 / factor;
+        }
+        (*value * factor) as f64
+    })
+    .speed(world_speed * factor)
+    .max_decimals(units.decimals() + 2)
+    .suffix(format!(" {}", units.symbol()))
+}
+
+/// [`length_drag_value`] limited to a range of world lengths
+pub(super) fn bounded_length_drag_value(
+    value: &mut f32,
+    units: UnitConverter,
+    world_speed: f32,
+    world_range: RangeInclusive<f32>,
+) -> egui::DragValue<'_> {
+    let factor = units.factor() as f64;
+    let display_range = *world_range.start() as f64 * factor..=*world_range.end() as f64 * factor;
+    length_drag_value(value, units, world_speed).range(display_range)
+}
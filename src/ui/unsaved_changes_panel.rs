@@ -0,0 +1,52 @@
+//! 未保存更改提示
+//!
+//! 新建、打开其他场景或退出前，若当前文档有未保存的更改，询问是否先保存。
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::document::{
+    DocumentAction, DocumentEvent, EditorDocument, UnsavedChanges, UnsavedChoice,
+};
+use crate::tr;
+
+pub(super) fn ui(ctx: &egui::Context, world: &mut World) {
+    let Some(pending) = world.get_resource::<UnsavedChanges>() else {
+        return;
+    };
+    let message = match pending.action {
+        DocumentAction::New => tr!("unsaved_before_new"),
+        DocumentAction::Open(_) => tr!("unsaved_before_open"),
+        DocumentAction::Exit => tr!("unsaved_before_exit"),
+    };
+    let name = world
+        .get_resource::<EditorDocument>()
+        .and_then(EditorDocument::name)
+        .unwrap_or_else(|| tr!("untitled"));
+
+    let mut choice = None;
+    egui::Window::new(tr!("unsaved_changes"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(egui::RichText::new(name).strong());
+            ui.label(message);
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button(tr!("unsaved_save")).clicked() {
+                    choice = Some(UnsavedChoice::Save);
+                }
+                if ui.button(tr!("unsaved_discard")).clicked() {
+                    choice = Some(UnsavedChoice::Discard);
+                }
+                if ui.button(tr!("cancel")).clicked() {
+                    choice = Some(UnsavedChoice::Cancel);
+                }
+            });
+        });
+
+    if let Some(choice) = choice {
+        world.send_event(DocumentEvent::ResolveUnsaved(choice));
+    }
+}
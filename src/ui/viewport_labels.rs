@@ -0,0 +1,362 @@
+//! Numeric labels drawn over the viewport next to editor handles
+//!
+//! Gizmos cannot render text, so values that belong to a viewport handle are painted on an
+//! egui foreground layer at the handle's screen position.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::collider_tools::kinematic_path::{KinematicPath, PathEditState};
+use crate::collider_tools::measure::{MeasureAnnotations, MeasureState};
+use crate::collider_tools::units::{UnitConverter, UnitSettings};
+use crate::collider_tools::velocity::{
+    InitialVelocity, VelocityAuthoringState, angular_handle_position, angular_handle_radius,
+    linear_handle_position,
+};
+use crate::grid::InfiniteGridSettings;
+use crate::ui::theme_colors::EditorThemeColors;
+use crate::{ToolMode, tr};
+
+pub(super) fn ui(ctx: &egui::Context, world: &mut World, current_mode: ToolMode) {
+    let Ok((camera, camera_transform)) = world
+        .query_filtered::<(&Camera, &GlobalTransform), With<SpritePickingCamera>>()
+        .single(world)
+        .map(|(camera, transform)| (camera.clone(), *transform))
+    else {
+        return;
+    };
+
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("viewport_labels"),
+    ));
+
+    // Rulers show the coordinates in measure mode
+    if current_mode != ToolMode::Measure
+        && world
+            .get_resource::<UnitSettings>()
+            .is_some_and(|settings| settings.grid_labels)
+    {
+        grid_labels(
+            &painter,
+            ctx.available_rect(),
+            world,
+            &camera,
+            &camera_transform,
+        );
+    }
+
+    measure_labels(
+        &painter,
+        world,
+        &camera,
+        &camera_transform,
+        current_mode == ToolMode::Measure,
+    );
+
+    match current_mode {
+        ToolMode::Velocity => velocity_labels(&painter, world, &camera, &camera_transform),
+        ToolMode::Path => path_labels(&painter, world, &camera, &camera_transform),
+        _ => {}
+    }
+}
+
+/// Coordinates along the bottom and left edges for every major grid line
+fn grid_labels(
+    painter: &egui::Painter,
+    area: egui::Rect,
+    world: &mut World,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) {
+    // Keeps a degenerate zoom from painting thousands of labels
+    const MAX_LABELS_PER_AXIS: i32 = 50;
+
+    let Some(bounds) = visible_world_bounds(camera, camera_transform) else {
+        return;
+    };
+    let Some(spacing) = major_grid_spacing(world, bounds.height()) else {
+        return;
+    };
+
+    let units = UnitConverter::from_world(world);
+    let color = egui::Color32::from_gray(160);
+    let font = egui::FontId::monospace(10.0);
+    let (min, max) = (bounds.min, bounds.max);
+
+    let first_x = (min.x / spacing).ceil() as i32;
+    let last_x = ((max.x / spacing).floor() as i32).min(first_x + MAX_LABELS_PER_AXIS);
+    for index in first_x..=last_x {
+        let x = index as f32 * spacing;
+        let Ok(screen) = camera.world_to_viewport(camera_transform, Vec3::new(x, min.y, 0.0))
+        else {
+            continue;
+        };
+        if screen.x < area.left() || screen.x > area.right() {
+            continue;
+        }
+        painter.text(
+            egui::pos2(screen.x + 2.0, area.bottom() - 2.0),
+            egui::Align2::LEFT_BOTTOM,
+            grid_label_text(&units, x),
+            font.clone(),
+            color,
+        );
+    }
+
+    let first_y = (min.y / spacing).ceil() as i32;
+    let last_y = ((max.y / spacing).floor() as i32).min(first_y + MAX_LABELS_PER_AXIS);
+    for index in first_y..=last_y {
+        let y = index as f32 * spacing;
+        let Ok(screen) = camera.world_to_viewport(camera_transform, Vec3::new(min.x, y, 0.0))
+        else {
+            continue;
+        };
+        if screen.y < area.top() || screen.y > area.bottom() {
+            continue;
+        }
+        painter.text(
+            egui::pos2(area.left() + 2.0, screen.y - 2.0),
+            egui::Align2::LEFT_BOTTOM,
+            grid_label_text(&units, y),
+            font.clone(),
+            color,
+        );
+    }
+
+    painter.text(
+        egui::pos2(area.right() - 4.0, area.bottom() - 2.0),
+        egui::Align2::RIGHT_BOTTOM,
+        format!("{}: {}", tr!("grid_spacing"), units.format_length(spacing)),
+        font,
+        color,
+    );
+}
+
+/// World-space rectangle covered by the camera viewport
+pub(super) fn visible_world_bounds(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) -> Option<Rect> {
+    let viewport = camera.logical_viewport_rect()?;
+    let top_left = camera
+        .viewport_to_world_2d(camera_transform, Vec2::ZERO)
+        .ok()?;
+    let bottom_right = camera
+        .viewport_to_world_2d(camera_transform, viewport.size())
+        .ok()?;
+    Some(Rect::from_corners(top_left, bottom_right))
+}
+
+/// Distance between major grid lines for a view of the given height
+///
+/// Spacing follows the grid shader: minor lines are `scale` apart, multiplied by a power of ten
+/// as the view zooms out, and every tenth line is a major line.
+pub(super) fn major_grid_spacing(world: &mut World, view_height: f32) -> Option<f32> {
+    let grid_scale = world
+        .query::<&InfiniteGridSettings>()
+        .iter(world)
+        .next()
+        .map(|settings| settings.scale)?;
+    let zoom_scaling = 10f32.powf((view_height / 300.0).max(1.0).log10().floor());
+    let spacing = grid_scale * zoom_scaling * 10.0;
+    (spacing > 0.0).then_some(spacing)
+}
+
+/// Grid coordinate in display units without trailing zeros
+pub(super) fn grid_label_text(units: &UnitConverter, world_value: f32) -> String {
+    let text = format!("{:.*}", units.decimals(), units.to_display(world_value));
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &text
+    };
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Readouts for kept dimensions and the measurement in progress
+fn measure_labels(
+    painter: &egui::Painter,
+    world: &mut World,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    in_measure_mode: bool,
+) {
+    let Some(annotations) = world.get_resource::<MeasureAnnotations>() else {
+        return;
+    };
+    if !annotations.visible && !in_measure_mode {
+        return;
+    }
+
+    let mut dimensions = annotations.dimensions.clone();
+    if in_measure_mode {
+        dimensions.extend(
+            world
+                .get_resource::<MeasureState>()
+                .and_then(MeasureState::preview),
+        );
+    }
+    let units = UnitConverter::from_world(world);
+    let color = world
+        .get_resource::<EditorThemeColors>()
+        .map(|colors| colors.measure_line)
+        .unwrap_or(Color::WHITE);
+
+    let transform_of = |entity: Entity| world.get::<GlobalTransform>(entity).copied();
+    for dimension in &dimensions {
+        let (Some(position), Some(value)) = (
+            dimension.label_position(transform_of),
+            dimension.measure(transform_of),
+        ) else {
+            continue;
+        };
+        draw_label(
+            painter,
+            camera,
+            camera_transform,
+            position,
+            value.format(&units),
+            color,
+        );
+    }
+}
+
+/// Time labels next to the keyframes of the path being edited
+fn path_labels(
+    painter: &egui::Painter,
+    world: &mut World,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) {
+    let Some(target) = world
+        .get_resource::<PathEditState>()
+        .and_then(|state| state.target)
+    else {
+        return;
+    };
+    let Some(path) = world.get::<KinematicPath>(target) else {
+        return;
+    };
+    let color = world
+        .get_resource::<EditorThemeColors>()
+        .map(|colors| colors.path_keyframe)
+        .unwrap_or(Color::WHITE);
+
+    for (index, keyframe) in path.keyframes.iter().enumerate() {
+        draw_label(
+            painter,
+            camera,
+            camera_transform,
+            keyframe.position,
+            format!("#{} t={:.2}s", index, keyframe.time),
+            color,
+        );
+    }
+}
+
+/// Labels for the linear and angular velocity handles
+fn velocity_labels(
+    painter: &egui::Painter,
+    world: &mut World,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) {
+    let target = world
+        .get_resource::<VelocityAuthoringState>()
+        .and_then(|state| state.target);
+    let theme_colors = world
+        .get_resource::<EditorThemeColors>()
+        .cloned()
+        .unwrap_or_default();
+    let units = UnitConverter::from_world(world);
+
+    let mut query = world.query::<(
+        Entity,
+        &GlobalTransform,
+        &Collider,
+        Option<&InitialVelocity>,
+    )>();
+    for (entity, body_transform, collider, initial_velocity) in query.iter(world) {
+        if initial_velocity.is_none() && target != Some(entity) {
+            continue;
+        }
+
+        let value = initial_velocity.copied().unwrap_or_default();
+        let origin = body_transform.translation().truncate();
+
+        if value.linear != Vec2::ZERO || target == Some(entity) {
+            let tip = linear_handle_position(origin, &value);
+            draw_label(
+                painter,
+                camera,
+                camera_transform,
+                tip,
+                format!(
+                    "v ({:.*}, {:.*})  |v| {}",
+                    units.decimals(),
+                    units.to_display(value.linear.x),
+                    units.decimals(),
+                    units.to_display(value.linear.y),
+                    units.format_speed(value.linear.length())
+                ),
+                theme_colors.velocity_linear,
+            );
+        }
+
+        if value.angular != 0.0 || target == Some(entity) {
+            let start_angle = body_transform.rotation().to_euler(EulerRot::ZYX).0;
+            let handle = angular_handle_position(
+                origin,
+                start_angle,
+                angular_handle_radius(collider),
+                value.angular,
+            );
+            draw_label(
+                painter,
+                camera,
+                camera_transform,
+                handle,
+                format!(
+                    "ω {:.2} rad/s ({:.0}°/s)",
+                    value.angular,
+                    value.angular.to_degrees()
+                ),
+                theme_colors.velocity_angular,
+            );
+        }
+    }
+}
+
+/// Paint a text label with a translucent background above and to the right of a world point
+fn draw_label(
+    painter: &egui::Painter,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    world_pos: Vec2,
+    text: String,
+    color: Color,
+) {
+    let Ok(screen_pos) = camera.world_to_viewport(camera_transform, world_pos.extend(0.0)) else {
+        return;
+    };
+
+    let [r, g, b, a] = color.to_srgba().to_f32_array();
+    let text_color = egui::Color32::from_rgba_unmultiplied(
+        (r * 255.0) as u8,
+        (g * 255.0) as u8,
+        (b * 255.0) as u8,
+        (a * 255.0) as u8,
+    );
+
+    let galley = painter.layout_no_wrap(text, egui::FontId::monospace(12.0), text_color);
+    let pos = egui::pos2(screen_pos.x + 10.0, screen_pos.y - 10.0 - galley.size().y);
+    let background = egui::Rect::from_min_size(pos, galley.size()).expand(3.0);
+    painter.rect_filled(background, 3.0, egui::Color32::from_black_alpha(160));
+    painter.galley(pos, galley, text_color);
+}
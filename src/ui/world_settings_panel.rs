@@ -0,0 +1,324 @@
+//! 世界设置面板
+//!
+//! 直接编辑 Avian 的场景级物理资源，修改即时生效，并随场景一起导出。
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::collider_tools::units::{LengthDisplayUnit, UnitConverter, UnitSettings};
+use crate::collider_tools::world_settings::{PhysicsTimestep, WorldPreset};
+use crate::tr;
+use crate::ui::panel_state::PanelState;
+use crate::ui::unit_widgets::length_drag_value;
+
+pub(super) fn ui(ctx: &egui::Context, world: &mut World) {
+    let mut open = true;
+
+    egui::Window::new(tr!("world_settings"))
+        .open(&mut open)
+        .default_width(300.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                // 预设
+                ui.label(tr!("world_presets"));
+                ui.horizontal_wrapped(|ui| {
+                    for preset in WorldPreset::ALL {
+                        if ui.button(tr!(preset.key())).clicked() {
+                            preset.apply(world);
+                        }
+                    }
+                });
+
+                ui.separator();
+                gravity_ui(ui, world);
+
+                ui.separator();
+                units_ui(ui, world);
+
+                ui.separator();
+                simulation_ui(ui, world);
+
+                ui.separator();
+                sleeping_ui(ui, world);
+
+                ui.separator();
+                default_material_ui(ui, world);
+
+                ui.separator();
+                ui.label(egui::RichText::new(tr!("world_settings_saved_note")).weak());
+            });
+        });
+
+    if !open {
+        if let Some(mut panel_state) = world.get_resource_mut::<PanelState>() {
+            panel_state.world_settings_visible = false;
+        }
+    }
+}
+
+/// 重力与长度单位
+fn gravity_ui(ui: &mut egui::Ui, world: &mut World) {
+    ui.label(tr!("gravity"));
+    let units = UnitConverter::from_world(world);
+    if let Some(mut gravity) = world.get_resource_mut::<Gravity>() {
+        let mut value = gravity.0;
+        let mut changed = false;
+        let suffix = format!(" {}/s²", units.symbol());
+        ui.horizontal(|ui| {
+            changed |= ui
+                .add(
+                    length_drag_value(&mut value.x, units, 1.0)
+                        .prefix("x: ")
+                        .suffix(suffix.clone()),
+                )
+                .changed();
+            changed |= ui
+                .add(
+                    length_drag_value(&mut value.y, units, 1.0)
+                        .prefix("y: ")
+                        .suffix(suffix),
+                )
+                .changed();
+            if ui.button(tr!("zero_gravity")).clicked() {
+                value = Vec2::ZERO;
+                changed = true;
+            }
+        });
+        if changed {
+            gravity.0 = value;
+        }
+    }
+
+    if let Some(mut length_unit) = world.get_resource_mut::<PhysicsLengthUnit>() {
+        let mut value = length_unit.0;
+        ui.horizontal(|ui| {
+            ui.label(tr!("physics_length_unit"));
+            if ui
+                .add(
+                    egui::DragValue::new(&mut value)
+                        .speed(1.0)
+                        .range(0.001..=f32::MAX),
+                )
+                .on_hover_text(tr!("physics_length_unit_desc"))
+                .changed()
+            {
+                length_unit.0 = value;
+            }
+        });
+    }
+}
+
+/// 显示单位与 SI 导出
+fn units_ui(ui: &mut egui::Ui, world: &mut World) {
+    ui.label(tr!("units"));
+    let Some(mut settings) = world.get_resource_mut::<UnitSettings>() else {
+        return;
+    };
+
+    let mut edited = settings.clone();
+    ui.horizontal(|ui| {
+        ui.label(tr!("display_unit"));
+        for unit in LengthDisplayUnit::ALL {
+            ui.selectable_value(&mut edited.display_unit, unit, unit.symbol());
+        }
+    });
+    ui.checkbox(&mut edited.grid_labels, tr!("grid_labels"));
+    ui.checkbox(&mut edited.export_si, tr!("export_si_units"))
+        .on_hover_text(tr!("export_si_units_desc"));
+
+    // 只在真正修改时写回，避免每帧触发变更检测
+    if edited != *settings {
+        *settings = edited;
+    }
+}
+
+/// 时间步长、子步数与求解器参数
+fn simulation_ui(ui: &mut egui::Ui, world: &mut World) {
+    ui.label(tr!("simulation"));
+
+    if let Some(mut timestep) = world.get_resource_mut::<PhysicsTimestep>() {
+        let mut hz = timestep.hz;
+        ui.horizontal(|ui| {
+            ui.label(tr!("physics_timestep"));
+            if ui
+                .add(
+                    egui::DragValue::new(&mut hz)
+                        .speed(1.0)
+                        .range(1.0..=1000.0)
+                        .suffix(" Hz"),
+                )
+                .changed()
+            {
+                timestep.hz = hz;
+            }
+        });
+    }
+
+    if let Some(mut substeps) = world.get_resource_mut::<SubstepCount>() {
+        let mut value = substeps.0;
+        ui.horizontal(|ui| {
+            ui.label(tr!("substep_count"));
+            if ui
+                .add(egui::DragValue::new(&mut value).range(1..=64))
+                .changed()
+            {
+                substeps.0 = value;
+            }
+        });
+    }
+
+    if let Some(mut solver) = world.get_resource_mut::<SolverConfig>() {
+        let mut restitution_iterations = solver.restitution_iterations;
+        let mut contact_damping_ratio = solver.contact_damping_ratio;
+        let mut contact_frequency_factor = solver.contact_frequency_factor;
+        let mut max_overlap_solve_speed = solver.max_overlap_solve_speed;
+        let mut changed = false;
+
+        egui::Grid::new("solver_config")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(tr!("restitution_iterations"));
+                changed |= ui
+                    .add(egui::DragValue::new(&mut restitution_iterations).range(0..=16))
+                    .changed();
+                ui.end_row();
+
+                ui.label(tr!("contact_damping_ratio"));
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut contact_damping_ratio)
+                            .speed(0.1)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label(tr!("contact_frequency_factor"));
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut contact_frequency_factor)
+                            .speed(0.1)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label(tr!("max_overlap_solve_speed"));
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut max_overlap_solve_speed)
+                            .speed(0.1)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .changed();
+                ui.end_row();
+            });
+
+        if changed {
+            solver.restitution_iterations = restitution_iterations;
+            solver.contact_damping_ratio = contact_damping_ratio;
+            solver.contact_frequency_factor = contact_frequency_factor;
+            solver.max_overlap_solve_speed = max_overlap_solve_speed;
+        }
+    }
+}
+
+/// 休眠阈值
+fn sleeping_ui(ui: &mut egui::Ui, world: &mut World) {
+    ui.label(tr!("sleeping"));
+
+    if let Some(mut threshold) = world.get_resource_mut::<SleepingThreshold>() {
+        let (mut linear, mut angular) = (threshold.linear, threshold.angular);
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label(tr!("sleep_linear_threshold"));
+            changed |= ui
+                .add(egui::DragValue::new(&mut linear).speed(0.01))
+                .changed();
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr!("sleep_angular_threshold"));
+            changed |= ui
+                .add(egui::DragValue::new(&mut angular).speed(0.01))
+                .changed();
+        });
+        if changed {
+            threshold.linear = linear;
+            threshold.angular = angular;
+        }
+    }
+
+    if let Some(mut deactivation_time) = world.get_resource_mut::<DeactivationTime>() {
+        let mut value = deactivation_time.0;
+        ui.horizontal(|ui| {
+            ui.label(tr!("deactivation_time"));
+            if ui
+                .add(
+                    egui::DragValue::new(&mut value)
+                        .speed(0.01)
+                        .range(0.0..=f32::MAX)
+                        .suffix(" s"),
+                )
+                .changed()
+            {
+                deactivation_time.0 = value;
+            }
+        });
+    }
+}
+
+/// 默认摩擦与弹性（没有 Friction/Restitution 组件的碰撞体使用）
+fn default_material_ui(ui: &mut egui::Ui, world: &mut World) {
+    ui.label(tr!("default_material"));
+
+    if let Some(mut friction) = world.get_resource_mut::<DefaultFriction>() {
+        let (mut dynamic, mut static_coefficient) = (
+            friction.0.dynamic_coefficient,
+            friction.0.static_coefficient,
+        );
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label(tr!("friction"));
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut dynamic)
+                        .speed(0.01)
+                        .range(0.0..=f32::MAX),
+                )
+                .changed();
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr!("static_friction"));
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut static_coefficient)
+                        .speed(0.01)
+                        .range(0.0..=f32::MAX),
+                )
+                .changed();
+        });
+        if changed {
+            friction.0.dynamic_coefficient = dynamic;
+            friction.0.static_coefficient = static_coefficient;
+        }
+    }
+
+    if let Some(mut restitution) = world.get_resource_mut::<DefaultRestitution>() {
+        let mut coefficient = restitution.0.coefficient;
+        ui.horizontal(|ui| {
+            ui.label(tr!("restitution"));
+            if ui
+                .add(
+                    egui::DragValue::new(&mut coefficient)
+                        .speed(0.01)
+                        .range(0.0..=1.0),
+                )
+                .changed()
+            {
+                restitution.0.coefficient = coefficient;
+            }
+        });
+    }
+}